@@ -0,0 +1,240 @@
+//! # Board task
+//!
+//! Moves the score board into a `tokio` task of its own and drives it through a cloneable handle, so many tasks can use the board without sharing any locks. Available with the `actor` feature
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::command::Command;
+use crate::error::ScoreBoardError;
+use crate::view::GameView;
+use crate::ScoreBoard;
+
+/// Number of the requests waiting for the board task, after which the senders wait for a free place
+const QUEUE_LENGTH: usize = 64;
+
+/// A request sent to the board task, with the channel for its reply
+enum Request {
+	/// Applies a command to the board
+	Apply(Command, oneshot::Sender<Result<(), ScoreBoardError>>),
+	/// Provides the summary of the board
+	Summary(oneshot::Sender<Vec<String>>),
+	/// Provides the running games of the board
+	Games(oneshot::Sender<Vec<GameView>>),
+	/// Stops the task and hands the board back
+	Stop(oneshot::Sender<ScoreBoard>),
+}
+
+/// A handle to a score board owned by a `tokio` task, see `ScoreBoard::spawn()`. Every method sends a request to the task and waits for its reply, and the task handles the requests one by one, in the order they came. Clones send to the same board, and the task stops when the last handle is dropped
+#[derive(Clone)]
+pub struct ScoreBoardHandle {
+	/// The requests to the board task
+	requests: mpsc::Sender<Request>,
+}
+
+impl ScoreBoardHandle {
+	/// Starts a new game with the score 0 - 0, see `ScoreBoard::start_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::start_game()`
+	/// * When the board task has stopped
+	pub async fn start_game<T: ToString, U: ToString>(&self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		self.apply(Command::StartGame { home: home.to_string(), away: away.to_string() }).await
+	}
+
+	/// Updates the score of a running game with absolute values, see `ScoreBoard::update_score()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `home_score` - A new score to be set for the home team
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away_score` - A new score to be set for the away team
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::update_score()`
+	/// * When the board task has stopped
+	pub async fn update_score<T: ToString, U: ToString>(&self, home: T, home_score: u8, away: U, away_score: u8) -> Result<(), ScoreBoardError> {
+		self.apply(Command::UpdateScore { home: home.to_string(), home_score, away: away.to_string(), away_score }).await
+	}
+
+	/// Finishes a game and removes it from the board, see `ScoreBoard::finish_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::finish_game()`
+	/// * When the board task has stopped
+	pub async fn finish_game<T: ToString, U: ToString>(&self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		self.apply(Command::FinishGame { home: home.to_string(), away: away.to_string() }).await
+	}
+
+	/// Applies a command to the board
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command to apply
+	///
+	/// # Errors
+	///
+	/// * When the command fails
+	/// * When the board task has stopped
+	pub async fn apply(&self, command: Command) -> Result<(), ScoreBoardError> {
+		self.request(|reply| Request::Apply(command, reply)).await?
+	}
+
+	/// Provides the summary of the board, see `ScoreBoard::get_summary()`
+	///
+	/// # Errors
+	///
+	/// * When the board task has stopped
+	pub async fn get_summary(&self) -> Result<Vec<String>, ScoreBoardError> {
+		self.request(Request::Summary).await
+	}
+
+	/// Provides the running games of the board, see `ScoreBoard::get_games()`
+	///
+	/// # Errors
+	///
+	/// * When the board task has stopped
+	pub async fn get_games(&self) -> Result<Vec<GameView>, ScoreBoardError> {
+		self.request(Request::Games).await
+	}
+
+	/// Stops the board task and hands the board back. The requests sent before are handled first, and the requests of the other handles after it fail
+	///
+	/// # Errors
+	///
+	/// * When the board task has already stopped
+	pub async fn stop(self) -> Result<ScoreBoard, ScoreBoardError> {
+		self.request(Request::Stop).await
+	}
+
+	/// Sends a request to the board task and waits for the reply
+	///
+	/// # Arguments
+	///
+	/// * `request` - makes the request from the channel for its reply
+	///
+	async fn request<R>(&self, request: impl FnOnce(oneshot::Sender<R>) -> Request) -> Result<R, ScoreBoardError> {
+		let (reply, response) = oneshot::channel();
+
+		if self.requests.send(request(reply)).await.is_err() {
+			warn!("Couldn't send the request, the score board task has stopped");
+			return Err(ScoreBoardError::BoardStopped);
+		}
+
+		response.await.map_err(|_| ScoreBoardError::BoardStopped)
+	}
+}
+
+impl ScoreBoard {
+	/// Moves the score board into a new `tokio` task and provides the handle to it. The task owns the board and handles the requests of the handles one at a time, so the board can be used from many tasks without any locks. Must be called within a `tokio` runtime
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+	/// let board = scoreboard_world_cup::ScoreBoard::new().spawn();
+	///
+	/// let operator = board.clone();
+	/// tokio::spawn(async move { operator.start_game("Japan", "Indonesia").await }).await.unwrap().unwrap();
+	///
+	/// board.update_score("Japan", 1, "Indonesia", 0).await.unwrap();
+	/// assert_eq!(board.get_summary().await.unwrap(), vec![String::from("Japan 1 - Indonesia 0")]);
+	/// # });
+	/// ```
+	pub fn spawn(self) -> ScoreBoardHandle {
+		trace!("Spawning the score board task");
+
+		let (requests, receiver) = mpsc::channel(QUEUE_LENGTH);
+		tokio::spawn(self.serve(receiver));
+
+		ScoreBoardHandle { requests }
+	}
+
+	/// Handles the requests until the stop request or until all the handles are gone. The lost replies are ignored, as their senders don't wait for them any more
+	///
+	/// # Arguments
+	///
+	/// * `receiver` - the requests from the handles
+	///
+	async fn serve(mut self, mut receiver: mpsc::Receiver<Request>) {
+		while let Some(request) = receiver.recv().await {
+			match request {
+				Request::Apply(command, reply) => {
+					let _ = reply.send(self.apply(command));
+				},
+				Request::Summary(reply) => {
+					let _ = reply.send(self.get_summary());
+				},
+				Request::Games(reply) => {
+					let _ = reply.send(self.get_games());
+				},
+				Request::Stop(reply) => {
+					trace!("Stopping the score board task");
+					let _ = reply.send(self);
+					return;
+				},
+			}
+		}
+
+		trace!("All the score board handles are gone, stopping the task");
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn handles_share_the_board() {
+		let board = ScoreBoard::new().spawn();
+
+		let operators: Vec<_> = [("Mexico", "Canada"), ("Spain", "Brazil"), ("Germany", "France")].into_iter()
+			.map(|(home, away)| {
+				let board = board.clone();
+				tokio::spawn(async move {
+					board.start_game(home, away).await.expect("Couldn't create the game");
+					board.update_score(home, 1, away, 0).await.expect("Couldn't update the game");
+				})
+			})
+			.collect();
+		for operator in operators {
+			operator.await.expect("The operator task failed");
+		}
+
+		assert_eq!(board.get_games().await.expect("Couldn't get the games").len(), 3);
+		assert_eq!(board.start_game("Mexico", "Spain").await, Err(ScoreBoardError::AlreadyPlaying(String::from("Mexico"))));
+	}
+
+	#[tokio::test]
+	async fn stopped_board_is_handed_back() {
+		let board = ScoreBoard::new().spawn();
+		let other = board.clone();
+		board.start_game("Mexico", "Canada").await.expect("Couldn't create the game");
+
+		let sb = board.stop().await.expect("Couldn't stop the board task");
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0")]);
+		assert_eq!(other.get_summary().await, Err(ScoreBoardError::BoardStopped));
+		assert_eq!(other.finish_game("Mexico", "Canada").await, Err(ScoreBoardError::BoardStopped));
+	}
+}
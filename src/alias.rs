@@ -0,0 +1,130 @@
+//! # Team aliases
+//!
+//! Lets the score board know the other names of the teams (ex. "USA" for "United States"), so the data sources using different names for the same team still reach the same game
+
+use std::string::{String, ToString};
+
+use log::trace;
+
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Adds another name of a team. From now on every operation and lookup given the alias works on the team's name instead, including the check if the team is already playing. The games started before keep the names they were started with
+	///
+	/// # Arguments
+	///
+	/// * `alias` - the other name of the team. Must be either a `String` or a type that is convertable to `String`
+	/// * `name` - the name of the team shown on the score board. If it is an alias itself, the alias is followed
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.add_alias("USA", "United States");
+	/// sb.add_alias("Korea Republic", "South Korea");
+	/// sb.start_game("USA", "South Korea").unwrap();
+	/// sb.update_score("United States", 1, "Korea Republic", 0).unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("United States 1 - South Korea 0")]);
+	/// assert!(sb.start_game("Korea Republic", "Japan").is_err());
+	/// ```
+	pub fn add_alias<T: ToString, U: ToString>(&mut self, alias: T, name: U) {
+		let alias = alias.to_string();
+		let name = self.resolve_team(name);
+
+		trace!("Adding the alias '{}' of '{}'", alias, name);
+
+		if alias != name {
+			self.aliases.insert(alias, name);
+		}
+	}
+
+	/// Removes all the aliases from the score board
+	pub fn clear_aliases(&mut self) {
+		trace!("Removing all aliases");
+
+		self.aliases.clear();
+	}
+
+	/// Provides the name of the team shown on the score board, which is the given name unless it is an alias
+	///
+	/// # Arguments
+	///
+	/// * `team` - name or alias of the team
+	///
+	pub(crate) fn resolve_team<T: ToString>(&self, team: T) -> String {
+		let name = team.to_string();
+
+		match self.aliases.get(&name) {
+			Some(resolved) => resolved.clone(),
+			None => name,
+		}
+	}
+
+	/// Provides the name shown on the board for a team, like `resolve_team()`, without allocating
+	///
+	/// # Arguments
+	///
+	/// * `team` - name or alias of the team
+	///
+	pub(crate) fn resolve_name<'a>(&'a self, team: &'a str) -> &'a str {
+		self.aliases.get(team).map_or(team, String::as_str)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn aliases_reach_the_same_game() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("USA", "United States");
+		sb.start_game("United States", "Mexico").expect("Couldn't create the game");
+		sb.update_score("USA", 2, "Mexico", 0).expect("Couldn't update the game");
+
+		assert_eq!(sb.get_score("USA", "Mexico"), Some((2, 0)));
+		assert_eq!(sb.get_game("USA", "Mexico").map(|game| game.home_team), Some(String::from("United States")));
+		assert_eq!(sb.get_audit_log("USA", "Mexico").len(), 2);
+
+		sb.finish_game("USA", "Mexico").expect("Couldn't finish the game");
+		assert_eq!(sb.get_archive_summary(), vec![String::from("United States 2 - 0 Mexico")]);
+	}
+
+	#[test]
+	fn aliased_team_cannot_play_twice() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("USA", "United States");
+		sb.start_game("United States", "Mexico").expect("Couldn't create the game");
+
+		let result = sb.start_game("Canada", "USA");
+		assert!(result.err().is_some_and(|result| result.to_string() == "United States is currently playing a game"));
+		let result = sb.start_game("USA", "United States");
+		assert!(result.err().is_some_and(|result| result.to_string() == "United States cannot play with itself"));
+	}
+
+	#[test]
+	fn chained_aliases_are_followed() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("Korea Republic", "South Korea");
+		sb.add_alias("KOR", "Korea Republic");
+		sb.add_alias("South Korea", "South Korea");
+
+		assert_eq!(sb.resolve_team("KOR"), "South Korea");
+		assert_eq!(sb.resolve_team("South Korea"), "South Korea");
+		assert_eq!(sb.resolve_team("Japan"), "Japan");
+	}
+
+	#[test]
+	fn cleared_aliases_are_not_followed() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("USA", "United States");
+		sb.clear_aliases();
+		sb.start_game("USA", "Mexico").expect("Couldn't create the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("USA 0 - Mexico 0")]);
+	}
+}
@@ -0,0 +1,166 @@
+//! # Common score board interface
+//!
+//! The basic operations of a score board as a trait, so the code using the board can work with any implementation: the in-memory `ScoreBoard`, a shared one like `RedisScoreBoard`, or a mock in tests
+
+use std::string::String;
+use std::vec::Vec;
+
+use crate::error::ScoreBoardError;
+use crate::sport::{Football, SportBoard};
+use crate::ScoreBoard;
+
+/// The basic operations of a football score board. All of them can fail, because the alternative implementations may need to reach a remote storage, even to read the summary
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{ScoreBoard, ScoreBoardApi, ScoreBoardError};
+///
+/// fn open_match(board: &mut dyn ScoreBoardApi) -> Result<Vec<String>, ScoreBoardError> {
+///     board.start_game("Japan", "Indonesia")?;
+///     board.update_score("Japan", 1, "Indonesia", 0)?;
+///     board.get_summary()
+/// }
+///
+/// let mut sb = ScoreBoard::new();
+/// assert_eq!(open_match(&mut sb).unwrap(), vec![String::from("Japan 1 - Indonesia 0")]);
+/// ```
+pub trait ScoreBoardApi {
+	/// Starts a new game with the score 0 - 0, see `ScoreBoard::start_game()`
+	fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError>;
+
+	/// Updates the score of a running game with absolute values, see `ScoreBoard::update_score()`
+	fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError>;
+
+	/// Finishes a game and removes it from the board, see `ScoreBoard::finish_game()`
+	fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError>;
+
+	/// Provides the summary of the running games, ordered by the total score and then by the start time, see `ScoreBoard::get_summary()`
+	fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError>;
+}
+
+impl ScoreBoardApi for ScoreBoard {
+	fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		ScoreBoard::start_game(self, home, away)
+	}
+
+	fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError> {
+		ScoreBoard::update_score(self, home, home_score, away, away_score)
+	}
+
+	fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		ScoreBoard::finish_game(self, home, away)
+	}
+
+	fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+		Ok(ScoreBoard::get_summary(self))
+	}
+}
+
+impl ScoreBoardApi for SportBoard<Football> {
+	fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		SportBoard::start_game(self, home, away)
+	}
+
+	fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError> {
+		SportBoard::update_score(self, home, away, (home_score, away_score))
+	}
+
+	fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		SportBoard::finish_game(self, home, away)
+	}
+
+	fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+		Ok(SportBoard::get_summary(self))
+	}
+}
+
+#[cfg(feature = "redis")]
+impl ScoreBoardApi for crate::redis_board::RedisScoreBoard {
+	fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		crate::redis_board::RedisScoreBoard::start_game(self, home, away)
+	}
+
+	fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError> {
+		crate::redis_board::RedisScoreBoard::update_score(self, home, home_score, away, away_score)
+	}
+
+	fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		crate::redis_board::RedisScoreBoard::finish_game(self, home, away)
+	}
+
+	fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+		crate::redis_board::RedisScoreBoard::get_summary(self)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::ToString;
+
+	/// A board recording the calls, as a user could write to test their code
+	#[derive(Default)]
+	struct MockBoard {
+		calls: Vec<String>,
+	}
+
+	impl ScoreBoardApi for MockBoard {
+		fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+			self.calls.push(format!("start {} {}", home, away));
+			Ok(())
+		}
+
+		fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError> {
+			self.calls.push(format!("update {} {} {} {}", home, home_score, away, away_score));
+			Ok(())
+		}
+
+		fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+			Err(ScoreBoardError::Storage(format!("finish {} {} unavailable", home, away)))
+		}
+
+		fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+			Ok(self.calls.clone())
+		}
+	}
+
+	fn play_match<B: ScoreBoardApi>(board: &mut B) -> Result<Vec<String>, ScoreBoardError> {
+		board.start_game("Mexico", "Canada")?;
+		board.start_game("Spain", "Brazil")?;
+		board.update_score("Mexico", 0, "Canada", 5)?;
+		board.finish_game("Spain", "Brazil")?;
+		board.get_summary()
+	}
+
+	#[test]
+	fn implementations_behave_the_same() {
+		let expected = vec![String::from("Mexico 0 - Canada 5")];
+
+		assert_eq!(play_match(&mut ScoreBoard::new()), Ok(expected.clone()));
+		assert_eq!(play_match(&mut SportBoard::<Football>::new()), Ok(expected));
+	}
+
+	#[test]
+	fn errors_of_the_board_are_passed_on() {
+		let mut sb = ScoreBoard::new();
+		let board: &mut dyn ScoreBoardApi = &mut sb;
+
+		let result = board.finish_game("Mexico", "Canada");
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for removal"));
+	}
+
+	#[test]
+	fn custom_implementation_can_be_used() {
+		let mut board = MockBoard::default();
+		let result = play_match(&mut board);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "finish Spain Brazil unavailable"));
+		assert_eq!(board.calls, vec!["start Mexico Canada", "start Spain Brazil", "update Mexico 0 Canada 5"]);
+	}
+}
@@ -0,0 +1,236 @@
+//! # Archive of finished games
+//!
+//! Keeps the results of the finished games, together with the way they were decided, so knockout results can be presented without ambiguity
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::string::String;
+use std::time::SystemTime;
+use std::vec::Vec;
+
+use crate::goal::Goal;
+use crate::minute::MatchClock;
+use crate::odds::Odds;
+use crate::period::Period;
+use crate::timezone::UtcOffset;
+use crate::xg::ExpectedGoals;
+use crate::ScoreBoard;
+
+/// The way the result of a game was decided
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Decision {
+	/// The game was decided in the regular time
+	RegularTime,
+	/// The game was decided in the extra time
+	AfterExtraTime,
+	/// The game was decided in a penalty shootout, after the extra time
+	AfterPenalties {
+		/// Penalties scored by the home team in the shootout
+		home: u8,
+		/// Penalties scored by the away team in the shootout
+		away: u8,
+	},
+	/// The game was awarded to one of the teams with `ScoreBoard::award_forfeit()`, the other team forfeiting it
+	Forfeit,
+}
+
+impl fmt::Display for Decision {
+	/// Implementation of `Display` trait, allowing it to be converted to a String. The regular time gives an empty string
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Decision::RegularTime => Ok(()),
+			Decision::AfterExtraTime => write!(f, "a.e.t."),
+			Decision::AfterPenalties { home, away } => write!(f, "a.e.t., {}-{} pen.", home, away),
+			Decision::Forfeit => write!(f, "awarded"),
+		}
+	}
+}
+
+impl Decision {
+	/// Name of the decision, as used in the JSON presentation of the events
+	pub(crate) fn get_name(&self) -> &'static str {
+		match self {
+			Decision::RegularTime => "regular_time",
+			Decision::AfterExtraTime => "extra_time",
+			Decision::AfterPenalties { .. } => "penalties",
+			Decision::Forfeit => "forfeit",
+		}
+	}
+}
+
+/// The result of a finished game
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArchivedGame {
+	/// Name of the home team
+	pub home_team: String,
+	/// Final score of the home team
+	pub home_score: u8,
+	/// Name of the away team
+	pub away_team: String,
+	/// Final score of the away team
+	pub away_score: u8,
+	/// Wall clock time of the start of the game
+	pub kickoff_time: SystemTime,
+	/// Wall clock time of the end of the game
+	pub finish_time: SystemTime,
+	/// The way the result was decided
+	pub decision: Decision,
+	/// The goals recorded with `ScoreBoard::record_goal()` or `ScoreBoard::record_goal_by()`, the earliest coming first
+	pub goals: Vec<Goal>,
+	/// The way the game was played, so it can go on when reopened. Missing for the awarded games
+	pub(crate) details: Option<PlayDetails>,
+}
+
+/// The way a finished game was played, kept in the archive for `ScoreBoard::reopen_game()`
+#[derive(Clone, Debug)]
+pub(crate) struct PlayDetails {
+	/// Every entered period with the score at its start, the earliest coming first
+	pub(crate) periods: Vec<(Period, (u8, u8))>,
+	/// Score frozen at the end of the first half, if it was reached
+	pub(crate) half_time_score: Option<(u8, u8)>,
+	/// Playing time of the last period, stopped at the finish
+	pub(crate) clock: MatchClock,
+	/// Name of the stadium, if it was given
+	pub(crate) venue: Option<String>,
+	/// Time zone of the stadium
+	pub(crate) utc_offset: UtcOffset,
+	/// Planned start of the game, if it was started from a fixture
+	pub(crate) scheduled_kickoff: Option<SystemTime>,
+	/// Group of the tournament, if it was given
+	pub(crate) group: Option<char>,
+	/// The last betting odds, if they were set
+	pub(crate) odds: Option<Odds>,
+	/// Expected goals of both teams
+	pub(crate) xg: ExpectedGoals,
+}
+
+impl PartialEq for PlayDetails {
+	/// The details don't tell the results apart, only the way to reopen them
+	fn eq(&self, _: &PlayDetails) -> bool {
+		true
+	}
+}
+
+impl Eq for PlayDetails {}
+
+impl Hash for PlayDetails {
+	/// The details don't tell the results apart, see `PartialEq`
+	fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl fmt::Display for ArchivedGame {
+	/// Implementation of `Display` trait, allowing it to be converted to a String, ex. "Spain 2 - 1 Italy (a.e.t.)"
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} {} - {} {}", self.home_team, self.home_score, self.away_score, self.away_team)?;
+
+		match self.decision {
+			Decision::RegularTime => Ok(()),
+			decision => write!(f, " ({})", decision),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Provides the results of all the finished games, the earliest finished coming first
+	pub fn get_archive(&self) -> &[ArchivedGame] {
+		&self.archive
+	}
+
+	/// Provides the results of all the finished games, the earliest finished coming first
+	///
+	/// # Returns
+	///
+	/// * A vector of strings, each string containing the home team, both scores and the away team, followed by the way the result was decided if it wasn't the regular time, ex. "Spain 2 - 1 Italy (a.e.t.)"
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Decision;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Italy").unwrap();
+	/// sb.update_score("Spain", 2, "Italy", 1).unwrap();
+	/// sb.finish_game_after("Spain", "Italy", Decision::AfterExtraTime).unwrap();
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Spain 2 - 1 Italy (a.e.t.)")]);
+	/// ```
+	pub fn get_archive_summary(&self) -> Vec<String> {
+		self.archive.iter().map(|game| self.locale.render_result(game)).collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::ToString;
+
+	fn finish(sb: &mut ScoreBoard, home: &str, home_score: u8, away: &str, away_score: u8, decision: Decision) {
+		sb.start_game(home, away).expect("Couldn't create the game");
+		sb.update_score(home, home_score, away, away_score).expect("Couldn't update the game");
+		sb.finish_game_after(home, away, decision).expect("Couldn't finish the game");
+	}
+
+	#[test]
+	fn finished_games_are_archived_in_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		sb.finish_game("Spain", "Brazil").expect("Couldn't finish the second game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Spain 0 - 0 Brazil"), String::from("Mexico 0 - 5 Canada")]);
+		assert!(sb.get_archive().iter().all(|game| game.decision == Decision::RegularTime));
+	}
+
+	#[test]
+	fn knockout_results_are_marked() {
+		let mut sb = ScoreBoard::new();
+		finish(&mut sb, "Spain", 2, "Italy", 1, Decision::AfterExtraTime);
+		finish(&mut sb, "Argentina", 3, "France", 3, Decision::AfterPenalties { home: 4, away: 2 });
+
+		assert_eq!(sb.get_archive_summary(), vec![
+			String::from("Spain 2 - 1 Italy (a.e.t.)"),
+			String::from("Argentina 3 - 3 France (a.e.t., 4-2 pen.)"),
+		]);
+	}
+
+	#[test]
+	fn archived_game_keeps_its_times() {
+		let mut sb = ScoreBoard::new();
+		let before = SystemTime::now();
+		finish(&mut sb, "Spain", 1, "Italy", 0, Decision::RegularTime);
+
+		let game = &sb.get_archive()[0];
+		assert!(game.kickoff_time >= before);
+		assert!(game.finish_time >= game.kickoff_time);
+	}
+
+	#[test]
+	fn drawn_shootout_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Italy").expect("Couldn't create the game");
+		let result = sb.finish_game_after("Spain", "Italy", Decision::AfterPenalties { home: 3, away: 3 });
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "A penalty shootout cannot end with a draw 3-3"));
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Italy 0")]);
+		assert!(sb.get_archive().is_empty());
+	}
+
+	#[test]
+	fn failed_finish_is_not_archived() {
+		let mut sb = ScoreBoard::new();
+		let _ = sb.finish_game("Spain", "Italy");
+
+		assert!(sb.get_archive().is_empty());
+	}
+
+	#[test]
+	fn regular_time_has_no_mark() {
+		assert_eq!(Decision::RegularTime.to_string(), "");
+	}
+}
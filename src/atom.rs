@@ -0,0 +1,260 @@
+//! # Atom feed export
+//!
+//! Renders the most recent score board events as an Atom (RFC 4287) feed, so they can be followed in feed readers and news aggregators
+
+use std::string::String;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::trace;
+
+use crate::archive::Decision;
+use crate::datetime::UtcDateTime;
+use crate::events::{EventRecord, ScoreBoardEvent};
+use crate::state::GameState;
+use crate::text::escape_xml;
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Exports the most recent events of the score board (kickoffs, score changes and final results) as an Atom feed. The newest events come first
+	///
+	/// # Arguments
+	///
+	/// * `title` - Title of the feed, ex. the name of the competition. It's also used to build the IDs of the feed and its entries
+	/// * `limit` - Maximum number of events in the feed
+	///
+	/// # Returns
+	///
+	/// * A string with a complete Atom XML document
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// let feed = sb.export_atom("World Cup", 10);
+	/// assert!(feed.contains("<title>Score update: Japan 1 - Indonesia 0</title>"));
+	/// assert!(feed.contains("<title>Kickoff: Japan - Indonesia</title>"));
+	/// ```
+	pub fn export_atom(&self, title: &str, limit: usize) -> String {
+		trace!("Exporting the last {} events as an Atom feed", limit);
+
+		let feed_id = get_feed_id(title);
+		let updated = match self.events.last() {
+			Some(record) => record.time,
+			None => SystemTime::now(),
+		};
+
+		let mut result = String::new();
+		result.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+		result.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+		result.push_str(&format!("  <id>{}</id>\n", escape_xml(&feed_id)));
+		result.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+		result.push_str(&format!("  <updated>{}</updated>\n", UtcDateTime::from_system_time(updated).to_rfc3339()));
+		result.push_str("  <author><name>scoreboard_world_cup</name></author>\n");
+
+		for record in self.events.iter().rev().take(limit) {
+			push_entry(&mut result, &feed_id, record);
+		}
+
+		result.push_str("</feed>\n");
+
+		trace!("Events exported");
+
+		result
+	}
+}
+
+/// Appends a single `entry` element to the feed
+///
+/// # Arguments
+///
+/// * `feed` - the feed being built
+/// * `feed_id` - ID of the feed, used as a prefix of the entry ID
+/// * `record` - the event to present
+///
+fn push_entry(feed: &mut String, feed_id: &str, record: &EventRecord) {
+	let updated = UtcDateTime::from_system_time(record.time).to_rfc3339();
+	let (title, content) = describe_event(&record.event);
+
+	feed.push_str("  <entry>\n");
+	feed.push_str(&format!("    <id>{}</id>\n", escape_xml(&get_entry_id(feed_id, record))));
+	feed.push_str(&format!("    <title>{}</title>\n", escape_xml(&title)));
+	feed.push_str(&format!("    <updated>{}</updated>\n", updated));
+	feed.push_str(&format!("    <content type=\"text\">{}</content>\n", escape_xml(&content)));
+	feed.push_str("  </entry>\n");
+}
+
+/// Provides a short title and a longer description of an event
+fn describe_event(event: &ScoreBoardEvent) -> (String, String) {
+	match event {
+		ScoreBoardEvent::GameStarted { home, away } => (
+			format!("Kickoff: {} - {}", home, away),
+			format!("The game between {} and {} has started", home, away),
+		),
+		ScoreBoardEvent::ScoreUpdated { home, home_score, away, away_score, .. } => (
+			format!("Score update: {} {} - {} {}", home, home_score, away, away_score),
+			format!("The score is now {} {} - {} {}", home, home_score, away, away_score),
+		),
+		ScoreBoardEvent::GameFinished { home, home_score, away, away_score } => (
+			format!("Full time: {} {} - {} {}", home, home_score, away, away_score),
+			format!("The game has finished with the result {} {} - {} {}", home, home_score, away, away_score),
+		),
+		ScoreBoardEvent::StateChanged { home, home_score, away, away_score, state } => (
+			format!("{}: {} {} - {} {}", describe_state(*state), home, home_score, away, away_score),
+			format!("The game between {} and {} is now {}", home, away, state),
+		),
+		ScoreBoardEvent::GameReopened { home, home_score, away, away_score } => (
+			format!("Reopened: {} {} - {} {}", home, home_score, away, away_score),
+			format!("The finished game between {} and {} goes on at {} - {}", home, away, home_score, away_score),
+		),
+		ScoreBoardEvent::SidesSwapped { home, home_score, away, away_score } => (
+			format!("Sides swapped: {} {} - {} {}", home, home_score, away, away_score),
+			format!("{} is now the home team and {} the away team, at {} - {}", home, away, home_score, away_score),
+		),
+		ScoreBoardEvent::PeriodStarted { home, home_score, away, away_score, period } => (
+			format!("Start of the {}: {} {} - {} {}", period, home, home_score, away, away_score),
+			format!("The game between {} and {} goes into the {} at {} - {}", home, away, period, home_score, away_score),
+		),
+		ScoreBoardEvent::ResultFinalized { home, home_score, away, away_score, decision, .. } => {
+			let decision = match decision {
+				Decision::RegularTime => String::new(),
+				decision => format!(" ({})", decision),
+			};
+			(
+				format!("Final result: {} {} - {} {}{}", home, home_score, away, away_score, decision),
+				format!("The result of the game between {} and {} is final: {} - {}{}", home, away, home_score, away_score, decision),
+			)
+		},
+		ScoreBoardEvent::TeamRenamed { old, new } => (
+			format!("Team renamed: {} to {}", old, new),
+			format!("The team {} is now called {}", old, new),
+		),
+	}
+}
+
+/// Provides a capitalized name of a state, for the entry titles
+fn describe_state(state: GameState) -> &'static str {
+	match state {
+		GameState::Scheduled => "Scheduled",
+		GameState::Live => "Resumed",
+		GameState::Paused => "Paused",
+		GameState::Finished => "Finished",
+		GameState::Abandoned => "Abandoned",
+		GameState::Postponed => "Postponed",
+	}
+}
+
+/// Builds a tag URI (RFC 4151) identifying the feed. The same title always gives the same ID
+fn get_feed_id(title: &str) -> String {
+	let slug: String = title
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+		.collect();
+
+	format!("tag:scoreboard_world_cup,2022:{}", slug)
+}
+
+/// Builds a globally unique ID of an entry, combining the feed ID, the event sequence number and the exact time of the event, so events of different board instances don't collide
+fn get_entry_id(feed_id: &str, record: &EventRecord) -> String {
+	let nanos = match record.time.duration_since(UNIX_EPOCH) {
+		Ok(duration) => duration.as_nanos(),
+		Err(_) => 0,
+	};
+
+	format!("{}/event/{}/{}", feed_id, record.sequence, nanos)
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::Duration;
+
+	const FEED_TITLE: &str = "World Cup";
+
+	fn count_entries(feed: &str) -> usize {
+		feed.matches("<entry>").count()
+	}
+
+	#[test]
+	fn empty_board_exports_a_feed_without_entries() {
+		let sb = ScoreBoard::new();
+		let result = sb.export_atom(FEED_TITLE, 10);
+
+		assert!(result.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n"));
+		assert!(result.contains("<id>tag:scoreboard_world_cup,2022:world-cup</id>"));
+		assert!(result.contains("<title>World Cup</title>"));
+		assert!(result.ends_with("</feed>\n"));
+		assert_eq!(count_entries(&result), 0);
+	}
+
+	#[test]
+	fn all_kinds_of_events_are_exported_newest_first() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.finish_game("Spain", "Brazil").expect("Couldn't finish the game");
+		let result = sb.export_atom(FEED_TITLE, 10);
+
+		let final_result = result.find("<title>Final result: Spain 1 - Brazil 0</title>").expect("Missing final result");
+		let full_time = result.find("<title>Full time: Spain 1 - Brazil 0</title>").expect("Missing full time");
+		let update = result.find("<title>Score update: Spain 1 - Brazil 0</title>").expect("Missing score update");
+		let kickoff = result.find("<title>Kickoff: Spain - Brazil</title>").expect("Missing kickoff");
+		assert_eq!(count_entries(&result), 4);
+		assert!(final_result < full_time);
+		assert!(full_time < update);
+		assert!(update < kickoff);
+	}
+
+	#[test]
+	fn only_the_most_recent_events_are_exported() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.export_atom(FEED_TITLE, 2);
+
+		assert_eq!(count_entries(&result), 2);
+		assert!(!result.contains("Kickoff"));
+	}
+
+	#[test]
+	fn team_names_are_escaped() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Trinidad & Tobago", "<Antigua>").expect("Couldn't create the game");
+		let result = sb.export_atom(FEED_TITLE, 10);
+
+		assert!(result.contains("<title>Kickoff: Trinidad &amp; Tobago - &lt;Antigua&gt;</title>"));
+	}
+
+	#[test]
+	fn entry_ids_are_unique() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.export_atom(FEED_TITLE, 10);
+
+		let ids: Vec<&str> = result.lines().filter(|line| line.starts_with("    <id>")).collect();
+		assert_eq!(ids.len(), 2);
+		assert_ne!(ids[0], ids[1]);
+	}
+
+	#[test]
+	fn entry_id_contains_sequence_and_time() {
+		let record = EventRecord {
+			sequence: 7,
+			time: UNIX_EPOCH + Duration::from_secs(1),
+			event: ScoreBoardEvent::GameStarted { home: String::from("Spain"), away: String::from("Brazil") },
+			operator: None,
+			revision: 1,
+			state: GameState::Live,
+		};
+
+		assert_eq!(get_entry_id("tag:feed", &record), "tag:feed/event/7/1000000000");
+	}
+}
@@ -0,0 +1,214 @@
+//! # Read-only board
+//!
+//! Hands the score board to the display components with only its queries, so showing the board can't change it
+
+use std::collections::BTreeMap;
+use std::io;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::archive::ArchivedGame;
+use crate::goal::GoalCounts;
+use crate::order::SortOrder;
+use crate::scorers::ScorerStanding;
+use crate::stats::TeamStanding;
+use crate::svg::SvgStyle;
+use crate::table::TableStyle;
+use crate::tie::Tie;
+use crate::timeline::TimelineEntry;
+use crate::view::{GameRef, GameView};
+use crate::ScoreBoard;
+
+/// A read-only view of a score board, with the summaries, the games and the statistics of the board, but none of its operations. It's cheap to copy, and it always shows the current state of the board
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::ScoreBoardView;
+///
+/// fn render(view: ScoreBoardView) -> String {
+///     view.get_summary().join("\n")
+/// }
+///
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.start_game("Japan", "Indonesia").unwrap();
+/// assert_eq!(render(sb.view()), "Japan 0 - Indonesia 0");
+/// ```
+#[derive(Clone, Copy)]
+pub struct ScoreBoardView<'a> {
+	/// The viewed board
+	board: &'a ScoreBoard,
+}
+
+impl<'a> From<&'a ScoreBoard> for ScoreBoardView<'a> {
+	/// Implementation of `From` trait, giving the read-only view of a board
+	fn from(board: &'a ScoreBoard) -> ScoreBoardView<'a> {
+		ScoreBoardView { board }
+	}
+}
+
+impl<'a> ScoreBoardView<'a> {
+	/// Provides the summary of the running games, see `ScoreBoard::get_summary()`
+	pub fn get_summary(&self) -> Vec<String> {
+		self.board.get_summary()
+	}
+
+	/// Provides the summary of the running games in the given order, see `ScoreBoard::get_summary_sorted()`
+	pub fn get_summary_sorted(&self, order: SortOrder) -> Vec<String> {
+		self.board.get_summary_sorted(order)
+	}
+
+	/// Provides the summaries of the running games by the groups, see `ScoreBoard::get_summary_grouped()`
+	pub fn get_summary_grouped(&self) -> BTreeMap<Option<char>, Vec<String>> {
+		self.board.get_summary_grouped()
+	}
+
+	/// Provides the summary of the running games with their minutes, see `ScoreBoard::get_summary_with_minutes()`
+	pub fn get_summary_with_minutes(&self) -> Vec<String> {
+		self.board.get_summary_with_minutes()
+	}
+
+	/// Writes the summary of the running games to the sink, see `ScoreBoard::write_summary()`
+	pub fn write_summary(&self, w: &mut impl io::Write) -> io::Result<()> {
+		self.board.write_summary(w)
+	}
+
+	/// Provides the summary of the running games as a table, see `ScoreBoard::render_table()`
+	pub fn render_table(&self, style: TableStyle) -> String {
+		self.board.render_table(style)
+	}
+
+	/// Provides the running games as an SVG image, see `ScoreBoard::render_svg()`
+	pub fn render_svg(&self, style: &SvgStyle) -> String {
+		self.board.render_svg(style)
+	}
+
+	/// Provides the running games, see `ScoreBoard::get_games()`
+	pub fn get_games(&self) -> Vec<GameView> {
+		self.board.get_games()
+	}
+
+	/// Provides the borrowed views of the running games, see `ScoreBoard::games_ref()`
+	pub fn games_ref(&self) -> impl ExactSizeIterator<Item = GameRef<'a>> + DoubleEndedIterator + 'a {
+		self.board.games_ref()
+	}
+
+	/// Provides a running game, see `ScoreBoard::get_game()`
+	pub fn get_game(&self, home: &str, away: &str) -> Option<GameView> {
+		self.board.get_game(home, away)
+	}
+
+	/// Provides the score of a running game, see `ScoreBoard::get_score()`
+	pub fn get_score(&self, home: &str, away: &str) -> Option<(u8, u8)> {
+		self.board.get_score(home, away)
+	}
+
+	/// Provides the leading game, see `ScoreBoard::leader()`
+	pub fn leader(&self) -> Option<GameView> {
+		self.board.leader()
+	}
+
+	/// Provides the game running for the longest time, see `ScoreBoard::longest_running_game()`
+	pub fn longest_running_game(&self) -> Option<GameView> {
+		self.board.longest_running_game()
+	}
+
+	/// Provides the running games by their age, see `ScoreBoard::games_by_age()`
+	pub fn games_by_age(&self) -> Vec<GameView> {
+		self.board.games_by_age()
+	}
+
+	/// Provides the goals of both teams of a running game per kind, see `ScoreBoard::get_goal_counts()`
+	pub fn get_goal_counts(&self, home: &str, away: &str) -> Option<(GoalCounts, GoalCounts)> {
+		self.board.get_goal_counts(home, away)
+	}
+
+	/// Provides the scores of a running game broken down by the kinds of the goals, see `ScoreBoard::get_score_breakdown()`
+	pub fn get_score_breakdown(&self, home: &str, away: &str) -> Option<(String, String)> {
+		self.board.get_score_breakdown(home, away)
+	}
+
+	/// Provides the timeline of a game, see `ScoreBoard::get_timeline()`
+	pub fn get_timeline<T: ToString, U: ToString>(&self, home: T, away: U) -> Vec<TimelineEntry> {
+		self.board.get_timeline(home, away)
+	}
+
+	/// Provides the players with the most goals, see `ScoreBoard::top_scorers()`
+	pub fn top_scorers(&self, n: usize) -> Vec<ScorerStanding> {
+		self.board.top_scorers(n)
+	}
+
+	/// Provides the standings of the teams in the finished games, see `ScoreBoard::get_standings()`
+	pub fn get_standings(&self) -> Vec<TeamStanding> {
+		self.board.get_standings()
+	}
+
+	/// Provides the histogram of the goals of the finished games, see `ScoreBoard::get_goal_histogram()`
+	pub fn get_goal_histogram(&self) -> Vec<usize> {
+		self.board.get_goal_histogram()
+	}
+
+	/// Provides the histogram of the final scores of the finished games, see `ScoreBoard::get_score_histogram()`
+	pub fn get_score_histogram(&self) -> Vec<((u8, u8), usize)> {
+		self.board.get_score_histogram()
+	}
+
+	/// Provides the results of the finished games, see `ScoreBoard::get_archive()`
+	pub fn get_archive(&self) -> &'a [ArchivedGame] {
+		self.board.get_archive()
+	}
+
+	/// Provides the results of the finished games as text, see `ScoreBoard::get_archive_summary()`
+	pub fn get_archive_summary(&self) -> Vec<String> {
+		self.board.get_archive_summary()
+	}
+
+	/// Provides the two-legged tie of two teams, see `ScoreBoard::get_tie()`
+	pub fn get_tie<T: ToString, U: ToString>(&self, first_team: T, second_team: U, away_goals: bool) -> Tie {
+		self.board.get_tie(first_team, second_team, away_goals)
+	}
+}
+
+impl ScoreBoard {
+	/// Provides a read-only view of the score board, for the code that only shows the board, see `ScoreBoardView`
+	pub fn view(&self) -> ScoreBoardView<'_> {
+		ScoreBoardView::from(self)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::goal::{GoalKind, Side};
+
+	#[test]
+	fn view_shows_the_board() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.record_goal_by("Spain", "Brazil", Side::Home, GoalKind::Penalty, "Morata").expect("Couldn't record the goal");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+		let view = sb.view();
+
+		assert_eq!(view.get_summary(), sb.get_summary());
+		assert_eq!(view.get_games(), sb.get_games());
+		assert_eq!(view.leader(), sb.get_game("Spain", "Brazil"));
+		assert_eq!(view.get_score("Spain", "Brazil"), Some((1, 0)));
+		assert_eq!(view.top_scorers(1)[0].player.name, "Morata");
+		assert_eq!(view.get_archive_summary(), vec![String::from("Mexico 0 - 0 Canada")]);
+	}
+
+	#[test]
+	fn view_follows_the_changes_of_the_board() {
+		let mut sb = ScoreBoard::new();
+		assert!(ScoreBoardView::from(&sb).get_summary().is_empty());
+
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		assert_eq!(sb.view().get_summary(), vec![String::from("Mexico 0 - Canada 0")]);
+	}
+}
@@ -0,0 +1,230 @@
+//! # Game builder
+//!
+//! Starts games with the optional details, like the venue or the group, without a new variant of `start_game()` for every combination of them
+
+use std::string::{String, ToString};
+use std::time::SystemTime;
+
+use log::warn;
+
+use crate::error::ScoreBoardError;
+use crate::timezone::UtcOffset;
+use crate::ScoreBoard;
+
+/// The optional details of a game
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct GameDetails {
+	/// Wall clock time of the start of the game, if it started before it was put on the board
+	pub(crate) kickoff: Option<SystemTime>,
+	/// Name of the stadium
+	pub(crate) venue: Option<String>,
+	/// Group of the tournament the game belongs to
+	pub(crate) group: Option<char>,
+	/// Time zone of the stadium
+	pub(crate) utc_offset: Option<UtcOffset>,
+}
+
+/// A game being prepared for the start, see `ScoreBoard::new_game()`
+#[must_use = "the game is started only with `start()` or scheduled with `schedule()`"]
+pub struct GameBuilder<'a> {
+	/// The score board the game is started on
+	board: &'a mut ScoreBoard,
+	/// Name or alias of the home team
+	home: String,
+	/// Name or alias of the away team
+	away: String,
+	/// The optional details
+	details: GameDetails,
+}
+
+impl GameBuilder<'_> {
+	/// Sets the wall clock time of the start of the game, for a game that is put on the board after it started. The match minutes count from that time, see `ScoreBoard::get_summary_with_minutes()`. A time in the future counts as the current time for the minutes
+	///
+	/// # Arguments
+	///
+	/// * `kickoff` - time of the start of the game
+	pub fn kickoff_at(mut self, kickoff: SystemTime) -> Self {
+		self.details.kickoff = Some(kickoff);
+		self
+	}
+
+	/// Sets the stadium the game is played at
+	///
+	/// # Arguments
+	///
+	/// * `venue` - name of the stadium
+	pub fn venue<T: ToString>(mut self, venue: T) -> Self {
+		self.details.venue = Some(venue.to_string());
+		self
+	}
+
+	/// Sets the group of the tournament the game belongs to, ex. 'B'
+	///
+	/// # Arguments
+	///
+	/// * `group` - letter of the group
+	pub fn group(mut self, group: char) -> Self {
+		self.details.group = Some(group);
+		self
+	}
+
+	/// Sets the time zone of the stadium, in which the schedule shows the kickoff, see `ScoreBoard::get_schedule()`. A started game keeps it in `GameView::utc_offset`. The zone is a fixed offset, without the daylight saving time, see `UtcOffset`; with the `time-zones` feature, `UtcOffset::in_zone()` gives the offset of a named zone at the kickoff
+	///
+	/// # Arguments
+	///
+	/// * `utc_offset` - offset of the local time of the stadium from UTC
+	pub fn utc_offset(mut self, utc_offset: UtcOffset) -> Self {
+		self.details.utc_offset = Some(utc_offset);
+		self
+	}
+
+	/// Starts the game, with initial score 0 - 0
+	///
+	/// # Errors
+	///
+	/// * The same as of `ScoreBoard::start_game()`
+	pub fn start(self) -> Result<(), ScoreBoardError> {
+		let home_name = self.board.resolve_team(self.home);
+		let away_name = self.board.resolve_team(self.away);
+
+		self.board.start_new_game(home_name, away_name, self.details)
+	}
+
+	/// Schedules the game for a future kickoff at its stadium, in its group and in its time zone, instead of starting it. The game started from the fixture keeps them. Besides the conflicts of the teams, the fixture also can't clash with another fixture at the same stadium
+	///
+	/// # Arguments
+	///
+	/// * `kickoff` - planned start time of the game
+	///
+	/// # Errors
+	///
+	/// * The same as of `ScoreBoard::schedule_game()`
+	/// * When the time of a past start was set with `kickoff_at()`, since a fixture starts at `kickoff`
+	/// * When the stadium already hosts a fixture too close to the kickoff (see `ScoreBoard::set_fixture_window()`)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let kickoff = SystemTime::now() + Duration::from_secs(3600);
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.new_game("Spain", "Brazil").venue("Lusail").schedule(kickoff).unwrap();
+	/// let result = sb.new_game("Japan", "Indonesia").venue("Lusail").schedule(kickoff);
+	/// assert!(result.unwrap_err().to_string().starts_with("Lusail already hosts Spain - Brazil at "));
+	/// ```
+	pub fn schedule(self, kickoff: SystemTime) -> Result<(), ScoreBoardError> {
+		let home_name = self.board.resolve_team(self.home);
+		let away_name = self.board.resolve_team(self.away);
+
+		if self.details.kickoff.is_some() {
+			warn!("Game between {} and {} can't be scheduled with the time of a past start", home_name, away_name);
+			return Err(ScoreBoardError::PastKickoff { home: home_name, away: away_name });
+		}
+
+		self.board.schedule_fixture(home_name, away_name, kickoff, self.details)
+	}
+}
+
+impl ScoreBoard {
+	/// Prepares a game between two teams, to be started with `GameBuilder::start()` after setting its optional details
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.new_game("Spain", "Brazil")
+	///     .kickoff_at(SystemTime::now() - Duration::from_secs(600))
+	///     .venue("Lusail")
+	///     .group('B')
+	///     .start()
+	///     .unwrap();
+	///
+	/// let game = sb.get_game("Spain", "Brazil").unwrap();
+	/// assert_eq!((game.venue.as_deref(), game.group), (Some("Lusail"), Some('B')));
+	/// assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Spain 0 - Brazil 0 (11')")]);
+	/// ```
+	pub fn new_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> GameBuilder<'_> {
+		GameBuilder { board: self, home: home.to_string(), away: away.to_string(), details: GameDetails::default() }
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::Duration;
+
+	#[test]
+	fn game_without_details_is_a_started_game() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game("Mexico", "Canada").start().expect("Couldn't start the game");
+
+		let game = sb.get_game("Mexico", "Canada").expect("Couldn't find the game");
+		assert_eq!((game.venue, game.group), (None, None));
+		assert_eq!(sb.get_events().len(), 1);
+	}
+
+	#[test]
+	fn builder_follows_the_rules_of_the_start() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't start the game");
+		sb.add_alias("BRA", "Brazil");
+
+		let result = sb.new_game("Canada", "Spain").venue("Lusail").start();
+		assert!(result.err().is_some_and(|result| result.to_string() == "Canada is currently playing a game"));
+
+		sb.new_game("Spain", "BRA").start().expect("Couldn't start the game");
+		assert!(sb.get_game("Spain", "Brazil").is_some());
+	}
+
+	#[test]
+	fn future_kickoff_counts_from_now() {
+		let mut sb = ScoreBoard::new();
+		let kickoff = SystemTime::now() + Duration::from_secs(600);
+		sb.new_game("Mexico", "Canada").kickoff_at(kickoff).start().expect("Couldn't start the game");
+
+		assert_eq!(sb.get_game("Mexico", "Canada").map(|game| game.kickoff_time), Some(kickoff));
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Mexico 0 - Canada 0 (1')")]);
+	}
+
+	#[test]
+	fn earlier_kickoff_is_ordered_first() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't start the first game");
+		sb.new_game("Spain", "Brazil").kickoff_at(SystemTime::now() - Duration::from_secs(600)).start().expect("Couldn't start the second game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0"), String::from("Spain 0 - Brazil 0")]);
+	}
+
+	#[test]
+	fn scheduled_group_is_kept_for_the_start() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game("Spain", "Brazil").group('B').venue("Lusail").schedule(SystemTime::now() + Duration::from_secs(600)).expect("Couldn't schedule the game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't start the game");
+
+		let game = sb.get_game("Spain", "Brazil").expect("Couldn't find the game");
+		assert_eq!((game.venue.as_deref(), game.group), (Some("Lusail"), Some('B')));
+	}
+
+	#[test]
+	fn fixture_with_past_kickoff_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.new_game("Spain", "Brazil").kickoff_at(SystemTime::now()).schedule(SystemTime::now() + Duration::from_secs(600));
+
+		assert_eq!(result, Err(ScoreBoardError::PastKickoff { home: String::from("Spain"), away: String::from("Brazil") }));
+		assert!(sb.get_schedule().is_empty());
+	}
+}
@@ -0,0 +1,274 @@
+//! # Operations on the whole board
+//!
+//! Starts, finishes or abandons many games at once, ex. at the kickoff and the end of a final matchday, where the games of a group are played at the same time
+
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{debug, trace};
+
+use crate::builder::GameDetails;
+use crate::error::ScoreBoardError;
+use crate::state::GameState;
+use crate::text::get_game_id;
+use crate::ScoreBoard;
+
+/// The reason of the games called off by `ScoreBoard::abandon_all()`
+const ABANDON_REASON: &str = "All the games were abandoned";
+
+/// Identifier of a game started with `ScoreBoard::start_games()`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GameId {
+	/// Name of the home team, after following the aliases
+	pub home: String,
+	/// Name of the away team, after following the aliases
+	pub away: String,
+}
+
+impl fmt::Display for GameId {
+	/// Implementation of `Display` trait, allowing it to be converted to a String. Gives the identifier of the game used in the MQTT topics and the Kafka keys, ex. "south-korea-japan"
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", get_game_id(&self.home, &self.away))
+	}
+}
+
+impl ScoreBoard {
+	/// Starts many games at once, as with `start_game()`, in the given order. Every game is started or rejected on its own, so a rejected fixture doesn't stop the others
+	///
+	/// # Arguments
+	///
+	/// * `pairs` - pairs of the home and the away team names. Must be either `String`s or a type that is convertable to `String`
+	///
+	/// # Returns
+	///
+	/// * The outcome of every pair, in the given order: the identifier of the started game, or the reason it wasn't started, the same as the errors of `start_game()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let outcomes = sb.start_games([("Ecuador", "Senegal"), ("Netherlands", "Qatar"), ("Qatar", "Ecuador")]);
+	///
+	/// assert_eq!(outcomes[0].as_ref().map(|id| id.to_string()), Ok(String::from("ecuador-senegal")));
+	/// assert!(outcomes[2].is_err());
+	/// assert_eq!(sb.get_summary().len(), 2);
+	/// ```
+	pub fn start_games<T: ToString, U: ToString, I: IntoIterator<Item = (T, U)>>(&mut self, pairs: I) -> Vec<Result<GameId, ScoreBoardError>> {
+		trace!("Starting many games");
+
+		let outcomes: Vec<Result<GameId, ScoreBoardError>> = pairs.into_iter()
+			.map(|(home, away)| {
+				let home_name = self.resolve_team(home);
+				let away_name = self.resolve_team(away);
+				self.start_new_game(home_name.clone(), away_name.clone(), GameDetails::default())
+					.map(|_| GameId { home: home_name, away: away_name })
+			})
+			.collect();
+
+		debug!("Started {} of {} games", outcomes.iter().filter(|outcome| outcome.is_ok()).count(), outcomes.len());
+
+		outcomes
+	}
+
+	/// Finishes all the running games, as with `finish_game()`, in the order of the board. Either all the games are finished, or none of them
+	///
+	/// # Returns
+	///
+	/// * Number of the finished games
+	///
+	/// # Errors
+	///
+	/// * When any of the games is paused, see `finish_game()`
+	/// * When any of the validators rejects the end of any of the games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Ecuador", "Senegal").unwrap();
+	/// sb.start_game("Netherlands", "Qatar").unwrap();
+	/// sb.update_score("Netherlands", 2, "Qatar", 0).unwrap();
+	///
+	/// assert_eq!(sb.finish_all(), Ok(2));
+	/// assert!(sb.get_summary().is_empty());
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Netherlands 2 - 0 Qatar"), String::from("Ecuador 0 - 0 Senegal")]);
+	/// ```
+	pub fn finish_all(&mut self) -> Result<usize, ScoreBoardError> {
+		trace!("Finishing all the {} games", self.data.len());
+
+		let count = self.staged(|staged| {
+			let running_teams = staged.get_running_teams();
+
+			for (home_name, away_name) in &running_teams {
+				staged.finish_game(home_name, away_name)?;
+			}
+
+			Ok(running_teams.len())
+		})?;
+
+		debug!("Finished {} games", count);
+
+		Ok(count)
+	}
+
+	/// Abandons all the running games and all the scheduled fixtures, as with `set_game_state()` and `GameState::Abandoned`, ex. when a matchday is called off. Every game and fixture gets its `StateChanged` event and is kept in `get_called_off_games()`, so it isn't a reset of the board: the archive, the history of the events and the settings of the board stay. Either all the games and fixtures are abandoned, or none of them
+	///
+	/// # Errors
+	///
+	/// * When abandoning any of the games or fixtures fails, leaving the score board as it was
+	/// * When the journal of the board can't be written (see `set_journal()`), leaving the score board as it was
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.schedule_game("Spain", "Brazil", SystemTime::now() + Duration::from_secs(3600)).unwrap();
+	/// sb.abandon_all().unwrap();
+	///
+	/// assert!(sb.get_summary().is_empty());
+	/// assert!(sb.start_game("Spain", "Brazil").is_ok());
+	/// assert_eq!(sb.get_called_off_games().len(), 2);
+	/// ```
+	pub fn abandon_all(&mut self) -> Result<(), ScoreBoardError> {
+		trace!("Abandoning all the {} games and {} fixtures", self.data.len(), self.schedule.len());
+
+		self.staged(|staged| {
+			let fixtures: Vec<(String, String)> = staged.schedule.iter().map(|fixture| (fixture.home_name.clone(), fixture.away_name.clone())).collect();
+
+			for (home_name, away_name) in staged.get_running_teams().into_iter().chain(fixtures) {
+				staged.change_state(home_name, away_name, GameState::Abandoned, Some(String::from(ABANDON_REASON)))?;
+			}
+
+			Ok(())
+		})?;
+
+		debug!("All the games and fixtures abandoned");
+
+		Ok(())
+	}
+
+	/// Provides the names of the teams of all the running games, in the order of the board
+	fn get_running_teams(&self) -> Vec<(String, String)> {
+		self.data.iter().map(|game| (game.home_team.name.clone(), game.away_team.name.clone())).collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::{Duration, SystemTime};
+
+	use crate::error::Rejection;
+	use crate::events::ScoreBoardEvent;
+	use crate::Command;
+
+	#[test]
+	fn every_fixture_gets_its_outcome() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("NED", "Netherlands");
+		let outcomes = sb.start_games(vec![(String::from("Ecuador"), String::from("Senegal")), (String::from("NED"), String::from("Qatar")), (String::from("Qatar"), String::from("Qatar"))]);
+
+		assert_eq!(outcomes, vec![
+			Ok(GameId { home: String::from("Ecuador"), away: String::from("Senegal") }),
+			Ok(GameId { home: String::from("Netherlands"), away: String::from("Qatar") }),
+			Err(ScoreBoardError::SameTeam(String::from("Qatar"))),
+		]);
+	}
+
+	#[test]
+	fn rejected_fixture_doesnt_stop_the_others() {
+		let mut sb = ScoreBoard::new();
+		sb.set_max_games(2);
+		let outcomes = sb.start_games([("Ecuador", "Senegal"), ("Netherlands", "Qatar"), ("Wales", "England")]);
+
+		assert_eq!(outcomes[2], Err(ScoreBoardError::TooManyGames(2)));
+		assert_eq!(sb.get_summary(), vec![String::from("Netherlands 0 - Qatar 0"), String::from("Ecuador 0 - Senegal 0")]);
+	}
+
+	#[test]
+	fn empty_board_finishes_nothing() {
+		let mut sb = ScoreBoard::new();
+
+		assert_eq!(sb.finish_all(), Ok(0));
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn every_finished_game_gets_its_event() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Ecuador", "Senegal").expect("Couldn't create the first game");
+		sb.start_game("Netherlands", "Qatar").expect("Couldn't create the second game");
+		sb.finish_all().expect("Couldn't finish the games");
+
+		let finished = sb.get_events().iter().filter(|record| matches!(record.event, ScoreBoardEvent::GameFinished { .. })).count();
+		assert_eq!(finished, 2);
+		assert_eq!(sb.get_archive().len(), 2);
+	}
+
+	#[test]
+	fn rejected_finish_keeps_all_the_games() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(|command: &Command, _: &ScoreBoard| match command {
+			Command::FinishGame { home, .. } if home == "Qatar" => Err(Rejection::new("host", "the host plays on")),
+			_ => Ok(()),
+		});
+		sb.start_game("Ecuador", "Senegal").expect("Couldn't create the first game");
+		sb.start_game("Qatar", "Netherlands").expect("Couldn't create the second game");
+
+		assert!(matches!(sb.finish_all(), Err(ScoreBoardError::Rejected(_))));
+		assert_eq!(sb.get_summary().len(), 2);
+		assert!(sb.get_archive().is_empty());
+	}
+
+	#[test]
+	fn all_games_and_fixtures_are_abandoned() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Ecuador", "Senegal").expect("Couldn't create the game");
+		sb.update_score("Ecuador", 1, "Senegal", 1).expect("Couldn't update the game");
+		sb.schedule_game("Qatar", "Netherlands", SystemTime::now() + Duration::from_secs(3600)).expect("Couldn't schedule the first fixture");
+		sb.schedule_game("Qatar", "Netherlands", SystemTime::now() + Duration::from_secs(3600 * 24)).expect("Couldn't schedule the second fixture");
+		sb.abandon_all().expect("Couldn't abandon the games");
+
+		assert!(sb.get_summary().is_empty());
+		assert!(sb.schedule.is_empty());
+		let abandoned = sb.get_events().iter().filter(|record| record.state == GameState::Abandoned).count();
+		assert_eq!(abandoned, 3);
+		let game = &sb.get_called_off_games()[0];
+		assert_eq!((game.home_score, game.reason.as_deref()), (1, Some(ABANDON_REASON)));
+	}
+
+	#[test]
+	fn abandoning_all_keeps_the_archive() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Ecuador", "Senegal").expect("Couldn't create the game");
+		sb.finish_game("Ecuador", "Senegal").expect("Couldn't finish the game");
+		sb.abandon_all().expect("Couldn't abandon the games");
+
+		assert_eq!(sb.get_archive().len(), 1);
+		assert!(sb.get_called_off_games().is_empty());
+	}
+
+	#[test]
+	fn failed_abandon_keeps_the_games_and_fixtures() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Ecuador", "Senegal").expect("Couldn't create the game");
+		sb.schedule_game("Qatar", "Netherlands", SystemTime::now() + Duration::from_secs(3600)).expect("Couldn't schedule the fixture");
+		// Every write to /dev/full fails, as on a full disk
+		sb.set_journal("/dev/full").expect("Couldn't open the journal");
+
+		assert!(matches!(sb.abandon_all(), Err(ScoreBoardError::Storage(_))));
+		assert_eq!(sb.get_summary(), vec![String::from("Ecuador 0 - Senegal 0")]);
+		assert_eq!(sb.schedule.len(), 1);
+		assert!(sb.get_called_off_games().is_empty());
+		assert_eq!(sb.get_events().len(), 1);
+	}
+}
@@ -0,0 +1,221 @@
+//! # Called off games
+//!
+//! Keeps the abandoned games and the postponed fixtures, with the reasons, off the ranking of the board but still visible, since the weather and the crowd incidents stop the games of the real tournaments
+
+use std::string::{String, ToString};
+use std::time::SystemTime;
+
+use log::trace;
+
+use crate::error::ScoreBoardError;
+use crate::state::GameState;
+use crate::ScoreBoard;
+
+/// An abandoned game or a postponed fixture
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CalledOffGame {
+	/// Name of the home team
+	pub home_team: String,
+	/// Score of the home team when the game was called off, 0 for a fixture
+	pub home_score: u8,
+	/// Name of the away team
+	pub away_team: String,
+	/// Score of the away team when the game was called off, 0 for a fixture
+	pub away_score: u8,
+	/// Wall clock time of the start of the game, or the planned start of a fixture
+	pub kickoff_time: SystemTime,
+	/// Either `GameState::Abandoned` or `GameState::Postponed`
+	pub state: GameState,
+	/// The reason, if it was given with `ScoreBoard::abandon_game()` or `ScoreBoard::postpone_fixture()`
+	pub reason: Option<String>,
+	/// Wall clock time when the game was called off
+	pub called_off_time: SystemTime,
+}
+
+impl CalledOffGame {
+	/// Returns a game called off now
+	///
+	/// # Arguments
+	///
+	/// * `home_team` - name of the home team
+	/// * `home_score` - score of the home team
+	/// * `away_team` - name of the away team
+	/// * `away_score` - score of the away team
+	/// * `kickoff_time` - the actual or the planned start of the game
+	/// * `state` - the state the game was moved to
+	/// * `reason` - the reason, if any
+	pub(crate) fn new(home_team: &str, home_score: u8, away_team: &str, away_score: u8, kickoff_time: SystemTime, state: GameState, reason: Option<String>) -> CalledOffGame {
+		CalledOffGame {
+			home_team: String::from(home_team),
+			home_score,
+			away_team: String::from(away_team),
+			away_score,
+			kickoff_time,
+			state,
+			reason,
+			called_off_time: SystemTime::now(),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Abandons a running game or a scheduled fixture. The game is removed from the board or the schedule without being archived, and is kept in `get_called_off_games()` with the reason. The same as `set_game_state()` with `GameState::Abandoned`, with a reason
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `reason` - Why the game was abandoned. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * The same as `set_game_state()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::GameState;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// sb.abandon_game("Japan", "Indonesia", "Thunderstorm").unwrap();
+	///
+	/// assert!(sb.get_summary().is_empty());
+	/// let game = &sb.get_called_off_games()[0];
+	/// assert_eq!((game.home_score, game.state, game.reason.as_deref()), (1, GameState::Abandoned, Some("Thunderstorm")));
+	/// ```
+	pub fn abandon_game<T: ToString, U: ToString, V: ToString>(&mut self, home: T, away: U, reason: V) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+		let reason = reason.to_string();
+
+		operation_span!(tracing::Level::INFO, "abandon_game", home = %home_name, away = %away_name);
+
+		trace!("Abandoning the game between '{}' and '{}': {}", home_name, away_name, reason);
+
+		self.change_state(home_name, away_name, GameState::Abandoned, Some(reason))
+	}
+
+	/// Postpones a scheduled fixture. The earliest fixture between the teams is removed from the schedule, and is kept in `get_called_off_games()` with the reason. The fixture can be scheduled again with `schedule_game()`. The same as `set_game_state()` with `GameState::Postponed`, with a reason
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `reason` - Why the fixture was postponed. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is neither a running nor a scheduled game between the given teams
+	/// * When the game is already running, as a running game can only be abandoned
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	/// use scoreboard_world_cup::GameState;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Japan", "Indonesia", SystemTime::now() + Duration::from_secs(3600)).unwrap();
+	/// sb.postpone_fixture("Japan", "Indonesia", "Waterlogged pitch").unwrap();
+	///
+	/// assert!(sb.start_game("Japan", "Indonesia").is_ok());
+	/// assert_eq!(sb.get_called_off_games()[0].state, GameState::Postponed);
+	/// ```
+	pub fn postpone_fixture<T: ToString, U: ToString, V: ToString>(&mut self, home: T, away: U, reason: V) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+		let reason = reason.to_string();
+
+		operation_span!(tracing::Level::INFO, "postpone_fixture", home = %home_name, away = %away_name);
+
+		trace!("Postponing the fixture between '{}' and '{}': {}", home_name, away_name, reason);
+
+		self.change_state(home_name, away_name, GameState::Postponed, Some(reason))
+	}
+
+	/// Provides the abandoned games and the postponed fixtures, the earliest called off coming first
+	pub fn get_called_off_games(&self) -> &[CalledOffGame] {
+		&self.called_off
+	}
+
+	/// Keeps a game that was called off
+	///
+	/// # Arguments
+	///
+	/// * `game` - the called off game
+	///
+	pub(crate) fn call_off(&mut self, game: CalledOffGame) {
+		self.called_off.push(game);
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::Duration;
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	#[test]
+	fn abandoned_game_leaves_the_ranking() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the second game");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 0).expect("Couldn't update the second game");
+		sb.abandon_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Crowd trouble").expect("Couldn't abandon the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0")]);
+		assert!(sb.get_archive().is_empty());
+
+		let game = &sb.get_called_off_games()[0];
+		assert_eq!((game.home_team.as_str(), game.home_score, game.away_score), (HOME_TEAM_NAME, 2, 0));
+		assert_eq!((game.state, game.reason.as_deref()), (GameState::Abandoned, Some("Crowd trouble")));
+	}
+
+	#[test]
+	fn postponed_fixture_keeps_its_planned_kickoff() {
+		let kickoff = SystemTime::now() + Duration::from_secs(3600);
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff).expect("Couldn't schedule the game");
+		sb.postpone_fixture(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Heavy snow").expect("Couldn't postpone the fixture");
+
+		let game = &sb.get_called_off_games()[0];
+		assert_eq!((game.kickoff_time, game.state), (kickoff, GameState::Postponed));
+		assert_eq!(sb.get_events()[0].state, GameState::Postponed);
+	}
+
+	#[test]
+	fn running_game_cannot_be_postponed() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		let result = sb.postpone_fixture(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Heavy snow");
+
+		assert_eq!(result, Err(ScoreBoardError::IllegalTransition { from: GameState::Live, to: GameState::Postponed }));
+		assert!(sb.get_called_off_games().is_empty());
+	}
+
+	#[test]
+	fn state_change_without_a_reason_is_kept_as_well() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Abandoned).expect("Couldn't abandon the game");
+
+		assert_eq!(sb.get_called_off_games()[0].reason, None);
+	}
+
+	#[test]
+	fn unknown_game_cannot_be_called_off() {
+		let mut sb = ScoreBoard::new();
+
+		assert!(matches!(sb.abandon_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Fog"), Err(ScoreBoardError::GameNotFound { .. })));
+		assert!(matches!(sb.postpone_fixture(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Fog"), Err(ScoreBoardError::GameNotFound { .. })));
+	}
+}
@@ -0,0 +1,288 @@
+//! # Cards and suspensions
+//!
+//! Records the yellow and red cards of the players and works out who is suspended for the next game of a team, so the organizers can check the eligibility before the kickoff
+
+use std::collections::HashMap;
+use std::string::{String, ToString};
+use std::time::SystemTime;
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::goal::Side;
+use crate::player::Player;
+use crate::ScoreBoard;
+
+/// The colour of a card
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CardKind {
+	/// A caution. Two of them in the same game make a red card
+	Yellow,
+	/// A sending off
+	Red,
+}
+
+impl CardKind {
+	/// Name of the colour, as used in the journal
+	pub(crate) fn get_name(&self) -> &'static str {
+		match self {
+			CardKind::Yellow => "yellow",
+			CardKind::Red => "red",
+		}
+	}
+}
+
+/// A card shown to a player in a game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BookedCard {
+	/// The player who got the card
+	pub(crate) player: Player,
+	/// The colour of the card
+	pub(crate) kind: CardKind,
+	/// Wall clock time of the start of the game, telling the games of the same teams apart
+	pub(crate) kickoff_time: SystemTime,
+}
+
+/// Cards of a player, counted while going through the games of the team
+#[derive(Clone, Copy, Debug, Default)]
+struct Discipline {
+	/// Yellow cards from the earlier games, not turned into a suspension yet
+	yellow_cards: u8,
+	/// Whether the player misses the following game of the team
+	suspended: bool,
+}
+
+impl ScoreBoard {
+	/// Records a card shown to a player of a team in a running game. The card refers to the player registered with `register_player()`, or to an unregistered player without a shirt number. The cards are used by `suspended_players()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `side` - The team of the player
+	/// * `player` - Name of the player. Must be either a `String` or a type that is convertable to `String`
+	/// * `kind` - The colour of the card
+	///
+	/// # Errors
+	///
+	/// * When there is no running game between the given teams
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{CardKind, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "Netherlands").unwrap();
+	/// sb.record_card("Argentina", "Netherlands", Side::Away, "Dumfries", CardKind::Red).unwrap();
+	/// sb.finish_game("Argentina", "Netherlands").unwrap();
+	///
+	/// let suspended = sb.suspended_players("Netherlands", "Senegal");
+	/// assert_eq!(suspended.iter().map(|player| player.name.as_str()).collect::<Vec<_>>(), vec!["Dumfries"]);
+	/// ```
+	pub fn record_card<T: ToString, U: ToString, V: ToString>(&mut self, home: T, away: U, side: Side, player: V, kind: CardKind) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+		let player_name = player.to_string();
+
+		trace!("Recording a {:?} card for {} of the {} team of '{}' - '{}'", kind, player_name, side, home_name, away_name);
+
+		let kickoff_time = match self.find_game_index(&home_name, &away_name) {
+			Some(game_index) => self.data[game_index].kickoff_time,
+			None => {
+				warn!("Couldn't find a game for update");
+				return Err(self.game_not_found(home_name, away_name, Operation::UpdateScore));
+			},
+		};
+
+		self.write_ahead_change(&["card", &home_name, &away_name, &side.to_string(), kind.get_name(), &player_name])?;
+
+		let team_name = match side {
+			Side::Home => &home_name,
+			Side::Away => &away_name,
+		};
+		let player = self.get_player(team_name, player_name);
+		self.cards.push(BookedCard { player, kind, kickoff_time });
+
+		Ok(())
+	}
+
+	/// Provides the players suspended for a fixture, the ones of the home team coming first. A player misses the next game of the team after a red card, or after a second yellow card in the same game, and after collecting two yellow cards in different games. The suspensions are served in the following started game of the team, whether it is on the schedule or not
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team of the fixture. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team of the fixture. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{CardKind, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "Saudi Arabia").unwrap();
+	/// sb.record_card("Argentina", "Saudi Arabia", Side::Home, "Romero", CardKind::Yellow).unwrap();
+	/// sb.finish_game("Argentina", "Saudi Arabia").unwrap();
+	/// assert!(sb.suspended_players("Argentina", "Mexico").is_empty());
+	///
+	/// sb.start_game("Argentina", "Mexico").unwrap();
+	/// sb.record_card("Argentina", "Mexico", Side::Home, "Romero", CardKind::Yellow).unwrap();
+	/// sb.finish_game("Argentina", "Mexico").unwrap();
+	/// assert_eq!(sb.suspended_players("Poland", "Argentina")[0].name, "Romero");
+	/// ```
+	pub fn suspended_players<T: ToString, U: ToString>(&self, home: T, away: U) -> Vec<Player> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		trace!("Getting the players suspended for the game between '{}' and '{}'", home_name, away_name);
+
+		let mut suspended = self.get_suspended(&home_name);
+		suspended.extend(self.get_suspended(&away_name));
+
+		suspended
+	}
+
+	/// Provides the players of a team suspended for its next game, ordered by name
+	///
+	/// # Arguments
+	///
+	/// * `team` - name of the team, after following the aliases
+	///
+	fn get_suspended(&self, team: &str) -> Vec<Player> {
+		let finished = self.archive.iter().filter(|game| game.home_team == team || game.away_team == team).map(|game| game.kickoff_time);
+		let running = self.data.iter().filter(|game| game.home_team.name == team || game.away_team.name == team).map(|game| game.kickoff_time);
+		let mut kickoff_times: Vec<SystemTime> = finished.chain(running).collect();
+		kickoff_times.sort();
+
+		let mut players: HashMap<&str, Discipline> = HashMap::new();
+		for kickoff_time in kickoff_times {
+			for discipline in players.values_mut() {
+				discipline.suspended = false;
+			}
+
+			let mut cards: HashMap<&str, (u8, bool)> = HashMap::new();
+			for card in self.cards.iter().filter(|card| card.kickoff_time == kickoff_time && card.player.team == team) {
+				let (yellow_cards, red_card) = cards.entry(&card.player.name).or_default();
+				match card.kind {
+					CardKind::Yellow => *yellow_cards += 1,
+					CardKind::Red => *red_card = true,
+				}
+			}
+
+			for (name, (yellow_cards, red_card)) in cards {
+				let discipline = players.entry(name).or_default();
+				if red_card || yellow_cards >= 2 {
+					discipline.suspended = true;
+				} else {
+					discipline.yellow_cards += yellow_cards;
+					if discipline.yellow_cards >= 2 {
+						discipline.yellow_cards = 0;
+						discipline.suspended = true;
+					}
+				}
+			}
+		}
+
+		let mut suspended: Vec<Player> = players.into_iter()
+			.filter(|(_, discipline)| discipline.suspended)
+			.map(|(name, _)| self.get_player(team, String::from(name)))
+			.collect();
+		suspended.sort_by(|a, b| a.name.cmp(&b.name));
+
+		suspended
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::Duration;
+
+	const TEAM_NAME: &str = "Argentina";
+
+	/// Plays a game of the team, one hour after the previous one, showing the given cards to its players
+	fn play(sb: &mut ScoreBoard, opponent: &str, cards: &[(&str, CardKind)]) {
+		let kickoff = SystemTime::now() - Duration::from_secs(3600 * (10 - sb.get_archive().len() as u64));
+		sb.new_game(TEAM_NAME, opponent).kickoff_at(kickoff).start().expect("Couldn't create the game");
+		for (player, kind) in cards {
+			sb.record_card(TEAM_NAME, opponent, Side::Home, *player, *kind).expect("Couldn't record the card");
+		}
+		sb.finish_game(TEAM_NAME, opponent).expect("Couldn't finish the game");
+	}
+
+	fn get_names(sb: &ScoreBoard) -> Vec<String> {
+		sb.suspended_players(TEAM_NAME, "Croatia").into_iter().map(|player| player.name).collect()
+	}
+
+	#[test]
+	fn two_yellow_cards_in_different_games_suspend() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Saudi Arabia", &[("Romero", CardKind::Yellow), ("Acuna", CardKind::Yellow)]);
+		assert!(get_names(&sb).is_empty());
+
+		play(&mut sb, "Mexico", &[("Romero", CardKind::Yellow)]);
+		assert_eq!(get_names(&sb), vec![String::from("Romero")]);
+	}
+
+	#[test]
+	fn suspension_is_served_in_the_next_game() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Saudi Arabia", &[("Romero", CardKind::Red)]);
+		play(&mut sb, "Mexico", &[]);
+
+		assert!(get_names(&sb).is_empty());
+	}
+
+	#[test]
+	fn second_yellow_card_in_a_game_is_a_red_card() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Saudi Arabia", &[("Acuna", CardKind::Yellow)]);
+		play(&mut sb, "Mexico", &[("Romero", CardKind::Yellow), ("Romero", CardKind::Yellow)]);
+		play(&mut sb, "Poland", &[]);
+		play(&mut sb, "Australia", &[("Romero", CardKind::Yellow)]);
+
+		// The yellow cards of the sending off are not accumulated
+		assert!(get_names(&sb).is_empty());
+	}
+
+	#[test]
+	fn accumulated_yellow_cards_are_cleared_by_the_suspension() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Saudi Arabia", &[("Romero", CardKind::Yellow)]);
+		play(&mut sb, "Mexico", &[("Romero", CardKind::Yellow)]);
+		play(&mut sb, "Poland", &[]);
+		play(&mut sb, "Australia", &[("Romero", CardKind::Yellow)]);
+
+		assert!(get_names(&sb).is_empty());
+	}
+
+	#[test]
+	fn suspensions_of_both_teams_are_provided() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player("Croatia", "Kovacic", 8).expect("Couldn't register the player");
+		sb.start_game(TEAM_NAME, "Croatia").expect("Couldn't create the game");
+		sb.record_card(TEAM_NAME, "Croatia", Side::Away, "Kovacic", CardKind::Red).expect("Couldn't record the first card");
+		sb.record_card(TEAM_NAME, "Croatia", Side::Home, "Paredes", CardKind::Red).expect("Couldn't record the second card");
+
+		let suspended = sb.suspended_players("Croatia", TEAM_NAME);
+		assert_eq!(suspended, vec![
+			Player { name: String::from("Kovacic"), number: Some(8), team: String::from("Croatia") },
+			Player { name: String::from("Paredes"), number: None, team: String::from(TEAM_NAME) },
+		]);
+	}
+
+	#[test]
+	fn card_needs_a_running_game() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.record_card(TEAM_NAME, "Croatia", Side::Home, "Paredes", CardKind::Yellow);
+
+		assert!(matches!(result, Err(ScoreBoardError::GameNotFound { .. })));
+	}
+}
@@ -0,0 +1,164 @@
+//! # Chat message formatting
+//!
+//! Renders games and summaries as the JSON message payloads of Discord and Slack, so the bots announcing goals don't need to implement the rendering themselves
+
+use std::string::String;
+use std::vec::Vec;
+
+use crate::datetime::UtcDateTime;
+use crate::snapshot::GameSnapshot;
+use crate::text::escape_json;
+
+/// Color of the Discord embeds, a football pitch green
+const EMBED_COLOR: u32 = 0x2E7D32;
+
+/// Header of the Slack summary message
+const SLACK_HEADER: &str = "Live scores";
+
+/// Text of the Slack summary message when no games are played
+const SLACK_NO_GAMES: &str = "No games are played at the moment";
+
+/// Renders a game as a Discord message with a single embed, ready to be posted to a Discord webhook. The embed shows the score in the title, both teams with their scores as inline fields, and the kickoff time as its timestamp
+///
+/// # Arguments
+///
+/// * `game` - the game to present, ex. one of `ScoreBoard::snapshot().games`
+///
+/// # Returns
+///
+/// * A JSON object with the `embeds` array
+///
+/// # Examples
+///
+/// ```
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.start_game("Japan", "Indonesia").unwrap();
+/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+/// let message = scoreboard_world_cup::format_discord_embed(&sb.snapshot().games[0]);
+/// assert!(message.starts_with("{\"embeds\":[{\"title\":\"Japan 1 - Indonesia 0\""));
+/// ```
+pub fn format_discord_embed(game: &GameSnapshot) -> String {
+	let home = escape_json(&game.home_team);
+	let away = escape_json(&game.away_team);
+
+	format!(
+		"{{\"embeds\":[{{\"title\":\"{} {} - {} {}\",\"color\":{},\"fields\":[{{\"name\":\"{}\",\"value\":\"{}\",\"inline\":true}},{{\"name\":\"{}\",\"value\":\"{}\",\"inline\":true}}],\"footer\":{{\"text\":\"Revision {}\"}},\"timestamp\":\"{}\"}}]}}",
+		home, game.home_score, away, game.away_score,
+		EMBED_COLOR,
+		home, game.home_score,
+		away, game.away_score,
+		game.revision,
+		UtcDateTime::from_system_time(game.kickoff_time).to_rfc3339(),
+	)
+}
+
+/// Renders a summary as a Slack message in the Block Kit format, ready to be posted to a Slack webhook or `chat.postMessage`. The message has a header followed by one section per game
+///
+/// # Arguments
+///
+/// * `summary` - the lines of the summary, ex. `ScoreBoard::get_summary()`
+///
+/// # Returns
+///
+/// * A JSON object with the `blocks` array
+///
+/// # Examples
+///
+/// ```
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.start_game("Japan", "Indonesia").unwrap();
+/// let message = scoreboard_world_cup::format_slack_blocks(&sb.get_summary());
+/// assert!(message.contains("{\"type\":\"section\",\"text\":{\"type\":\"mrkdwn\",\"text\":\"Japan 0 - Indonesia 0\"}}"));
+/// ```
+pub fn format_slack_blocks(summary: &[String]) -> String {
+	let mut blocks = Vec::with_capacity(summary.len() + 1);
+	blocks.push(format!("{{\"type\":\"header\",\"text\":{{\"type\":\"plain_text\",\"text\":\"{}\"}}}}", SLACK_HEADER));
+
+	if summary.is_empty() {
+		blocks.push(get_slack_section(SLACK_NO_GAMES));
+	}
+
+	for line in summary {
+		blocks.push(get_slack_section(line));
+	}
+
+	format!("{{\"blocks\":[{}]}}", blocks.join(","))
+}
+
+/// Builds a Slack section block with a single line of text
+fn get_slack_section(text: &str) -> String {
+	format!("{{\"type\":\"section\",\"text\":{{\"type\":\"mrkdwn\",\"text\":\"{}\"}}}}", escape_json(&escape_slack(text)))
+}
+
+/// Escapes the characters that have a special meaning in Slack `mrkdwn` text
+fn escape_slack(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::{Duration, UNIX_EPOCH};
+
+	use crate::period::Period;
+	use crate::state::GameState;
+	use crate::timezone::UtcOffset;
+
+	fn get_game(home: &str, home_score: u8, away: &str, away_score: u8) -> GameSnapshot {
+		GameSnapshot {
+			home_team: String::from(home),
+			home_score,
+			away_team: String::from(away),
+			away_score,
+			kickoff_time: UNIX_EPOCH + Duration::from_secs(1_671_375_600),
+			revision: 7,
+			state: GameState::Live,
+			period: Period::FirstHalf,
+			venue: None,
+			group: None,
+			utc_offset: UtcOffset::UTC,
+		}
+	}
+
+	#[test]
+	fn discord_embed_has_the_expected_structure() {
+		let expected = "{\"embeds\":[{\"title\":\"Argentina 3 - France 3\",\"color\":3046706,\"fields\":[{\"name\":\"Argentina\",\"value\":\"3\",\"inline\":true},{\"name\":\"France\",\"value\":\"3\",\"inline\":true}],\"footer\":{\"text\":\"Revision 7\"},\"timestamp\":\"2022-12-18T15:00:00Z\"}]}";
+
+		assert_eq!(format_discord_embed(&get_game("Argentina", 3, "France", 3)), expected);
+	}
+
+	#[test]
+	fn discord_embed_escapes_team_names() {
+		let result = format_discord_embed(&get_game("\"Home\"", 0, "Away\\", 0));
+
+		assert!(result.contains("\"title\":\"\\\"Home\\\" 0 - Away\\\\ 0\""));
+	}
+
+	#[test]
+	fn slack_blocks_have_one_section_per_game() {
+		let summary = vec![String::from("Mexico 0 - Canada 5"), String::from("Spain 1 - Brazil 0")];
+		let expected = "{\"blocks\":[{\"type\":\"header\",\"text\":{\"type\":\"plain_text\",\"text\":\"Live scores\"}},{\"type\":\"section\",\"text\":{\"type\":\"mrkdwn\",\"text\":\"Mexico 0 - Canada 5\"}},{\"type\":\"section\",\"text\":{\"type\":\"mrkdwn\",\"text\":\"Spain 1 - Brazil 0\"}}]}";
+
+		assert_eq!(format_slack_blocks(&summary), expected);
+	}
+
+	#[test]
+	fn empty_summary_gives_a_placeholder_section() {
+		let result = format_slack_blocks(&[]);
+
+		assert!(result.contains(SLACK_NO_GAMES));
+		assert_eq!(result.matches("\"type\":\"section\"").count(), 1);
+	}
+
+	#[test]
+	fn slack_text_is_escaped() {
+		let result = format_slack_blocks(&[String::from("Trinidad & Tobago 1 - <Antigua> \"B\" 0")]);
+
+		assert!(result.contains("\"text\":\"Trinidad &amp; Tobago 1 - &lt;Antigua&gt; \\\"B\\\" 0\""));
+	}
+}
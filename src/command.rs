@@ -0,0 +1,333 @@
+//! # Commands
+//!
+//! Score board operations represented as values, so they can be collected, queued, logged, replayed and applied together
+
+use std::string::String;
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::goal::{GoalKind, Side};
+use crate::state::GameState;
+use crate::ScoreBoard;
+
+/// A single score board operation, applied with `ScoreBoard::apply()`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Command {
+	/// Starts a game, same as `ScoreBoard::start_game()`
+	StartGame {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// Updates a score of a running game, same as `ScoreBoard::update_score()`
+	UpdateScore {
+		/// Name of the home team
+		home: String,
+		/// New score of the home team
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// New score of the away team
+		away_score: u8,
+	},
+	/// Finishes a game, same as `ScoreBoard::finish_game()`
+	FinishGame {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// Adds a single goal to a running game, same as `ScoreBoard::record_goal()`
+	RecordGoal {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+		/// The team the goal counts for
+		side: Side,
+		/// The way the goal was scored
+		kind: GoalKind,
+	},
+	/// Pauses a running game, same as `ScoreBoard::set_game_state()` with `GameState::Paused`
+	PauseGame {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// Resumes a paused game, same as `ScoreBoard::set_game_state()` with `GameState::Live`
+	ResumeGame {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// Brings a finished game back onto the board, same as `ScoreBoard::reopen_game()`
+	ReopenGame {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+}
+
+impl Command {
+	/// Provides the names of the home and the away team of the game the command is about, ex. to queue the commands per game
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let command = scoreboard_world_cup::Command::FinishGame { home: String::from("Japan"), away: String::from("Indonesia") };
+	/// assert_eq!(command.get_teams(), ("Japan", "Indonesia"));
+	/// ```
+	pub fn get_teams(&self) -> (&str, &str) {
+		match self {
+			Command::StartGame { home, away }
+			| Command::UpdateScore { home, away, .. }
+			| Command::FinishGame { home, away }
+			| Command::RecordGoal { home, away, .. }
+			| Command::PauseGame { home, away }
+			| Command::ResumeGame { home, away }
+			| Command::ReopenGame { home, away } => (home, away),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Applies a list of commands atomically: either all of them succeed, or the score board is left unchanged. Commands are applied in the given order, so a later command can refer to a game started by an earlier one
+	///
+	/// # Arguments
+	///
+	/// * `commands` - the commands to apply
+	///
+	/// # Errors
+	///
+	/// * When any of the commands fails. The error names the failing command (counting from 1) and the reason
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Command;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let result = sb.apply_batch(vec![
+	///     Command::StartGame { home: String::from("Japan"), away: String::from("Indonesia") },
+	///     Command::UpdateScore { home: String::from("Japan"), home_score: 1, away: String::from("Indonesia"), away_score: 0 },
+	///     Command::FinishGame { home: String::from("Qatar"), away: String::from("Ecuador") },
+	/// ]);
+	/// assert!(result.is_err());
+	/// assert!(sb.get_summary().is_empty());
+	/// ```
+	pub fn apply_batch(&mut self, commands: Vec<Command>) -> Result<(), ScoreBoardError> {
+		trace!("Applying a batch of {} commands", commands.len());
+
+		let command_count = commands.len();
+
+		self.staged(|staged| {
+			for (id, command) in commands.into_iter().enumerate() {
+				if let Err(error) = staged.apply(command) {
+					warn!("Command {} of {} failed: {}", id + 1, command_count, error);
+					return Err(ScoreBoardError::BatchFailed { index: id, count: command_count, error: Box::new(error) });
+				}
+			}
+
+			Ok(())
+		})?;
+
+		trace!("Batch applied");
+
+		Ok(())
+	}
+
+	/// Applies a single command, the same way as calling the operation it represents. The commands can be kept, sent or logged as values, and applied again later, ex. to replay a game on another board
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command to apply
+	///
+	/// # Errors
+	///
+	/// * When any of the interceptors rejects the command, see `add_interceptor()`
+	/// * The same as the operation the command represents
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Command, GameState};
+	///
+	/// let commands = vec![
+	///     Command::StartGame { home: String::from("Japan"), away: String::from("Indonesia") },
+	///     Command::PauseGame { home: String::from("Japan"), away: String::from("Indonesia") },
+	/// ];
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// for command in commands {
+	///     sb.apply(command).unwrap();
+	/// }
+	/// assert_eq!(sb.get_game("Japan", "Indonesia").unwrap().state, GameState::Paused);
+	/// ```
+	pub fn apply(&mut self, command: Command) -> Result<(), ScoreBoardError> {
+		trace!("Applying the command: {:?}", command);
+
+		self.intercept(command)
+	}
+
+	/// Applies a single command to the board, after it passed all the interceptors
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command to apply
+	///
+	/// # Errors
+	///
+	/// * The same as the operation the command represents
+	///
+	pub(crate) fn dispatch(&mut self, command: Command) -> Result<(), ScoreBoardError> {
+		match command {
+			Command::StartGame { home, away } => self.start_game(home, away),
+			Command::UpdateScore { home, home_score, away, away_score } => self.update_score(home, home_score, away, away_score),
+			Command::FinishGame { home, away } => self.finish_game(home, away),
+			Command::RecordGoal { home, away, side, kind } => self.record_goal(home, away, side, kind),
+			Command::PauseGame { home, away } => self.set_game_state(home, away, GameState::Paused),
+			Command::ResumeGame { home, away } => self.set_game_state(home, away, GameState::Live),
+			Command::ReopenGame { home, away } => self.reopen_game(home, away),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn start(home: &str, away: &str) -> Command {
+		Command::StartGame { home: String::from(home), away: String::from(away) }
+	}
+
+	fn update(home: &str, home_score: u8, away: &str, away_score: u8) -> Command {
+		Command::UpdateScore { home: String::from(home), home_score, away: String::from(away), away_score }
+	}
+
+	fn finish(home: &str, away: &str) -> Command {
+		Command::FinishGame { home: String::from(home), away: String::from(away) }
+	}
+
+	#[test]
+	fn empty_batch_changes_nothing() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.apply_batch(Vec::new());
+
+		assert!(result.is_ok());
+		assert!(sb.get_summary().is_empty());
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn valid_batch_is_applied_in_order() {
+		let expected_summary = vec![String::from("Spain 2 - Brazil 1")];
+
+		let mut sb = ScoreBoard::new();
+		let result = sb.apply_batch(vec![
+			start("Spain", "Brazil"),
+			start("Mexico", "Canada"),
+			update("Spain", 2, "Brazil", 1),
+			finish("Mexico", "Canada"),
+		]);
+
+		assert!(result.is_ok());
+		assert_eq!(sb.get_summary(), expected_summary);
+		assert_eq!(sb.get_events().len(), 5);
+	}
+
+	#[test]
+	fn failing_command_leaves_the_board_unchanged() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Germany", "France").expect("Couldn't create the game");
+		let result = sb.apply_batch(vec![
+			update("Germany", 1, "France", 0),
+			start("Spain", "Brazil"),
+			update("Spain", 1, "Italy", 0),
+		]);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Command 3 of 3 failed: Couldn't find a game for update"));
+		assert_eq!(sb.get_summary(), vec![String::from("Germany 0 - France 0")]);
+		assert_eq!(sb.get_events().len(), 1);
+	}
+
+	#[test]
+	fn first_failing_command_is_reported() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.apply_batch(vec![
+			start("Spain", "Spain"),
+			finish("Mexico", "Canada"),
+		]);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Command 1 of 2 failed: Spain cannot play with itself"));
+	}
+
+	#[test]
+	fn all_the_commands_are_applied() {
+		let game = || (String::from("Spain"), String::from("Brazil"));
+		let mut sb = ScoreBoard::new();
+
+		sb.apply(start("Spain", "Brazil")).expect("Couldn't start the game");
+		let (home, away) = game();
+		sb.apply(Command::RecordGoal { home, away, side: Side::Away, kind: GoalKind::Penalty }).expect("Couldn't record the goal");
+		let (home, away) = game();
+		sb.apply(Command::PauseGame { home, away }).expect("Couldn't pause the game");
+		assert_eq!(sb.get_game("Spain", "Brazil").map(|game| game.state), Some(GameState::Paused));
+		let (home, away) = game();
+		sb.apply(Command::ResumeGame { home, away }).expect("Couldn't resume the game");
+		sb.apply(finish("Spain", "Brazil")).expect("Couldn't finish the game");
+		let (home, away) = game();
+		sb.apply(Command::ReopenGame { home, away }).expect("Couldn't reopen the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 1")]);
+		assert_eq!(sb.get_game("Spain", "Brazil").map(|game| game.state), Some(GameState::Live));
+	}
+
+	#[test]
+	fn failing_command_is_reported() {
+		let mut sb = ScoreBoard::new();
+		let command = Command::PauseGame { home: String::from("Spain"), away: String::from("Brazil") };
+
+		assert!(matches!(sb.apply(command), Err(ScoreBoardError::GameNotFound { .. })));
+	}
+
+	#[test]
+	fn conflicts_inside_the_batch_are_detected() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.apply_batch(vec![
+			start("Spain", "Brazil"),
+			start("Brazil", "Germany"),
+		]);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Command 2 of 2 failed: Brazil is currently playing a game"));
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn failing_batch_brings_back_the_archive() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let result = sb.apply_batch(vec![
+			Command::ReopenGame { home: String::from("Mexico"), away: String::from("Canada") },
+			finish("Spain", "Brazil"),
+			finish("Germany", "France"),
+		]);
+
+		assert!(result.is_err());
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Mexico 0 - 0 Canada")]);
+		assert_eq!(sb.get_events().len(), 4);
+	}
+}
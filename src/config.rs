@@ -0,0 +1,151 @@
+//! # Score board settings
+//!
+//! Gathers the behavioral options of the score board in one structure, so a board can be set up in one step instead of a sequence of setter calls
+
+use std::time::Duration;
+
+use log::trace;
+
+use crate::debounce::DebounceMode;
+use crate::forfeit::DEFAULT_FORFEIT_SCORE;
+use crate::limit::DEFAULT_MAX_SCORE;
+use crate::order::SortOrder;
+use crate::ScoreBoard;
+
+/// Settings of a score board. The default settings are the ones of `ScoreBoard::new()`
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{ScoreBoard, ScoreBoardConfig, SortOrder};
+///
+/// let config = ScoreBoardConfig { sort_order: SortOrder::Kickoff, max_games: Some(16), ..ScoreBoardConfig::default() };
+/// let sb = ScoreBoard::with_config(config);
+/// assert_eq!(sb.get_config(), config);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScoreBoardConfig {
+	/// Order of the games on the board and in the summary, see `ScoreBoard::set_sort_order()`
+	pub sort_order: SortOrder,
+	/// Highest number of the games played at the same time, or `None` for no limit, see `ScoreBoard::set_max_games()`
+	pub max_games: Option<usize>,
+	/// Highest score of a team, or `None` for no limit, see `ScoreBoard::set_max_score()`
+	pub max_score: Option<u8>,
+	/// Score of the winner of an awarded game, see `ScoreBoard::set_forfeit_score()`
+	pub forfeit_score: u8,
+	/// Whether only the known national teams can play, see `ScoreBoard::set_strict_countries()`
+	pub strict_countries: bool,
+	/// Minimum time between two separate updates of the same game with the handling of the faster updates, or `None` for no debouncing, see `ScoreBoard::set_debounce()`
+	pub debounce: Option<(Duration, DebounceMode)>,
+}
+
+impl Default for ScoreBoardConfig {
+	/// Implementation of `Default` trait, with the settings of `ScoreBoard::new()`
+	fn default() -> ScoreBoardConfig {
+		ScoreBoardConfig {
+			sort_order: SortOrder::default(),
+			max_games: None,
+			max_score: Some(DEFAULT_MAX_SCORE),
+			forfeit_score: DEFAULT_FORFEIT_SCORE,
+			strict_countries: false,
+			debounce: None,
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Returns a newly created, empty score board with the given settings
+	///
+	/// # Arguments
+	///
+	/// * `config` - settings of the score board
+	///
+	pub fn with_config(config: ScoreBoardConfig) -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.set_config(config);
+		sb
+	}
+
+	/// Changes all the settings of the score board at once. The running games are reordered according to the new sort order
+	///
+	/// # Arguments
+	///
+	/// * `config` - the new settings of the score board
+	///
+	pub fn set_config(&mut self, config: ScoreBoardConfig) {
+		trace!("Setting the score board up with {:?}", config);
+
+		self.max_games = config.max_games;
+		self.max_score = config.max_score;
+		self.forfeit_score = config.forfeit_score;
+		self.strict_countries = config.strict_countries;
+		match config.debounce {
+			Some((interval, mode)) => self.set_debounce(interval, mode),
+			None => self.clear_debounce(),
+		}
+		self.set_sort_order(config.sort_order);
+	}
+
+	/// Provides the current settings of the score board
+	pub fn get_config(&self) -> ScoreBoardConfig {
+		ScoreBoardConfig {
+			sort_order: self.sort_order,
+			max_games: self.max_games,
+			max_score: self.max_score,
+			forfeit_score: self.forfeit_score,
+			strict_countries: self.strict_countries,
+			debounce: self.get_debounce(),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+
+	fn get_config() -> ScoreBoardConfig {
+		ScoreBoardConfig {
+			sort_order: SortOrder::GoalDifference,
+			max_games: Some(1),
+			max_score: Some(5),
+			forfeit_score: 2,
+			strict_countries: true,
+			debounce: Some((Duration::from_secs(60), DebounceMode::Reject)),
+		}
+	}
+
+	#[test]
+	fn default_config_is_the_one_of_a_new_board() {
+		assert_eq!(ScoreBoard::new().get_config(), ScoreBoardConfig::default());
+	}
+
+	#[test]
+	fn config_options_are_applied() {
+		let mut sb = ScoreBoard::with_config(get_config());
+
+		assert!(sb.start_game("Lakers", "Celtics").is_err());
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		assert!(sb.start_game("Mexico", "Canada").is_err());
+		assert!(sb.update_score("Spain", 6, "Brazil", 0).is_err());
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		assert!(sb.update_score("Spain", 2, "Brazil", 0).is_err());
+		assert_eq!(sb.get_config(), get_config());
+	}
+
+	#[test]
+	fn config_can_be_changed() {
+		let mut sb = ScoreBoard::with_config(get_config());
+		sb.set_config(ScoreBoardConfig::default());
+		sb.start_game("Lakers", "Celtics").expect("Couldn't create the first game");
+		sb.start_game("Porto", "Ajax").expect("Couldn't create the second game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Porto 0 - Ajax 0"), String::from("Lakers 0 - Celtics 0")]);
+		assert_eq!(sb.get_config(), ScoreBoardConfig::default());
+	}
+}
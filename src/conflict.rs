@@ -0,0 +1,205 @@
+//! # Schedule conflicts
+//!
+//! Keeps the schedule consistent, rejecting the fixtures that clash with the scheduled ones: a team playing twice within the time of a game, or a stadium booked for two games at once
+
+use std::fmt;
+use std::string::String;
+use std::time::{Duration, SystemTime};
+
+use log::{trace, warn};
+
+use crate::datetime::UtcDateTime;
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// Time taken by a game on a new score board, with the breaks and the extra time, see `ScoreBoard::set_fixture_window()`
+pub const DEFAULT_FIXTURE_WINDOW: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// A scheduled fixture that clashes with a new one, see `ScoreBoard::schedule_game()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleConflict {
+	/// The team already plays a fixture too close to the kickoff of the new one
+	TeamBusy {
+		/// Name of the team playing both fixtures
+		team: String,
+		/// Name of the home team of the scheduled fixture
+		home: String,
+		/// Name of the away team of the scheduled fixture
+		away: String,
+		/// Kickoff of the scheduled fixture
+		kickoff: SystemTime,
+	},
+	/// The stadium already hosts a fixture too close to the kickoff of the new one
+	VenueTaken {
+		/// Name of the stadium
+		venue: String,
+		/// Name of the home team of the scheduled fixture
+		home: String,
+		/// Name of the away team of the scheduled fixture
+		away: String,
+		/// Kickoff of the scheduled fixture
+		kickoff: SystemTime,
+	},
+}
+
+impl fmt::Display for ScheduleConflict {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ScheduleConflict::TeamBusy { team, home, away, kickoff } => {
+				write!(f, "{} already plays {} - {} at {}", team, home, away, UtcDateTime::from_system_time(*kickoff).to_rfc3339())
+			},
+			ScheduleConflict::VenueTaken { venue, home, away, kickoff } => {
+				write!(f, "{} already hosts {} - {} at {}", venue, home, away, UtcDateTime::from_system_time(*kickoff).to_rfc3339())
+			},
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Sets the time taken by a game. Two fixtures of the same team, or at the same stadium, with the kickoffs closer than that clash, and the later scheduled one is rejected
+	///
+	/// # Arguments
+	///
+	/// * `window` - the time from the kickoff to the end of a game. `Duration::ZERO` turns the checks off
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let kickoff = SystemTime::now() + Duration::from_secs(3600);
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Japan", "Indonesia", kickoff).unwrap();
+	/// let result = sb.schedule_game("Japan", "Spain", kickoff + Duration::from_secs(2 * 3600));
+	/// assert!(result.unwrap_err().to_string().starts_with("Japan already plays Japan - Indonesia at "));
+	///
+	/// sb.set_fixture_window(Duration::from_secs(2 * 3600));
+	/// sb.schedule_game("Japan", "Spain", kickoff + Duration::from_secs(2 * 3600)).unwrap();
+	/// ```
+	pub fn set_fixture_window(&mut self, window: Duration) {
+		trace!("Setting the fixture window to {:?}", window);
+
+		self.fixture_window = window;
+	}
+
+	/// Provides the time taken by a game, see `set_fixture_window()`
+	pub fn get_fixture_window(&self) -> Duration {
+		self.fixture_window
+	}
+
+	/// Checks if a new fixture clashes with any of the scheduled ones
+	///
+	/// # Arguments
+	///
+	/// * `home` - name of the home team
+	/// * `away` - name of the away team
+	/// * `kickoff` - planned start time of the new fixture
+	/// * `venue` - name of the stadium, if it's known
+	///
+	/// # Errors
+	///
+	/// * The conflict with the earliest scheduled fixture that clashes with the new one
+	///
+	pub(crate) fn check_schedule_conflicts(&self, home: &str, away: &str, kickoff: SystemTime, venue: Option<&str>) -> Result<(), ScoreBoardError> {
+		let window = self.fixture_window;
+		let clashes = |other: SystemTime| match other.duration_since(kickoff) {
+			Ok(distance) => distance < window,
+			Err(error) => error.duration() < window,
+		};
+
+		let conflict = self.schedule.iter()
+			.filter(|fixture| clashes(fixture.kickoff))
+			.filter_map(|fixture| {
+				let team = [home, away].into_iter().find(|team| *team == fixture.home_name || *team == fixture.away_name);
+				let shared_venue = venue.filter(|venue| fixture.venue.as_deref() == Some(*venue));
+
+				let conflict = match (team, shared_venue) {
+					(Some(team), _) => ScheduleConflict::TeamBusy {
+						team: String::from(team),
+						home: fixture.home_name.clone(),
+						away: fixture.away_name.clone(),
+						kickoff: fixture.kickoff,
+					},
+					(None, Some(venue)) => ScheduleConflict::VenueTaken {
+						venue: String::from(venue),
+						home: fixture.home_name.clone(),
+						away: fixture.away_name.clone(),
+						kickoff: fixture.kickoff,
+					},
+					(None, None) => return None,
+				};
+
+				Some((fixture.kickoff, conflict))
+			})
+			.min_by_key(|(kickoff, _)| *kickoff)
+			.map(|(_, conflict)| conflict);
+
+		match conflict {
+			Some(conflict) => {
+				warn!("{}", conflict);
+				Err(ScoreBoardError::ScheduleConflict(conflict))
+			},
+			None => Ok(()),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HOUR: Duration = Duration::from_secs(60 * 60);
+
+	/// Provides the time of the given hour of the test
+	fn hour(number: u32) -> SystemTime {
+		SystemTime::UNIX_EPOCH + Duration::from_secs(1_671_375_600) + HOUR * number
+	}
+
+	#[test]
+	fn team_cannot_play_two_overlapping_fixtures() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", hour(0)).expect("Couldn't schedule the first game");
+		let result = sb.schedule_game("Croatia", "Argentina", hour(2));
+
+		assert_eq!(result, Err(ScoreBoardError::ScheduleConflict(ScheduleConflict::TeamBusy {
+			team: String::from("Argentina"),
+			home: String::from("Argentina"),
+			away: String::from("France"),
+			kickoff: hour(0),
+		})));
+		assert!(result.is_err_and(|error| error.to_string() == "Argentina already plays Argentina - France at 2022-12-18T15:00:00Z"));
+		assert!(sb.schedule_game("Croatia", "Argentina", hour(3)).is_ok());
+	}
+
+	#[test]
+	fn venue_cannot_host_two_overlapping_fixtures() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game("Argentina", "France").venue("Lusail").schedule(hour(4)).expect("Couldn't schedule the first game");
+		let result = sb.new_game("Croatia", "Morocco").venue("Lusail").schedule(hour(2));
+
+		assert_eq!(result, Err(ScoreBoardError::ScheduleConflict(ScheduleConflict::VenueTaken {
+			venue: String::from("Lusail"),
+			home: String::from("Argentina"),
+			away: String::from("France"),
+			kickoff: hour(4),
+		})));
+		assert!(sb.new_game("Croatia", "Morocco").venue("Khalifa").schedule(hour(2)).is_ok());
+		assert!(sb.schedule_game("Japan", "Spain", hour(4)).is_ok());
+	}
+
+	#[test]
+	fn conflicts_can_be_turned_off() {
+		let mut sb = ScoreBoard::new();
+		sb.set_fixture_window(Duration::ZERO);
+		sb.schedule_game("Argentina", "France", hour(0)).expect("Couldn't schedule the first game");
+
+		assert!(sb.schedule_game("Argentina", "Croatia", hour(0)).is_ok());
+		assert_eq!(sb.get_fixture_window(), Duration::ZERO);
+	}
+}
@@ -0,0 +1,120 @@
+//! # Countdown to the kickoff
+//!
+//! Combines the live summary with the upcoming fixtures, each with the time left to its kickoff, for the ticker screens shown before the matches
+
+use std::string::String;
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::ScoreBoard;
+
+/// Formats the time left to a kickoff, ex. "00:42:10". The hours aren't wrapped into days
+///
+/// # Arguments
+///
+/// * `left` - the time left
+///
+fn format_countdown(left: Duration) -> String {
+	let seconds = left.as_secs();
+
+	format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+impl ScoreBoard {
+	/// Provides the running games, like `get_summary()`, followed by the scheduled games in the order of their kickoffs, each with the time left to the kickoff, ex. "Argentina - France kicks off in 00:42:10". A fixture past its planned kickoff, but not started yet, shows "kicks off in 00:00:00"
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Croatia", "Morocco").unwrap();
+	/// sb.schedule_game("Argentina", "France", SystemTime::now() + Duration::from_secs(2 * 3600)).unwrap();
+	///
+	/// let lines = sb.get_matchday();
+	/// assert_eq!(lines[0], "Croatia 0 - Morocco 0");
+	/// assert!(lines[1].starts_with("Argentina - France kicks off in 0"));
+	/// ```
+	pub fn get_matchday(&self) -> Vec<String> {
+		self.get_matchday_at(SystemTime::now())
+	}
+
+	/// Provides the running games and the countdowns to the scheduled kickoffs at the given time, see `get_matchday()`. The ticker screens pass the time of their own clock, so the countdowns are recomputed on every refresh
+	///
+	/// # Arguments
+	///
+	/// * `now` - the time the countdowns are counted from
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let kickoff = SystemTime::now() + Duration::from_secs(3600);
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Argentina", "France", kickoff).unwrap();
+	///
+	/// let now = kickoff - Duration::from_secs(42 * 60 + 10);
+	/// assert_eq!(sb.get_matchday_at(now), vec![String::from("Argentina - France kicks off in 00:42:10")]);
+	/// ```
+	pub fn get_matchday_at(&self, now: SystemTime) -> Vec<String> {
+		trace!("Getting the summary with the countdowns to the kickoffs");
+
+		let mut fixtures: Vec<_> = self.schedule.iter().collect();
+		fixtures.sort_by_key(|fixture| fixture.kickoff);
+
+		let countdowns = fixtures.into_iter().map(|fixture| {
+			let left = fixture.kickoff.duration_since(now).unwrap_or(Duration::ZERO);
+
+			format!("{} - {} kicks off in {}", fixture.home_name, fixture.away_name, format_countdown(left))
+		});
+
+		self.get_summary().into_iter().chain(countdowns).collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HOUR: Duration = Duration::from_secs(60 * 60);
+
+	#[test]
+	fn countdown_shows_the_hours_minutes_and_seconds() {
+		assert_eq!(format_countdown(Duration::from_millis(2_530_999)), "00:42:10");
+		assert_eq!(format_countdown(HOUR * 30), "30:00:00");
+		assert_eq!(format_countdown(Duration::ZERO), "00:00:00");
+	}
+
+	#[test]
+	fn fixtures_follow_the_running_games_by_kickoff() {
+		let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Croatia", "Morocco").expect("Couldn't create the game");
+		sb.schedule_game("Argentina", "France", start + HOUR * 5).expect("Couldn't schedule the first game");
+		sb.schedule_game("Japan", "Spain", start + HOUR).expect("Couldn't schedule the second game");
+
+		assert_eq!(sb.get_matchday_at(start), vec![
+			String::from("Croatia 0 - Morocco 0"),
+			String::from("Japan - Spain kicks off in 01:00:00"),
+			String::from("Argentina - France kicks off in 05:00:00"),
+		]);
+	}
+
+	#[test]
+	fn overdue_kickoff_counts_down_to_zero() {
+		let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", start).expect("Couldn't schedule the game");
+
+		assert_eq!(sb.get_matchday_at(start + HOUR), vec![String::from("Argentina - France kicks off in 00:00:00")]);
+	}
+}
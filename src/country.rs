@@ -0,0 +1,344 @@
+//! # Countries
+//!
+//! A small table of the national teams with their FIFA codes and ISO 3166 codes, used to decorate the summary with emoji flags and to reject unknown teams in the strict mode
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::text::find_closest;
+use crate::{ScoreBoard, Team};
+
+/// A national team known to the score board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Country {
+	/// English name of the country, as used by FIFA
+	pub(crate) name: &'static str,
+	/// FIFA trigramme of the country, ex. "ESP"
+	pub(crate) fifa_code: &'static str,
+	/// ISO 3166-1 alpha-2 code of the country, ex. "ES", or the ISO 3166-2 code of a subdivision playing on its own, ex. "GB-ENG"
+	pub(crate) iso_code: &'static str,
+}
+
+/// Shortens the definition of the table
+const fn country(name: &'static str, fifa_code: &'static str, iso_code: &'static str) -> Country {
+	Country { name, fifa_code, iso_code }
+}
+
+/// The known national teams, ordered by name
+pub(crate) const COUNTRIES: &[Country] = &[
+	country("Albania", "ALB", "AL"),
+	country("Algeria", "ALG", "DZ"),
+	country("Angola", "ANG", "AO"),
+	country("Argentina", "ARG", "AR"),
+	country("Armenia", "ARM", "AM"),
+	country("Australia", "AUS", "AU"),
+	country("Austria", "AUT", "AT"),
+	country("Belgium", "BEL", "BE"),
+	country("Bolivia", "BOL", "BO"),
+	country("Bosnia and Herzegovina", "BIH", "BA"),
+	country("Brazil", "BRA", "BR"),
+	country("Bulgaria", "BUL", "BG"),
+	country("Burkina Faso", "BFA", "BF"),
+	country("Cameroon", "CMR", "CM"),
+	country("Canada", "CAN", "CA"),
+	country("Cape Verde", "CPV", "CV"),
+	country("Chad", "CHA", "TD"),
+	country("Chile", "CHI", "CL"),
+	country("China", "CHN", "CN"),
+	country("Colombia", "COL", "CO"),
+	country("Costa Rica", "CRC", "CR"),
+	country("Croatia", "CRO", "HR"),
+	country("Czechia", "CZE", "CZ"),
+	country("Denmark", "DEN", "DK"),
+	country("Ecuador", "ECU", "EC"),
+	country("Egypt", "EGY", "EG"),
+	country("England", "ENG", "GB-ENG"),
+	country("Finland", "FIN", "FI"),
+	country("France", "FRA", "FR"),
+	country("Georgia", "GEO", "GE"),
+	country("Germany", "GER", "DE"),
+	country("Ghana", "GHA", "GH"),
+	country("Greece", "GRE", "GR"),
+	country("Haiti", "HAI", "HT"),
+	country("Honduras", "HON", "HN"),
+	country("Hungary", "HUN", "HU"),
+	country("Iceland", "ISL", "IS"),
+	country("Indonesia", "IDN", "ID"),
+	country("Iran", "IRN", "IR"),
+	country("Iraq", "IRQ", "IQ"),
+	country("Italy", "ITA", "IT"),
+	country("Ivory Coast", "CIV", "CI"),
+	country("Jamaica", "JAM", "JM"),
+	country("Japan", "JPN", "JP"),
+	country("Jordan", "JOR", "JO"),
+	country("Mali", "MLI", "ML"),
+	country("Mexico", "MEX", "MX"),
+	country("Monaco", "MON", "MC"),
+	country("Morocco", "MAR", "MA"),
+	country("Netherlands", "NED", "NL"),
+	country("New Zealand", "NZL", "NZ"),
+	country("Nigeria", "NGA", "NG"),
+	country("North Macedonia", "MKD", "MK"),
+	country("Northern Ireland", "NIR", "GB-NIR"),
+	country("Norway", "NOR", "NO"),
+	country("Panama", "PAN", "PA"),
+	country("Paraguay", "PAR", "PY"),
+	country("Peru", "PER", "PE"),
+	country("Poland", "POL", "PL"),
+	country("Portugal", "POR", "PT"),
+	country("Qatar", "QAT", "QA"),
+	country("Republic of Ireland", "IRL", "IE"),
+	country("Romania", "ROU", "RO"),
+	country("Saudi Arabia", "KSA", "SA"),
+	country("Scotland", "SCO", "GB-SCT"),
+	country("Senegal", "SEN", "SN"),
+	country("Serbia", "SRB", "RS"),
+	country("Slovakia", "SVK", "SK"),
+	country("Slovenia", "SVN", "SI"),
+	country("South Africa", "RSA", "ZA"),
+	country("South Korea", "KOR", "KR"),
+	country("Spain", "ESP", "ES"),
+	country("Sweden", "SWE", "SE"),
+	country("Switzerland", "SUI", "CH"),
+	country("Tunisia", "TUN", "TN"),
+	country("Turkey", "TUR", "TR"),
+	country("Ukraine", "UKR", "UA"),
+	country("United States", "USA", "US"),
+	country("Uruguay", "URU", "UY"),
+	country("Uzbekistan", "UZB", "UZ"),
+	country("Venezuela", "VEN", "VE"),
+	country("Wales", "WAL", "GB-WLS"),
+];
+
+/// Finds a country by its name, FIFA code or ISO code, ignoring the case
+///
+/// # Arguments
+///
+/// * `team` - name or code of the team
+///
+pub(crate) fn find_country(team: &str) -> Option<&'static Country> {
+	COUNTRIES.iter().find(|country| {
+		country.name.eq_ignore_ascii_case(team) || country.fifa_code.eq_ignore_ascii_case(team) || country.iso_code.eq_ignore_ascii_case(team)
+	})
+}
+
+impl Country {
+	/// Provides the emoji flag of the country. Countries use the pair of the regional indicator symbols of their ISO 3166-1 code, and the subdivisions (ex. England) the black flag with the tag sequence of their ISO 3166-2 code
+	pub(crate) fn get_flag(&self) -> String {
+		match self.iso_code.split_once('-') {
+			None => self.iso_code.chars()
+				.filter_map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32)))
+				.collect(),
+			Some((country, subdivision)) => {
+				let mut flag = String::from('\u{1F3F4}');
+				flag.extend(country.chars().chain(subdivision.chars())
+					.filter_map(|c| char::from_u32(0xE0000 + c.to_ascii_lowercase() as u32)));
+				flag.push('\u{E007F}');
+				flag
+			},
+		}
+	}
+}
+
+/// Finds the known country with the name most similar to the given one
+///
+/// # Arguments
+///
+/// * `team` - name of the team
+///
+/// # Returns
+///
+/// * The name of the country, or `None` if no name is similar enough
+///
+pub(crate) fn find_closest_country(team: &str) -> Option<&'static str> {
+	find_closest(team, COUNTRIES.iter().map(|country| country.name))
+}
+
+/// Provides the emoji flag of a national team
+///
+/// # Arguments
+///
+/// * `team` - name, FIFA code or ISO 3166 code of the team, ex. "Spain", "ESP" or "ES"
+///
+/// # Returns
+///
+/// * The flag, or `None` if the team isn't a known country
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(scoreboard_world_cup::get_flag("Spain"), Some(String::from("🇪🇸")));
+/// assert_eq!(scoreboard_world_cup::get_flag("BRA"), Some(String::from("🇧🇷")));
+/// assert_eq!(scoreboard_world_cup::get_flag("Lakers"), None);
+/// ```
+pub fn get_flag(team: &str) -> Option<String> {
+	find_country(team).map(Country::get_flag)
+}
+
+/// Provides the three letter code of a team, for the displays too small for the full names: the FIFA code of a known country, ex. "ESP", and the first three letters or digits of any other name in upper case, ex. "LAK" for "Lakers"
+///
+/// # Arguments
+///
+/// * `team` - name of the team
+///
+#[cfg_attr(not(feature = "embedded-graphics"), allow(dead_code))]
+pub(crate) fn get_team_code(team: &str) -> String {
+	match find_country(team) {
+		Some(country) => String::from(country.fifa_code),
+		None => team.chars().filter(|c| c.is_alphanumeric()).take(3).flat_map(char::to_uppercase).collect(),
+	}
+}
+
+/// Puts the flag of a team on the given side of the text, if the team is a known country
+fn decorate(team: &Team, flag_first: bool) -> String {
+	match get_flag(&team.name) {
+		Some(flag) if flag_first => format!("{} {}", flag, team),
+		Some(flag) => format!("{} {}", team, flag),
+		None => team.to_string(),
+	}
+}
+
+impl ScoreBoard {
+	/// Turns the strict mode on or off. In the strict mode only the known national teams, given by their names or codes (ex. "Spain", "ESP" or "ES"), can be scheduled and started, so typos and junk entries don't reach an official board. The strict mode is off by default
+	///
+	/// # Arguments
+	///
+	/// * `strict` - whether to accept only the known countries
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_strict_countries(true);
+	/// sb.start_game("Spain", "BRA").unwrap();
+	/// let result = sb.start_game("Gremany", "France");
+	/// assert_eq!(result.unwrap_err().to_string(), "Gremany is not a known country, did you mean Germany?");
+	/// ```
+	pub fn set_strict_countries(&mut self, strict: bool) {
+		trace!("Accepting only the known countries: {}", strict);
+
+		self.strict_countries = strict;
+	}
+
+	/// Checks that both teams are known countries, if the strict mode is on
+	///
+	/// # Errors
+	///
+	/// * When any of the teams isn't a known country, with the closest known country as a suggestion
+	///
+	pub(crate) fn check_countries(&self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		if !self.strict_countries {
+			return Ok(());
+		}
+
+		for team in [home, away] {
+			if find_country(team).is_none() {
+				let suggestion = find_closest_country(team).map(String::from);
+				warn!("{} is not a known country", team);
+				return Err(ScoreBoardError::UnknownCountry { team: String::from(team), suggestion });
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Provides the summary of the running games, like `get_summary()`, with the emoji flags of the national teams on the outer sides, ex. "🇪🇸 Spain 10 - Brazil 2 🇧🇷". Teams that aren't known countries are shown without a flag
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 10, "Brazil", 2).unwrap();
+	/// assert_eq!(sb.get_summary_with_flags(), vec![String::from("🇪🇸 Spain 10 - Brazil 2 🇧🇷")]);
+	/// ```
+	pub fn get_summary_with_flags(&self) -> Vec<String> {
+		trace!("Getting the score board summary with flags");
+
+		self.data.iter()
+			.map(|game| format!("{} - {}", decorate(&game.home_team, true), decorate(&game.away_team, false)))
+			.collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn countries_are_found_by_name_or_code() {
+		assert_eq!(find_country("south korea").map(|country| country.fifa_code), Some("KOR"));
+		assert_eq!(find_country("SUI").map(|country| country.name), Some("Switzerland"));
+		assert_eq!(find_country("mx").map(|country| country.name), Some("Mexico"));
+		assert_eq!(find_country("Atlantis"), None);
+	}
+
+	#[test]
+	fn table_is_ordered_and_consistent() {
+		assert!(COUNTRIES.windows(2).all(|pair| pair[0].name < pair[1].name));
+		assert!(COUNTRIES.iter().all(|country| country.fifa_code.len() == 3 && country.iso_code.chars().all(|c| c.is_ascii_uppercase() || c == '-')));
+	}
+
+	#[test]
+	fn subdivisions_have_tag_sequence_flags() {
+		assert_eq!(get_flag("England"), Some(String::from("\u{1F3F4}\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}")));
+		assert_eq!(get_flag("SCO").map(|flag| flag.chars().count()), Some(7));
+	}
+
+	#[test]
+	fn unknown_teams_are_shown_without_flags() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Porto", "Japan").expect("Couldn't create the game");
+		sb.update_score("Porto", 1, "Japan", 3).expect("Couldn't update the game");
+
+		assert_eq!(sb.get_summary_with_flags(), vec![String::from("Porto 1 - Japan 3 🇯🇵")]);
+	}
+
+	#[test]
+	fn team_codes_are_three_letters() {
+		assert_eq!(get_team_code("Spain"), "ESP");
+		assert_eq!(get_team_code("es"), "ESP");
+		assert_eq!(get_team_code("Real Madrid"), "REA");
+		assert_eq!(get_team_code("L.A. Lakers"), "LAL");
+	}
+
+	#[test]
+	fn closest_country_is_suggested() {
+		assert_eq!(find_closest_country("Brasil"), Some("Brazil"));
+		assert_eq!(find_closest_country("south korae"), Some("South Korea"));
+		assert_eq!(find_closest_country("Lakers"), None);
+	}
+
+	#[test]
+	fn strict_mode_rejects_unknown_teams() {
+		let mut sb = ScoreBoard::new();
+		sb.set_strict_countries(true);
+
+		let result = sb.start_game("Lakers", "Celtics");
+		assert_eq!(result, Err(ScoreBoardError::UnknownCountry { team: String::from("Lakers"), suggestion: None }));
+		let result = sb.start_game("Spain", "Brasil");
+		assert!(result.err().is_some_and(|result| result.to_string() == "Brasil is not a known country, did you mean Brazil?"));
+		let result = sb.schedule_game("Porto", "Ajax", std::time::SystemTime::now());
+		assert!(result.is_err());
+
+		sb.start_game("esp", "Brazil").expect("Couldn't create the game");
+		assert_eq!(sb.get_events().len(), 1);
+	}
+
+	#[test]
+	fn strict_mode_is_off_by_default() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Lakers", "Celtics").expect("Couldn't create the game");
+		sb.set_strict_countries(true);
+		sb.set_strict_countries(false);
+
+		sb.start_game("Porto", "Ajax").expect("Couldn't create the other game");
+	}
+}
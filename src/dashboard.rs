@@ -0,0 +1,181 @@
+//! # Desktop dashboards
+//!
+//! A ready-made `egui` widget drawing the running games of the board, for the control-room dashboards. The scores that changed a moment ago are highlighted, and the widget asks for a repaint when the highlight has to go away
+
+use std::time::{Duration, Instant};
+
+use egui::{Color32, Grid, Response, RichText, Ui, Widget};
+use log::trace;
+
+use crate::ScoreBoard;
+
+/// How long a changed score is highlighted by default
+const DEFAULT_HIGHLIGHT: Duration = Duration::from_secs(10);
+
+/// How often the minutes of the games are refreshed while the board has running games
+const CLOCK_REFRESH: Duration = Duration::from_secs(1);
+
+/// An `egui` widget showing the running games in the order of the summary, one row per game with the teams, the score and the minute of the match. A score changed within the highlight time is drawn on the highlight color
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use scoreboard_world_cup::ScoreBoardWidget;
+///
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.start_game("Spain", "Brazil").unwrap();
+///
+/// let ctx = egui::Context::default();
+/// let mut output = ctx.run_ui(egui::RawInput::default(), |ui| {
+///     ui.add(ScoreBoardWidget::new(&sb).highlight_for(Duration::from_secs(5)));
+/// });
+/// output.textures_delta.clear();
+/// ```
+#[derive(Clone, Copy)]
+pub struct ScoreBoardWidget<'a> {
+	/// The shown board
+	board: &'a ScoreBoard,
+	/// How long a changed score is highlighted
+	highlight: Duration,
+	/// Background of a changed score
+	highlight_color: Color32,
+}
+
+impl<'a> ScoreBoardWidget<'a> {
+	/// Creates a widget of a board, highlighting the changed scores for 10 seconds on a gold background
+	///
+	/// # Arguments
+	///
+	/// * `board` - the shown board
+	pub fn new(board: &'a ScoreBoard) -> Self {
+		ScoreBoardWidget { board, highlight: DEFAULT_HIGHLIGHT, highlight_color: Color32::GOLD }
+	}
+
+	/// Sets how long a changed score is highlighted, `Duration::ZERO` for no highlight
+	///
+	/// # Arguments
+	///
+	/// * `highlight` - the time after the change of the score
+	pub fn highlight_for(mut self, highlight: Duration) -> Self {
+		self.highlight = highlight;
+		self
+	}
+
+	/// Sets the background of a changed score
+	///
+	/// # Arguments
+	///
+	/// * `color` - the background
+	pub fn highlight_color(mut self, color: Color32) -> Self {
+		self.highlight_color = color;
+		self
+	}
+}
+
+impl Widget for ScoreBoardWidget<'_> {
+	/// Implementation of `Widget` trait, drawing the games as a grid, or a "No games" label for an empty board
+	fn ui(self, ui: &mut Ui) -> Response {
+		trace!("Drawing {} games in a widget", self.board.data.len());
+
+		if self.board.data.is_empty() {
+			return ui.label("No games");
+		}
+
+		let now = Instant::now();
+		let half_minutes = self.board.get_half_minutes();
+		let mut repaint_after = CLOCK_REFRESH;
+
+		let response = Grid::new("scoreboard_world_cup").striped(true).show(ui, |ui| {
+			for (view, game) in self.board.get_games().into_iter().zip(&self.board.data) {
+				let mut score = RichText::new(format!("{} - {}", view.home_score, view.away_score)).strong();
+				let highlighted_for = view.last_updated
+					.map(|last_updated| self.highlight.saturating_sub(now.saturating_duration_since(last_updated)))
+					.filter(|left| !left.is_zero());
+				if let Some(left) = highlighted_for {
+					score = score.background_color(self.highlight_color).color(Color32::BLACK);
+					repaint_after = repaint_after.min(left);
+				}
+
+				ui.label(view.home_team);
+				ui.label(score);
+				ui.label(view.away_team);
+				ui.label(game.get_minute(now, half_minutes));
+				ui.end_row();
+			}
+		}).response;
+
+		ui.ctx().request_repaint_after(repaint_after);
+
+		response
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::vec::Vec;
+
+	use egui::epaint::Shape;
+	use egui::{Context, RawInput};
+
+	/// Draws the widget and provides the texts it shows, each with the information whether it is highlighted
+	fn draw(widget: ScoreBoardWidget) -> Vec<(String, bool)> {
+		let ctx = Context::default();
+		let mut output = None;
+		for _ in 0..2 {
+			let mut frame = ctx.run_ui(RawInput::default(), |ui| {
+				ui.add(widget);
+			});
+			frame.textures_delta.clear();
+			output = Some(frame);
+		}
+
+		output.expect("Couldn't draw the widget").shapes.into_iter()
+			.filter_map(|clipped| match clipped.shape {
+				Shape::Text(text) => Some((String::from(text.galley.text()), text.galley.job.sections.iter().any(|section| section.format.background == widget.highlight_color))),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn games_are_shown_in_the_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 2, "Brazil", 1).expect("Couldn't update the second game");
+
+		let texts: Vec<String> = draw(ScoreBoardWidget::new(&sb)).into_iter().map(|(text, _)| text).collect();
+		assert_eq!(texts, vec!["Spain", "2 - 1", "Brazil", "1'", "Mexico", "0 - 0", "Canada", "1'"]);
+	}
+
+	#[test]
+	fn only_changed_scores_are_highlighted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 2, "Brazil", 1).expect("Couldn't update the second game");
+
+		let highlighted: Vec<String> = draw(ScoreBoardWidget::new(&sb).highlight_color(Color32::RED)).into_iter()
+			.filter(|(_, highlighted)| *highlighted)
+			.map(|(text, _)| text)
+			.collect();
+		assert_eq!(highlighted, vec!["2 - 1"]);
+		assert!(draw(ScoreBoardWidget::new(&sb).highlight_for(Duration::ZERO)).iter().all(|(_, highlighted)| !highlighted));
+	}
+
+	#[test]
+	fn empty_board_says_so() {
+		let sb = ScoreBoard::new();
+
+		assert_eq!(draw(ScoreBoardWidget::new(&sb)), vec![(String::from("No games"), false)]);
+	}
+}
@@ -0,0 +1,214 @@
+//! # Date and time helpers
+//!
+//! Converts wall clock timestamps to calendar dates and back, so they can be presented in the export formats and read from the imported data without pulling in a date library
+
+use std::string::String;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A calendar date and a time of day, in UTC
+#[derive(Debug, PartialEq)]
+pub(crate) struct UtcDateTime {
+	pub(crate) year: i64,
+	pub(crate) month: u32,
+	pub(crate) day: u32,
+	pub(crate) hour: u32,
+	pub(crate) minute: u32,
+	pub(crate) second: u32,
+}
+
+impl UtcDateTime {
+	/// Converts a wall clock timestamp to a UTC calendar date. Sub-second precision is dropped
+	///
+	/// # Arguments
+	///
+	/// * `time` - the timestamp to convert
+	///
+	pub(crate) fn from_system_time(time: SystemTime) -> UtcDateTime {
+		let seconds = match time.duration_since(UNIX_EPOCH) {
+			Ok(duration) => duration.as_secs() as i64,
+			Err(error) => -(error.duration().as_secs_f64().ceil() as i64),
+		};
+
+		let days = seconds.div_euclid(86_400);
+		let seconds_of_day = seconds.rem_euclid(86_400);
+
+		// Civil from days algorithm, shifting the year to start on the 1st of March so the leap day is the last day of a year
+		let shifted_days = days + 719_468;
+		let era = shifted_days.div_euclid(146_097);
+		let day_of_era = shifted_days.rem_euclid(146_097);
+		let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let shifted_month = (5 * day_of_year + 2) / 153;
+		let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+		let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+		let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+		UtcDateTime {
+			year,
+			month,
+			day,
+			hour: (seconds_of_day / 3_600) as u32,
+			minute: (seconds_of_day % 3_600 / 60) as u32,
+			second: (seconds_of_day % 60) as u32,
+		}
+	}
+
+	/// Parses a date in the RFC 3339 format with the UTC offset, ex. "2022-12-18T15:00:00Z" or "2022-12-18T15:00:00.000Z". Fractions of a second are dropped
+	///
+	/// # Arguments
+	///
+	/// * `text` - the date to parse
+	///
+	/// # Returns
+	///
+	/// * The date, or `None` if the text isn't a valid date in the expected format
+	///
+	#[cfg_attr(not(feature = "football-data"), allow(dead_code))]
+	pub(crate) fn parse_rfc3339(text: &str) -> Option<UtcDateTime> {
+		let text = text.strip_suffix('Z')?;
+		let text = match text.split_once('.') {
+			Some((time, fraction)) if !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit()) => time,
+			Some(_) => return None,
+			None => text,
+		};
+
+		let bytes = text.as_bytes();
+		if bytes.len() != 19 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' || bytes[13] != b':' || bytes[16] != b':' {
+			return None;
+		}
+
+		let number = |from: usize, to: usize| -> Option<u32> {
+			let digits = &text[from..to];
+			if digits.chars().all(|c| c.is_ascii_digit()) { digits.parse().ok() } else { None }
+		};
+
+		let date = UtcDateTime {
+			year: number(0, 4)? as i64,
+			month: number(5, 7)?,
+			day: number(8, 10)?,
+			hour: number(11, 13)?,
+			minute: number(14, 16)?,
+			second: number(17, 19)?,
+		};
+
+		let days_in_month = match date.month {
+			1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+			4 | 6 | 9 | 11 => 30,
+			2 if date.year % 4 == 0 && (date.year % 100 != 0 || date.year % 400 == 0) => 29,
+			2 => 28,
+			_ => return None,
+		};
+
+		if date.day == 0 || date.day > days_in_month || date.hour > 23 || date.minute > 59 || date.second > 59 {
+			return None;
+		}
+
+		Some(date)
+	}
+
+	/// Converts the date back to a wall clock timestamp
+	#[cfg_attr(not(feature = "football-data"), allow(dead_code))]
+	pub(crate) fn to_system_time(&self) -> SystemTime {
+		// Days from civil algorithm, the reverse of the one in `from_system_time()`
+		let year = if self.month <= 2 { self.year - 1 } else { self.year };
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let shifted_month = if self.month > 2 { self.month - 3 } else { self.month + 9 } as i64;
+		let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		let days = era * 146_097 + day_of_era - 719_468;
+
+		let seconds = days * 86_400 + (self.hour * 3_600 + self.minute * 60 + self.second) as i64;
+
+		if seconds >= 0 {
+			UNIX_EPOCH + Duration::from_secs(seconds as u64)
+		} else {
+			UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+		}
+	}
+
+	/// Formats the date in the basic ISO 8601 format used by iCalendar, ex. "20221218T150000Z"
+	pub(crate) fn to_basic_format(&self) -> String {
+		format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z", self.year, self.month, self.day, self.hour, self.minute, self.second)
+	}
+
+	/// Formats the date in the RFC 3339 format used by Atom and JSON feeds, ex. "2022-12-18T15:00:00Z"
+	pub(crate) fn to_rfc3339(&self) -> String {
+		format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", self.year, self.month, self.day, self.hour, self.minute, self.second)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn at(seconds: u64) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(seconds)
+	}
+
+	#[test]
+	fn epoch_is_converted_correctly() {
+		let expected = UtcDateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+
+		assert_eq!(UtcDateTime::from_system_time(UNIX_EPOCH), expected);
+	}
+
+	#[test]
+	fn world_cup_final_is_converted_correctly() {
+		let expected = UtcDateTime { year: 2022, month: 12, day: 18, hour: 15, minute: 0, second: 0 };
+
+		assert_eq!(UtcDateTime::from_system_time(at(1_671_375_600)), expected);
+	}
+
+	#[test]
+	fn leap_day_is_converted_correctly() {
+		let expected = UtcDateTime { year: 2024, month: 2, day: 29, hour: 23, minute: 59, second: 59 };
+
+		assert_eq!(UtcDateTime::from_system_time(at(1_709_251_199)), expected);
+	}
+
+	#[test]
+	fn time_before_epoch_is_converted_correctly() {
+		let expected = UtcDateTime { year: 1969, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+
+		assert_eq!(UtcDateTime::from_system_time(UNIX_EPOCH - Duration::from_secs(1)), expected);
+	}
+
+	#[test]
+	fn basic_format_is_padded() {
+		assert_eq!(UtcDateTime::from_system_time(at(1_668_956_400)).to_basic_format(), "20221120T150000Z");
+	}
+
+	#[test]
+	fn rfc3339_format_is_padded() {
+		assert_eq!(UtcDateTime::from_system_time(at(1_668_956_400)).to_rfc3339(), "2022-11-20T15:00:00Z");
+	}
+
+	#[test]
+	fn rfc3339_date_is_parsed() {
+		let expected = UtcDateTime { year: 2022, month: 12, day: 18, hour: 15, minute: 0, second: 0 };
+
+		assert_eq!(UtcDateTime::parse_rfc3339("2022-12-18T15:00:00Z"), Some(expected));
+		assert!(UtcDateTime::parse_rfc3339("2022-12-18T15:00:00.250Z").is_some());
+	}
+
+	#[test]
+	fn invalid_rfc3339_dates_are_rejected() {
+		for text in ["", "2022-12-18", "2022-12-18T15:00:00", "2022-12-18T15:00:00+01:00", "2022-13-18T15:00:00Z", "2023-02-29T15:00:00Z", "2022-12-18T24:00:00Z", "2022-12-18T15:00:00.Z", "2022-1a-18T15:00:00Z"] {
+			assert_eq!(UtcDateTime::parse_rfc3339(text), None, "'{}' should be rejected", text);
+		}
+	}
+
+	#[test]
+	fn conversion_to_system_time_reverses_the_conversion_from_it() {
+		for seconds in [0, 1_671_375_600, 1_709_251_199, 951_782_400] {
+			assert_eq!(UtcDateTime::from_system_time(at(seconds)).to_system_time(), at(seconds));
+		}
+
+		assert_eq!(UtcDateTime::from_system_time(UNIX_EPOCH - Duration::from_secs(1)).to_system_time(), UNIX_EPOCH - Duration::from_secs(1));
+	}
+}
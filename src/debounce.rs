@@ -0,0 +1,278 @@
+//! # Debouncing of score updates
+//!
+//! Protects the score board from updates of the same game arriving in a quick succession, ex. duplicate webhook deliveries or double-clicks in operator UIs
+
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{debug, trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::events::ScoreBoardEvent;
+use crate::ScoreBoard;
+
+/// What happens to a score update that arrives too soon after the previous update of the same game
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebounceMode {
+	/// The update is rejected with `ScoreBoardError::UpdateTooSoon` and the score stays as it was
+	Reject,
+	/// The update is applied, but it is merged with the previous update instead of being recorded as a separate event. The merged event gets the new score, time and revision, and the next sequence number, and it is passed on to the notified parties again, so they see every update with its own sequence number and the revisions without gaps. `ScoreBoard::get_events()` keeps only the merged event, so the sequence numbers and the revisions of the updates merged into it are missing there. When another event was recorded in the meantime, or the previous update recorded a goal with `ScoreBoard::record_goal()`, the update is recorded as a new event, so no goal is lost
+	Coalesce,
+}
+
+/// The debounce settings of a score board
+#[derive(Clone, Copy)]
+pub(crate) struct DebouncePolicy {
+	/// Minimum time between two separate updates of the same game
+	interval: Duration,
+	/// What happens to the updates coming faster
+	mode: DebounceMode,
+}
+
+impl ScoreBoard {
+	/// Turns on debouncing of score updates. Every game can then be updated at most once per `interval`, and the faster updates are handled according to `mode`
+	///
+	/// # Arguments
+	///
+	/// * `interval` - Minimum time between two separate updates of the same game
+	/// * `mode` - What happens to the updates coming faster
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::Duration;
+	/// use scoreboard_world_cup::DebounceMode;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_debounce(Duration::from_secs(2), DebounceMode::Reject);
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// assert!(sb.update_score("Japan", 1, "Indonesia", 0).is_err());
+	/// ```
+	pub fn set_debounce(&mut self, interval: Duration, mode: DebounceMode) {
+		trace!("Debouncing updates within {:?} with mode {:?}", interval, mode);
+
+		self.debounce = Some(DebouncePolicy { interval, mode });
+	}
+
+	/// Turns off debouncing of score updates
+	pub fn clear_debounce(&mut self) {
+		trace!("Turning off debouncing");
+
+		self.debounce = None;
+	}
+
+	/// Provides the debounce settings, as the interval and the mode, or `None` if debouncing is off
+	pub(crate) fn get_debounce(&self) -> Option<(Duration, DebounceMode)> {
+		self.debounce.map(|policy| (policy.interval, policy.mode))
+	}
+
+	/// Checks if an update of a game comes too soon after its previous update
+	///
+	/// # Arguments
+	///
+	/// * `game_index` - index of the updated game in `data` structure
+	///
+	/// # Returns
+	///
+	/// * `true` if the update should be merged with the previous one, `false` if it should be recorded as a separate event
+	///
+	/// # Errors
+	///
+	/// * When the update comes too soon and the debounce mode is `DebounceMode::Reject`
+	///
+	pub(crate) fn check_debounce(&self, game_index: usize) -> Result<bool, ScoreBoardError> {
+		let policy = match self.debounce {
+			Some(policy) => policy,
+			None => return Ok(false),
+		};
+
+		let game = &self.data[game_index];
+		let too_soon = match game.last_update {
+			Some(last_update) => last_update.elapsed() < policy.interval,
+			None => false,
+		};
+
+		if !too_soon {
+			return Ok(false);
+		}
+
+		match policy.mode {
+			DebounceMode::Reject => {
+				warn!("Update of the game between {} and {} came too soon", game.home_team.name, game.away_team.name);
+				Err(ScoreBoardError::UpdateTooSoon { home: game.home_team.name.clone(), away: game.away_team.name.clone() })
+			},
+			DebounceMode::Coalesce => {
+				debug!("Merging the update of {} and {} with the previous one", game.home_team.name, game.away_team.name);
+				Ok(true)
+			},
+		}
+	}
+
+	/// Merges a score update into the last event, if it is the update of the same game without a recorded goal, and passes the merged event on to the notified parties again, with the next sequence number. Otherwise records the update as a new event
+	///
+	/// # Arguments
+	///
+	/// * `event` - the `ScoreBoardEvent::ScoreUpdated` event to merge
+	/// * `revision` - revision of the updated game, after the update
+	///
+	pub(crate) fn coalesce_event(&mut self, event: ScoreBoardEvent, revision: u64) {
+		if let (Some(last), ScoreBoardEvent::ScoreUpdated { home, away, .. }) = (self.events.last_mut(), &event) {
+			if let ScoreBoardEvent::ScoreUpdated { home: last_home, away: last_away, goal: None, .. } = &last.event {
+				if last_home == home && last_away == away {
+					trace!("Merging event: {:?}", event);
+					last.event = event;
+					last.sequence += 1;
+					last.time = SystemTime::now();
+					last.operator = self.operator.clone();
+					last.revision = revision;
+					if let Some(record) = self.events.last() {
+						self.publish(record);
+					}
+					return;
+				}
+			}
+		}
+
+		self.record_event(event, revision);
+	}
+
+	/// Remembers the time of the last update of a game
+	pub(crate) fn mark_updated(&mut self, game_index: usize) {
+		self.data[game_index].last_update = Some(Instant::now());
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::{String, ToString};
+	use std::thread;
+	use std::vec::Vec;
+
+	use crate::goal::{GoalKind, Side};
+
+	const LONG_INTERVAL: Duration = Duration::from_secs(60);
+	const SHORT_INTERVAL: Duration = Duration::from_millis(20);
+
+	#[test]
+	fn updates_are_not_debounced_by_default() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.update_score("Spain", 1, "Brazil", 0);
+
+		assert!(result.is_ok());
+		assert_eq!(sb.get_events().len(), 3);
+	}
+
+	#[test]
+	fn fast_update_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Reject);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.update_score("Spain", 2, "Brazil", 0);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Update of the game between Spain and Brazil came too soon"));
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 1 - Brazil 0")]);
+		assert_eq!(sb.get_events().len(), 2);
+	}
+
+	#[test]
+	fn other_games_are_not_affected() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Reject);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the first game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the first game");
+		let result = sb.update_score("Mexico", 1, "Canada", 0);
+
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn update_after_the_interval_is_accepted() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(SHORT_INTERVAL, DebounceMode::Reject);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		thread::sleep(SHORT_INTERVAL * 2);
+		let result = sb.update_score("Spain", 2, "Brazil", 0);
+
+		assert!(result.is_ok());
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 2 - Brazil 0")]);
+	}
+
+	#[test]
+	fn fast_updates_are_coalesced_into_one_event() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Coalesce);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game again");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the game once more");
+
+		let events = sb.get_events();
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 2 - Brazil 0")]);
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[1].sequence, 4);
+		assert_eq!(events[1].revision, 4);
+		assert_eq!(events[1].event, ScoreBoardEvent::ScoreUpdated { home: String::from("Spain"), home_score: 2, away: String::from("Brazil"), away_score: 0, goal: None });
+	}
+
+	#[test]
+	fn sequence_goes_on_after_the_merged_updates() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Coalesce);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the game again");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+
+		let sequences: Vec<(u64, u64)> = sb.get_events().iter().map(|record| (record.sequence, record.revision)).collect();
+		assert_eq!(sequences, vec![(1, 1), (3, 3), (4, 1)]);
+	}
+
+	#[test]
+	fn update_is_not_merged_into_a_goal() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Coalesce);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.record_goal("Spain", "Brazil", Side::Home, GoalKind::Penalty).expect("Couldn't record the goal");
+		sb.update_score("Spain", 1, "Brazil", 1).expect("Couldn't update the game");
+
+		let events = sb.get_events();
+		assert_eq!(events.len(), 3);
+		assert!(matches!(&events[1].event, ScoreBoardEvent::ScoreUpdated { home_score: 1, away_score: 0, goal: Some(goal), .. } if goal.kind == GoalKind::Penalty));
+		assert!(matches!(&events[2].event, ScoreBoardEvent::ScoreUpdated { home_score: 1, away_score: 1, goal: None, .. }));
+	}
+
+	#[test]
+	fn update_is_not_merged_across_other_events() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Coalesce);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the game again");
+
+		assert_eq!(sb.get_events().len(), 4);
+	}
+
+	#[test]
+	fn cleared_debounce_accepts_all_updates() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(LONG_INTERVAL, DebounceMode::Reject);
+		sb.clear_debounce();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.update_score("Spain", 2, "Brazil", 0);
+
+		assert!(result.is_ok());
+	}
+}
@@ -0,0 +1,333 @@
+//! # Group draw
+//!
+//! Draws the teams from the seeding pots into groups, keeping the teams of the same confederation apart, so the group games can be put on the board right after the draw. The draw is driven by a seed, so the same seed always gives the same groups
+
+use std::collections::HashMap;
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{debug, trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::random::SeededRandom;
+
+/// A continental confederation of the national teams
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Confederation {
+	/// Asia
+	Afc,
+	/// Africa
+	Caf,
+	/// North and Central America, and the Caribbean
+	Concacaf,
+	/// South America
+	Conmebol,
+	/// Oceania
+	Ofc,
+	/// Europe
+	Uefa,
+}
+
+impl fmt::Display for Confederation {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Confederation::Afc => write!(f, "AFC"),
+			Confederation::Caf => write!(f, "CAF"),
+			Confederation::Concacaf => write!(f, "CONCACAF"),
+			Confederation::Conmebol => write!(f, "CONMEBOL"),
+			Confederation::Ofc => write!(f, "OFC"),
+			Confederation::Uefa => write!(f, "UEFA"),
+		}
+	}
+}
+
+/// A team taking part in the draw
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DrawTeam {
+	/// Name of the team
+	pub name: String,
+	/// Confederation of the team
+	pub confederation: Confederation,
+}
+
+impl DrawTeam {
+	/// Returns a team of the given confederation
+	///
+	/// # Arguments
+	///
+	/// * `name` - Name of the team. Must be either a `String` or a type that is convertable to `String`
+	/// * `confederation` - Confederation of the team
+	pub fn new<T: ToString>(name: T, confederation: Confederation) -> DrawTeam {
+		DrawTeam { name: name.to_string(), confederation }
+	}
+}
+
+/// The limits of the teams of the same confederation in a group
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DrawRules {
+	/// The most teams of the same confederation in a group, unless the confederation has its own limit
+	pub max_per_confederation: usize,
+	/// The limits of the confederations that differ from `max_per_confederation`
+	pub limits: HashMap<Confederation, usize>,
+}
+
+impl DrawRules {
+	/// Returns the rules allowing a single team of every confederation in a group
+	pub fn new() -> DrawRules {
+		DrawRules { max_per_confederation: 1, limits: HashMap::new() }
+	}
+
+	/// Returns the rules of the World Cup: a single team of every confederation in a group, except for UEFA having up to two
+	pub fn world_cup() -> DrawRules {
+		DrawRules::new().with_limit(Confederation::Uefa, 2)
+	}
+
+	/// Sets the limit of a confederation
+	///
+	/// # Arguments
+	///
+	/// * `confederation` - the confederation
+	/// * `max` - the most teams of the confederation in a group
+	pub fn with_limit(mut self, confederation: Confederation, max: usize) -> DrawRules {
+		self.limits.insert(confederation, max);
+		self
+	}
+
+	/// Provides the most teams of a confederation in a group
+	fn get_limit(&self, confederation: Confederation) -> usize {
+		self.limits.get(&confederation).copied().unwrap_or(self.max_per_confederation)
+	}
+}
+
+impl Default for DrawRules {
+	/// Implementation of `Default` trait, the same as `DrawRules::new()`
+	fn default() -> DrawRules {
+		DrawRules::new()
+	}
+}
+
+/// A group of the draw
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Group {
+	/// Letter of the group, from 'A'. Use it with `GameBuilder::group()` for the games of the group
+	pub letter: char,
+	/// The teams of the group, in the order of the pots
+	pub teams: Vec<DrawTeam>,
+}
+
+impl Group {
+	/// Provides all the games of the group, every team playing every other team once, the team from the higher pot at home
+	///
+	/// # Returns
+	///
+	/// * A vector of pairs of the home and the away team names
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{draw_groups, Confederation, DrawRules, DrawTeam};
+	///
+	/// let pots = vec![vec![DrawTeam::new("Qatar", Confederation::Afc)], vec![DrawTeam::new("Ecuador", Confederation::Conmebol)]];
+	/// let groups = draw_groups(&pots, &DrawRules::new(), 2022).unwrap();
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// for (home, away) in groups[0].get_fixtures() {
+	///     sb.new_game(home, away).group(groups[0].letter).start().unwrap();
+	/// }
+	/// assert_eq!(sb.get_game("Qatar", "Ecuador").unwrap().group, Some('A'));
+	/// ```
+	pub fn get_fixtures(&self) -> Vec<(String, String)> {
+		let mut fixtures = Vec::new();
+
+		for (index, home) in self.teams.iter().enumerate() {
+			for away in &self.teams[index + 1..] {
+				fixtures.push((home.name.clone(), away.name.clone()));
+			}
+		}
+
+		fixtures
+	}
+}
+
+/// Draws the teams from the pots into groups. There are as many groups as teams in a pot, and every group gets a team from every pot. The teams of a pot are drawn in a random order, and every drawn team goes to the first group where it's allowed by the rules, also keeping the rest of the draw possible
+///
+/// # Arguments
+///
+/// * `pots` - the seeding pots, the strongest teams coming first
+/// * `rules` - the limits of the teams of the same confederation in a group
+/// * `seed` - the seed of the draw. The same seed gives the same groups
+///
+/// # Errors
+///
+/// * When the pots are empty or have different numbers of teams
+/// * When the teams can't be drawn into groups under the rules
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{draw_groups, Confederation, DrawRules, DrawTeam};
+///
+/// let pots = vec![
+///     vec![DrawTeam::new("Brazil", Confederation::Conmebol), DrawTeam::new("France", Confederation::Uefa)],
+///     vec![DrawTeam::new("Argentina", Confederation::Conmebol), DrawTeam::new("Japan", Confederation::Afc)],
+/// ];
+/// let groups = draw_groups(&pots, &DrawRules::new(), 7).unwrap();
+///
+/// // Brazil and Argentina can't meet in a group
+/// let brazil = groups.iter().find(|group| group.teams[0].name == "Brazil").unwrap();
+/// assert_eq!(brazil.teams[1].name, "Japan");
+/// ```
+pub fn draw_groups(pots: &[Vec<DrawTeam>], rules: &DrawRules, seed: u64) -> Result<Vec<Group>, ScoreBoardError> {
+	trace!("Drawing {} pots with seed {}", pots.len(), seed);
+
+	let group_count = match pots.first() {
+		Some(pot) if !pot.is_empty() => pot.len(),
+		_ => {
+			warn!("There are no teams to draw");
+			return Err(ScoreBoardError::DrawFailed(String::from("There are no teams to draw")));
+		},
+	};
+
+	if let Some((index, pot)) = pots.iter().enumerate().find(|(_, pot)| pot.len() != group_count) {
+		warn!("Pot {} has {} teams instead of {}", index + 1, pot.len(), group_count);
+		return Err(ScoreBoardError::DrawFailed(format!("Pot {} has {} teams instead of {}", index + 1, pot.len(), group_count)));
+	}
+
+	let mut random = SeededRandom::new(seed);
+	let drawn: Vec<DrawTeam> = pots.iter().flat_map(|pot| {
+		let mut pot = pot.clone();
+		random.shuffle(&mut pot);
+		pot
+	}).collect();
+
+	let mut groups: Vec<Vec<&DrawTeam>> = vec![Vec::new(); group_count];
+	if !place(&drawn, 0, group_count, rules, &mut groups) {
+		warn!("The teams can't be drawn under the rules");
+		return Err(ScoreBoardError::DrawFailed(String::from("The teams can't be drawn into groups under the rules")));
+	}
+
+	debug!("Drew {} groups", group_count);
+
+	Ok(('A'..).zip(groups).map(|(letter, teams)| Group { letter, teams: teams.into_iter().cloned().collect() }).collect())
+}
+
+/// Places the drawn teams, starting from the given one, into the groups, trying the groups in order and going back when a team can't be placed anywhere
+///
+/// # Arguments
+///
+/// * `drawn` - all the teams in the order of the draw, pot after pot
+/// * `index` - position of the team to place
+/// * `group_count` - number of the groups, which is the number of the teams in a pot
+/// * `rules` - the limits of the teams of the same confederation in a group
+/// * `groups` - the teams placed so far
+///
+/// # Returns
+///
+/// * Whether all the remaining teams were placed
+fn place<'a>(drawn: &'a [DrawTeam], index: usize, group_count: usize, rules: &DrawRules, groups: &mut Vec<Vec<&'a DrawTeam>>) -> bool {
+	let team = match drawn.get(index) {
+		Some(team) => team,
+		None => return true,
+	};
+	let pot_size = index / group_count + 1;
+	let limit = rules.get_limit(team.confederation);
+
+	for group_index in 0..group_count {
+		let group = &groups[group_index];
+		if group.len() < pot_size && group.iter().filter(|other| other.confederation == team.confederation).count() < limit {
+			groups[group_index].push(team);
+			if place(drawn, index + 1, group_count, rules, groups) {
+				return true;
+			}
+			groups[group_index].pop();
+		}
+	}
+
+	false
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::collections::HashSet;
+
+	fn get_pots() -> Vec<Vec<DrawTeam>> {
+		vec![
+			vec![DrawTeam::new("Qatar", Confederation::Afc), DrawTeam::new("Brazil", Confederation::Conmebol), DrawTeam::new("Belgium", Confederation::Uefa), DrawTeam::new("France", Confederation::Uefa)],
+			vec![DrawTeam::new("Mexico", Confederation::Concacaf), DrawTeam::new("Netherlands", Confederation::Uefa), DrawTeam::new("Denmark", Confederation::Uefa), DrawTeam::new("Germany", Confederation::Uefa)],
+			vec![DrawTeam::new("Senegal", Confederation::Caf), DrawTeam::new("Japan", Confederation::Afc), DrawTeam::new("Uruguay", Confederation::Conmebol), DrawTeam::new("Iran", Confederation::Afc)],
+			vec![DrawTeam::new("Cameroon", Confederation::Caf), DrawTeam::new("Canada", Confederation::Concacaf), DrawTeam::new("Ecuador", Confederation::Conmebol), DrawTeam::new("Saudi Arabia", Confederation::Afc)],
+		]
+	}
+
+	#[test]
+	fn every_group_follows_the_rules() {
+		let pots = get_pots();
+		let rules = DrawRules::world_cup();
+
+		for seed in 0..50 {
+			let groups = draw_groups(&pots, &rules, seed).expect("Couldn't draw the groups");
+			assert_eq!(groups.iter().map(|group| group.letter).collect::<String>(), "ABCD");
+
+			for group in &groups {
+				for (pot, team) in pots.iter().zip(&group.teams) {
+					assert!(pot.contains(team));
+				}
+				for team in &group.teams {
+					let same = group.teams.iter().filter(|other| other.confederation == team.confederation).count();
+					assert!(same <= rules.get_limit(team.confederation), "Seed {} broke the rules in group {}", seed, group.letter);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn same_seed_gives_the_same_groups() {
+		let pots = get_pots();
+		let first = draw_groups(&pots, &DrawRules::world_cup(), 2022).expect("Couldn't draw the groups");
+		let second = draw_groups(&pots, &DrawRules::world_cup(), 2022).expect("Couldn't draw the groups again");
+
+		assert_eq!(first, second);
+	}
+
+	#[test]
+	fn different_seeds_give_different_groups() {
+		let pots = get_pots();
+		let draws: HashSet<Vec<Group>> = (0..20).map(|seed| draw_groups(&pots, &DrawRules::world_cup(), seed).expect("Couldn't draw the groups")).collect();
+
+		assert!(draws.len() > 1);
+	}
+
+	#[test]
+	fn impossible_draw_is_rejected() {
+		let pots = get_pots();
+		let result = draw_groups(&pots, &DrawRules::new(), 1);
+
+		assert_eq!(result, Err(ScoreBoardError::DrawFailed(String::from("The teams can't be drawn into groups under the rules"))));
+	}
+
+	#[test]
+	fn pots_must_have_the_same_size() {
+		let mut pots = get_pots();
+		pots[2].pop();
+
+		assert_eq!(draw_groups(&pots, &DrawRules::world_cup(), 1), Err(ScoreBoardError::DrawFailed(String::from("Pot 3 has 3 teams instead of 4"))));
+		assert!(draw_groups(&[], &DrawRules::world_cup(), 1).is_err());
+	}
+
+	#[test]
+	fn fixtures_pair_every_team_once() {
+		let groups = draw_groups(&get_pots(), &DrawRules::world_cup(), 3).expect("Couldn't draw the groups");
+		let fixtures = groups[0].get_fixtures();
+
+		assert_eq!(fixtures.len(), 6);
+		assert_eq!(fixtures[0], (groups[0].teams[0].name.clone(), groups[0].teams[1].name.clone()));
+	}
+}
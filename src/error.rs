@@ -0,0 +1,318 @@
+//! # Errors
+//!
+//! Typed errors returned by the score board operations. Every error converts to the same human readable message with `to_string()`
+
+use std::boxed::Box;
+use std::error::Error;
+use std::fmt;
+use std::string::String;
+use std::time::Duration;
+
+use crate::conflict::ScheduleConflict;
+use crate::period::Period;
+use crate::policy::PolicyViolation;
+use crate::snapshot::SNAPSHOT_VERSION;
+use crate::state::GameState;
+
+/// The operation that needed an existing game
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+	/// Updating a score, with `update_score()`
+	UpdateScore,
+	/// Finishing a game, with `finish_game()`
+	FinishGame,
+	/// Reopening a finished game, with `reopen_game()`
+	ReopenGame,
+	/// Moving a game to the next period, with `start_period()`
+	StartPeriod,
+	/// Moving a game to another state, with `set_game_state()`
+	ChangeState,
+}
+
+impl fmt::Display for Operation {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Operation::UpdateScore => write!(f, "update"),
+			Operation::FinishGame => write!(f, "removal"),
+			Operation::ReopenGame => write!(f, "reopening"),
+			Operation::StartPeriod => write!(f, "period change"),
+			Operation::ChangeState => write!(f, "state change"),
+		}
+	}
+}
+
+/// A rejection of an operation by a user supplied `Validator`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+	/// Name of the rule that rejected the operation, ex. "max goals per update"
+	pub rule: String,
+	/// Explanation of the rejection
+	pub reason: String,
+}
+
+impl Rejection {
+	/// Creates a new rejection
+	///
+	/// # Arguments
+	///
+	/// * `rule` - Name of the rule that rejected the operation. Must be either a `String` or a type that is convertable to `String`
+	/// * `reason` - Explanation of the rejection. Must be either a `String` or a type that is convertable to `String`
+	pub fn new<T: ToString, U: ToString>(rule: T, reason: U) -> Rejection {
+		Rejection { rule: rule.to_string(), reason: reason.to_string() }
+	}
+}
+
+impl fmt::Display for Rejection {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Rejected by {}: {}", self.rule, self.reason)
+	}
+}
+
+/// An error of a score board operation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScoreBoardError {
+	/// Both teams of a game have the same name
+	SameTeam(String),
+	/// The team is already playing a game
+	AlreadyPlaying(String),
+	/// The game between the two teams is already scheduled for the same time
+	AlreadyScheduled {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// There is no active game between the two teams, or no finished one for `Operation::ReopenGame`
+	GameNotFound {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+		/// The operation that needed the game
+		operation: Operation,
+		/// The running team with the name most similar to a team that isn't playing, if any is similar enough
+		suggestion: Option<String>,
+	},
+	/// The score update came too soon after the previous update of the same game, see `ScoreBoard::set_debounce()`
+	UpdateTooSoon {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// A competition with the same ID is already registered in the `BoardManager`
+	CompetitionAlreadyRegistered(String),
+	/// There is no competition with the given ID in the `BoardManager`
+	CompetitionNotFound(String),
+	/// A score doesn't fit the score board range (0 - 255)
+	ScoreOutOfRange(u32),
+	/// Data couldn't be encoded or decoded. Contains the description of the problem
+	Encoding(String),
+	/// A line of a plain-text feed doesn't follow any of the formats, see `ScoreBoard::apply_feed_line()`. Contains the line
+	InvalidFeedLine(String),
+	/// The external storage of the score board failed. Contains the description of the problem
+	Storage(String),
+	/// A command of a batch failed, so the batch wasn't applied
+	BatchFailed {
+		/// Position of the failing command in the batch, counting from 0
+		index: usize,
+		/// Number of the commands in the batch
+		count: usize,
+		/// The error of the failing command
+		error: Box<ScoreBoardError>,
+	},
+	/// A `Validator` rejected the operation
+	Rejected(Rejection),
+	/// A game can't move to the requested period, because it has already reached it or a later one
+	PeriodOrder {
+		/// The current period of the game
+		current: Period,
+		/// The requested period
+		requested: Period,
+	},
+	/// A game can't move between the given states, see `GameState`
+	IllegalTransition {
+		/// The current state of the game
+		from: GameState,
+		/// The requested state
+		to: GameState,
+	},
+	/// A penalty shootout was given without a winner. Contains the penalties of the home and the away team
+	DrawnShootout(u8, u8),
+	/// The score board already has as many running games as allowed, see `ScoreBoard::set_max_games()`. Contains the limit
+	TooManyGames(usize),
+	/// The team isn't a known country, while the score board only accepts national teams, see `ScoreBoard::set_strict_countries()`
+	UnknownCountry {
+		/// Name of the team
+		team: String,
+		/// The known country with the most similar name, if any is similar enough
+		suggestion: Option<String>,
+	},
+	/// Betting odds can't be paid out, because they aren't a finite number above 1, see `ScoreBoard::set_odds()`. Contains the invalid odds
+	InvalidOdds(String),
+	/// Expected goals aren't a finite number, see `ScoreBoard::record_xg()`. Contains the invalid value
+	InvalidExpectedGoals(String),
+	/// The team isn't playing any of the running games
+	TeamNotPlaying(String),
+	/// Another player of the team already has the shirt number, see `ScoreBoard::register_player()`
+	NumberTaken {
+		/// Name of the team
+		team: String,
+		/// The shirt number
+		number: u8,
+	},
+	/// The group draw failed, see `draw_groups()`. Contains the reason
+	DrawFailed(String),
+	/// The snapshot is of a version this release can't migrate, see `SNAPSHOT_VERSION`. Contains the version of the snapshot
+	UnsupportedSnapshotVersion(u32),
+	/// The score is above the highest plausible score, see `ScoreBoard::set_max_score()`
+	ImplausibleScore {
+		/// The score over the limit
+		score: u8,
+		/// The limit
+		max_score: u8,
+	},
+	/// The team neither plays, nor is scheduled, nor played, see `ScoreBoard::rename_team()`. Contains the name of the team
+	UnknownTeam(String),
+	/// The summary template has an unknown placeholder or an unmatched brace, see `ScoreBoard::set_summary_template()`. Contains the template
+	InvalidTemplate(String),
+	/// The page template can't be parsed or rendered, see `PageTemplate::parse()`. Contains the reason
+	InvalidPageTemplate(String),
+	/// The task owning the board has stopped, see `ScoreBoard::spawn()`
+	BoardStopped,
+	/// The line of a command script doesn't follow any of the formats, see `Command::parse()`. Contains the line
+	InvalidCommand(String),
+	/// The image can't have the requested resolution, see `ScoreBoard::render_png()`
+	InvalidImageSize {
+		/// The requested width
+		width: u32,
+		/// The requested height
+		height: u32,
+	},
+	/// The matchup policy doesn't allow the game, see `ScoreBoard::set_matchup_policy()`
+	PolicyViolation(PolicyViolation),
+	/// The new fixture clashes with a scheduled one, see `ScoreBoard::set_fixture_window()`
+	ScheduleConflict(ScheduleConflict),
+	/// The offset from UTC, in minutes, is over 18 hours either way
+	InvalidUtcOffset(i32),
+	/// The named time zone isn't in the `chrono-tz` database, see `UtcOffset::in_zone()`. Contains the name
+	UnknownTimeZone(String),
+	/// A half of a match can't be shorter than a minute, see `ScoreBoard::set_half_length()`. Contains the requested length
+	InvalidHalfLength(Duration),
+	/// The new name of a team is already used by another team the board knows, see `ScoreBoard::rename_team()`. Contains the name
+	TeamNameTaken(String),
+	/// A fixture was given the time of a past start with `GameBuilder::kickoff_at()`, see `GameBuilder::schedule()`
+	PastKickoff {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+}
+
+impl fmt::Display for ScoreBoardError {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ScoreBoardError::SameTeam(team) => write!(f, "{} cannot play with itself", team),
+			ScoreBoardError::AlreadyPlaying(team) => write!(f, "{} is currently playing a game", team),
+			ScoreBoardError::AlreadyScheduled { home, away } => write!(f, "Game between {} and {} is already scheduled", home, away),
+			ScoreBoardError::GameNotFound { operation, suggestion: None, .. } => write!(f, "Couldn't find a game for {}", operation),
+			ScoreBoardError::GameNotFound { operation, suggestion: Some(suggestion), .. } => write!(f, "Couldn't find a game for {}, did you mean {}?", operation, suggestion),
+			ScoreBoardError::UpdateTooSoon { home, away } => write!(f, "Update of the game between {} and {} came too soon", home, away),
+			ScoreBoardError::CompetitionAlreadyRegistered(competition) => write!(f, "Competition {} is already registered", competition),
+			ScoreBoardError::CompetitionNotFound(competition) => write!(f, "Couldn't find competition {}", competition),
+			ScoreBoardError::ScoreOutOfRange(score) => write!(f, "Score {} is out of range", score),
+			ScoreBoardError::Encoding(description) => write!(f, "{}", description),
+			ScoreBoardError::Storage(description) => write!(f, "{}", description),
+			ScoreBoardError::InvalidFeedLine(line) => write!(f, "Couldn't parse the feed line '{}'", line),
+			ScoreBoardError::BatchFailed { index, count, error } => write!(f, "Command {} of {} failed: {}", index + 1, count, error),
+			ScoreBoardError::Rejected(rejection) => write!(f, "{}", rejection),
+			ScoreBoardError::PeriodOrder { current, requested } => write!(f, "The {} cannot follow the {}", requested, current),
+			ScoreBoardError::IllegalTransition { from, to } => write!(f, "A game cannot go from {} to {}", from, to),
+			ScoreBoardError::DrawnShootout(home, away) => write!(f, "A penalty shootout cannot end with a draw {}-{}", home, away),
+			ScoreBoardError::TooManyGames(max_games) => write!(f, "The score board cannot have more than {} games at the same time", max_games),
+			ScoreBoardError::UnknownCountry { team, suggestion: None } => write!(f, "{} is not a known country", team),
+			ScoreBoardError::UnknownCountry { team, suggestion: Some(suggestion) } => write!(f, "{} is not a known country, did you mean {}?", team, suggestion),
+			ScoreBoardError::InvalidOdds(odds) => write!(f, "Odds {} are not above 1", odds),
+			ScoreBoardError::InvalidExpectedGoals(xg) => write!(f, "Expected goals {} are not a number", xg),
+			ScoreBoardError::TeamNotPlaying(team) => write!(f, "{} isn't playing a game", team),
+			ScoreBoardError::NumberTaken { team, number } => write!(f, "Number {} of {} is already taken", number, team),
+			ScoreBoardError::DrawFailed(reason) => write!(f, "{}", reason),
+			ScoreBoardError::UnsupportedSnapshotVersion(version) => write!(f, "Snapshot version {} is not supported, the newest supported version is {}", version, SNAPSHOT_VERSION),
+			ScoreBoardError::ImplausibleScore { score, max_score } => write!(f, "Score {} is above the maximum of {}", score, max_score),
+			ScoreBoardError::UnknownTeam(team) => write!(f, "Couldn't find the team {}", team),
+			ScoreBoardError::InvalidTemplate(template) => write!(f, "Couldn't parse the summary template '{}'", template),
+			ScoreBoardError::InvalidPageTemplate(reason) => write!(f, "Invalid page template: {}", reason),
+			ScoreBoardError::BoardStopped => write!(f, "The score board task has stopped"),
+			ScoreBoardError::InvalidCommand(line) => write!(f, "Couldn't parse the command '{}'", line),
+			ScoreBoardError::InvalidImageSize { width, height } => write!(f, "Images of {}x{} pixels can't be rendered", width, height),
+			ScoreBoardError::PolicyViolation(violation) => write!(f, "{}", violation),
+			ScoreBoardError::ScheduleConflict(conflict) => write!(f, "{}", conflict),
+			ScoreBoardError::InvalidUtcOffset(minutes) => write!(f, "UTC offset of {} minutes is out of range", minutes),
+			ScoreBoardError::UnknownTimeZone(zone) => write!(f, "Couldn't find the time zone {}", zone),
+			ScoreBoardError::InvalidHalfLength(length) => write!(f, "A half of {:?} is shorter than a minute", length),
+			ScoreBoardError::TeamNameTaken(team) => write!(f, "The name {} is already taken by another team", team),
+			ScoreBoardError::PastKickoff { home, away } => write!(f, "Game between {} and {} can't be scheduled with the time of a past start", home, away),
+		}
+	}
+}
+
+impl Error for ScoreBoardError {
+	/// Provides the error of the failing command for `BatchFailed` errors
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			ScoreBoardError::BatchFailed { error, .. } => Some(error.as_ref()),
+			_ => None,
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::ToString;
+
+	#[test]
+	fn game_not_found_message_names_the_operation() {
+		let error = ScoreBoardError::GameNotFound { home: String::from("Spain"), away: String::from("Brazil"), operation: Operation::UpdateScore, suggestion: None };
+
+		assert_eq!(error.to_string(), "Couldn't find a game for update");
+	}
+
+	#[test]
+	fn game_not_found_message_shows_the_suggestion() {
+		let error = ScoreBoardError::GameNotFound { home: String::from("Spian"), away: String::from("Brazil"), operation: Operation::FinishGame, suggestion: Some(String::from("Spain")) };
+
+		assert_eq!(error.to_string(), "Couldn't find a game for removal, did you mean Spain?");
+	}
+
+	#[test]
+	fn batch_failure_message_counts_from_one() {
+		let error = ScoreBoardError::BatchFailed { index: 0, count: 2, error: Box::new(ScoreBoardError::SameTeam(String::from("Spain"))) };
+
+		assert_eq!(error.to_string(), "Command 1 of 2 failed: Spain cannot play with itself");
+	}
+
+	#[test]
+	fn batch_failure_has_a_source() {
+		let error = ScoreBoardError::BatchFailed { index: 0, count: 1, error: Box::new(ScoreBoardError::AlreadyPlaying(String::from("Spain"))) };
+
+		assert!(error.source().is_some_and(|source| source.to_string() == "Spain is currently playing a game"));
+		assert!(ScoreBoardError::SameTeam(String::from("Spain")).source().is_none());
+	}
+
+	#[test]
+	fn rejection_message_names_the_rule() {
+		let error = ScoreBoardError::Rejected(Rejection::new("max goals", "too many goals at once"));
+
+		assert_eq!(error.to_string(), "Rejected by max goals: too many goals at once");
+	}
+}
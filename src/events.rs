@@ -0,0 +1,650 @@
+//! # Score board events
+//!
+//! Every change of the score board is recorded as an event, so the history of the games can be presented and processed later
+
+use std::string::{String, ToString};
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::archive::Decision;
+use crate::datetime::UtcDateTime;
+use crate::goal::Goal;
+use crate::period::Period;
+use crate::player::Player;
+use crate::state::GameState;
+use crate::text::escape_json;
+use crate::ScoreBoard;
+
+/// A single change of the score board
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScoreBoardEvent {
+	/// A game has started, with the score 0 - 0
+	GameStarted {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// A score of a running game has changed
+	ScoreUpdated {
+		/// Name of the home team
+		home: String,
+		/// New score of the home team
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// New score of the away team
+		away_score: u8,
+		/// The goal that changed the score, if it was recorded with `ScoreBoard::record_goal()`
+		goal: Option<Goal>,
+	},
+	/// A game has finished and was removed from the score board
+	GameFinished {
+		/// Name of the home team
+		home: String,
+		/// Final score of the home team
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// Final score of the away team
+		away_score: u8,
+	},
+	/// A game was paused, resumed or abandoned, or a scheduled game was abandoned. An abandoned game was removed from the score board
+	StateChanged {
+		/// Name of the home team
+		home: String,
+		/// Score of the home team at the change
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// Score of the away team at the change
+		away_score: u8,
+		/// The new state of the game
+		state: GameState,
+	},
+	/// A finished game was brought back onto the score board with `ScoreBoard::reopen_game()`
+	GameReopened {
+		/// Name of the home team
+		home: String,
+		/// Score of the home team, as it was at the finish
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// Score of the away team, as it was at the finish
+		away_score: u8,
+	},
+	/// The final result of a game, recorded right after its `GameFinished`, so the systems that only need the results, like the standings and the news, can skip all the other events
+	ResultFinalized {
+		/// Name of the home team
+		home: String,
+		/// Final score of the home team
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// Final score of the away team
+		away_score: u8,
+		/// Wall clock time from the kickoff to the finish of the game
+		duration: Duration,
+		/// The way the result was decided, ex. after the extra time or the penalties
+		decision: Decision,
+	},
+	/// The home and the away team of a running game were swapped with `ScoreBoard::swap_sides()`. The teams and the scores are the ones after the swap
+	SidesSwapped {
+		/// Name of the new home team
+		home: String,
+		/// Score of the new home team
+		home_score: u8,
+		/// Name of the new away team
+		away: String,
+		/// Score of the new away team
+		away_score: u8,
+	},
+	/// A running game moved to the next period with `ScoreBoard::start_period()`
+	PeriodStarted {
+		/// Name of the home team
+		home: String,
+		/// Score of the home team at the start of the period
+		home_score: u8,
+		/// Name of the away team
+		away: String,
+		/// Score of the away team at the start of the period
+		away_score: u8,
+		/// The period that began
+		period: Period,
+	},
+	/// A team was renamed with `ScoreBoard::rename_team()`. The earlier events keep the names they had when they were recorded, only the history of the games follows the new name
+	TeamRenamed {
+		/// The name the team had before
+		old: String,
+		/// The new name of the team
+		new: String,
+	},
+}
+
+impl ScoreBoardEvent {
+	/// Provides the names of the home and the away team of the game the event is about. A renamed team isn't about a game, so `TeamRenamed` provides the old and the new name
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::ScoreBoardEvent;
+	///
+	/// let event = ScoreBoardEvent::GameStarted { home: String::from("Japan"), away: String::from("Indonesia") };
+	/// assert_eq!(event.get_teams(), ("Japan", "Indonesia"));
+	/// ```
+	pub fn get_teams(&self) -> (&str, &str) {
+		match self {
+			ScoreBoardEvent::GameStarted { home, away } => (home, away),
+			ScoreBoardEvent::ScoreUpdated { home, away, .. } => (home, away),
+			ScoreBoardEvent::GameFinished { home, away, .. } => (home, away),
+			ScoreBoardEvent::StateChanged { home, away, .. } => (home, away),
+			ScoreBoardEvent::GameReopened { home, away, .. } => (home, away),
+			ScoreBoardEvent::ResultFinalized { home, away, .. } => (home, away),
+			ScoreBoardEvent::SidesSwapped { home, away, .. } => (home, away),
+			ScoreBoardEvent::PeriodStarted { home, away, .. } => (home, away),
+			ScoreBoardEvent::TeamRenamed { old, new } => (old, new),
+		}
+	}
+}
+
+/// An event as it was recorded by the score board
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventRecord {
+	/// Number of the event, starting from 1 and increasing by 1 with every recorded event. The score updates merged with `DebounceMode::Coalesce` take a number each, but only the merged event stays in `ScoreBoard::get_events()`
+	pub sequence: u64,
+	/// Wall clock time when the event was recorded
+	pub time: SystemTime,
+	/// The recorded change
+	pub event: ScoreBoardEvent,
+	/// Identity of the operator who made the change, if it was set with `ScoreBoard::set_operator()`
+	pub operator: Option<String>,
+	/// Revision of the changed game after the change. It is 1 for the start of a game and increases by 1 with every update and state change, and the end of a game comes with the next revision. The abandonment of a game that never started has revision 0. A renamed team has the unchanged revision of its running game, or 0 when it isn't playing. A gap between the revisions of the same game means that some of its events were missed, apart from `ScoreBoard::get_events()` after the score updates merged with `DebounceMode::Coalesce`
+	pub revision: u64,
+	/// State of the changed game after the change. A renamed team has the state of its running game, or `GameState::Scheduled` when it isn't playing
+	pub state: GameState,
+}
+
+impl ScoreBoard {
+	/// Provides all the events recorded by the score board, the oldest coming first
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::ScoreBoardEvent;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let events = sb.get_events();
+	/// assert_eq!(events.len(), 1);
+	/// assert_eq!(events[0].event, ScoreBoardEvent::GameStarted { home: String::from("Japan"), away: String::from("Indonesia") });
+	/// ```
+	pub fn get_events(&self) -> &[EventRecord] {
+		&self.events
+	}
+
+	/// Sets the identity of the operator making the following changes. Every recorded event carries the operator that was set at the time, so the changes can be audited later
+	///
+	/// # Arguments
+	///
+	/// * `operator` - Identity of the operator, ex. a user name. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_operator("referee.assistant");
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(sb.get_events()[0].operator, Some(String::from("referee.assistant")));
+	/// ```
+	pub fn set_operator<T: ToString>(&mut self, operator: T) {
+		let operator_name = operator.to_string();
+
+		trace!("Changes are now made by '{}'", operator_name);
+
+		self.operator = Some(operator_name);
+	}
+
+	/// Clears the identity of the operator. The following events are recorded without an operator
+	pub fn clear_operator(&mut self) {
+		trace!("Changes are now made by an unknown operator");
+
+		self.operator = None;
+	}
+
+	/// Provides the identity of the operator making the changes, if it's set
+	pub fn get_operator(&self) -> Option<&str> {
+		self.operator.as_deref()
+	}
+
+	/// Provides the audit log of a game: all the recorded events of the game between the two teams, with their times and operators, the oldest coming first. Games that were already finished are included too, so the log can be used to resolve disputes after the match
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_operator("alice");
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.start_game("Qatar", "Ecuador").unwrap();
+	/// sb.set_operator("bob");
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	///
+	/// let log = sb.get_audit_log("Japan", "Indonesia");
+	/// assert_eq!(log.len(), 2);
+	/// assert_eq!(log[0].operator.as_deref(), Some("alice"));
+	/// assert_eq!(log[1].operator.as_deref(), Some("bob"));
+	/// ```
+	pub fn get_audit_log<T: ToString, U: ToString>(&self, home: T, away: U) -> Vec<&EventRecord> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		trace!("Getting the audit log of the game between '{}' and '{}'", home_name, away_name);
+
+		self.events.iter()
+			// A renamed team isn't a change of a game, even if the old and the new name happen to be the asked teams
+			.filter(|record| !matches!(record.event, ScoreBoardEvent::TeamRenamed { .. }) && record.event.get_teams() == (home_name.as_str(), away_name.as_str()))
+			.collect()
+	}
+
+	/// Records a new event, stamping it with the next sequence number, the current time and the current operator
+	///
+	/// # Arguments
+	///
+	/// * `event` - the change to record
+	/// * `revision` - revision of the changed game, after the change
+	///
+	pub(crate) fn record_event(&mut self, event: ScoreBoardEvent, revision: u64) {
+		trace!("Recording event: {:?}", event);
+
+		let sequence = self.get_last_sequence() + 1;
+
+		let state = self.get_event_state(&event);
+
+		self.events.push(EventRecord { sequence, time: SystemTime::now(), event, operator: self.operator.clone(), revision, state });
+
+		if let Some(record) = self.events.last() {
+			self.publish(record);
+		}
+	}
+
+	/// Records the finish of the last archived game: the `GameFinished` event, followed by its `ResultFinalized`
+	///
+	/// # Arguments
+	///
+	/// * `revision` - revision of the finished game, after the finish
+	///
+	pub(crate) fn record_finish(&mut self, revision: u64) {
+		let result = match self.archive.last() {
+			Some(game) => game.clone(),
+			None => return,
+		};
+
+		self.record_event(ScoreBoardEvent::GameFinished {
+			home: result.home_team.clone(),
+			home_score: result.home_score,
+			away: result.away_team.clone(),
+			away_score: result.away_score,
+		}, revision);
+		self.record_event(ScoreBoardEvent::ResultFinalized {
+			home: result.home_team,
+			home_score: result.home_score,
+			away: result.away_team,
+			away_score: result.away_score,
+			duration: result.finish_time.duration_since(result.kickoff_time).unwrap_or(Duration::ZERO),
+			decision: result.decision,
+		}, revision);
+	}
+
+	/// Passes a recorded event on to the notified parties, like the webhooks, the MQTT broker, the Kafka topic and the JSON Lines sink. The journal isn't one of them, since it gets the changes before they are made, see `write_ahead()`
+	///
+	/// # Arguments
+	///
+	/// * `record` - the recorded event
+	///
+	pub(crate) fn publish(&self, record: &EventRecord) {
+		// Staged changes are passed on once they are applied
+		if self.is_staging() {
+			return;
+		}
+
+		#[cfg(feature = "webhooks")]
+		if let Some(webhooks) = &self.webhooks {
+			webhooks.notify(record);
+		}
+
+		#[cfg(feature = "mqtt")]
+		if let Some(mqtt) = &self.mqtt {
+			mqtt.notify(record);
+		}
+
+		#[cfg(feature = "kafka")]
+		if let Some(kafka) = &self.kafka {
+			kafka.notify(record);
+		}
+
+		if let Some(json_lines) = &self.json_lines {
+			json_lines.notify(record);
+		}
+	}
+
+	/// Provides the sequence number of the last recorded event, including the events removed by `compact_log()`, or 0 when no events were recorded
+	pub(crate) fn get_last_sequence(&self) -> u64 {
+		match (self.events.last(), &self.checkpoint) {
+			(Some(last), _) => last.sequence,
+			(None, Some(checkpoint)) => checkpoint.sequence,
+			(None, None) => 0,
+		}
+	}
+
+	/// Passes all the events recorded after the given one on to the notified parties. Used when staged changes are applied to the board
+	///
+	/// # Arguments
+	///
+	/// * `sequence` - sequence number of the last event that was already passed on
+	///
+	pub(crate) fn publish_events_after(&self, sequence: u64) {
+		for record in self.events.iter().filter(|record| record.sequence > sequence) {
+			self.publish(record);
+		}
+	}
+}
+
+impl EventRecord {
+	/// Presents the event as a JSON object, ex. `{"sequence":2,"time":"2022-12-18T15:10:00Z","type":"score_updated","home":"Japan","home_score":1,"away":"Indonesia","away_score":0,"revision":2,"state":"live","operator":null}`. The `type` is one of "game_started", "score_updated", "game_finished", "state_changed", "game_reopened", "result_finalized", "sides_swapped", "period_started" and "team_renamed", the `state` is one of the `GameState`s in lower case, and the scores of a started game are 0. A score update caused by a recorded goal has an additional `goal` object, ex. `"goal":{"side":"home","kind":"penalty"}`, with the `scorer` if it was recorded, and the `scorer_number` if the scorer is registered, ex. `"goal":{"side":"home","kind":"penalty","scorer":"Messi","scorer_number":10}`. A final result has the additional `duration_ms` of the game and the `decision`, one of "regular_time", "extra_time", "penalties" and "forfeit", with the `penalties` of a shootout, ex. `"duration_ms":7200000,"decision":"penalties","penalties":{"home":4,"away":2}`. A started period has the additional `period`, one of "first_half", "second_half" and "extra_time", ex. `"period":"second_half"`. A renamed team has the `old` and the `new` name instead of the teams and the scores, ex. `"type":"team_renamed","old":"Agrentina","new":"Argentina"`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert!(sb.get_events()[0].to_json().contains("\"type\":\"game_started\",\"home\":\"Japan\""));
+	/// ```
+	pub fn to_json(&self) -> String {
+		let operator = match &self.operator {
+			Some(operator) => format!("\"{}\"", escape_json(operator)),
+			None => String::from("null"),
+		};
+
+		if let ScoreBoardEvent::TeamRenamed { old, new } = &self.event {
+			return format!(
+				"{{\"sequence\":{},\"time\":\"{}\",\"type\":\"team_renamed\",\"old\":\"{}\",\"new\":\"{}\",\"revision\":{},\"state\":\"{}\",\"operator\":{}}}",
+				self.sequence,
+				UtcDateTime::from_system_time(self.time).to_rfc3339(),
+				escape_json(old),
+				escape_json(new),
+				self.revision,
+				self.state.get_name(),
+				operator,
+			);
+		}
+
+		let (kind, home, home_score, away, away_score) = match &self.event {
+			ScoreBoardEvent::GameStarted { home, away } => ("game_started", home, 0, away, 0),
+			ScoreBoardEvent::ScoreUpdated { home, home_score, away, away_score, .. } => ("score_updated", home, *home_score, away, *away_score),
+			ScoreBoardEvent::GameFinished { home, home_score, away, away_score } => ("game_finished", home, *home_score, away, *away_score),
+			ScoreBoardEvent::StateChanged { home, home_score, away, away_score, .. } => ("state_changed", home, *home_score, away, *away_score),
+			ScoreBoardEvent::GameReopened { home, home_score, away, away_score } => ("game_reopened", home, *home_score, away, *away_score),
+			ScoreBoardEvent::ResultFinalized { home, home_score, away, away_score, .. } => ("result_finalized", home, *home_score, away, *away_score),
+			ScoreBoardEvent::SidesSwapped { home, home_score, away, away_score } => ("sides_swapped", home, *home_score, away, *away_score),
+			ScoreBoardEvent::PeriodStarted { home, home_score, away, away_score, .. } => ("period_started", home, *home_score, away, *away_score),
+			ScoreBoardEvent::TeamRenamed { old, new } => ("team_renamed", old, 0, new, 0),
+		};
+
+		let details = match &self.event {
+			ScoreBoardEvent::ScoreUpdated { goal: Some(goal), .. } => {
+				let scorer = match &goal.scorer {
+					Some(Player { name, number: Some(number), .. }) => format!(",\"scorer\":\"{}\",\"scorer_number\":{}", escape_json(name), number),
+					Some(Player { name, number: None, .. }) => format!(",\"scorer\":\"{}\"", escape_json(name)),
+					None => String::new(),
+				};
+				format!(",\"goal\":{{\"side\":\"{}\",\"kind\":\"{}\"{}}}", goal.side, goal.kind.get_name(), scorer)
+			},
+			ScoreBoardEvent::ResultFinalized { duration, decision, .. } => {
+				let penalties = match decision {
+					Decision::AfterPenalties { home, away } => format!(",\"penalties\":{{\"home\":{},\"away\":{}}}", home, away),
+					_ => String::new(),
+				};
+				format!(",\"duration_ms\":{},\"decision\":\"{}\"{}", duration.as_millis(), decision.get_name(), penalties)
+			},
+			ScoreBoardEvent::PeriodStarted { period, .. } => format!(",\"period\":\"{}\"", period.get_name()),
+			_ => String::new(),
+		};
+
+		format!(
+			"{{\"sequence\":{},\"time\":\"{}\",\"type\":\"{}\",\"home\":\"{}\",\"home_score\":{},\"away\":\"{}\",\"away_score\":{},\"revision\":{},\"state\":\"{}\",\"operator\":{}{}}}",
+			self.sequence,
+			UtcDateTime::from_system_time(self.time).to_rfc3339(),
+			kind,
+			escape_json(home),
+			home_score,
+			escape_json(away),
+			away_score,
+			self.revision,
+			self.state.get_name(),
+			operator,
+			details,
+		)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::vec::Vec;
+
+	use crate::goal::{GoalKind, Side};
+
+	const HOME_TEAM_NAME: &str = "Monaco";
+	const AWAY_TEAM_NAME: &str = "Switzerland";
+
+	fn get_event_list(sb: &ScoreBoard) -> Vec<ScoreBoardEvent> {
+		sb.get_events().iter().map(|record| record.event.clone()).collect()
+	}
+
+	#[test]
+	fn new_board_has_no_events() {
+		let sb = ScoreBoard::new();
+
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn game_lifecycle_is_recorded() {
+		let expected_events = vec![
+			ScoreBoardEvent::GameStarted { home: String::from(HOME_TEAM_NAME), away: String::from(AWAY_TEAM_NAME) },
+			ScoreBoardEvent::ScoreUpdated { home: String::from(HOME_TEAM_NAME), home_score: 1, away: String::from(AWAY_TEAM_NAME), away_score: 0, goal: None },
+			ScoreBoardEvent::GameFinished { home: String::from(HOME_TEAM_NAME), home_score: 1, away: String::from(AWAY_TEAM_NAME), away_score: 0 },
+		];
+
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+		let events = get_event_list(&sb);
+
+		assert_eq!(events[..3], expected_events);
+		assert!(matches!(&events[3], ScoreBoardEvent::ResultFinalized { home_score: 1, away_score: 0, decision: Decision::RegularTime, .. }));
+	}
+
+	#[test]
+	fn sequence_numbers_increase_by_one() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 1).expect("Couldn't update the game");
+
+		let sequences: Vec<u64> = sb.get_events().iter().map(|record| record.sequence).collect();
+		assert_eq!(sequences, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn revisions_are_counted_per_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the first game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 0).expect("Couldn't update the first game again");
+		sb.update_score("Mexico", 0, "Canada", 1).expect("Couldn't update the second game");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the first game");
+
+		let revisions: Vec<u64> = sb.get_events().iter().map(|record| record.revision).collect();
+		assert_eq!(revisions, vec![1, 2, 1, 3, 2, 4, 4]);
+	}
+
+	#[test]
+	fn events_have_no_operator_by_default() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert_eq!(sb.get_operator(), None);
+		assert_eq!(sb.get_events()[0].operator, None);
+	}
+
+	#[test]
+	fn events_carry_the_operator_set_at_the_time() {
+		let mut sb = ScoreBoard::new();
+		sb.set_operator("alice");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.set_operator("bob");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		sb.clear_operator();
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+
+		let operators: Vec<Option<String>> = sb.get_events().iter().map(|record| record.operator.clone()).collect();
+		assert_eq!(operators, vec![Some(String::from("alice")), Some(String::from("bob")), None, None]);
+	}
+
+	#[test]
+	fn audit_log_contains_only_the_given_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first game");
+		sb.start_game("Nigeria", "Chad").expect("Couldn't create the second game");
+		sb.update_score("Nigeria", 1, "Chad", 0).expect("Couldn't update the second game");
+		sb.update_score(HOME_TEAM_NAME, 0, AWAY_TEAM_NAME, 1).expect("Couldn't update the first game");
+		let result = sb.get_audit_log(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		let sequences: Vec<u64> = result.iter().map(|record| record.sequence).collect();
+		assert_eq!(sequences, vec![1, 4]);
+	}
+
+	#[test]
+	fn audit_log_covers_finished_games() {
+		let mut sb = ScoreBoard::new();
+		sb.set_operator("alice");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+		let result = sb.get_audit_log(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		assert_eq!(result.len(), 3);
+		assert!(result.iter().all(|record| record.operator.as_deref() == Some("alice")));
+	}
+
+	#[test]
+	fn audit_log_respects_home_and_away_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert!(sb.get_audit_log(AWAY_TEAM_NAME, HOME_TEAM_NAME).is_empty());
+	}
+
+	#[test]
+	fn failed_operations_are_not_recorded() {
+		let mut sb = ScoreBoard::new();
+		let _ = sb.start_game(HOME_TEAM_NAME, HOME_TEAM_NAME);
+		let _ = sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0);
+		let _ = sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn event_is_presented_as_json() {
+		let record = EventRecord {
+			sequence: 3,
+			time: std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_671_375_600),
+			event: ScoreBoardEvent::GameFinished { home: String::from("C\u{f4}te \"d'Ivoire\""), home_score: 2, away: String::from(AWAY_TEAM_NAME), away_score: 1 },
+			operator: Some(String::from("alice")),
+			revision: 4,
+			state: GameState::Finished,
+		};
+		let expected = format!(
+			"{{\"sequence\":3,\"time\":\"2022-12-18T15:00:00Z\",\"type\":\"game_finished\",\"home\":\"C\u{f4}te \\\"d'Ivoire\\\"\",\"home_score\":2,\"away\":\"{}\",\"away_score\":1,\"revision\":4,\"state\":\"finished\",\"operator\":\"alice\"}}",
+			AWAY_TEAM_NAME,
+		);
+
+		assert_eq!(record.to_json(), expected);
+	}
+
+	#[test]
+	fn goal_is_presented_in_json() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Away, GoalKind::OwnGoal).expect("Couldn't record the goal");
+
+		assert!(sb.get_events()[1].to_json().ends_with(",\"operator\":null,\"goal\":{\"side\":\"away\",\"kind\":\"own_goal\"}}"));
+	}
+
+	#[test]
+	fn scorer_is_presented_in_json() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.register_player(AWAY_TEAM_NAME, "Xhaka", 10).expect("Couldn't register the player");
+		sb.record_goal_by(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OpenPlay, "Ben \"Yedder\"").expect("Couldn't record the first goal");
+		sb.record_goal_by(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Away, GoalKind::OpenPlay, "Xhaka").expect("Couldn't record the second goal");
+
+		assert!(sb.get_events()[1].to_json().ends_with(",\"goal\":{\"side\":\"home\",\"kind\":\"open_play\",\"scorer\":\"Ben \\\"Yedder\\\"\"}}"));
+		assert!(sb.get_events()[2].to_json().ends_with(",\"goal\":{\"side\":\"away\",\"kind\":\"open_play\",\"scorer\":\"Xhaka\",\"scorer_number\":10}}"));
+	}
+
+	#[test]
+	fn final_result_is_presented_in_json() {
+		let record = EventRecord {
+			sequence: 8,
+			time: std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_671_375_600),
+			event: ScoreBoardEvent::ResultFinalized {
+				home: String::from("Argentina"),
+				home_score: 3,
+				away: String::from("France"),
+				away_score: 3,
+				duration: Duration::from_secs(9000),
+				decision: Decision::AfterPenalties { home: 4, away: 2 },
+			},
+			operator: None,
+			revision: 9,
+			state: GameState::Finished,
+		};
+
+		assert!(record.to_json().contains("\"type\":\"result_finalized\""));
+		assert!(record.to_json().ends_with(",\"operator\":null,\"duration_ms\":9000000,\"decision\":\"penalties\",\"penalties\":{\"home\":4,\"away\":2}}"));
+	}
+
+	#[test]
+	fn final_result_follows_the_finish() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.finish_game_after(HOME_TEAM_NAME, AWAY_TEAM_NAME, Decision::AfterExtraTime).expect("Couldn't finish the game");
+		let archived = &sb.get_archive()[0];
+
+		let record = &sb.get_events()[2];
+		assert_eq!(record.revision, sb.get_events()[1].revision);
+		assert_eq!(record.event, ScoreBoardEvent::ResultFinalized {
+			home: String::from(HOME_TEAM_NAME),
+			home_score: 0,
+			away: String::from(AWAY_TEAM_NAME),
+			away_score: 0,
+			duration: archived.finish_time.duration_since(archived.kickoff_time).expect("Couldn't measure the game"),
+			decision: Decision::AfterExtraTime,
+		});
+	}
+}
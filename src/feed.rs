@@ -0,0 +1,278 @@
+//! # Plain-text live feed
+//!
+//! Drives the score board with single lines of a text based wire feed, so the integrators don't need to write their own parsers
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::{Command, ScoreBoard};
+
+/// Keyword of a line starting a game
+const KICKOFF_KEYWORD: &str = "KO";
+/// Keyword of a line finishing a game
+const FULL_TIME_KEYWORD: &str = "FT";
+/// Separator of the teams in a line starting a game
+const VERSUS_SEPARATOR: &str = "v";
+
+impl ScoreBoard {
+	/// Applies a single line of a plain-text live feed
+	///
+	/// The feed has three kinds of lines. Keywords are case-insensitive, and the words can be separated with any amount of white space. Team names can have several words, ex. "Costa Rica"
+	///
+	/// | Line | Format | Example | Same as |
+	/// | --- | --- | --- | --- |
+	/// | Kickoff | `KO <home> v <away>` | `KO MEX v CAN` | `start_game("MEX", "CAN")` |
+	/// | Score | `<home> <home score>-<away score> <away>` | `MEX 0-5 CAN` | `update_score("MEX", 0, "CAN", 5)` |
+	/// | Full time | `FT <home> <home score>-<away score> <away>` | `FT MEX 0-5 CAN` | `update_score("MEX", 0, "CAN", 5)` followed by `finish_game("MEX", "CAN")` |
+	///
+	/// A full time line is applied atomically: when the game can't be finished, its score isn't updated either. When the game already has the score of the line, it is only finished, so the line isn't taken for another update, ex. by the debouncing (see `set_debounce()`)
+	///
+	/// # Arguments
+	///
+	/// * `line` - a single line of the feed
+	///
+	/// # Errors
+	///
+	/// * When the line doesn't follow any of the formats
+	/// * When a score doesn't fit the score board range (0 - 255)
+	/// * The same as the operations the line stands for
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.apply_feed_line("KO MEX v CAN").unwrap();
+	/// sb.apply_feed_line("MEX 0-5 CAN").unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("MEX 0 - CAN 5")]);
+	/// sb.apply_feed_line("FT MEX 0-5 CAN").unwrap();
+	/// assert!(sb.get_summary().is_empty());
+	/// ```
+	pub fn apply_feed_line(&mut self, line: &str) -> Result<(), ScoreBoardError> {
+		trace!("Applying feed line '{}'", line);
+
+		let mut commands = parse_feed_line(line)?;
+
+		if let [Command::UpdateScore { home, home_score, away, away_score }, Command::FinishGame { .. }] = commands.as_slice() {
+			if self.get_score(home, away) == Some((*home_score, *away_score)) {
+				trace!("The game already has the final score");
+				commands.remove(0);
+			}
+		}
+
+		match commands.len() {
+			1 => self.apply(commands.remove(0)),
+			_ => self.apply_batch(commands).map_err(|error| match error {
+				ScoreBoardError::BatchFailed { error, .. } => *error,
+				error => error,
+			}),
+		}
+	}
+}
+
+/// Turns a line of the feed into the commands it stands for
+///
+/// # Arguments
+///
+/// * `line` - a single line of the feed
+///
+/// # Errors
+///
+/// * When the line doesn't follow any of the formats, or a score is out of range
+///
+fn parse_feed_line(line: &str) -> Result<Vec<Command>, ScoreBoardError> {
+	let words: Vec<&str> = line.split_whitespace().collect();
+
+	match words.first() {
+		Some(word) if word.eq_ignore_ascii_case(KICKOFF_KEYWORD) => {
+			let separator = words.iter().position(|word| word.eq_ignore_ascii_case(VERSUS_SEPARATOR));
+			match separator {
+				Some(id) if id > 1 && id < words.len() - 1 => Ok(vec![Command::StartGame {
+					home: words[1..id].join(" "),
+					away: words[id + 1..].join(" "),
+				}]),
+				_ => Err(get_invalid_line_error(line)),
+			}
+		},
+		Some(word) if word.eq_ignore_ascii_case(FULL_TIME_KEYWORD) => {
+			let (home, home_score, away, away_score) = parse_score(line, &words[1..])?;
+			Ok(vec![
+				Command::UpdateScore { home: home.clone(), home_score, away: away.clone(), away_score },
+				Command::FinishGame { home, away },
+			])
+		},
+		Some(_) => {
+			let (home, home_score, away, away_score) = parse_score(line, &words)?;
+			Ok(vec![Command::UpdateScore { home, home_score, away, away_score }])
+		},
+		None => Err(get_invalid_line_error(line)),
+	}
+}
+
+/// Parses the `<home> <home score>-<away score> <away>` part of a line
+///
+/// # Arguments
+///
+/// * `line` - the whole line, used in the error message
+/// * `words` - the words of the score part
+///
+/// # Returns
+///
+/// * Name and score of the home team, followed by the name and score of the away team
+///
+fn parse_score(line: &str, words: &[&str]) -> Result<(String, u8, String, u8), ScoreBoardError> {
+	let score_id = match words.iter().position(|word| is_score(word)) {
+		Some(id) if id > 0 && id < words.len() - 1 => id,
+		_ => return Err(get_invalid_line_error(line)),
+	};
+
+	let (home_score, away_score) = match words[score_id].split_once('-') {
+		Some(scores) => scores,
+		None => return Err(get_invalid_line_error(line)),
+	};
+
+	Ok((words[..score_id].join(" "), parse_goals(line, home_score)?, words[score_id + 1..].join(" "), parse_goals(line, away_score)?))
+}
+
+/// Checks if a word looks like a score, ex. "0-5"
+fn is_score(word: &str) -> bool {
+	match word.split_once('-') {
+		Some((home, away)) => !home.is_empty() && !away.is_empty() && home.chars().all(|c| c.is_ascii_digit()) && away.chars().all(|c| c.is_ascii_digit()),
+		None => false,
+	}
+}
+
+/// Converts the goals of a team to the score board range
+fn parse_goals(line: &str, goals: &str) -> Result<u8, ScoreBoardError> {
+	match goals.parse::<u32>() {
+		Ok(goals) => match u8::try_from(goals) {
+			Ok(goals) => Ok(goals),
+			Err(_) => {
+				warn!("Score {} is out of range", goals);
+				Err(ScoreBoardError::ScoreOutOfRange(goals))
+			},
+		},
+		Err(_) => Err(get_invalid_line_error(line)),
+	}
+}
+
+/// Provides the error of a line that doesn't follow any of the formats
+fn get_invalid_line_error(line: &str) -> ScoreBoardError {
+	warn!("Couldn't parse the feed line '{}'", line);
+	ScoreBoardError::InvalidFeedLine(line.to_string())
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::Duration;
+
+	use crate::{DebounceMode, ScoreBoardEvent};
+
+	#[test]
+	fn kickoff_line_starts_a_game() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.apply_feed_line("KO Mexico v Canada");
+
+		assert!(result.is_ok());
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0")]);
+	}
+
+	#[test]
+	fn score_line_updates_a_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		let result = sb.apply_feed_line("MEX 0-5 CAN");
+
+		assert!(result.is_ok());
+		assert_eq!(sb.get_summary(), vec![String::from("MEX 0 - CAN 5")]);
+	}
+
+	#[test]
+	fn full_time_line_records_the_result_and_finishes_the_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		let result = sb.apply_feed_line("FT MEX 1-5 CAN");
+
+		assert!(result.is_ok());
+		assert!(sb.get_summary().is_empty());
+		assert!(sb.get_events().iter().rev().nth(1).is_some_and(|record| record.event == crate::ScoreBoardEvent::GameFinished {
+			home: String::from("MEX"),
+			home_score: 1,
+			away: String::from("CAN"),
+			away_score: 5,
+		}));
+	}
+
+	#[test]
+	fn full_time_line_with_the_current_score_only_finishes_the_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		sb.apply_feed_line("MEX 0-5 CAN").expect("Couldn't apply the score");
+		sb.apply_feed_line("FT MEX 0-5 CAN").expect("Couldn't apply the full time");
+
+		let events: Vec<&ScoreBoardEvent> = sb.get_events().iter().map(|record| &record.event).collect();
+		assert_eq!(events.len(), 4);
+		assert!(matches!(events[2], ScoreBoardEvent::GameFinished { home_score: 0, away_score: 5, .. }));
+		assert_eq!(sb.get_events()[2].revision, 3);
+		assert_eq!(sb.get_archive_summary(), vec![String::from("MEX 0 - 5 CAN")]);
+	}
+
+	#[test]
+	fn full_time_line_isnt_debounced_after_the_same_score() {
+		let mut sb = ScoreBoard::new();
+		sb.set_debounce(Duration::from_secs(60), DebounceMode::Reject);
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		sb.apply_feed_line("MEX 0-5 CAN").expect("Couldn't apply the score");
+
+		assert_eq!(sb.apply_feed_line("FT MEX 0-4 CAN"), Err(ScoreBoardError::UpdateTooSoon { home: String::from("MEX"), away: String::from("CAN") }));
+		assert_eq!(sb.apply_feed_line("FT MEX 0-5 CAN"), Ok(()));
+		assert!(sb.get_summary().is_empty());
+		assert_eq!(sb.get_archive_summary(), vec![String::from("MEX 0 - 5 CAN")]);
+	}
+
+	#[test]
+	fn names_can_have_several_words_and_keywords_any_case() {
+		let mut sb = ScoreBoard::new();
+		sb.apply_feed_line("  ko  Costa Rica V South   Korea ").expect("Couldn't apply the kickoff");
+		sb.apply_feed_line("Costa Rica 2-1 South Korea").expect("Couldn't apply the score");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Costa Rica 2 - South Korea 1")]);
+	}
+
+	#[test]
+	fn malformed_lines_are_rejected() {
+		let mut sb = ScoreBoard::new();
+
+		for line in ["", "KO", "KO MEX", "KO MEX v", "KO v CAN", "MEX CAN", "MEX 0-5", "0-5 CAN", "MEX 0:5 CAN", "FT MEX CAN", "MEX -5 CAN"] {
+			let result = sb.apply_feed_line(line);
+			assert_eq!(result, Err(ScoreBoardError::InvalidFeedLine(String::from(line))), "Line '{}' should be rejected", line);
+		}
+	}
+
+	#[test]
+	fn out_of_range_score_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		let result = sb.apply_feed_line("MEX 0-256 CAN");
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Score 256 is out of range"));
+	}
+
+	#[test]
+	fn failing_full_time_line_leaves_the_game_unchanged() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("MEX", "CAN").expect("Couldn't create the game");
+		let result = sb.apply_feed_line("FT CAN 5-0 MEX");
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for update"));
+		assert_eq!(sb.get_summary(), vec![String::from("MEX 0 - CAN 0")]);
+	}
+}
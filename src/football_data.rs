@@ -0,0 +1,338 @@
+//! # football-data.org importer
+//!
+//! Mirrors a live tournament from the football-data.org API (v4), turning its match payloads into score board operations
+
+use std::string::String;
+use std::vec::Vec;
+
+use log::{debug, trace, warn};
+use serde::Deserialize;
+
+use crate::datetime::UtcDateTime;
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// A response of the API, either a list of matches (ex. `/v4/competitions/WC/matches`) or a single match (ex. `/v4/matches/{id}`)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Payload {
+	/// A list of matches
+	List {
+		/// The matches
+		matches: Vec<Match>,
+	},
+	/// A single match
+	Single(Match),
+}
+
+/// A match, with only the fields needed by the score board
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Match {
+	/// Planned kickoff time, ex. "2022-12-18T15:00:00Z"
+	utc_date: String,
+	/// Status of the match, ex. "TIMED", "IN_PLAY" or "FINISHED"
+	status: String,
+	/// The home team
+	home_team: ApiTeam,
+	/// The away team
+	away_team: ApiTeam,
+	/// The score of the match
+	score: ApiScore,
+}
+
+/// A team of a match
+#[derive(Deserialize)]
+struct ApiTeam {
+	/// Name of the team, missing when the team of a knockout match isn't known yet
+	name: Option<String>,
+}
+
+/// The score of a match
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiScore {
+	/// The current or the final score
+	full_time: ApiGoals,
+}
+
+/// Goals of both teams, missing before the kickoff
+#[derive(Deserialize)]
+struct ApiGoals {
+	/// Goals of the home team
+	home: Option<u32>,
+	/// Goals of the away team
+	away: Option<u32>,
+}
+
+impl ScoreBoard {
+	/// Imports a football-data.org (v4) match payload, bringing the score board in line with it. The payload can be a list of matches or a single match. The matches are handled by their status:
+	///
+	/// * `SCHEDULED` and `TIMED` - the game is scheduled, unless it is already scheduled for the same time or being played
+	/// * `IN_PLAY` and `PAUSED` - the game is started if it isn't played yet, and its score is updated if it has changed
+	/// * `FINISHED` - the game is given its final score and finished, if it is being played
+	/// * Any other status (ex. `POSTPONED`) and matches with unknown teams are skipped
+	///
+	/// The import is atomic: when any of the operations fails, the score board is left unchanged
+	///
+	/// # Arguments
+	///
+	/// * `payload` - the JSON response of the API
+	///
+	/// # Returns
+	///
+	/// * The number of the applied operations. Importing the same payload again gives 0
+	///
+	/// # Errors
+	///
+	/// * When the payload can't be decoded, or a kickoff time or a score is invalid
+	/// * The same as the operations applied to the board
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let payload = r#"{"matches": [{
+	///     "utcDate": "2022-12-18T15:00:00Z",
+	///     "status": "IN_PLAY",
+	///     "homeTeam": {"name": "Argentina"},
+	///     "awayTeam": {"name": "France"},
+	///     "score": {"fullTime": {"home": 2, "away": 0}}
+	/// }]}"#;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// assert_eq!(sb.import_football_data(payload).unwrap(), 2);
+	/// assert_eq!(sb.get_summary(), vec![String::from("Argentina 2 - France 0")]);
+	/// ```
+	pub fn import_football_data(&mut self, payload: &str) -> Result<usize, ScoreBoardError> {
+		operation_span!(tracing::Level::INFO, "import_football_data", bytes = payload.len());
+
+		trace!("Importing a football-data.org payload");
+
+		let matches = match serde_json::from_str(payload) {
+			Ok(Payload::List { matches }) => matches,
+			Ok(Payload::Single(single)) => vec![single],
+			Err(error) => {
+				warn!("Couldn't decode the football-data.org payload: {}", error);
+				return Err(ScoreBoardError::Encoding(format!("Couldn't decode the football-data.org payload: {}", error)));
+			},
+		};
+
+		let operations = self.staged(|staged| {
+			let mut operations = 0;
+
+			for api_match in matches {
+				operations += staged.import_match(api_match)?;
+			}
+
+			Ok(operations)
+		})?;
+
+		trace!("Imported with {} operations", operations);
+
+		Ok(operations)
+	}
+
+	/// Brings a single game in line with the match of the payload
+	///
+	/// # Arguments
+	///
+	/// * `api_match` - the match of the payload
+	///
+	/// # Returns
+	///
+	/// * The number of the applied operations
+	///
+	/// # Errors
+	///
+	/// * When the kickoff time or a score is invalid, or any of the operations fails
+	///
+	fn import_match(&mut self, api_match: Match) -> Result<usize, ScoreBoardError> {
+		let (home, away) = match (api_match.home_team.name, api_match.away_team.name) {
+			(Some(home), Some(away)) => (home, away),
+			_ => {
+				debug!("Skipping a match with unknown teams");
+				return Ok(0);
+			},
+		};
+
+		let current_score = self.get_score(&home, &away);
+		let mut operations = 0;
+
+		match api_match.status.as_str() {
+			"SCHEDULED" | "TIMED" => {
+				let kickoff = match UtcDateTime::parse_rfc3339(&api_match.utc_date) {
+					Some(date) => date.to_system_time(),
+					None => {
+						warn!("Couldn't parse the kickoff time '{}'", api_match.utc_date);
+						return Err(ScoreBoardError::Encoding(format!("Couldn't parse the kickoff time '{}'", api_match.utc_date)));
+					},
+				};
+				let scheduled = self.schedule.iter().any(|fixture| fixture.home_name == home && fixture.away_name == away && fixture.kickoff == kickoff);
+				if current_score.is_none() && !scheduled {
+					self.schedule_game(home, away, kickoff)?;
+					operations += 1;
+				}
+			},
+			"IN_PLAY" | "PAUSED" => {
+				if current_score.is_none() {
+					self.start_game(&home, &away)?;
+					operations += 1;
+				}
+				let new_score = get_goals(&api_match.score.full_time)?;
+				if current_score.unwrap_or((0, 0)) != new_score {
+					self.update_score(&home, new_score.0, &away, new_score.1)?;
+					operations += 1;
+				}
+			},
+			"FINISHED" => {
+				if let Some(score) = current_score {
+					let final_score = get_goals(&api_match.score.full_time)?;
+					if score != final_score {
+						self.update_score(&home, final_score.0, &away, final_score.1)?;
+						operations += 1;
+					}
+					self.finish_game(&home, &away)?;
+					operations += 1;
+				}
+			},
+			status => debug!("Skipping the match of {} and {} with status {}", home, away, status),
+		}
+
+		Ok(operations)
+	}
+}
+
+/// Converts the goals of the payload to the score board range. Missing goals count as 0
+fn get_goals(goals: &ApiGoals) -> Result<(u8, u8), ScoreBoardError> {
+	let convert = |goals: Option<u32>| -> Result<u8, ScoreBoardError> {
+		let goals = goals.unwrap_or(0);
+		match u8::try_from(goals) {
+			Ok(goals) => Ok(goals),
+			Err(_) => {
+				warn!("Score {} is out of range", goals);
+				Err(ScoreBoardError::ScoreOutOfRange(goals))
+			},
+		}
+	};
+
+	Ok((convert(goals.home)?, convert(goals.away)?))
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::ToString;
+
+	fn get_match(status: &str, home: &str, home_score: Option<u32>, away: &str, away_score: Option<u32>) -> String {
+		let goals = |goals: Option<u32>| match goals {
+			Some(goals) => goals.to_string(),
+			None => String::from("null"),
+		};
+
+		format!(
+			r#"{{"id": 1, "utcDate": "2022-12-18T15:00:00Z", "status": "{}", "stage": "FINAL", "homeTeam": {{"id": 762, "name": "{}", "tla": "ARG"}}, "awayTeam": {{"id": 773, "name": "{}", "tla": "FRA"}}, "score": {{"winner": null, "fullTime": {{"home": {}, "away": {}}}, "halfTime": {{"home": null, "away": null}}}}}}"#,
+			status, home, away, goals(home_score), goals(away_score),
+		)
+	}
+
+	fn get_list(matches: &[String]) -> String {
+		format!(r#"{{"filters": {{}}, "resultSet": {{"count": {}}}, "matches": [{}]}}"#, matches.len(), matches.join(", "))
+	}
+
+	#[test]
+	fn timed_match_is_scheduled_once() {
+		let payload = get_list(&[get_match("TIMED", "Argentina", None, "France", None)]);
+
+		let mut sb = ScoreBoard::new();
+		let result_1 = sb.import_football_data(&payload);
+		let result_2 = sb.import_football_data(&payload);
+
+		assert_eq!(result_1, Ok(1));
+		assert_eq!(result_2, Ok(0));
+		assert_eq!(sb.schedule.len(), 1);
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn live_match_is_started_and_kept_up_to_date() {
+		let mut sb = ScoreBoard::new();
+		sb.import_football_data(&get_match("TIMED", "Argentina", None, "France", None)).expect("Couldn't import the fixture");
+		let result_1 = sb.import_football_data(&get_match("IN_PLAY", "Argentina", Some(0), "France", Some(0)));
+		let result_2 = sb.import_football_data(&get_match("PAUSED", "Argentina", Some(2), "France", Some(0)));
+		let result_3 = sb.import_football_data(&get_match("IN_PLAY", "Argentina", Some(2), "France", Some(0)));
+
+		assert_eq!(result_1, Ok(1));
+		assert_eq!(result_2, Ok(1));
+		assert_eq!(result_3, Ok(0));
+		assert!(sb.schedule.is_empty());
+		assert_eq!(sb.get_summary(), vec![String::from("Argentina 2 - France 0")]);
+	}
+
+	#[test]
+	fn finished_match_gets_its_final_score_and_is_removed() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		let result = sb.import_football_data(&get_match("FINISHED", "Argentina", Some(3), "France", Some(3)));
+
+		assert_eq!(result, Ok(2));
+		assert!(sb.get_summary().is_empty());
+		assert!(sb.get_events().iter().rev().nth(1).is_some_and(|record| record.event == crate::ScoreBoardEvent::GameFinished {
+			home: String::from("Argentina"),
+			home_score: 3,
+			away: String::from("France"),
+			away_score: 3,
+		}));
+	}
+
+	#[test]
+	fn finished_match_that_was_not_followed_is_skipped() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.import_football_data(&get_match("FINISHED", "Argentina", Some(3), "France", Some(3)));
+
+		assert_eq!(result, Ok(0));
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn other_statuses_and_unknown_teams_are_skipped() {
+		let unknown_teams = r#"{"utcDate": "2022-12-18T15:00:00Z", "status": "TIMED", "homeTeam": {"name": null}, "awayTeam": {"name": null}, "score": {"fullTime": {"home": null, "away": null}}}"#;
+		let payload = get_list(&[get_match("POSTPONED", "Argentina", None, "France", None), String::from(unknown_teams)]);
+
+		let mut sb = ScoreBoard::new();
+		let result = sb.import_football_data(&payload);
+
+		assert_eq!(result, Ok(0));
+		assert!(sb.schedule.is_empty());
+	}
+
+	#[test]
+	fn failed_import_leaves_the_board_unchanged() {
+		let payload = get_list(&[
+			get_match("IN_PLAY", "Argentina", Some(1), "France", Some(0)),
+			get_match("IN_PLAY", "Croatia", Some(0), "France", Some(0)),
+		]);
+
+		let mut sb = ScoreBoard::new();
+		let result = sb.import_football_data(&payload);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "France is currently playing a game"));
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn invalid_payloads_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		let result_1 = sb.import_football_data("{\"matches\": 5}");
+		let result_2 = sb.import_football_data(&get_match("TIMED", "Argentina", None, "France", None).replace("2022-12-18T15:00:00Z", "tomorrow"));
+		let result_3 = sb.import_football_data(&get_match("IN_PLAY", "Argentina", Some(300), "France", Some(0)));
+
+		assert!(result_1.err().is_some_and(|result| result.to_string().starts_with("Couldn't decode the football-data.org payload")));
+		assert!(result_2.err().is_some_and(|result| result.to_string() == "Couldn't parse the kickoff time 'tomorrow'"));
+		assert!(result_3.err().is_some_and(|result| result.to_string() == "Score 300 is out of range"));
+	}
+}
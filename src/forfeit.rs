@@ -0,0 +1,227 @@
+//! # Forfeits
+//!
+//! Awards the result of a game to one of the teams, ex. when the other team fielded an ineligible player or didn't turn up, so the awarded results are kept in the archive with the played ones
+
+use std::string::ToString;
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::archive::{ArchivedGame, Decision};
+use crate::command::Command;
+use crate::error::ScoreBoardError;
+use crate::events::ScoreBoardEvent;
+use crate::goal::Side;
+use crate::ScoreBoard;
+use crate::transaction::ArchiveChange;
+
+/// The score of the winner of an awarded game on a new score board, see `ScoreBoard::set_forfeit_score()`
+pub const DEFAULT_FORFEIT_SCORE: u8 = 3;
+
+impl ScoreBoard {
+	/// Awards the game to the winner, the loser forfeiting it. The result is archived with `Decision::Forfeit`, the winner getting the forfeit score and the loser none. A running game between the teams is removed from the board without its goals, otherwise the earliest fixture between them is removed from the schedule, if there is one
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `winner` - The team the game is awarded to
+	///
+	/// # Errors
+	///
+	/// * When both team names are the same
+	/// * When any of the validators rejects the end of the game
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Decision, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 0, "Indonesia", 1).unwrap();
+	/// sb.award_forfeit("Japan", "Indonesia", Side::Home).unwrap();
+	///
+	/// assert!(sb.get_summary().is_empty());
+	/// assert_eq!(sb.get_archive()[0].decision, Decision::Forfeit);
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Japan 3 - 0 Indonesia (awarded)")]);
+	/// ```
+	pub fn award_forfeit<T: ToString, U: ToString>(&mut self, home: T, away: U, winner: Side) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "award_forfeit", home = %home_name, away = %away_name, winner = %winner);
+
+		trace!("Awarding the game between '{}' and '{}' to the {} team", home_name, away_name, winner);
+
+		if home_name == away_name {
+			warn!("{} cannot play with itself", home_name);
+			return Err(ScoreBoardError::SameTeam(home_name));
+		}
+
+		self.validate(|| Command::FinishGame { home: home_name.clone(), away: away_name.clone() })?;
+
+		let (home_score, away_score) = match winner {
+			Side::Home => (self.forfeit_score, 0),
+			Side::Away => (0, self.forfeit_score),
+		};
+
+		// The journal gets the final result only, without the duration
+		self.write_ahead(&ScoreBoardEvent::ResultFinalized {
+			home: home_name.clone(),
+			home_score,
+			away: away_name.clone(),
+			away_score,
+			duration: Duration::ZERO,
+			decision: Decision::Forfeit,
+		})?;
+
+		let (kickoff_time, revision) = match self.find_game_index(&home_name, &away_name) {
+			Some(game_index) => {
+				let game = self.remove_game(game_index);
+				(game.kickoff_time, game.revision + 1)
+			},
+			None => {
+				let kickoff_time = self.schedule.iter()
+					.filter(|fixture| fixture.home_name == home_name && fixture.away_name == away_name)
+					.map(|fixture| fixture.kickoff)
+					.min();
+				self.remove_from_schedule(&home_name, &away_name);
+				(kickoff_time.unwrap_or_else(SystemTime::now), 0)
+			},
+		};
+
+		self.archive.push(ArchivedGame {
+			home_team: home_name,
+			home_score,
+			away_team: away_name,
+			away_score,
+			kickoff_time,
+			finish_time: SystemTime::now(),
+			decision: Decision::Forfeit,
+			goals: Vec::new(),
+			details: None,
+		});
+		self.stage_archive_change(ArchiveChange::Added);
+		self.record_finish(revision);
+
+		trace!("Game awarded successfully");
+
+		Ok(())
+	}
+
+	/// Changes the score of the winner of the games awarded with `award_forfeit()`. The default score is `DEFAULT_FORFEIT_SCORE`
+	///
+	/// # Arguments
+	///
+	/// * `forfeit_score` - the score of the winner of an awarded game
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Side;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_forfeit_score(2);
+	/// sb.award_forfeit("Japan", "Indonesia", Side::Away).unwrap();
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Japan 0 - 2 Indonesia (awarded)")]);
+	/// ```
+	pub fn set_forfeit_score(&mut self, forfeit_score: u8) {
+		trace!("Awarding the forfeits {} - 0", forfeit_score);
+
+		self.forfeit_score = forfeit_score;
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::time::Duration;
+
+	use crate::error::Rejection;
+	use crate::events::ScoreBoardEvent;
+
+	const HOME_TEAM_NAME: &str = "Chile";
+	const AWAY_TEAM_NAME: &str = "Bolivia";
+
+	#[test]
+	fn running_game_is_replaced_by_the_awarded_result() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 1).expect("Couldn't update the game");
+		let kickoff_time = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game").kickoff_time;
+		sb.award_forfeit(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Away).expect("Couldn't award the game");
+
+		let game = &sb.get_archive()[0];
+		assert_eq!((game.home_score, game.away_score, game.decision), (0, 3, Decision::Forfeit));
+		assert_eq!(game.kickoff_time, kickoff_time);
+		assert!(game.goals.is_empty());
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn fixture_is_awarded_without_being_played() {
+		let kickoff = SystemTime::now() + Duration::from_secs(3600);
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff).expect("Couldn't schedule the game");
+		sb.award_forfeit(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home).expect("Couldn't award the game");
+
+		assert_eq!(sb.get_archive()[0].kickoff_time, kickoff);
+		assert!(sb.schedule.is_empty());
+	}
+
+	#[test]
+	fn awarded_result_is_recorded_as_a_finished_game() {
+		let mut sb = ScoreBoard::new();
+		sb.award_forfeit(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home).expect("Couldn't award the game");
+
+		assert_eq!(sb.get_events()[0].event, ScoreBoardEvent::GameFinished {
+			home: String::from(HOME_TEAM_NAME),
+			home_score: DEFAULT_FORFEIT_SCORE,
+			away: String::from(AWAY_TEAM_NAME),
+			away_score: 0,
+		});
+		assert!(matches!(&sb.get_events()[1].event, ScoreBoardEvent::ResultFinalized { decision: Decision::Forfeit, home_score: DEFAULT_FORFEIT_SCORE, .. }));
+	}
+
+	#[test]
+	fn awarded_result_counts_in_a_tie() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first leg");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 0).expect("Couldn't update the first leg");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the first leg");
+		sb.award_forfeit(AWAY_TEAM_NAME, HOME_TEAM_NAME, Side::Home).expect("Couldn't award the second leg");
+
+		let tie = sb.get_tie(HOME_TEAM_NAME, AWAY_TEAM_NAME, false);
+		assert_eq!(tie.aggregate_score(), (2, 3));
+		assert_eq!(tie.winner(), Some(AWAY_TEAM_NAME));
+	}
+
+	#[test]
+	fn team_cannot_forfeit_to_itself() {
+		let mut sb = ScoreBoard::new();
+
+		assert_eq!(sb.award_forfeit(HOME_TEAM_NAME, HOME_TEAM_NAME, Side::Home), Err(ScoreBoardError::SameTeam(String::from(HOME_TEAM_NAME))));
+		assert!(sb.get_archive().is_empty());
+	}
+
+	#[test]
+	fn validators_can_reject_the_forfeit() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(|command: &Command, _: &ScoreBoard| match command {
+			Command::FinishGame { .. } => Err(Rejection::new("no finishes", "the game goes on")),
+			_ => Ok(()),
+		});
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert!(matches!(sb.award_forfeit(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home), Err(ScoreBoardError::Rejected(_))));
+		assert!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).is_some());
+	}
+}
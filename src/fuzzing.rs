@@ -0,0 +1,200 @@
+//! # Arbitrary values for fuzzing and property tests
+//!
+//! Implements `arbitrary::Arbitrary` for the commands and the score board, so the systems built on the score board can be fuzzed or property-tested with generated operations
+
+use std::string::String;
+use std::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::goal::{GoalKind, Side};
+use crate::limit::DEFAULT_MAX_SCORE;
+use crate::{Command, ScoreBoard};
+
+/// Team names the generated commands choose from. The pool is small, so the commands often refer to the same games
+const TEAMS: [&str; 8] = ["Spain", "Brazil", "Germany", "France", "Mexico", "Canada", "Japan", "Indonesia"];
+
+/// Highest score of a generated command
+const MAX_SCORE: u8 = 15;
+
+/// Highest number of the commands in a generated sequence
+const MAX_COMMANDS: usize = 64;
+
+/// Picks a team from the pool
+fn arbitrary_team(u: &mut Unstructured<'_>) -> Result<String> {
+	Ok(String::from(TEAMS[u.choose_index(TEAMS.len())?]))
+}
+
+/// Any command, regardless of the state of a board. Most of them fail when applied to a board, which is good for testing the error handling
+impl<'a> Arbitrary<'a> for Command {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Command> {
+		let command = match u.int_in_range(0..=6)? {
+			0 => Command::StartGame { home: arbitrary_team(u)?, away: arbitrary_team(u)? },
+			1 => Command::UpdateScore {
+				home: arbitrary_team(u)?,
+				home_score: u.int_in_range(0..=MAX_SCORE)?,
+				away: arbitrary_team(u)?,
+				away_score: u.int_in_range(0..=MAX_SCORE)?,
+			},
+			2 => Command::FinishGame { home: arbitrary_team(u)?, away: arbitrary_team(u)? },
+			3 => Command::RecordGoal {
+				home: arbitrary_team(u)?,
+				away: arbitrary_team(u)?,
+				side: *u.choose(&[Side::Home, Side::Away])?,
+				kind: *u.choose(&[GoalKind::OpenPlay, GoalKind::Penalty, GoalKind::OwnGoal, GoalKind::FreeKick])?,
+			},
+			4 => Command::PauseGame { home: arbitrary_team(u)?, away: arbitrary_team(u)? },
+			5 => Command::ResumeGame { home: arbitrary_team(u)?, away: arbitrary_team(u)? },
+			_ => Command::ReopenGame { home: arbitrary_team(u)?, away: arbitrary_team(u)? },
+		};
+
+		Ok(command)
+	}
+}
+
+/// A sequence of commands that all succeed when applied in order to an empty score board: games are started by free teams, and only the running games are updated (one goal at a time) and finished
+///
+/// The sequence ends when the generator runs out of data, so shrinking the input of a failing case shortens the sequence
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use scoreboard_world_cup::{OperationSequence, ScoreBoard};
+///
+/// let data = [1, 0, 1, 1, 42, 7, 13];
+/// let sequence = OperationSequence::arbitrary(&mut Unstructured::new(&data)).unwrap();
+/// let mut sb = ScoreBoard::new();
+/// assert!(sb.apply_batch(sequence.commands).is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationSequence {
+	/// The commands, in the order they are applied
+	pub commands: Vec<Command>,
+}
+
+impl<'a> Arbitrary<'a> for OperationSequence {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<OperationSequence> {
+		// The running games as indexes of the teams with the scores
+		let mut games: Vec<(usize, usize, u8, u8)> = Vec::new();
+		let mut commands = Vec::new();
+
+		while commands.len() < MAX_COMMANDS && u.arbitrary()? {
+			let free_teams: Vec<usize> = (0..TEAMS.len())
+				.filter(|team| !games.iter().any(|(home, away, _, _)| home == team || away == team))
+				.collect();
+
+			let command = match u.int_in_range(0..=6)? {
+				_ if games.is_empty() => 0,
+				0 if free_teams.len() < 2 => 1,
+				command => command,
+			};
+
+			if command == 0 {
+				let home = free_teams[u.choose_index(free_teams.len())?];
+				let away = *u.choose(&free_teams.iter().filter(|team| **team != home).copied().collect::<Vec<usize>>())?;
+				games.push((home, away, 0, 0));
+				commands.push(Command::StartGame { home: String::from(TEAMS[home]), away: String::from(TEAMS[away]) });
+				continue;
+			}
+
+			let index = u.choose_index(games.len())?;
+			let (home, away, home_score, away_score) = games[index];
+
+			// A game with a score at the limit of the board is finished instead of updated
+			if command == 1 && home_score.max(away_score) < DEFAULT_MAX_SCORE {
+				let (home_score, away_score) = if u.arbitrary()? { (home_score.saturating_add(1), away_score) } else { (home_score, away_score.saturating_add(1)) };
+				games[index] = (home, away, home_score, away_score);
+				commands.push(Command::UpdateScore { home: String::from(TEAMS[home]), home_score, away: String::from(TEAMS[away]), away_score });
+			} else {
+				games.remove(index);
+				commands.push(Command::FinishGame { home: String::from(TEAMS[home]), away: String::from(TEAMS[away]) });
+			}
+		}
+
+		Ok(OperationSequence { commands })
+	}
+}
+
+/// A score board in the state reached by an `OperationSequence`
+impl<'a> Arbitrary<'a> for ScoreBoard {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<ScoreBoard> {
+		let sequence = OperationSequence::arbitrary(u)?;
+		let mut sb = ScoreBoard::new();
+
+		for command in sequence.commands {
+			sb.apply(command).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+		}
+
+		Ok(sb)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Some bytes looking random enough, different for every seed
+	fn get_data(seed: u32, length: usize) -> Vec<u8> {
+		let mut state = seed.wrapping_mul(2654435761) | 1;
+
+		(0..length).map(|_| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			state as u8
+		}).collect()
+	}
+
+	#[test]
+	fn generated_sequences_always_apply() {
+		let mut longest = 0;
+
+		for seed in 0..200 {
+			let data = get_data(seed, 256);
+			let sequence = OperationSequence::arbitrary(&mut Unstructured::new(&data)).expect("Couldn't generate the commands");
+
+			let mut sb = ScoreBoard::new();
+			let result = sb.apply_batch(sequence.commands.clone());
+			assert!(result.is_ok(), "{:?} failed with {:?}", sequence, result);
+			longest = longest.max(sequence.commands.len());
+		}
+
+		assert!(longest >= 5);
+	}
+
+	#[test]
+	fn empty_data_gives_an_empty_board() {
+		let sequence = OperationSequence::arbitrary(&mut Unstructured::new(&[])).expect("Couldn't generate the commands");
+		let sb = ScoreBoard::arbitrary(&mut Unstructured::new(&[])).expect("Couldn't generate the board");
+
+		assert!(sequence.commands.is_empty());
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn boards_are_generated_deterministically() {
+		let data = get_data(1, 256);
+		let first = ScoreBoard::arbitrary(&mut Unstructured::new(&data)).expect("Couldn't generate the first board");
+		let second = ScoreBoard::arbitrary(&mut Unstructured::new(&data)).expect("Couldn't generate the second board");
+
+		assert_eq!(first.get_summary(), second.get_summary());
+		assert!(!first.get_events().is_empty());
+	}
+
+	#[test]
+	fn single_commands_use_the_team_pool() {
+		let data = get_data(1, 64);
+		let mut u = Unstructured::new(&data);
+
+		for _ in 0..10 {
+			let command = Command::arbitrary(&mut u).expect("Couldn't generate the command");
+			let (home, away) = command.get_teams();
+			assert!(TEAMS.contains(&home) && TEAMS.contains(&away));
+		}
+	}
+}
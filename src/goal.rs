@@ -0,0 +1,415 @@
+//! # Goal classification
+//!
+//! Records single goals together with their kind, so the score can be broken down for statistics, ex. "2 (1 pen)"
+
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::player::Player;
+use crate::ScoreBoard;
+
+/// One of the two teams of a game
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+	/// The home team
+	Home,
+	/// The away team
+	Away,
+}
+
+/// The way a goal was scored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GoalKind {
+	/// A goal from open play
+	OpenPlay,
+	/// A penalty kick
+	Penalty,
+	/// A goal scored by a player of the other team
+	OwnGoal,
+	/// A direct free kick
+	FreeKick,
+}
+
+impl GoalKind {
+	/// Name of the kind, as used in the JSON presentation of the events
+	pub(crate) fn get_name(&self) -> &'static str {
+		match self {
+			GoalKind::OpenPlay => "open_play",
+			GoalKind::Penalty => "penalty",
+			GoalKind::OwnGoal => "own_goal",
+			GoalKind::FreeKick => "free_kick",
+		}
+	}
+
+	/// Short name of the kind, as used in score breakdowns. Empty for the open play goals
+	fn get_abbreviation(&self) -> &'static str {
+		match self {
+			GoalKind::OpenPlay => "",
+			GoalKind::Penalty => "pen",
+			GoalKind::OwnGoal => "og",
+			GoalKind::FreeKick => "fk",
+		}
+	}
+}
+
+/// A single goal
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Goal {
+	/// The team the goal counts for. An own goal counts for the team that didn't score it
+	pub side: Side,
+	/// The way the goal was scored
+	pub kind: GoalKind,
+	/// The player who scored the goal, if it was recorded with `ScoreBoard::record_goal_by()`
+	pub scorer: Option<Player>,
+}
+
+/// Number of the goals of a team, per kind. Only the goals recorded with `ScoreBoard::record_goal()` are counted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoalCounts {
+	/// Goals from open play
+	pub open_play: u8,
+	/// Penalty kicks
+	pub penalty: u8,
+	/// Own goals of the other team
+	pub own_goal: u8,
+	/// Direct free kicks
+	pub free_kick: u8,
+}
+
+impl GoalCounts {
+	/// Provides the number of the goals of the given kind
+	pub fn get(&self, kind: GoalKind) -> u8 {
+		match kind {
+			GoalKind::OpenPlay => self.open_play,
+			GoalKind::Penalty => self.penalty,
+			GoalKind::OwnGoal => self.own_goal,
+			GoalKind::FreeKick => self.free_kick,
+		}
+	}
+
+	/// Counts the goals of a team
+	fn count(goals: &[Goal], side: Side) -> GoalCounts {
+		let mut counts = GoalCounts::default();
+
+		for goal in goals.iter().filter(|goal| goal.side == side) {
+			let count = match goal.kind {
+				GoalKind::OpenPlay => &mut counts.open_play,
+				GoalKind::Penalty => &mut counts.penalty,
+				GoalKind::OwnGoal => &mut counts.own_goal,
+				GoalKind::FreeKick => &mut counts.free_kick,
+			};
+			*count += 1;
+		}
+
+		counts
+	}
+
+	/// Presents a score with the goals that weren't scored from open play, ex. "2 (1 pen)" or "3 (1 pen, 1 og)"
+	fn describe(&self, score: u8) -> String {
+		let details: Vec<String> = [GoalKind::Penalty, GoalKind::OwnGoal, GoalKind::FreeKick]
+			.iter()
+			.filter(|kind| self.get(**kind) > 0)
+			.map(|kind| format!("{} {}", self.get(*kind), kind.get_abbreviation()))
+			.collect();
+
+		if details.is_empty() {
+			score.to_string()
+		} else {
+			format!("{} ({})", score, details.join(", "))
+		}
+	}
+}
+
+impl fmt::Display for Side {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Side::Home => write!(f, "home"),
+			Side::Away => write!(f, "away"),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Adds a single goal to the score of a running match, recording its kind. The change is recorded as a `ScoreBoardEvent::ScoreUpdated` carrying the goal
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `side` - The team the goal counts for
+	/// * `kind` - The way the goal was scored
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	/// * When the score of the team is already at the maximum of 255
+	/// * When the update is rejected, the same way as by `update_score()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{GoalKind, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.record_goal("Japan", "Indonesia", Side::Home, GoalKind::OpenPlay).unwrap();
+	/// sb.record_goal("Japan", "Indonesia", Side::Home, GoalKind::Penalty).unwrap();
+	/// assert_eq!(sb.get_score_breakdown("Japan", "Indonesia"), Some((String::from("2 (1 pen)"), String::from("0"))));
+	/// ```
+	pub fn record_goal<T: ToString, U: ToString>(&mut self, home: T, away: U, side: Side, kind: GoalKind) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		self.add_goal(home_name, away_name, Goal { side, kind, scorer: None })
+	}
+
+	/// Adds a single goal to the score of a running match, the same way as `record_goal()`, together with the player who scored it. The goal refers to the player registered with `register_player()`, or to an unregistered player without a shirt number. The scorers are counted by `top_scorers()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `side` - The team the goal counts for
+	/// * `kind` - The way the goal was scored
+	/// * `scorer` - Name of the player who scored the goal. The scorer of an own goal plays for the other team than the one the goal counts for. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * The same as of `record_goal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Goal, GoalKind, Player, ScoreBoardEvent, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "France").unwrap();
+	/// sb.register_player("France", "Mbappe", 10).unwrap();
+	/// sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::Penalty, "Mbappe").unwrap();
+	/// assert_eq!(sb.get_score("Argentina", "France"), Some((0, 1)));
+	///
+	/// let goal = &sb.get_events()[1].event;
+	/// assert!(matches!(goal, ScoreBoardEvent::ScoreUpdated { goal: Some(Goal { scorer: Some(Player { number: Some(10), .. }), .. }), .. }));
+	/// ```
+	pub fn record_goal_by<T: ToString, U: ToString, V: ToString>(&mut self, home: T, away: U, side: Side, kind: GoalKind, scorer: V) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		// The scorer of an own goal plays for the other team
+		let scorer_team = if (side == Side::Home) != (kind == GoalKind::OwnGoal) { &home_name } else { &away_name };
+		let scorer = self.get_player(scorer_team, scorer.to_string());
+
+		self.add_goal(home_name, away_name, Goal { side, kind, scorer: Some(scorer) })
+	}
+
+	/// Adds a single goal to the score of a running match
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team, after following the aliases
+	/// * `away_name` - name of the away team, after following the aliases
+	/// * `goal` - the goal
+	///
+	fn add_goal(&mut self, home_name: String, away_name: String, goal: Goal) -> Result<(), ScoreBoardError> {
+		let Goal { side, kind, .. } = goal;
+
+		operation_span!(tracing::Level::INFO, "record_goal", home = %home_name, away = %away_name, side = %side);
+
+		trace!("Recording a {:?} goal for the {} team of '{}' - '{}'", kind, side, home_name, away_name);
+
+		let (home_score, away_score) = match self.get_score(&home_name, &away_name) {
+			Some(score) => score,
+			None => {
+				warn!("Couldn't find a game for update");
+				return Err(self.game_not_found(home_name, away_name, Operation::UpdateScore));
+			},
+		};
+
+		let (new_home_score, new_away_score) = match side {
+			Side::Home => (home_score.checked_add(1), Some(away_score)),
+			Side::Away => (Some(home_score), away_score.checked_add(1)),
+		};
+
+		match (new_home_score, new_away_score) {
+			(Some(new_home_score), Some(new_away_score)) => self.set_score(home_name, new_home_score, away_name, new_away_score, Some(goal)),
+			_ => {
+				warn!("Score is out of range");
+				Err(ScoreBoardError::ScoreOutOfRange(u8::MAX as u32 + 1))
+			},
+		}
+	}
+
+	/// Provides the goals of both teams of a running match, per kind
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	///
+	/// # Returns
+	///
+	/// * The goal counts of the home and the away team, or `None` if there is no active match between the given teams
+	pub fn get_goal_counts(&self, home: &str, away: &str) -> Option<(GoalCounts, GoalCounts)> {
+		self.find_game(home, away)
+			.map(|game| (GoalCounts::count(&game.goals, Side::Home), GoalCounts::count(&game.goals, Side::Away)))
+	}
+
+	/// Provides the scores of both teams of a running match, with the goals that weren't scored from open play, ex. "2 (1 pen)"
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	///
+	/// # Returns
+	///
+	/// * The described scores of the home and the away team, or `None` if there is no active match between the given teams
+	pub fn get_score_breakdown(&self, home: &str, away: &str) -> Option<(String, String)> {
+		let (home_score, away_score) = self.get_score(home, away)?;
+		let (home_counts, away_counts) = self.get_goal_counts(home, away)?;
+
+		Some((home_counts.describe(home_score), away_counts.describe(away_score)))
+	}
+}
+
+/// Keeps the recorded goals consistent with the scores, after a score was lowered (ex. a disallowed goal). The most recent goals of the team are dropped first
+///
+/// # Arguments
+///
+/// * `goals` - the recorded goals of a game, the oldest coming first
+/// * `home_score` - the current score of the home team
+/// * `away_score` - the current score of the away team
+///
+pub(crate) fn trim_goals(goals: &mut Vec<Goal>, home_score: u8, away_score: u8) {
+	for (side, score) in [(Side::Home, home_score), (Side::Away, away_score)] {
+		while goals.iter().filter(|goal| goal.side == side).count() > score as usize {
+			if let Some(index) = goals.iter().rposition(|goal| goal.side == side) {
+				goals.remove(index);
+			}
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::events::ScoreBoardEvent;
+
+	const HOME_TEAM_NAME: &str = "Argentina";
+	const AWAY_TEAM_NAME: &str = "France";
+
+	fn goal(sb: &mut ScoreBoard, side: Side, kind: GoalKind) {
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, side, kind).expect("Couldn't record the goal");
+	}
+
+	#[test]
+	fn goals_are_counted_per_kind() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		goal(&mut sb, Side::Home, GoalKind::Penalty);
+		goal(&mut sb, Side::Home, GoalKind::OpenPlay);
+		goal(&mut sb, Side::Away, GoalKind::Penalty);
+		goal(&mut sb, Side::Away, GoalKind::FreeKick);
+		goal(&mut sb, Side::Home, GoalKind::OwnGoal);
+
+		let (home, away) = sb.get_goal_counts(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't get the counts");
+		assert_eq!(home, GoalCounts { open_play: 1, penalty: 1, own_goal: 1, free_kick: 0 });
+		assert_eq!(away, GoalCounts { open_play: 0, penalty: 1, own_goal: 0, free_kick: 1 });
+		assert_eq!(sb.get_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((3, 2)));
+		assert_eq!(sb.get_summary(), vec![String::from("Argentina 3 - France 2")]);
+	}
+
+	#[test]
+	fn score_breakdown_lists_set_pieces() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		goal(&mut sb, Side::Home, GoalKind::Penalty);
+		goal(&mut sb, Side::Home, GoalKind::OpenPlay);
+		goal(&mut sb, Side::Home, GoalKind::OwnGoal);
+		goal(&mut sb, Side::Away, GoalKind::OpenPlay);
+
+		assert_eq!(sb.get_score_breakdown(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((String::from("3 (1 pen, 1 og)"), String::from("1"))));
+	}
+
+	#[test]
+	fn goal_is_part_of_the_event() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		goal(&mut sb, Side::Away, GoalKind::Penalty);
+
+		assert_eq!(sb.get_events()[1].event, ScoreBoardEvent::ScoreUpdated {
+			home: String::from(HOME_TEAM_NAME),
+			home_score: 0,
+			away: String::from(AWAY_TEAM_NAME),
+			away_score: 1,
+			goal: Some(Goal { side: Side::Away, kind: GoalKind::Penalty, scorer: None }),
+		});
+	}
+
+	#[test]
+	fn scorer_of_an_own_goal_plays_for_the_other_team() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player(AWAY_TEAM_NAME, "Varane", 4).expect("Couldn't register the player");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_goal_by(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OwnGoal, "Varane").expect("Couldn't record the goal");
+
+		let game = sb.find_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.goals[0].scorer, Some(Player { name: String::from("Varane"), number: Some(4), team: String::from(AWAY_TEAM_NAME) }));
+	}
+
+	#[test]
+	fn lowered_score_drops_the_latest_goals() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		goal(&mut sb, Side::Home, GoalKind::Penalty);
+		goal(&mut sb, Side::Home, GoalKind::FreeKick);
+		goal(&mut sb, Side::Away, GoalKind::OpenPlay);
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 1).expect("Couldn't correct the score");
+
+		let (home, away) = sb.get_goal_counts(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't get the counts");
+		assert_eq!(home, GoalCounts { penalty: 1, ..GoalCounts::default() });
+		assert_eq!(away, GoalCounts { open_play: 1, ..GoalCounts::default() });
+	}
+
+	#[test]
+	fn goals_set_with_absolute_scores_have_no_kind() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		goal(&mut sb, Side::Home, GoalKind::Penalty);
+
+		assert_eq!(sb.get_score_breakdown(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((String::from("3 (1 pen)"), String::from("0"))));
+	}
+
+	#[test]
+	fn goal_in_missing_game_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OpenPlay);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for update"));
+		assert_eq!(sb.get_goal_counts(HOME_TEAM_NAME, AWAY_TEAM_NAME), None);
+	}
+
+	#[test]
+	fn goal_over_the_maximum_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.clear_max_score();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 255, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		let result = sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OpenPlay);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Score 256 is out of range"));
+	}
+}
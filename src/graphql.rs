@@ -0,0 +1,287 @@
+//! # GraphQL API
+//!
+//! A GraphQL schema over a shared score board, so the front-ends can ask for exactly the fields they show. Available with the `graphql` feature
+//!
+//! ```graphql
+//! type Query {
+//!   games: [Game!]!
+//!   game(homeTeam: String!, awayTeam: String!): Game
+//! }
+//!
+//! type Mutation {
+//!   startGame(homeTeam: String!, awayTeam: String!): Game!
+//!   updateScore(homeTeam: String!, homeScore: Int!, awayTeam: String!, awayScore: Int!): Game!
+//!   finishGame(homeTeam: String!, awayTeam: String!): Boolean!
+//! }
+//!
+//! type Subscription {
+//!   scoreChanges: Game!
+//! }
+//! ```
+
+use std::string::{String, ToString};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use async_graphql::{Context, Object, Result, Schema, Subscription};
+use log::{trace, warn};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::error::ScoreBoardError;
+use crate::{GameView, ScoreBoard};
+
+/// Number of the score changes kept for a subscriber that reads them too slowly. A subscriber that falls further behind misses the oldest changes
+const CHANGES_CAPACITY: usize = 64;
+
+/// The GraphQL schema of the score board, built with `graphql_schema()`
+pub type ScoreBoardSchema = Schema<GraphQlQuery, GraphQlMutation, GraphQlSubscription>;
+
+/// The score board shared by all the requests to the schema
+struct SharedBoard {
+	/// The score board
+	board: Mutex<ScoreBoard>,
+	/// The games after every score change, followed by the subscriptions
+	changes: broadcast::Sender<GameView>,
+}
+
+impl SharedBoard {
+	/// Locks the board. A panic of another request doesn't make it unusable, as every operation leaves the board consistent
+	fn lock(&self) -> MutexGuard<'_, ScoreBoard> {
+		self.board.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+/// Provides the board shared by the requests of the schema
+fn get_shared<'a>(ctx: &Context<'a>) -> &'a SharedBoard {
+	ctx.data_unchecked::<SharedBoard>()
+}
+
+/// Logs a failed operation, before it is reported in the response
+fn report(error: ScoreBoardError) -> ScoreBoardError {
+	warn!("GraphQL operation failed: {}", error);
+	error
+}
+
+/// Builds the GraphQL schema over the given score board. The schema is cheap to clone, and the clones share the board
+///
+/// # Arguments
+///
+/// * `board` - the score board to serve
+///
+/// # Examples
+///
+/// ```
+/// let schema = scoreboard_world_cup::graphql_schema(scoreboard_world_cup::ScoreBoard::new());
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// schema.execute(r#"mutation { startGame(homeTeam: "Mexico", awayTeam: "Canada") { homeTeam } }"#).await;
+/// let response = schema.execute("{ games { homeTeam homeScore awayTeam awayScore } }").await;
+/// assert_eq!(response.data.to_string(), r#"{games: [{homeTeam: "Mexico", homeScore: 0, awayTeam: "Canada", awayScore: 0}]}"#);
+/// # });
+/// ```
+pub fn graphql_schema(board: ScoreBoard) -> ScoreBoardSchema {
+	let (changes, _) = broadcast::channel(CHANGES_CAPACITY);
+
+	Schema::build(GraphQlQuery, GraphQlMutation, GraphQlSubscription)
+		.data(SharedBoard { board: Mutex::new(board), changes })
+		.finish()
+}
+
+#[Object(name = "Game")]
+impl GameView {
+	/// Name of the home team
+	async fn home_team(&self) -> &str {
+		&self.home_team
+	}
+
+	/// Score of the home team
+	async fn home_score(&self) -> u8 {
+		self.home_score
+	}
+
+	/// Name of the away team
+	async fn away_team(&self) -> &str {
+		&self.away_team
+	}
+
+	/// Score of the away team
+	async fn away_score(&self) -> u8 {
+		self.away_score
+	}
+
+	/// The current period, ex. "second half"
+	async fn period(&self) -> String {
+		self.period.to_string()
+	}
+
+	/// State of the game, ex. "live"
+	async fn state(&self) -> String {
+		self.state.to_string()
+	}
+
+	/// Revision of the game, growing with every change
+	async fn revision(&self) -> u64 {
+		self.revision
+	}
+}
+
+/// Queries of the schema
+pub struct GraphQlQuery;
+
+#[Object]
+impl GraphQlQuery {
+	/// The running games, in the order of the summary
+	async fn games(&self, ctx: &Context<'_>) -> Vec<GameView> {
+		trace!("GraphQL games");
+
+		get_shared(ctx).lock().get_games()
+	}
+
+	/// The running game between the given teams, if there is one
+	async fn game(&self, ctx: &Context<'_>, home_team: String, away_team: String) -> Option<GameView> {
+		trace!("GraphQL game of '{}' and '{}'", home_team, away_team);
+
+		get_shared(ctx).lock().get_game(&home_team, &away_team)
+	}
+}
+
+/// Mutations of the schema
+pub struct GraphQlMutation;
+
+#[Object]
+impl GraphQlMutation {
+	/// Starts a game, see `ScoreBoard::start_game()`
+	async fn start_game(&self, ctx: &Context<'_>, home_team: String, away_team: String) -> Result<GameView> {
+		trace!("GraphQL startGame of '{}' and '{}'", home_team, away_team);
+
+		let mut board = get_shared(ctx).lock();
+		board.start_game(&home_team, &away_team).map_err(report)?;
+
+		Ok(board.get_game(&home_team, &away_team).ok_or("The started game is missing")?)
+	}
+
+	/// Updates the score of a game, see `ScoreBoard::update_score()`. The updated game is passed to the `scoreChanges` subscriptions
+	async fn update_score(&self, ctx: &Context<'_>, home_team: String, home_score: u8, away_team: String, away_score: u8) -> Result<GameView> {
+		trace!("GraphQL updateScore of '{}' and '{}'", home_team, away_team);
+
+		let shared = get_shared(ctx);
+		let mut board = shared.lock();
+		board.update_score(&home_team, home_score, &away_team, away_score).map_err(report)?;
+
+		let game = board.get_game(&home_team, &away_team).ok_or("The updated game is missing")?;
+		// Having no subscribers is not an error
+		let _ = shared.changes.send(game.clone());
+
+		Ok(game)
+	}
+
+	/// Finishes a game, see `ScoreBoard::finish_game()`
+	async fn finish_game(&self, ctx: &Context<'_>, home_team: String, away_team: String) -> Result<bool> {
+		trace!("GraphQL finishGame of '{}' and '{}'", home_team, away_team);
+
+		get_shared(ctx).lock().finish_game(home_team, away_team).map_err(report)?;
+
+		Ok(true)
+	}
+}
+
+/// Subscriptions of the schema
+pub struct GraphQlSubscription;
+
+#[Subscription]
+impl GraphQlSubscription {
+	/// The game after every score change made with the `updateScore` mutation
+	async fn score_changes(&self, ctx: &Context<'_>) -> impl Stream<Item = GameView> {
+		trace!("GraphQL scoreChanges");
+
+		BroadcastStream::new(get_shared(ctx).changes.subscribe()).filter_map(|change| change.ok())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run<F: std::future::Future>(future: F) -> F::Output {
+		tokio::runtime::Builder::new_current_thread().enable_all().build().expect("Couldn't build the runtime").block_on(future)
+	}
+
+	fn get_schema() -> ScoreBoardSchema {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		graphql_schema(sb)
+	}
+
+	#[test]
+	fn games_have_only_the_requested_fields() {
+		let schema = get_schema();
+		let response = run(schema.execute("{ games { homeTeam awayTeam } }"));
+
+		assert!(response.errors.is_empty());
+		assert_eq!(response.data.to_string(), r#"{games: [{homeTeam: "Spain", awayTeam: "Brazil"}, {homeTeam: "Mexico", awayTeam: "Canada"}]}"#);
+	}
+
+	#[test]
+	fn game_is_found_by_teams() {
+		let schema = get_schema();
+		let response = run(schema.execute(r#"{ game(homeTeam: "Spain", awayTeam: "Brazil") { homeScore awayScore period state } }"#));
+		assert_eq!(response.data.to_string(), r#"{game: {homeScore: 10, awayScore: 2, period: "first half", state: "live"}}"#);
+
+		let response = run(schema.execute(r#"{ game(homeTeam: "Spain", awayTeam: "Canada") { homeScore } }"#));
+		assert_eq!(response.data.to_string(), "{game: null}");
+	}
+
+	#[test]
+	fn mutations_change_the_shared_board() {
+		let schema = get_schema();
+		let response = run(schema.execute(r#"mutation { updateScore(homeTeam: "Mexico", homeScore: 0, awayTeam: "Canada", awayScore: 5) { homeScore awayScore } }"#));
+		assert_eq!(response.data.to_string(), "{updateScore: {homeScore: 0, awayScore: 5}}");
+
+		let response = run(schema.clone().execute(r#"mutation { finishGame(homeTeam: "Spain", awayTeam: "Brazil") }"#));
+		assert_eq!(response.data.to_string(), "{finishGame: true}");
+
+		let response = run(schema.execute("{ games { homeTeam } }"));
+		assert_eq!(response.data.to_string(), r#"{games: [{homeTeam: "Mexico"}]}"#);
+	}
+
+	#[test]
+	fn failed_mutation_reports_the_error() {
+		let schema = get_schema();
+		let response = run(schema.execute(r#"mutation { startGame(homeTeam: "Brazil", awayTeam: "Italy") { homeTeam } }"#));
+
+		assert_eq!(response.errors.len(), 1);
+		assert_eq!(response.errors[0].message, "Brazil is currently playing a game");
+	}
+
+	#[test]
+	fn score_out_of_range_is_rejected() {
+		let schema = get_schema();
+		let response = run(schema.execute(r#"mutation { updateScore(homeTeam: "Mexico", homeScore: 256, awayTeam: "Canada", awayScore: 0) { homeScore } }"#));
+
+		assert_eq!(response.errors.len(), 1);
+		assert_eq!(run(schema.execute(r#"{ game(homeTeam: "Mexico", awayTeam: "Canada") { homeScore } }"#)).data.to_string(), "{game: {homeScore: 0}}");
+	}
+
+	#[test]
+	fn subscription_follows_the_score_changes() {
+		let schema = get_schema();
+
+		run(async {
+			let mut changes = schema.execute_stream("subscription { scoreChanges { homeTeam homeScore awayScore } }");
+			// The subscription starts on the first poll of the stream
+			let first = tokio::time::timeout(std::time::Duration::from_millis(10), changes.next()).await;
+			assert!(first.is_err());
+
+			schema.execute(r#"mutation { updateScore(homeTeam: "Mexico", homeScore: 1, awayTeam: "Canada", awayScore: 0) { homeScore } }"#).await;
+			let change = changes.next().await.expect("The subscription has ended");
+			assert_eq!(change.data.to_string(), r#"{scoreChanges: {homeTeam: "Mexico", homeScore: 1, awayScore: 0}}"#);
+		});
+	}
+}
@@ -0,0 +1,328 @@
+//! # gRPC service
+//!
+//! Serves the score board as the `ScoreBoardService` of `proto/scoreboard.proto`, with a server-streaming call following every change of the board. Available with the `grpc` feature
+//!
+//! Failed operations end with the status codes:
+//!
+//! | Error | Code |
+//! | ------ | ------ |
+//! | `ScoreBoardError::GameNotFound` | `NOT_FOUND` |
+//! | `ScoreBoardError::AlreadyPlaying`, `ScoreBoardError::AlreadyScheduled` | `ALREADY_EXISTS` |
+//! | `ScoreBoardError::UpdateTooSoon`, `ScoreBoardError::TooManyGames`, `ScoreBoardError::Rejected`, `ScoreBoardError::IllegalTransition` | `FAILED_PRECONDITION` |
+//! | `ScoreBoardError::Storage` | `UNAVAILABLE` |
+//! | Any other error | `INVALID_ARGUMENT` |
+
+use std::convert::Infallible;
+use std::string::{String, ToString};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll};
+use std::vec::Vec;
+
+use log::{trace, warn};
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+use tokio_stream::StreamExt;
+use tonic::codegen::{http, Body, BoxFuture, BoxStream, Service, StdError};
+use tonic::server::{Grpc, NamedService, ServerStreamingService, UnaryService};
+use tonic::{Code, Request, Response, Status};
+use tonic_prost::ProstCodec;
+
+use crate::error::ScoreBoardError;
+use crate::protobuf::{self, BoardState, FinishGameRequest, OperationReply, StartGameRequest, StreamSummaryRequest, UpdateScoreRequest};
+use crate::ScoreBoard;
+
+/// Full name of the service, as in the schema
+const SERVICE_NAME: &str = "scoreboard.ScoreBoardService";
+
+/// A score board served over gRPC. Clones share the same board, so the service can be handed to a `tonic` server while the application keeps a handle to it
+///
+/// # Examples
+///
+/// ```no_run
+/// use scoreboard_world_cup::{GrpcScoreBoard, ScoreBoard};
+///
+/// # async fn serve() -> Result<(), Box<dyn std::error::Error>> {
+/// let service = GrpcScoreBoard::new(ScoreBoard::new());
+/// tonic::transport::Server::builder()
+///     .add_service(service)
+///     .serve("0.0.0.0:50051".parse()?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GrpcScoreBoard {
+	/// The served score board
+	board: Arc<Mutex<ScoreBoard>>,
+	/// The state of the board after the last change, followed by the summary streams
+	updates: watch::Sender<BoardState>,
+}
+
+impl GrpcScoreBoard {
+	/// Returns a service serving the given score board
+	///
+	/// # Arguments
+	///
+	/// * `board` - the score board to serve
+	pub fn new(board: ScoreBoard) -> GrpcScoreBoard {
+		let (updates, _) = watch::channel(board.to_board_state());
+
+		GrpcScoreBoard { board: Arc::new(Mutex::new(board)), updates }
+	}
+
+	/// Provides the summary of the served score board, see `ScoreBoard::get_summary()`
+	pub fn get_summary(&self) -> Vec<String> {
+		self.lock().get_summary()
+	}
+
+	/// Locks the board. A panic of another user of the board doesn't make it unusable, as every operation leaves the board consistent
+	fn lock(&self) -> MutexGuard<'_, ScoreBoard> {
+		self.board.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Applies an operation to the board and passes the new state to the summary streams
+	///
+	/// # Arguments
+	///
+	/// * `operation` - the operation to apply
+	///
+	/// # Errors
+	///
+	/// * The status matching the error of the operation
+	fn change<F: FnOnce(&mut ScoreBoard) -> Result<(), ScoreBoardError>>(&self, operation: F) -> Result<Response<OperationReply>, Status> {
+		let mut board = self.lock();
+
+		if let Err(error) = operation(&mut board) {
+			warn!("gRPC operation failed: {}", error);
+			return Err(to_status(&error));
+		}
+
+		self.updates.send_replace(board.to_board_state());
+
+		Ok(Response::new(OperationReply {}))
+	}
+
+	/// Handles the `StartGame` call
+	fn start_game(&self, request: StartGameRequest) -> Result<Response<OperationReply>, Status> {
+		trace!("gRPC StartGame of '{}' and '{}'", request.home_team, request.away_team);
+
+		self.change(|board| board.start_game(request.home_team, request.away_team))
+	}
+
+	/// Handles the `UpdateScore` call
+	fn update_score(&self, request: UpdateScoreRequest) -> Result<Response<OperationReply>, Status> {
+		trace!("gRPC UpdateScore of '{}' and '{}'", request.home_team, request.away_team);
+
+		self.change(|board| {
+			let home_score = protobuf::to_score(request.home_score)?;
+			let away_score = protobuf::to_score(request.away_score)?;
+			board.update_score(request.home_team, home_score, request.away_team, away_score)
+		})
+	}
+
+	/// Handles the `FinishGame` call
+	fn finish_game(&self, request: FinishGameRequest) -> Result<Response<OperationReply>, Status> {
+		trace!("gRPC FinishGame of '{}' and '{}'", request.home_team, request.away_team);
+
+		self.change(|board| board.finish_game(request.home_team, request.away_team))
+	}
+
+	/// Handles the `StreamSummary` call. The stream starts with the current state of the board
+	fn stream_summary(&self) -> BoxStream<BoardState> {
+		trace!("gRPC StreamSummary");
+
+		Box::pin(WatchStream::new(self.updates.subscribe()).map(Ok))
+	}
+}
+
+/// Converts an error of the score board to a gRPC status, see the table in the module description
+fn to_status(error: &ScoreBoardError) -> Status {
+	let code = match error {
+		ScoreBoardError::GameNotFound { .. } => Code::NotFound,
+		ScoreBoardError::AlreadyPlaying(_) | ScoreBoardError::AlreadyScheduled { .. } => Code::AlreadyExists,
+		ScoreBoardError::UpdateTooSoon { .. } | ScoreBoardError::TooManyGames(_) | ScoreBoardError::Rejected(_) | ScoreBoardError::IllegalTransition { .. } => Code::FailedPrecondition,
+		ScoreBoardError::Storage(_) => Code::Unavailable,
+		_ => Code::InvalidArgument,
+	};
+
+	Status::new(code, error.to_string())
+}
+
+/// Adapts a unary call handler to the `tonic` server
+struct UnaryCall<F>(GrpcScoreBoard, F);
+
+impl<M, F> UnaryService<M> for UnaryCall<F>
+where
+	F: Fn(&GrpcScoreBoard, M) -> Result<Response<OperationReply>, Status>,
+{
+	type Response = OperationReply;
+	type Future = BoxFuture<Response<OperationReply>, Status>;
+
+	fn call(&mut self, request: Request<M>) -> Self::Future {
+		let result = (self.1)(&self.0, request.into_inner());
+
+		Box::pin(async move { result })
+	}
+}
+
+/// Adapts the `StreamSummary` call handler to the `tonic` server
+struct StreamSummaryCall(GrpcScoreBoard);
+
+impl ServerStreamingService<StreamSummaryRequest> for StreamSummaryCall {
+	type Response = BoardState;
+	type ResponseStream = BoxStream<BoardState>;
+	type Future = BoxFuture<Response<BoxStream<BoardState>>, Status>;
+
+	fn call(&mut self, _: Request<StreamSummaryRequest>) -> Self::Future {
+		let stream = self.0.stream_summary();
+
+		Box::pin(async move { Ok(Response::new(stream)) })
+	}
+}
+
+impl<B> Service<http::Request<B>> for GrpcScoreBoard
+where
+	B: Body + Send + 'static,
+	B::Error: Into<StdError> + Send + 'static,
+{
+	type Response = http::Response<tonic::body::Body>;
+	type Error = Infallible;
+	type Future = BoxFuture<Self::Response, Self::Error>;
+
+	fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, request: http::Request<B>) -> Self::Future {
+		let service = self.clone();
+
+		match request.uri().path() {
+			"/scoreboard.ScoreBoardService/StartGame" => Box::pin(async move {
+				Ok(Grpc::new(ProstCodec::default()).unary(UnaryCall(service, GrpcScoreBoard::start_game), request).await)
+			}),
+			"/scoreboard.ScoreBoardService/UpdateScore" => Box::pin(async move {
+				Ok(Grpc::new(ProstCodec::default()).unary(UnaryCall(service, GrpcScoreBoard::update_score), request).await)
+			}),
+			"/scoreboard.ScoreBoardService/FinishGame" => Box::pin(async move {
+				Ok(Grpc::new(ProstCodec::default()).unary(UnaryCall(service, GrpcScoreBoard::finish_game), request).await)
+			}),
+			"/scoreboard.ScoreBoardService/StreamSummary" => Box::pin(async move {
+				Ok(Grpc::new(ProstCodec::default()).server_streaming(StreamSummaryCall(service), request).await)
+			}),
+			path => {
+				warn!("Unknown gRPC method {}", path);
+				Box::pin(async move { Ok(Status::unimplemented("Unknown method").into_http()) })
+			},
+		}
+	}
+}
+
+impl NamedService for GrpcScoreBoard {
+	const NAME: &'static str = SERVICE_NAME;
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use tokio_stream::wrappers::TcpListenerStream;
+	use tonic::client::Grpc as GrpcClient;
+	use tonic::codegen::http::uri::PathAndQuery;
+	use tonic::transport::{Channel, Server};
+
+	/// Serves the board on a free local port and connects a client to it
+	async fn connect(service: GrpcScoreBoard) -> GrpcClient<Channel> {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("Couldn't bind the server");
+		let address = listener.local_addr().expect("Couldn't get the server address");
+		tokio::spawn(Server::builder().add_service(service).serve_with_incoming(TcpListenerStream::new(listener)));
+
+		let channel = Channel::from_shared(format!("http://{}", address)).expect("Couldn't parse the address")
+			.connect().await.expect("Couldn't connect to the server");
+		GrpcClient::new(channel)
+	}
+
+	/// Makes a unary call
+	async fn call<M: prost::Message + 'static>(client: &mut GrpcClient<Channel>, method: &'static str, message: M) -> Result<OperationReply, Status> {
+		client.ready().await.expect("Couldn't reach the server");
+		let path = PathAndQuery::from_static(method);
+
+		client.unary(Request::new(message), path, ProstCodec::default()).await.map(Response::into_inner)
+	}
+
+	fn start(home_team: &str, away_team: &str) -> StartGameRequest {
+		StartGameRequest { home_team: String::from(home_team), away_team: String::from(away_team) }
+	}
+
+	#[tokio::test]
+	async fn operations_change_the_served_board() {
+		let service = GrpcScoreBoard::new(ScoreBoard::new());
+		let mut client = connect(service.clone()).await;
+
+		call(&mut client, "/scoreboard.ScoreBoardService/StartGame", start("Mexico", "Canada")).await.expect("Couldn't start the game");
+		call(&mut client, "/scoreboard.ScoreBoardService/UpdateScore", UpdateScoreRequest {
+			home_team: String::from("Mexico"),
+			home_score: 0,
+			away_team: String::from("Canada"),
+			away_score: 5,
+		}).await.expect("Couldn't update the game");
+		assert_eq!(service.get_summary(), vec![String::from("Mexico 0 - Canada 5")]);
+
+		call(&mut client, "/scoreboard.ScoreBoardService/FinishGame", FinishGameRequest {
+			home_team: String::from("Mexico"),
+			away_team: String::from("Canada"),
+		}).await.expect("Couldn't finish the game");
+		assert!(service.get_summary().is_empty());
+	}
+
+	#[tokio::test]
+	async fn errors_have_matching_codes() {
+		let mut client = connect(GrpcScoreBoard::new(ScoreBoard::new())).await;
+		call(&mut client, "/scoreboard.ScoreBoardService/StartGame", start("Mexico", "Canada")).await.expect("Couldn't start the game");
+
+		let status = call(&mut client, "/scoreboard.ScoreBoardService/StartGame", start("Canada", "Spain")).await.expect_err("Started a game twice");
+		assert_eq!((status.code(), status.message()), (Code::AlreadyExists, "Canada is currently playing a game"));
+		let status = call(&mut client, "/scoreboard.ScoreBoardService/FinishGame", FinishGameRequest::default()).await.expect_err("Finished a missing game");
+		assert_eq!(status.code(), Code::NotFound);
+		let status = call(&mut client, "/scoreboard.ScoreBoardService/UpdateScore", UpdateScoreRequest {
+			home_team: String::from("Mexico"),
+			home_score: 256,
+			away_team: String::from("Canada"),
+			away_score: 0,
+		}).await.expect_err("Accepted a score out of range");
+		assert_eq!(status.code(), Code::InvalidArgument);
+	}
+
+	#[tokio::test]
+	async fn summary_stream_follows_the_changes() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let service = GrpcScoreBoard::new(sb);
+		let mut client = connect(service.clone()).await;
+
+		client.ready().await.expect("Couldn't reach the server");
+		let path = PathAndQuery::from_static("/scoreboard.ScoreBoardService/StreamSummary");
+		let mut stream = client.server_streaming(Request::new(StreamSummaryRequest {}), path, ProstCodec::<StreamSummaryRequest, BoardState>::default())
+			.await.expect("Couldn't open the stream").into_inner();
+
+		let first = stream.message().await.expect("Couldn't read the stream").expect("The stream has ended");
+		assert_eq!(first.games.len(), 1);
+
+		service.change(|board| board.update_score("Spain", 1, "Brazil", 0)).expect("Couldn't update the game");
+		let second = stream.message().await.expect("Couldn't read the stream").expect("The stream has ended");
+		assert_eq!(second.games[0].home_score, 1);
+	}
+
+	#[test]
+	fn unknown_method_is_unimplemented() {
+		let mut service = GrpcScoreBoard::new(ScoreBoard::new());
+		let request = http::Request::builder().uri("/scoreboard.ScoreBoardService/Reset").body(tonic::body::Body::empty()).expect("Couldn't build the request");
+		let response = tokio::runtime::Builder::new_current_thread().build().expect("Couldn't build the runtime")
+			.block_on(service.call(request)).expect("The call failed");
+
+		assert_eq!(response.headers().get("grpc-status").map(|code| code.as_bytes()), Some("12".as_bytes()));
+	}
+}
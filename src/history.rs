@@ -0,0 +1,376 @@
+//! # Board history
+//!
+//! Rebuilds the state of the board at a past moment from the recorded events, for the replays of the past situations and the investigation of the disputed changes, and collapses the old events into a checkpoint, so the history of a long tournament doesn't grow without bounds
+
+use std::cmp::Reverse;
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+use log::{debug, trace};
+
+use crate::events::{EventRecord, ScoreBoardEvent};
+use crate::order::SortOrder;
+use crate::period::Period;
+use crate::snapshot::{BoardSnapshot, GameSnapshot};
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+use crate::ScoreBoard;
+
+/// The state of the board after the last event removed by `ScoreBoard::compact_log()`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogCheckpoint {
+	/// Sequence number of the last removed event
+	pub sequence: u64,
+	/// Wall clock time of the last removed event
+	pub time: SystemTime,
+	/// The games as they stood after the last removed event, in the order of the board at the compaction
+	pub snapshot: BoardSnapshot,
+}
+
+impl ScoreBoard {
+	/// Provides the games of the board as they stood at the given time, by replaying the events recorded until then. The games are in the current order of the board (see `set_sort_order()`), and the kickoff time of a game is the time of its start on the board. The replay starts from the checkpoint of `compact_log()`, so the times before the checkpoint give the board as it stood at the checkpoint. A board restored with `from_snapshot()` has no events from before the restore
+	///
+	/// # Arguments
+	///
+	/// * `time` - the moment to rebuild the board at. The events recorded at this very time are included
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// let half_time = sb.get_events().last().unwrap().time;
+	/// sb.update_score("Japan", 2, "Indonesia", 0).unwrap();
+	/// sb.finish_game("Japan", "Indonesia").unwrap();
+	///
+	/// let snapshot = sb.state_at(half_time);
+	/// assert_eq!((snapshot.games[0].home_score, snapshot.games[0].away_score), (1, 0));
+	/// ```
+	pub fn state_at(&self, time: SystemTime) -> BoardSnapshot {
+		trace!("Rebuilding the score board at {:?}", time);
+
+		let count = self.events.iter().take_while(|record| record.time <= time).count();
+		let games = self.replay(&self.events[..count]);
+
+		debug!("Rebuilt {} games", games.len());
+
+		BoardSnapshot::new(games)
+	}
+
+	/// Removes the events older than the retention window, keeping the state of the board after them as a checkpoint. The recent events can still be replayed with `state_at()`, and the sequence numbers of the following events go on from the removed ones. The removed events are no longer in `get_events()`, `get_audit_log()` and `get_timeline()`, and the notes of the games made before them are removed as well
+	///
+	/// # Arguments
+	///
+	/// * `retention` - how long the events are kept
+	///
+	/// # Returns
+	///
+	/// * The number of the removed events
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::Duration;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// assert_eq!(sb.compact_log(Duration::ZERO), 2);
+	///
+	/// assert!(sb.get_events().is_empty());
+	/// assert_eq!(sb.get_log_checkpoint().unwrap().snapshot.games[0].home_score, 1);
+	/// sb.update_score("Japan", 2, "Indonesia", 0).unwrap();
+	/// assert_eq!(sb.get_events()[0].sequence, 3);
+	/// ```
+	pub fn compact_log(&mut self, retention: Duration) -> usize {
+		trace!("Compacting the events older than {:?}", retention);
+
+		match SystemTime::now().checked_sub(retention) {
+			Some(cutoff) => self.compact_before(cutoff),
+			None => 0,
+		}
+	}
+
+	/// Provides the state of the board after the last event removed by `compact_log()`, if any events were removed
+	pub fn get_log_checkpoint(&self) -> Option<&LogCheckpoint> {
+		self.checkpoint.as_ref()
+	}
+
+	/// Removes the events recorded before the given time, keeping the state of the board after them as a checkpoint
+	///
+	/// # Arguments
+	///
+	/// * `cutoff` - the time of the oldest event to keep
+	///
+	/// # Returns
+	///
+	/// * The number of the removed events
+	///
+	fn compact_before(&mut self, cutoff: SystemTime) -> usize {
+		let count = self.events.iter().take_while(|record| record.time < cutoff).count();
+		let (sequence, time) = match count.checked_sub(1).map(|last| &self.events[last]) {
+			Some(last) => (last.sequence, last.time),
+			None => return 0,
+		};
+
+		let games = self.replay(&self.events[..count]);
+		self.events.drain(..count);
+		self.notes.retain(|note| note.after_sequence >= sequence);
+		self.checkpoint = Some(LogCheckpoint { sequence, time, snapshot: BoardSnapshot::new(games) });
+
+		debug!("Compacted {} events", count);
+
+		count
+	}
+
+	/// Replays the events on top of the checkpoint, if there is one
+	///
+	/// # Arguments
+	///
+	/// * `records` - the events to replay, the oldest coming first
+	///
+	/// # Returns
+	///
+	/// * The games after the events, in the order of the board
+	///
+	fn replay(&self, records: &[EventRecord]) -> Vec<GameSnapshot> {
+		let mut games = self.checkpoint.as_ref().map_or_else(Vec::new, |checkpoint| checkpoint.snapshot.games.clone());
+		let mut finished: Vec<GameSnapshot> = Vec::new();
+
+		for record in records {
+			let (home, away) = record.event.get_teams();
+			let position = games.iter().position(|game| game.home_team == home && game.away_team == away);
+
+			match (&record.event, position) {
+				(ScoreBoardEvent::GameStarted { home, away }, _) => games.push(GameSnapshot {
+					home_team: home.clone(),
+					home_score: 0,
+					away_team: away.clone(),
+					away_score: 0,
+					kickoff_time: record.time,
+					revision: record.revision,
+					state: GameState::Live,
+					period: Period::FirstHalf,
+					venue: None,
+					group: None,
+					utc_offset: UtcOffset::UTC,
+				}),
+				(ScoreBoardEvent::ScoreUpdated { home_score, away_score, .. }, Some(position)) => {
+					let game = &mut games[position];
+					game.home_score = *home_score;
+					game.away_score = *away_score;
+					game.revision = record.revision;
+				},
+				(ScoreBoardEvent::GameFinished { .. }, Some(position)) => {
+					finished.push(games.remove(position));
+				},
+				(ScoreBoardEvent::GameReopened { home, home_score, away, away_score }, _) => {
+					// The kickoff is only known when the finish of the game is replayed as well
+					let kickoff_time = finished.iter()
+						.rposition(|game| &game.home_team == home && &game.away_team == away)
+						.map_or(record.time, |index| finished.remove(index).kickoff_time);
+					games.push(GameSnapshot {
+						home_team: home.clone(),
+						home_score: *home_score,
+						away_team: away.clone(),
+						away_score: *away_score,
+						kickoff_time,
+						revision: record.revision,
+						state: GameState::Live,
+						period: Period::FirstHalf,
+						venue: None,
+						group: None,
+						utc_offset: UtcOffset::UTC,
+					});
+				},
+				(ScoreBoardEvent::StateChanged { state: GameState::Abandoned, .. }, Some(position)) => {
+					games.remove(position);
+				},
+				(ScoreBoardEvent::StateChanged { .. } | ScoreBoardEvent::PeriodStarted { .. }, Some(position)) => games[position].revision = record.revision,
+				(ScoreBoardEvent::SidesSwapped { home, home_score, away, away_score }, _) => {
+					// The game is still known by its teams from before the swap
+					if let Some(game) = games.iter_mut().find(|game| &game.home_team == away && &game.away_team == home) {
+						game.home_team.clone_from(home);
+						game.home_score = *home_score;
+						game.away_team.clone_from(away);
+						game.away_score = *away_score;
+						game.revision = record.revision;
+					}
+				},
+				// The events of the games started before the board was restored from a snapshot
+				_ => (),
+			}
+		}
+
+		match self.sort_order {
+			SortOrder::Ranking => games.sort_by_key(|game| (Reverse(game.get_total_score()), Reverse(game.kickoff_time))),
+			SortOrder::Kickoff => games.sort_by_key(|game| game.kickoff_time),
+			SortOrder::GoalDifference => games.sort_by_key(|game| (Reverse(game.get_total_score()), Reverse(game.home_score.abs_diff(game.away_score)), Reverse(game.kickoff_time))),
+		}
+
+		games
+	}
+}
+
+impl GameSnapshot {
+	/// Provides the sum of the scores of both teams
+	fn get_total_score(&self) -> u16 {
+		u16::from(self.home_score) + u16::from(self.away_score)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::time::Duration;
+
+	/// Provides the time of the given minute of the test
+	fn minute(number: u64) -> SystemTime {
+		SystemTime::UNIX_EPOCH + Duration::from_secs(60 * number)
+	}
+
+	/// Sets the time of every recorded event to the following minute, so the events have distinct times
+	fn spread_events(sb: &mut ScoreBoard) {
+		for (index, record) in sb.events.iter_mut().enumerate() {
+			record.time = minute(index as u64 + 1);
+		}
+	}
+
+	fn get_summary(snapshot: &BoardSnapshot) -> Vec<String> {
+		snapshot.games.iter().map(|game| format!("{} {} - {} {}", game.home_team, game.home_score, game.away_team, game.away_score)).collect()
+	}
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 1, "Canada", 0).expect("Couldn't update the first game");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the second game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+		sb.set_game_state("Spain", "Brazil", GameState::Paused).expect("Couldn't pause the second game");
+		sb.set_game_state("Spain", "Brazil", GameState::Abandoned).expect("Couldn't abandon the second game");
+		spread_events(&mut sb);
+		sb
+	}
+
+	#[test]
+	fn board_is_rebuilt_at_every_event() {
+		let sb = get_board();
+
+		assert!(sb.state_at(minute(0)).games.is_empty());
+		assert_eq!(get_summary(&sb.state_at(minute(2))), vec![String::from("Spain 0 - Brazil 0"), String::from("Mexico 0 - Canada 0")]);
+		assert_eq!(get_summary(&sb.state_at(minute(3))), vec![String::from("Mexico 1 - Canada 0"), String::from("Spain 0 - Brazil 0")]);
+		assert_eq!(get_summary(&sb.state_at(minute(5))), vec![String::from("Spain 2 - Brazil 0")]);
+		assert!(sb.state_at(minute(8)).games.is_empty());
+	}
+
+	#[test]
+	fn rebuilt_games_keep_their_revisions() {
+		let sb = get_board();
+		let snapshot = sb.state_at(minute(7));
+
+		assert_eq!(snapshot.games[0].revision, 3);
+		assert_eq!(snapshot.games[0].kickoff_time, minute(2));
+	}
+
+	#[test]
+	fn rebuilt_games_follow_the_order_of_the_board() {
+		let mut sb = get_board();
+		sb.set_sort_order(SortOrder::Kickoff);
+
+		assert_eq!(get_summary(&sb.state_at(minute(4))), vec![String::from("Mexico 1 - Canada 0"), String::from("Spain 2 - Brazil 0")]);
+	}
+
+	#[test]
+	fn board_is_rebuilt_across_a_swap_of_the_sides() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Canada", "Mexico").expect("Couldn't create the game");
+		sb.update_score("Canada", 0, "Mexico", 1).expect("Couldn't update the game");
+		sb.swap_sides("Canada", "Mexico").expect("Couldn't swap the sides");
+		sb.update_score("Mexico", 2, "Canada", 0).expect("Couldn't update the swapped game");
+		spread_events(&mut sb);
+
+		assert_eq!(get_summary(&sb.state_at(minute(2))), vec![String::from("Canada 0 - Mexico 1")]);
+		assert_eq!(get_summary(&sb.state_at(minute(3))), vec![String::from("Mexico 1 - Canada 0")]);
+		assert_eq!(get_summary(&sb.state_at(minute(4))), vec![String::from("Mexico 2 - Canada 0")]);
+		assert_eq!(sb.state_at(minute(4)).games[0].revision, 4);
+	}
+
+	#[test]
+	fn restored_board_has_no_history() {
+		let mut sb = ScoreBoard::from_snapshot(get_board().state_at(minute(4))).expect("Couldn't restore the board");
+		sb.update_score("Spain", 3, "Brazil", 0).expect("Couldn't update the game");
+		spread_events(&mut sb);
+
+		assert!(sb.state_at(minute(1)).games.is_empty());
+	}
+
+	#[test]
+	fn compaction_keeps_the_recent_history_replayable() {
+		let mut sb = get_board();
+		let expected: Vec<BoardSnapshot> = (0..=8).map(|number| sb.state_at(minute(number))).collect();
+
+		assert_eq!(sb.compact_before(minute(4)), 3);
+		assert_eq!(sb.get_events().first().map(|record| record.sequence), Some(4));
+		for number in 3..=8 {
+			assert_eq!(sb.state_at(minute(number)), expected[number as usize]);
+		}
+		// The older history is gone, and the board is shown as it stood at the checkpoint
+		assert_eq!(sb.state_at(minute(1)), expected[3]);
+	}
+
+	#[test]
+	fn compactions_build_on_the_checkpoint() {
+		let mut sb = get_board();
+		let expected = sb.state_at(minute(5));
+
+		assert_eq!(sb.compact_before(minute(2)), 1);
+		assert_eq!(sb.compact_before(minute(2)), 0);
+		assert_eq!(sb.compact_before(minute(6)), 4);
+
+		let checkpoint = sb.get_log_checkpoint().expect("Couldn't find the checkpoint");
+		assert_eq!((checkpoint.sequence, checkpoint.time), (5, minute(5)));
+		assert_eq!(checkpoint.snapshot, expected);
+	}
+
+	#[test]
+	fn sequence_goes_on_after_the_compaction() {
+		let mut sb = get_board();
+		sb.compact_before(minute(9));
+		assert!(sb.get_events().is_empty());
+
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		assert_eq!(sb.get_events()[0].sequence, 9);
+	}
+
+	#[test]
+	fn old_notes_are_compacted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		sb.add_note("Mexico", "Canada", "Heavy rain").expect("Couldn't add the first note");
+		sb.update_score("Mexico", 1, "Canada", 0).expect("Couldn't update the game");
+		sb.add_note("Mexico", "Canada", "VAR check").expect("Couldn't add the second note");
+		spread_events(&mut sb);
+
+		sb.compact_before(minute(2));
+		assert_eq!(sb.notes.iter().map(|note| note.text.as_str()).collect::<Vec<_>>(), vec!["Heavy rain", "VAR check"]);
+		sb.compact_before(minute(3));
+		assert_eq!(sb.notes.iter().map(|note| note.text.as_str()).collect::<Vec<_>>(), vec!["VAR check"]);
+	}
+
+	#[test]
+	fn nothing_is_compacted_within_the_retention() {
+		let mut sb = get_board();
+
+		assert_eq!(sb.compact_log(Duration::MAX), 0);
+		assert_eq!(sb.get_events().len(), 8);
+		assert_eq!(sb.get_log_checkpoint(), None);
+	}
+}
@@ -0,0 +1,277 @@
+//! # iCalendar export
+//!
+//! Renders the scheduled and the currently played games as an iCalendar (RFC 5545) feed, so the match day can be subscribed to from any calendar application
+
+use std::string::String;
+use std::time::{Duration, SystemTime};
+
+use log::trace;
+
+use crate::datetime::UtcDateTime;
+use crate::ScoreBoard;
+
+/// Expected length of a game in the calendar: two halves, the break and a margin for the stoppage time
+const GAME_DURATION: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Line ending required by the iCalendar format
+const CRLF: &str = "\r\n";
+
+impl ScoreBoard {
+	/// Exports the score board as an iCalendar feed. Every scheduled game is an event starting at its planned kickoff, and every currently played game is an event that started at its actual kickoff, with the current score in its description
+	///
+	/// # Returns
+	///
+	/// * A string with a complete `VCALENDAR` object, using CRLF line endings
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Argentina", "France", UNIX_EPOCH + Duration::from_secs(1_671_375_600)).unwrap();
+	/// let calendar = sb.export_ics();
+	/// assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+	/// assert!(calendar.contains("DTSTART:20221218T150000Z\r\n"));
+	/// assert!(calendar.contains("SUMMARY:Argentina - France\r\n"));
+	/// ```
+	pub fn export_ics(&self) -> String {
+		trace!("Exporting the score board as an iCalendar feed");
+
+		let time_stamp = UtcDateTime::from_system_time(SystemTime::now()).to_basic_format();
+
+		let mut result = String::new();
+		push_line(&mut result, "BEGIN:VCALENDAR");
+		push_line(&mut result, "VERSION:2.0");
+		push_line(&mut result, "PRODID:-//Daydreamest//scoreboard_world_cup//EN");
+		push_line(&mut result, "CALSCALE:GREGORIAN");
+
+		for fixture in &self.schedule {
+			push_event(
+				&mut result,
+				&time_stamp,
+				&get_uid(&fixture.home_name, &fixture.away_name, fixture.kickoff),
+				fixture.kickoff,
+				&format!("{} - {}", fixture.home_name, fixture.away_name),
+				"Scheduled",
+			);
+		}
+
+		for game in &self.data {
+			push_event(
+				&mut result,
+				&time_stamp,
+				// A game started from a fixture stays the same event
+				&get_uid(&game.home_team.name, &game.away_team.name, game.scheduled_kickoff.unwrap_or(game.kickoff_time)),
+				game.kickoff_time,
+				&format!("{} - {}", game.home_team.name, game.away_team.name),
+				&format!("Live: {}", game),
+			);
+		}
+
+		push_line(&mut result, "END:VCALENDAR");
+
+		trace!("Score board exported");
+
+		result
+	}
+}
+
+/// Appends a single `VEVENT` object to the calendar
+///
+/// # Arguments
+///
+/// * `calendar` - the calendar being built
+/// * `time_stamp` - creation time of the calendar, already formatted
+/// * `uid` - identifier of the event, made with `get_uid()`
+/// * `kickoff` - start time of the game
+/// * `summary` - title of the event
+/// * `description` - longer description of the event
+///
+fn push_event(calendar: &mut String, time_stamp: &str, uid: &str, kickoff: SystemTime, summary: &str, description: &str) {
+	let start = UtcDateTime::from_system_time(kickoff).to_basic_format();
+	let end = UtcDateTime::from_system_time(kickoff + GAME_DURATION).to_basic_format();
+
+	push_line(calendar, "BEGIN:VEVENT");
+	push_line(calendar, &format!("UID:{}@scoreboard_world_cup", uid));
+	push_line(calendar, &format!("DTSTAMP:{}", time_stamp));
+	push_line(calendar, &format!("DTSTART:{}", start));
+	push_line(calendar, &format!("DTEND:{}", end));
+	push_line(calendar, &format!("SUMMARY:{}", escape_text(summary)));
+	push_line(calendar, &format!("DESCRIPTION:{}", escape_text(description)));
+	push_line(calendar, "END:VEVENT");
+}
+
+/// Builds an identifier of an event that stays the same between exports, so calendar applications update existing events instead of duplicating them. The identifier is made of the fixture, so it doesn't change when the game kicks off late
+///
+/// # Arguments
+///
+/// * `home_name` - name of the home team
+/// * `away_name` - name of the away team
+/// * `kickoff` - planned start time of the game, or the actual one for a game that wasn't scheduled
+///
+fn get_uid(home_name: &str, away_name: &str, kickoff: SystemTime) -> String {
+	let teams: String = format!("{}-{}", home_name, away_name)
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+		.collect();
+
+	format!("{}-{}", teams, UtcDateTime::from_system_time(kickoff).to_basic_format())
+}
+
+/// Escapes the characters that have a special meaning in iCalendar text values
+fn escape_text(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+
+	for c in text.chars() {
+		match c {
+			'\\' => result.push_str("\\\\"),
+			';' => result.push_str("\\;"),
+			',' => result.push_str("\\,"),
+			'\n' => result.push_str("\\n"),
+			'\r' => (),
+			_ => result.push(c),
+		}
+	}
+
+	result
+}
+
+/// Appends a line to the calendar. Lines longer than 75 bytes are folded, as required by the iCalendar format
+fn push_line(calendar: &mut String, line: &str) {
+	let mut line_length = 0;
+
+	for c in line.chars() {
+		if line_length + c.len_utf8() > 75 {
+			calendar.push_str(CRLF);
+			calendar.push(' ');
+			line_length = 1;
+		}
+		calendar.push(c);
+		line_length += c.len_utf8();
+	}
+
+	calendar.push_str(CRLF);
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::UNIX_EPOCH;
+
+	const FINAL_KICKOFF: u64 = 1_671_375_600;
+
+	fn at(seconds: u64) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(seconds)
+	}
+
+	fn count_events(calendar: &str) -> usize {
+		calendar.matches("BEGIN:VEVENT\r\n").count()
+	}
+
+	#[test]
+	fn empty_board_exports_an_empty_calendar() {
+		let sb = ScoreBoard::new();
+		let result = sb.export_ics();
+
+		assert!(result.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+		assert!(result.ends_with("END:VCALENDAR\r\n"));
+		assert_eq!(count_events(&result), 0);
+	}
+
+	#[test]
+	fn scheduled_game_is_exported_as_an_event() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", at(FINAL_KICKOFF)).expect("Couldn't schedule the game");
+		let result = sb.export_ics();
+
+		assert_eq!(count_events(&result), 1);
+		assert!(result.contains("UID:argentina-france-20221218T150000Z@scoreboard_world_cup\r\n"));
+		assert!(result.contains("DTSTART:20221218T150000Z\r\n"));
+		assert!(result.contains("DTEND:20221218T170000Z\r\n"));
+		assert!(result.contains("SUMMARY:Argentina - France\r\n"));
+		assert!(result.contains("DESCRIPTION:Scheduled\r\n"));
+	}
+
+	#[test]
+	fn live_game_is_exported_with_its_score() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		sb.update_score("Argentina", 3, "France", 3).expect("Couldn't update the game");
+		let result = sb.export_ics();
+
+		assert_eq!(count_events(&result), 1);
+		assert!(result.contains("SUMMARY:Argentina - France\r\n"));
+		assert!(result.contains("DESCRIPTION:Live: Argentina 3 - France 3\r\n"));
+	}
+
+	#[test]
+	fn started_game_is_no_longer_exported_as_scheduled() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", at(FINAL_KICKOFF)).expect("Couldn't schedule the game");
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		let result = sb.export_ics();
+
+		assert_eq!(count_events(&result), 1);
+		assert!(!result.contains("DESCRIPTION:Scheduled\r\n"));
+		assert!(result.contains("DESCRIPTION:Live: Argentina 0 - France 0\r\n"));
+	}
+
+	#[test]
+	fn scheduled_and_live_games_are_exported_together() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", at(FINAL_KICKOFF)).expect("Couldn't schedule the first game");
+		sb.schedule_game("Croatia", "Morocco", at(FINAL_KICKOFF - 86_400)).expect("Couldn't schedule the second game");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		let result = sb.export_ics();
+
+		assert_eq!(count_events(&result), 3);
+	}
+
+	#[test]
+	fn special_characters_are_escaped() {
+		assert_eq!(escape_text("A, B; C\\D\nE"), "A\\, B\\; C\\\\D\\nE");
+	}
+
+	#[test]
+	fn team_names_in_uid_are_sanitized() {
+		assert_eq!(get_uid("Côte d'Ivoire", "Korea Republic", at(FINAL_KICKOFF)), "c_te_d_ivoire-korea_republic-20221218T150000Z");
+	}
+
+	#[test]
+	fn started_fixture_keeps_its_uid() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Argentina", "France", at(FINAL_KICKOFF)).expect("Couldn't schedule the game");
+		let scheduled = sb.export_ics();
+		sb.new_game("Argentina", "France").kickoff_at(at(FINAL_KICKOFF + 600)).start().expect("Couldn't create the game");
+		let live = sb.export_ics();
+
+		let uid = "UID:argentina-france-20221218T150000Z@scoreboard_world_cup\r\n";
+		assert!(scheduled.contains(uid));
+		assert!(live.contains(uid));
+		assert!(live.contains("DTSTART:20221218T151000Z\r\n"));
+	}
+
+	#[test]
+	fn long_lines_are_folded() {
+		let mut calendar = String::new();
+		push_line(&mut calendar, &"A".repeat(100));
+
+		assert_eq!(calendar, format!("{}\r\n {}\r\n", "A".repeat(75), "A".repeat(25)));
+	}
+
+	#[test]
+	fn long_summaries_are_folded_in_the_export() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Democratic People's Republic of Korea", "Saint Vincent and the Grenadines", at(FINAL_KICKOFF)).expect("Couldn't schedule the game");
+		let result = sb.export_ics();
+
+		assert!(result.contains("SUMMARY:Democratic People's Republic of Korea - Saint Vincent and the Grena\r\n dines\r\n"));
+		assert!(result.split("\r\n").all(|line| line.len() <= 75));
+	}
+}
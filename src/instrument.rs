@@ -0,0 +1,128 @@
+//! # Tracing instrumentation
+//!
+//! With the `tracing` feature, every score board operation runs inside a `tracing` span, carrying the team names and scores as structured fields. Without the feature the spans are compiled out, and only the `log` messages are emitted
+
+/// Enters a span for the rest of the enclosing block. Accepts the same arguments as `tracing::span!`, starting with the level
+macro_rules! operation_span {
+	($($arguments:tt)*) => {
+		#[cfg(feature = "tracing")]
+		let _span = tracing::span!($($arguments)*).entered();
+	};
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+	use std::fmt;
+	use std::string::{String, ToString};
+	use std::sync::{Arc, Mutex};
+	use std::vec::Vec;
+
+	use tracing::field::{Field, Visit};
+	use tracing::span::{Attributes, Id, Record};
+	use tracing::{Event, Metadata, Subscriber};
+
+	use crate::{BoardManager, ScoreBoard};
+
+	/// Name and fields of a created span
+	type SpanRecord = (String, Vec<(String, String)>);
+
+	/// A subscriber remembering all the created spans
+	struct SpanCollector {
+		spans: Arc<Mutex<Vec<SpanRecord>>>,
+	}
+
+	/// Collects the fields of a span as strings
+	struct FieldCollector(Vec<(String, String)>);
+
+	impl Visit for FieldCollector {
+		fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+			self.0.push((field.name().to_string(), format!("{:?}", value)));
+		}
+	}
+
+	impl Subscriber for SpanCollector {
+		fn enabled(&self, _: &Metadata<'_>) -> bool {
+			true
+		}
+
+		fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+			let mut fields = FieldCollector(Vec::new());
+			attributes.record(&mut fields);
+
+			let mut spans = self.spans.lock().unwrap();
+			spans.push((attributes.metadata().name().to_string(), fields.0));
+
+			Id::from_u64(spans.len() as u64)
+		}
+
+		fn record(&self, _: &Id, _: &Record<'_>) {}
+
+		fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+		fn event(&self, _: &Event<'_>) {}
+
+		fn enter(&self, _: &Id) {}
+
+		fn exit(&self, _: &Id) {}
+	}
+
+	/// Runs the given code and returns all the spans it created
+	fn collect_spans<F: FnOnce()>(code: F) -> Vec<SpanRecord> {
+		let spans = Arc::new(Mutex::new(Vec::new()));
+		let subscriber = SpanCollector { spans: spans.clone() };
+
+		tracing::subscriber::with_default(subscriber, code);
+
+		let result = spans.lock().unwrap().clone();
+		result
+	}
+
+	fn field(name: &str, value: &str) -> (String, String) {
+		(String::from(name), String::from(value))
+	}
+
+	#[test]
+	fn start_game_runs_in_a_span_with_team_names() {
+		let spans = collect_spans(|| {
+			let mut sb = ScoreBoard::new();
+			sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		});
+
+		assert_eq!(spans, vec![(String::from("start_game"), vec![field("home", "Japan"), field("away", "Indonesia")])]);
+	}
+
+	#[test]
+	fn update_score_runs_in_a_span_with_scores() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		let spans = collect_spans(|| {
+			sb.update_score("Japan", 2, "Indonesia", 1).expect("Couldn't update the game");
+		});
+
+		assert_eq!(spans, vec![(String::from("update_score"), vec![field("home", "Japan"), field("home_score", "2"), field("away", "Indonesia"), field("away_score", "1")])]);
+	}
+
+	#[test]
+	fn failed_finish_game_still_runs_in_a_span() {
+		let mut sb = ScoreBoard::new();
+		let spans = collect_spans(|| {
+			let _ = sb.finish_game("Japan", "Indonesia");
+		});
+
+		assert_eq!(spans, vec![(String::from("finish_game"), vec![field("home", "Japan"), field("away", "Indonesia")])]);
+	}
+
+	#[test]
+	fn manager_operations_run_in_spans_with_competition() {
+		let spans = collect_spans(|| {
+			let mut manager = BoardManager::new();
+			manager.add_competition("World Cup").expect("Couldn't add the competition");
+		});
+
+		assert_eq!(spans, vec![(String::from("add_competition"), vec![field("competition", "World Cup")])]);
+	}
+}
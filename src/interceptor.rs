@@ -0,0 +1,242 @@
+//! # Interceptors
+//!
+//! Wraps the commands applied to the score board in a chain of layers, each of them able to inspect, change or reject a command before it reaches the board, so the logging, the access rules and the metrics don't have to be repeated at every call site
+
+use std::sync::Arc;
+
+use log::trace;
+
+use crate::command::Command;
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// A layer around the commands applied with `ScoreBoard::apply()`. The layer gets the command together with the rest of the chain, and decides what reaches the board: it can pass the command on, pass a changed command on, or return an error without passing anything. Work done after `Next::run()` sees the result of the command, ex. to measure its time
+///
+/// Any closure taking a `Command` and a `Next` and returning `Result<(), ScoreBoardError>` is an interceptor too
+pub trait Interceptor {
+	/// Handles a command on its way to the board
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command about to be applied
+	/// * `next` - the rest of the chain, ending with the board
+	///
+	/// # Errors
+	///
+	/// * When the layer rejects the command, or the rest of the chain fails
+	fn intercept(&self, command: Command, next: Next<'_>) -> Result<(), ScoreBoardError>;
+}
+
+impl<F> Interceptor for F
+where
+	F: Fn(Command, Next<'_>) -> Result<(), ScoreBoardError>,
+{
+	fn intercept(&self, command: Command, next: Next<'_>) -> Result<(), ScoreBoardError> {
+		self(command, next)
+	}
+}
+
+/// The rest of the interceptor chain, ending with the score board, see `Interceptor`
+pub struct Next<'a> {
+	/// The board at the end of the chain
+	board: &'a mut ScoreBoard,
+	/// The layers still to pass, the outermost coming first
+	interceptors: &'a [Arc<dyn Interceptor + Send + Sync>],
+}
+
+impl<'a> Next<'a> {
+	/// Provides the score board, in the state from before the command
+	pub fn get_board(&self) -> &ScoreBoard {
+		self.board
+	}
+
+	/// Passes a command on to the next layer, or applies it to the board after the last layer
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command to pass on, the received one or a changed one
+	///
+	/// # Errors
+	///
+	/// * When any of the following layers rejects the command
+	/// * The same as the operation the command represents
+	pub fn run(self, command: Command) -> Result<(), ScoreBoardError> {
+		match self.interceptors.split_first() {
+			Some((interceptor, interceptors)) => interceptor.intercept(command, Next { board: self.board, interceptors }),
+			None => self.board.dispatch(command),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Adds an interceptor to the score board. The first added interceptor is the outermost layer: it sees every command first and its result last. Interceptors wrap the commands of `apply()` and everything built on it, like `apply_batch()`, the command scripts and the feeds, but not the direct calls of the operations, like `start_game()`
+	///
+	/// # Arguments
+	///
+	/// * `interceptor` - the layer to add
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Command, Next, Rejection, ScoreBoardError};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// // Team names come in upper case from the stadium feed
+	/// sb.add_interceptor(|command: Command, next: Next| match command {
+	///     Command::StartGame { home, away } => next.run(Command::StartGame { home: home.to_uppercase(), away: away.to_uppercase() }),
+	///     command => next.run(command),
+	/// });
+	/// // Scores never go down
+	/// sb.add_interceptor(|command: Command, next: Next| match command {
+	///     Command::UpdateScore { ref home, home_score, ref away, away_score }
+	///         if next.get_board().get_score(home, away).is_some_and(|(old_home, old_away)| home_score < old_home || away_score < old_away) =>
+	///         Err(ScoreBoardError::Rejected(Rejection::new("no corrections", "the score can't go down"))),
+	///     command => next.run(command),
+	/// });
+	///
+	/// sb.apply(Command::StartGame { home: String::from("Japan"), away: String::from("Indonesia") }).unwrap();
+	/// sb.apply(Command::UpdateScore { home: String::from("JAPAN"), home_score: 1, away: String::from("INDONESIA"), away_score: 0 }).unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("JAPAN 1 - INDONESIA 0")]);
+	/// assert!(sb.apply(Command::UpdateScore { home: String::from("JAPAN"), home_score: 0, away: String::from("INDONESIA"), away_score: 0 }).is_err());
+	/// ```
+	pub fn add_interceptor<I: Interceptor + Send + Sync + 'static>(&mut self, interceptor: I) {
+		trace!("Adding an interceptor");
+
+		self.interceptors.push(Arc::new(interceptor));
+	}
+
+	/// Removes all the interceptors from the score board
+	pub fn clear_interceptors(&mut self) {
+		trace!("Removing all interceptors");
+
+		self.interceptors.clear();
+	}
+
+	/// Passes a command through all the interceptors to the board
+	///
+	/// # Arguments
+	///
+	/// * `command` - the command to apply
+	///
+	/// # Errors
+	///
+	/// * When any of the interceptors rejects the command
+	/// * The same as the operation the command represents
+	///
+	pub(crate) fn intercept(&mut self, command: Command) -> Result<(), ScoreBoardError> {
+		if self.interceptors.is_empty() {
+			return self.dispatch(command);
+		}
+
+		// The chain borrows the board mutably, so it runs over its own handles to the layers
+		let interceptors = self.interceptors.clone();
+
+		Next { board: self, interceptors: &interceptors }.run(command)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::sync::Mutex;
+	use std::vec::Vec;
+
+	use crate::error::Rejection;
+
+	fn start(home: &str, away: &str) -> Command {
+		Command::StartGame { home: String::from(home), away: String::from(away) }
+	}
+
+	/// Records the commands it sees and their results, under its name
+	struct Recorder {
+		name: &'static str,
+		log: Arc<Mutex<Vec<String>>>,
+	}
+
+	impl Interceptor for Recorder {
+		fn intercept(&self, command: Command, next: Next<'_>) -> Result<(), ScoreBoardError> {
+			let (home, away) = command.get_teams();
+			let entry = format!("{} before {} - {}", self.name, home, away);
+			self.log.lock().expect("Couldn't lock the log").push(entry);
+
+			let result = next.run(command);
+
+			let entry = format!("{} after {}", self.name, if result.is_ok() { "ok" } else { "error" });
+			self.log.lock().expect("Couldn't lock the log").push(entry);
+			result
+		}
+	}
+
+	#[test]
+	fn layers_wrap_each_other_in_the_order_they_were_added() {
+		let log = Arc::new(Mutex::new(Vec::new()));
+		let mut sb = ScoreBoard::new();
+		sb.add_interceptor(Recorder { name: "outer", log: log.clone() });
+		sb.add_interceptor(Recorder { name: "inner", log: log.clone() });
+		sb.apply(start("Spain", "Brazil")).expect("Couldn't create the game");
+		sb.apply(start("Spain", "Brazil")).expect_err("The second start should fail");
+
+		assert_eq!(*log.lock().expect("Couldn't lock the log"), vec![
+			"outer before Spain - Brazil", "inner before Spain - Brazil", "inner after ok", "outer after ok",
+			"outer before Spain - Brazil", "inner before Spain - Brazil", "inner after error", "outer after error",
+		]);
+	}
+
+	#[test]
+	fn rejected_command_does_not_reach_the_board() {
+		let log = Arc::new(Mutex::new(Vec::new()));
+		let mut sb = ScoreBoard::new();
+		sb.add_interceptor(|command: Command, next: Next| match command {
+			Command::StartGame { ref home, .. } if home == "Mexico" => Err(ScoreBoardError::Rejected(Rejection::new("auth", "no rights to Mexico"))),
+			command => next.run(command),
+		});
+		sb.add_interceptor(Recorder { name: "inner", log: log.clone() });
+		let result = sb.apply(start("Mexico", "Canada"));
+
+		assert_eq!(result, Err(ScoreBoardError::Rejected(Rejection::new("auth", "no rights to Mexico"))));
+		assert!(log.lock().expect("Couldn't lock the log").is_empty());
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn changed_command_is_applied() {
+		let mut sb = ScoreBoard::new();
+		sb.add_interceptor(|command: Command, next: Next| match command {
+			Command::StartGame { home, away } => next.run(Command::StartGame { home: away, away: home }),
+			command => next.run(command),
+		});
+		sb.apply(start("Spain", "Brazil")).expect("Couldn't create the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Brazil 0 - Spain 0")]);
+	}
+
+	#[test]
+	fn batches_pass_the_interceptors_and_direct_calls_do_not() {
+		let count = Arc::new(Mutex::new(0));
+		let seen = count.clone();
+		let mut sb = ScoreBoard::new();
+		sb.add_interceptor(move |command: Command, next: Next| {
+			*seen.lock().expect("Couldn't lock the counter") += 1;
+			next.run(command)
+		});
+		sb.apply_batch(vec![start("Spain", "Brazil"), start("Mexico", "Canada")]).expect("Couldn't apply the batch");
+		sb.start_game("Germany", "France").expect("Couldn't create the game");
+
+		assert_eq!(*count.lock().expect("Couldn't lock the counter"), 2);
+	}
+
+	#[test]
+	fn cleared_interceptors_are_not_called() {
+		let mut sb = ScoreBoard::new();
+		sb.add_interceptor(|_: Command, _: Next| Err(ScoreBoardError::Rejected(Rejection::new("reject all", "nothing is allowed"))));
+		sb.clear_interceptors();
+
+		assert!(sb.apply(start("Spain", "Brazil")).is_ok());
+	}
+}
@@ -0,0 +1,903 @@
+//! # Journal
+//!
+//! Appends every change of the score board to a file, synced to the disk before the change is made on the board, so a board can be recovered after the process dies in the middle of a matchday. A change that can't be appended isn't made at all
+//!
+//! Every change is one line of tab separated fields, starting with the time of the change in milliseconds since the Unix epoch and the kind of the change:
+//!
+//! * `<time> started <home> <away> <kickoff time> <UTC offset in minutes> [<venue> [<group>]]`, with the kickoff time in milliseconds since the Unix epoch, an empty UTC offset when it wasn't given, and an empty venue when only the group is known. The lines of the older journals have only the teams, and the game kicks off at the time of the change
+//! * `<time> score <home> <away> <home score> <away score>`
+//! * `<time> goal <home> <away> <home|away> <open_play|penalty|own_goal|free_kick> [<scorer>]`
+//! * `<time> finished <home> <away> [extra_time | penalties <home penalties> <away penalties>]`, without the decision when the game was finished in the regular time
+//! * `<time> forfeit <home> <away> <home score> <away score>`
+//! * `<time> state <home> <away> <state>`
+//! * `<time> reopened <home> <away>`
+//! * `<time> swapped <home> <away>`, with the teams after the swap
+//! * `<time> period <home> <away> <second_half|extra_time>`
+//! * `<time> renamed <old name> <new name>`
+//! * `<time> scheduled <home> <away> <kickoff time> <UTC offset in minutes> [<venue> [<group>]]`, with the kickoff time in milliseconds since the Unix epoch, and an empty venue when only the group is known
+//! * `<time> card <home> <away> <home|away> <yellow|red> <player>`
+//! * `<time> note <home> <away> <text>`
+//! * `<time> odds <home> <away> [<home win> <draw> <away win>]`, without the odds when they were cleared
+//! * `<time> xg <team> <expected goals>`
+//! * `<time> player <team> <name> <number>`
+//!
+//! Tabs, new lines and backslashes in the names are written as `\t`, `\n` and `\\`
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::mem;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::vec::Vec;
+
+use log::{debug, trace, warn};
+
+use crate::archive::Decision;
+use crate::builder::GameDetails;
+use crate::cards::CardKind;
+use crate::conflict::DEFAULT_FIXTURE_WINDOW;
+use crate::error::ScoreBoardError;
+use crate::events::ScoreBoardEvent;
+use crate::goal::{GoalKind, Side};
+use crate::limit::DEFAULT_MAX_SCORE;
+use crate::odds::Odds;
+use crate::period::Period;
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+use crate::ScoreBoard;
+
+/// Appends the events to the journal file
+#[derive(Clone)]
+pub(crate) struct Journal {
+	/// The journal file, shared by the copies of the score board
+	file: Arc<Mutex<File>>,
+}
+
+impl Journal {
+	/// Opens the journal file for appending, creating it if it doesn't exist
+	///
+	/// # Arguments
+	///
+	/// * `path` - path of the journal file
+	///
+	/// # Errors
+	///
+	/// * When the file can't be opened
+	fn open(path: &Path) -> Result<Journal, ScoreBoardError> {
+		let file = OpenOptions::new().create(true).append(true).open(path)
+			.map_err(|error| ScoreBoardError::Storage(format!("Couldn't open the journal {}: {}", path.display(), error)))?;
+
+		Ok(Journal { file: Arc::new(Mutex::new(file)) })
+	}
+
+	/// Appends lines to the journal and syncs them to the disk. Nothing is written for no lines
+	///
+	/// # Arguments
+	///
+	/// * `lines` - the lines, each with the new line at the end
+	///
+	/// # Errors
+	///
+	/// * When the lines can't be written or synced
+	pub(crate) fn append(&self, lines: &str) -> Result<(), ScoreBoardError> {
+		if lines.is_empty() {
+			return Ok(());
+		}
+
+		let mut file = match self.file.lock() {
+			Ok(file) => file,
+			Err(poisoned) => poisoned.into_inner(),
+		};
+
+		file.write_all(lines.as_bytes()).and_then(|_| file.sync_data()).map_err(|error| {
+			warn!("Couldn't append to the journal: {}", error);
+			ScoreBoardError::Storage(format!("Couldn't append to the journal: {}", error))
+		})
+	}
+}
+
+impl ScoreBoard {
+	/// Turns on the journal. From now on, every change is appended to the file and synced to the disk before it is made on the board, see the format above. Changes of batches and transactions are appended together, right before they are applied. When the journal can't be written, the change isn't made and the operation fails with `ScoreBoardError::Storage`, so the board never gets ahead of its journal
+	///
+	/// Calling it again replaces the previous journal. The file isn't cleared, so a board turning the journal on after `recover()` goes on with the same file
+	///
+	/// # Arguments
+	///
+	/// * `path` - path of the journal file. The file is created if it doesn't exist
+	///
+	/// # Errors
+	///
+	/// * When the file can't be opened for appending
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_journal("matchday.journal").unwrap();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_journal<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ScoreBoardError> {
+		let path = path.as_ref();
+
+		trace!("Journaling the events to {}", path.display());
+
+		self.journal = Some(Journal::open(path)?);
+
+		Ok(())
+	}
+
+	/// Turns off the journal
+	pub fn clear_journal(&mut self) {
+		trace!("Turning off the journal");
+
+		self.journal = None;
+	}
+
+	/// Returns a score board recovered from a journal written with `set_journal()`, with the journal turned on again, so the board can go on after a restart. The changes are made again in the order they were journaled, and the started games keep their original kickoff times, venues, groups and time zones
+	///
+	/// A missing file gives an empty board. A line cut short by a crash at the end of the file is dropped. The settings of the board, like the aliases and the limits, aren't journaled. The changes were checked against the limits when they were made, so they are made again without the limits and the validators, and the recovered board has the default settings
+	///
+	/// # Arguments
+	///
+	/// * `path` - path of the journal file
+	///
+	/// # Errors
+	///
+	/// * When the file can't be read or written
+	/// * When a line of the file isn't a journaled change
+	/// * When a change can't be made again, ex. when the journal was written by another board
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let path = std::env::temp_dir().join("scoreboard-recover-example.journal");
+	/// # std::fs::remove_file(&path).ok();
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_journal(&path).unwrap();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// drop(sb);
+	///
+	/// let sb = scoreboard_world_cup::ScoreBoard::recover(&path).unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("Japan 1 - Indonesia 0")]);
+	/// # std::fs::remove_file(&path).ok();
+	/// ```
+	pub fn recover<P: AsRef<Path>>(path: P) -> Result<ScoreBoard, ScoreBoardError> {
+		let path = path.as_ref();
+
+		trace!("Recovering the score board from the journal {}", path.display());
+
+		let storage_error = |error: io::Error| ScoreBoardError::Storage(format!("Couldn't recover from the journal {}: {}", path.display(), error));
+
+		let content = match std::fs::read(path) {
+			Ok(content) => content,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(error) => return Err(storage_error(error)),
+		};
+
+		// Only the lines ending with a new line were written completely
+		let complete_length = content.iter().rposition(|byte| *byte == b'\n').map_or(0, |position| position + 1);
+		if complete_length < content.len() {
+			warn!("Dropping the incomplete last line of the journal");
+			OpenOptions::new().write(true).open(path).and_then(|file| file.set_len(complete_length as u64)).map_err(storage_error)?;
+		}
+
+		let content = String::from_utf8_lossy(&content[..complete_length]);
+
+		let mut sb = ScoreBoard::new();
+		// The limits the changes were checked against when they were made aren't journaled
+		sb.max_score = None;
+		sb.fixture_window = Duration::ZERO;
+		let mut line_count = 0;
+
+		for (index, line) in content.lines().enumerate() {
+			let entry = parse(line).ok_or_else(|| ScoreBoardError::Encoding(format!("Couldn't read line {} of the journal", index + 1)))?;
+
+			sb.replay_entry(entry).map_err(|error| {
+				warn!("Couldn't replay line {} of the journal: {}", index + 1, error);
+				ScoreBoardError::Storage(format!("Couldn't replay line {} of the journal {}: {}", index + 1, path.display(), error))
+			})?;
+			line_count += 1;
+		}
+
+		sb.max_score = Some(DEFAULT_MAX_SCORE);
+		sb.fixture_window = DEFAULT_FIXTURE_WINDOW;
+
+		debug!("Replayed {} lines of the journal", line_count);
+
+		sb.set_journal(path)?;
+
+		Ok(sb)
+	}
+
+	/// Appends a change to the journal, when it is turned on, before the change is made on the board
+	///
+	/// # Arguments
+	///
+	/// * `event` - the change about to be made
+	///
+	/// # Errors
+	///
+	/// * When the change can't be written or synced
+	pub(crate) fn write_ahead(&mut self, event: &ScoreBoardEvent) -> Result<(), ScoreBoardError> {
+		match encode(SystemTime::now(), event) {
+			Some(line) => self.write_ahead_line(line),
+			None => Ok(()),
+		}
+	}
+
+	/// Appends a change that isn't recorded as an event to the journal, when it is turned on, before the change is made on the board, ex. a rename of a team
+	///
+	/// # Arguments
+	///
+	/// * `fields` - the kind of the change, followed by its fields, see the format above
+	///
+	/// # Errors
+	///
+	/// * When the change can't be written or synced
+	pub(crate) fn write_ahead_change(&mut self, fields: &[&str]) -> Result<(), ScoreBoardError> {
+		let mut line = get_millis(SystemTime::now()).to_string();
+		for field in fields {
+			line.push('\t');
+			line.push_str(&escape(field));
+		}
+		line.push('\n');
+
+		self.write_ahead_line(line)
+	}
+
+	/// Appends the start of a game to the journal, when it is turned on, before the game is started on the board. The line carries the kickoff and the details of a game started with `ScoreBoard::new_game()`, which the `GameStarted` event doesn't have
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team
+	/// * `away_name` - name of the away team
+	/// * `kickoff` - the time of the kickoff
+	/// * `details` - the details given at the start. The ones of the fixture aren't journaled, since the fixture comes back from its own line
+	///
+	/// # Errors
+	///
+	/// * When the change can't be written or synced
+	pub(crate) fn write_ahead_start(&mut self, home_name: &str, away_name: &str, kickoff: SystemTime, details: &GameDetails) -> Result<(), ScoreBoardError> {
+		let kickoff_millis = get_millis(kickoff).to_string();
+		let utc_offset_minutes = details.utc_offset.map(|utc_offset| utc_offset.get_minutes().to_string()).unwrap_or_default();
+		let group_letter = details.group.map(String::from);
+		let mut fields: Vec<&str> = vec!["started", home_name, away_name, &kickoff_millis, &utc_offset_minutes];
+		push_place(&mut fields, details.venue.as_deref(), group_letter.as_deref());
+
+		self.write_ahead_change(&fields)
+	}
+
+	/// Appends a line to the journal, when it is turned on. The lines of the staged changes are kept until the changes are applied
+	///
+	/// # Arguments
+	///
+	/// * `line` - the line, with the new line at the end
+	///
+	/// # Errors
+	///
+	/// * When the line can't be written or synced
+	fn write_ahead_line(&mut self, line: String) -> Result<(), ScoreBoardError> {
+		match &self.journal {
+			Some(_) if self.is_staging() => {
+				self.stage_journal_line(&line);
+				Ok(())
+			},
+			Some(journal) => journal.append(&line),
+			None => Ok(()),
+		}
+	}
+
+	/// Makes a journaled change again
+	///
+	/// # Arguments
+	///
+	/// * `entry` - the change
+	///
+	/// # Errors
+	///
+	/// * The same as the operation making the change
+	fn replay_entry(&mut self, entry: JournalEntry) -> Result<(), ScoreBoardError> {
+		match entry {
+			JournalEntry::Started { home, away, kickoff, details: GameDetails { venue, group, utc_offset, .. } } => {
+				let mut game = self.new_game(home, away).kickoff_at(kickoff);
+				if let Some(venue) = venue {
+					game = game.venue(venue);
+				}
+				if let Some(group) = group {
+					game = game.group(group);
+				}
+				if let Some(utc_offset) = utc_offset {
+					game = game.utc_offset(utc_offset);
+				}
+				game.start()
+			},
+			JournalEntry::Score { home, away, home_score, away_score } => self.update_score(home, home_score, away, away_score),
+			JournalEntry::Goal { home, away, side, kind, scorer: None } => self.record_goal(home, away, side, kind),
+			JournalEntry::Goal { home, away, side, kind, scorer: Some(scorer) } => self.record_goal_by(home, away, side, kind, scorer),
+			JournalEntry::Finished { home, away, decision } => self.finish_game_after(home, away, decision),
+			JournalEntry::Forfeit { home, away, home_score, away_score } => {
+				// The forfeit score may have been changed since, so the awarded score is taken from the journal
+				let (winner, score) = if home_score >= away_score { (Side::Home, home_score) } else { (Side::Away, away_score) };
+				let forfeit_score = mem::replace(&mut self.forfeit_score, score);
+				let result = self.award_forfeit(home, away, winner);
+				self.forfeit_score = forfeit_score;
+				result
+			},
+			JournalEntry::State { home, away, state: GameState::Abandoned } => self.change_state(home, away, GameState::Abandoned, None),
+			JournalEntry::State { home, away, state: GameState::Postponed } => self.change_state(home, away, GameState::Postponed, None),
+			JournalEntry::State { home, away, state } => self.set_game_state(home, away, state),
+			JournalEntry::Reopened { home, away } => self.reopen_game(home, away),
+			JournalEntry::Swapped { home, away } => self.swap_sides(away, home),
+			JournalEntry::Period { home, away, period } => self.start_period(home, away, period),
+			JournalEntry::Renamed { old, new } => self.rename_team(old, new),
+			JournalEntry::Scheduled { home, away, kickoff, details } => self.schedule_fixture(home, away, kickoff, details),
+			JournalEntry::Card { home, away, side, kind, player } => self.record_card(home, away, side, player, kind),
+			JournalEntry::Note { time, home, away, text } => {
+				self.add_note(home, away, text)?;
+				// The note keeps the time it was added at
+				if let Some(note) = self.notes.last_mut() {
+					note.time = time;
+				}
+				Ok(())
+			},
+			JournalEntry::Odds { home, away, odds: Some(odds) } => self.set_odds(home, away, odds),
+			JournalEntry::Odds { home, away, odds: None } => self.clear_odds(home, away),
+			JournalEntry::Xg { team, delta } => self.record_xg(team, delta),
+			JournalEntry::Player { team, name, number } => self.register_player(team, name, number),
+		}
+	}
+}
+
+/// A change read from the journal
+#[derive(Debug, PartialEq)]
+enum JournalEntry {
+	/// A game has started, with its kickoff and the details given at the start
+	Started { home: String, away: String, kickoff: SystemTime, details: GameDetails },
+	/// A score has changed
+	Score { home: String, away: String, home_score: u8, away_score: u8 },
+	/// A goal was recorded
+	Goal { home: String, away: String, side: Side, kind: GoalKind, scorer: Option<String> },
+	/// A game has finished, with the way its result was decided
+	Finished { home: String, away: String, decision: Decision },
+	/// A game was awarded to one of the teams
+	Forfeit { home: String, away: String, home_score: u8, away_score: u8 },
+	/// A game has moved to another state
+	State { home: String, away: String, state: GameState },
+	/// A finished game was reopened
+	Reopened { home: String, away: String },
+	/// The sides of a game were swapped, the teams given as they are after the swap
+	Swapped { home: String, away: String },
+	/// A game moved to the next period
+	Period { home: String, away: String, period: Period },
+	/// A team was renamed
+	Renamed { old: String, new: String },
+	/// A game was scheduled, with its venue, group and time zone
+	Scheduled { home: String, away: String, kickoff: SystemTime, details: GameDetails },
+	/// A card was shown to a player
+	Card { home: String, away: String, side: Side, kind: CardKind, player: String },
+	/// A note was added to a game, at the given time
+	Note { time: SystemTime, home: String, away: String, text: String },
+	/// The odds of a game were set or cleared
+	Odds { home: String, away: String, odds: Option<Odds> },
+	/// Expected goals were added for a team
+	Xg { team: String, delta: f64 },
+	/// A player was registered
+	Player { team: String, name: String, number: u8 },
+}
+
+/// Provides the milliseconds since the Unix epoch of a timestamp
+///
+/// # Arguments
+///
+/// * `time` - the timestamp
+///
+pub(crate) fn get_millis(time: SystemTime) -> u128 {
+	time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO).as_millis()
+}
+
+/// Writes an event as a line of the journal, with the new line at the end
+///
+/// # Arguments
+///
+/// * `time` - time of the event
+/// * `event` - the event to write
+///
+/// # Returns
+///
+/// * The line, or `None` for the events that aren't journaled on their own
+///
+fn encode(time: SystemTime, event: &ScoreBoardEvent) -> Option<String> {
+	let time = get_millis(time);
+	let (home, away) = event.get_teams();
+
+	let mut fields = vec![time.to_string()];
+	let mut push = |values: &[&str]| fields.extend(values.iter().map(|value| escape(value)));
+
+	match event {
+		// The start comes with its details, see `ScoreBoard::write_ahead_start()`
+		ScoreBoardEvent::GameStarted { .. } => return None,
+		ScoreBoardEvent::ScoreUpdated { goal: Some(goal), .. } => {
+			push(&["goal", home, away, &goal.side.to_string(), goal.kind.get_name()]);
+			if let Some(scorer) = &goal.scorer {
+				push(&[&scorer.name]);
+			}
+		},
+		ScoreBoardEvent::ScoreUpdated { home_score, away_score, goal: None, .. } => push(&["score", home, away, &home_score.to_string(), &away_score.to_string()]),
+		// The finish comes back with the final result, which knows the way the result was decided
+		ScoreBoardEvent::GameFinished { .. } => return None,
+		ScoreBoardEvent::ResultFinalized { decision: Decision::RegularTime, .. } => push(&["finished", home, away]),
+		ScoreBoardEvent::ResultFinalized { decision: Decision::AfterExtraTime, .. } => push(&["finished", home, away, Decision::AfterExtraTime.get_name()]),
+		ScoreBoardEvent::ResultFinalized { decision: decision @ Decision::AfterPenalties { home: home_penalties, away: away_penalties }, .. } => {
+			push(&["finished", home, away, decision.get_name(), &home_penalties.to_string(), &away_penalties.to_string()]);
+		},
+		ScoreBoardEvent::ResultFinalized { home_score, away_score, decision: Decision::Forfeit, .. } => push(&["forfeit", home, away, &home_score.to_string(), &away_score.to_string()]),
+		ScoreBoardEvent::StateChanged { state, .. } => push(&["state", home, away, state.get_name()]),
+		ScoreBoardEvent::GameReopened { .. } => push(&["reopened", home, away]),
+		ScoreBoardEvent::SidesSwapped { .. } => push(&["swapped", home, away]),
+		ScoreBoardEvent::PeriodStarted { period, .. } => push(&["period", home, away, period.get_name()]),
+		// Journaled as a change of the board, see `ScoreBoard::rename_team()`
+		ScoreBoardEvent::TeamRenamed { .. } => return None,
+	}
+
+	Some(format!("{}\n", fields.join("\t")))
+}
+
+/// Reads a line of the journal, without the new line
+///
+/// # Arguments
+///
+/// * `line` - the line to read
+///
+/// # Returns
+///
+/// * The change, or `None` when the line isn't a journaled change
+fn parse(line: &str) -> Option<JournalEntry> {
+	let fields: Vec<String> = line.split('\t').map(unescape).collect();
+	let time = UNIX_EPOCH + Duration::from_millis(fields.first()?.parse().ok()?);
+	let (home, away) = (fields.get(2)?.clone(), fields.get(3)?.clone());
+	let rest: Vec<&str> = fields[4..].iter().map(String::as_str).collect();
+
+	let entry = match (fields[1].as_str(), rest.as_slice()) {
+		("started", []) => JournalEntry::Started { home, away, kickoff: time, details: GameDetails::default() },
+		("started", [kickoff, utc_offset, rest @ ..]) if rest.len() <= 2 => {
+			let kickoff = UNIX_EPOCH + Duration::from_millis(kickoff.parse().ok()?);
+			let utc_offset = match *utc_offset {
+				"" => None,
+				utc_offset => Some(UtcOffset::from_minutes(utc_offset.parse().ok()?).ok()?),
+			};
+			let (venue, group) = parse_place(rest)?;
+			JournalEntry::Started { home, away, kickoff, details: GameDetails { kickoff: None, venue, group, utc_offset } }
+		},
+		("score", [home_score, away_score]) => JournalEntry::Score { home, away, home_score: home_score.parse().ok()?, away_score: away_score.parse().ok()? },
+		("goal", [side, kind, scorer @ ..]) if scorer.len() <= 1 => {
+			let side = parse_side(side)?;
+			let kind = [GoalKind::OpenPlay, GoalKind::Penalty, GoalKind::OwnGoal, GoalKind::FreeKick].into_iter().find(|candidate| candidate.get_name() == *kind)?;
+			JournalEntry::Goal { home, away, side, kind, scorer: scorer.first().map(|scorer| scorer.to_string()) }
+		},
+		("finished", []) => JournalEntry::Finished { home, away, decision: Decision::RegularTime },
+		("finished", ["extra_time"]) => JournalEntry::Finished { home, away, decision: Decision::AfterExtraTime },
+		("finished", ["penalties", home_penalties, away_penalties]) => {
+			let decision = Decision::AfterPenalties { home: home_penalties.parse().ok()?, away: away_penalties.parse().ok()? };
+			JournalEntry::Finished { home, away, decision }
+		},
+		("forfeit", [home_score, away_score]) => JournalEntry::Forfeit { home, away, home_score: home_score.parse().ok()?, away_score: away_score.parse().ok()? },
+		("state", [state]) => {
+			let state = [GameState::Scheduled, GameState::Live, GameState::Paused, GameState::Finished, GameState::Abandoned, GameState::Postponed]
+				.into_iter()
+				.find(|candidate| candidate.get_name() == *state)?;
+			JournalEntry::State { home, away, state }
+		},
+		("reopened", []) => JournalEntry::Reopened { home, away },
+		("swapped", []) => JournalEntry::Swapped { home, away },
+		("period", [period]) => {
+			let period = [Period::FirstHalf, Period::SecondHalf, Period::ExtraTime].into_iter().find(|candidate| candidate.get_name() == *period)?;
+			JournalEntry::Period { home, away, period }
+		},
+		("renamed", []) => JournalEntry::Renamed { old: home, new: away },
+		("scheduled", [kickoff, utc_offset, rest @ ..]) if rest.len() <= 2 => {
+			let kickoff = UNIX_EPOCH + Duration::from_millis(kickoff.parse().ok()?);
+			let utc_offset = UtcOffset::from_minutes(utc_offset.parse().ok()?).ok()?;
+			let (venue, group) = parse_place(rest)?;
+			JournalEntry::Scheduled { home, away, kickoff, details: GameDetails { kickoff: None, venue, group, utc_offset: Some(utc_offset) } }
+		},
+		("card", [side, kind, player]) => {
+			let side = parse_side(side)?;
+			let kind = [CardKind::Yellow, CardKind::Red].into_iter().find(|candidate| candidate.get_name() == *kind)?;
+			JournalEntry::Card { home, away, side, kind, player: player.to_string() }
+		},
+		("note", [text]) => JournalEntry::Note { time, home, away, text: text.to_string() },
+		("odds", []) => JournalEntry::Odds { home, away, odds: None },
+		("odds", [home_win, draw, away_win]) => JournalEntry::Odds { home, away, odds: Some(Odds::new(home_win.parse().ok()?, draw.parse().ok()?, away_win.parse().ok()?)) },
+		("xg", []) => JournalEntry::Xg { team: home, delta: away.parse().ok()? },
+		("player", [number]) => JournalEntry::Player { team: home, name: away, number: number.parse().ok()? },
+		_ => return None,
+	};
+
+	Some(entry)
+}
+
+/// Appends the venue and the group of a game to the fields of its line, with an empty venue when only the group is known
+///
+/// # Arguments
+///
+/// * `fields` - the fields of the line
+/// * `venue` - the stadium, if it is known
+/// * `group` - the letter of the group, if it is known
+///
+pub(crate) fn push_place<'a>(fields: &mut Vec<&'a str>, venue: Option<&'a str>, group: Option<&'a str>) {
+	match (venue, group) {
+		(venue, Some(group)) => fields.extend([venue.unwrap_or_default(), group]),
+		(Some(venue), None) => fields.push(venue),
+		(None, None) => (),
+	}
+}
+
+/// Reads the venue and the group written by `push_place()`
+fn parse_place(fields: &[&str]) -> Option<(Option<String>, Option<char>)> {
+	match fields {
+		[] => Some((None, None)),
+		[venue] => Some((Some(venue.to_string()), None)),
+		[venue, group] => Some(((!venue.is_empty()).then(|| venue.to_string()), Some(parse_group(group)?))),
+		_ => None,
+	}
+}
+
+/// Reads the letter of a group
+fn parse_group(group: &str) -> Option<char> {
+	let mut letters = group.chars();
+	match (letters.next(), letters.next()) {
+		(Some(letter), None) => Some(letter),
+		_ => None,
+	}
+}
+
+/// Reads the side of a team, written as in `Side::fmt()`
+fn parse_side(side: &str) -> Option<Side> {
+	match side {
+		"home" => Some(Side::Home),
+		"away" => Some(Side::Away),
+		_ => None,
+	}
+}
+
+/// Escapes the tabs, the new lines and the backslashes of a field
+fn escape(field: &str) -> String {
+	field.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+/// Brings back the tabs, the new lines and the backslashes of an escaped field
+fn unescape(field: &str) -> String {
+	let mut result = String::with_capacity(field.len());
+	let mut chars = field.chars();
+
+	while let Some(c) = chars.next() {
+		match (c, chars.clone().next()) {
+			('\\', Some('t')) => result.push('\t'),
+			('\\', Some('n')) => result.push('\n'),
+			('\\', Some('\\')) => result.push('\\'),
+			_ => {
+				result.push(c);
+				continue;
+			},
+		}
+		chars.next();
+	}
+
+	result
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::fs;
+	use std::path::PathBuf;
+
+	use crate::round_robin::generate_round_robin;
+	use crate::timeline::TimelineKind;
+
+	/// Provides a path of a journal file in the temporary directory, without the file
+	fn get_path(name: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("scoreboard-{}-{}.journal", name, std::process::id()));
+		fs::remove_file(&path).ok();
+		path
+	}
+
+	#[test]
+	fn recovered_board_has_the_same_games() {
+		let path = get_path("same-games");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.record_goal_by("Spain", "Brazil", Side::Home, GoalKind::Penalty, "Morata").expect("Couldn't record the goal");
+		sb.update_score("Japan", 2, "Indonesia", 1).expect("Couldn't update the first game");
+		sb.set_game_state("Japan", "Indonesia", GameState::Paused).expect("Couldn't pause the first game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the third game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the third game");
+		sb.reopen_game("Mexico", "Canada").expect("Couldn't reopen the third game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), sb.get_summary());
+		assert_eq!(recovered.get_game("Japan", "Indonesia").map(|game| game.state), Some(GameState::Paused));
+		let kickoff_time = recovered.get_game("Spain", "Brazil").expect("Couldn't find the recovered game").kickoff_time;
+		let original_kickoff_time = sb.get_game("Spain", "Brazil").expect("Couldn't find the game").kickoff_time;
+		assert!(kickoff_time.duration_since(original_kickoff_time).or_else(|_| original_kickoff_time.duration_since(kickoff_time)).is_ok_and(|difference| difference < Duration::from_secs(1)));
+		assert_eq!(recovered.top_scorers(1)[0].player.name, "Morata");
+		assert_eq!(recovered.get_events().len(), sb.get_events().len());
+	}
+
+	#[test]
+	fn recovered_board_goes_on_with_the_journal() {
+		let path = get_path("goes-on");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+
+		let mut recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		recovered.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board again");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Japan 1 - Indonesia 0")]);
+	}
+
+	#[test]
+	fn swapped_sides_are_recovered() {
+		let path = get_path("swapped");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Indonesia", "Japan").expect("Couldn't create the game");
+		sb.update_score("Indonesia", 0, "Japan", 1).expect("Couldn't update the game");
+		sb.swap_sides("Indonesia", "Japan").expect("Couldn't swap the sides");
+		sb.update_score("Japan", 2, "Indonesia", 0).expect("Couldn't update the swapped game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Japan 2 - Indonesia 0")]);
+		assert_eq!(recovered.get_game("Japan", "Indonesia").map(|game| game.revision), sb.get_game("Japan", "Indonesia").map(|game| game.revision));
+	}
+
+	#[test]
+	fn periods_are_recovered() {
+		let path = get_path("periods");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		sb.start_period("Japan", "Indonesia", Period::SecondHalf).expect("Couldn't start the second half");
+		sb.update_score("Japan", 1, "Indonesia", 1).expect("Couldn't update the game in the second half");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.half_time_score("Japan", "Indonesia"), Some((1, 0)));
+		assert_eq!(recovered.get_game("Japan", "Indonesia").map(|game| game.periods), sb.get_game("Japan", "Indonesia").map(|game| game.periods));
+		assert_eq!(recovered.get_game("Japan", "Indonesia").map(|game| game.revision), Some(4));
+	}
+
+	#[test]
+	fn decisions_of_the_results_are_recovered() {
+		let path = get_path("decisions");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Croatia", "Brazil").expect("Couldn't create the first game");
+		sb.update_score("Croatia", 1, "Brazil", 1).expect("Couldn't update the first game");
+		sb.finish_game_after("Croatia", "Brazil", Decision::AfterPenalties { home: 4, away: 2 }).expect("Couldn't finish the first game");
+		sb.start_game("Spain", "Germany").expect("Couldn't create the second game");
+		sb.update_score("Spain", 2, "Germany", 1).expect("Couldn't update the second game");
+		sb.finish_game_after("Spain", "Germany", Decision::AfterExtraTime).expect("Couldn't finish the second game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		let decisions: Vec<Decision> = recovered.get_archive().iter().map(|game| game.decision).collect();
+		assert_eq!(decisions, vec![Decision::AfterPenalties { home: 4, away: 2 }, Decision::AfterExtraTime]);
+		assert_eq!(recovered.get_archive_summary(), sb.get_archive_summary());
+	}
+
+	#[test]
+	fn forfeit_of_a_game_that_never_started_is_recovered() {
+		let path = get_path("forfeit");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.set_forfeit_score(2);
+		sb.award_forfeit("Japan", "Indonesia", Side::Away).expect("Couldn't award the game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.award_forfeit("Spain", "Brazil", Side::Home).expect("Couldn't award the running game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert!(recovered.get_summary().is_empty());
+		assert_eq!(recovered.get_archive_summary(), vec![String::from("Japan 0 - 2 Indonesia (awarded)"), String::from("Spain 2 - 0 Brazil (awarded)")]);
+		assert!(recovered.get_archive().iter().all(|game| game.decision == Decision::Forfeit));
+	}
+
+	#[test]
+	fn renamed_team_is_recovered() {
+		let path = get_path("renamed");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Agrentina", "France").expect("Couldn't create the game");
+		sb.update_score("Agrentina", 1, "France", 0).expect("Couldn't update the game");
+		sb.rename_team("Agrentina", "Argentina").expect("Couldn't rename the team");
+		sb.update_score("Argentina", 2, "France", 0).expect("Couldn't update the renamed game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Argentina 2 - France 0")]);
+		assert_eq!(recovered.get_audit_log("Argentina", "France").len(), 3);
+	}
+
+	#[test]
+	fn score_over_the_default_limit_is_recovered() {
+		let path = get_path("limit");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.clear_max_score();
+		sb.start_game("Australia", "American Samoa").expect("Couldn't create the game");
+		sb.update_score("Australia", 31, "American Samoa", 0).expect("Couldn't update the game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Australia 31 - American Samoa 0")]);
+		assert_eq!(recovered.max_score, Some(DEFAULT_MAX_SCORE));
+	}
+
+	#[test]
+	fn changes_outside_the_events_are_recovered() {
+		let path = get_path("outside-events");
+		let kickoff = UNIX_EPOCH + Duration::from_secs(1_671_382_800);
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.register_player("Netherlands", "Dumfries", 22).expect("Couldn't register the player");
+		sb.new_game("Argentina", "Croatia").venue("Lusail").group('C').utc_offset(UtcOffset::from_hours(3).expect("Couldn't create the offset")).schedule(kickoff).expect("Couldn't schedule the game");
+		sb.start_game("Argentina", "Netherlands").expect("Couldn't create the game");
+		sb.record_card("Argentina", "Netherlands", Side::Away, "Dumfries", CardKind::Red).expect("Couldn't record the card");
+		sb.add_note("Argentina", "Netherlands", "Mass confrontation").expect("Couldn't add the note");
+		sb.set_odds("Argentina", "Netherlands", Odds::new(2.5, 3.1, 3.25)).expect("Couldn't set the odds");
+		sb.record_xg("Argentina", 0.76).expect("Couldn't record the expected goals");
+
+		sb.new_game("Morocco", "Portugal").group('F').schedule(kickoff).expect("Couldn't schedule the second game");
+
+		let mut recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_players("Netherlands"), sb.get_players("Netherlands"));
+		assert_eq!(recovered.get_schedule(), sb.get_schedule());
+		assert_eq!(recovered.suspended_players("Netherlands", "Senegal")[0].number, Some(22));
+		assert_eq!(recovered.get_timeline("Argentina", "Netherlands")[1].kind, TimelineKind::Note(String::from("Mass confrontation")));
+		let game = recovered.get_game("Argentina", "Netherlands").expect("Couldn't find the recovered game");
+		assert_eq!((game.odds, game.xg.home), (Some(Odds::new(2.5, 3.1, 3.25)), 0.76));
+		recovered.start_game("Morocco", "Portugal").expect("Couldn't start the recovered fixture");
+		let game = recovered.get_game("Morocco", "Portugal").expect("Couldn't find the started fixture");
+		assert_eq!((game.venue, game.group), (None, Some('F')));
+	}
+
+	#[test]
+	fn details_of_the_started_games_are_recovered() {
+		let path = get_path("started-details");
+		let kickoff = SystemTime::now() - Duration::from_secs(1800);
+		let utc_offset = UtcOffset::from_hours(3).expect("Couldn't create the offset");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.new_game("Morocco", "Portugal").group('F').start().expect("Couldn't create the first game");
+		sb.new_game("Argentina", "Croatia").kickoff_at(kickoff).venue("Lusail").group('C').utc_offset(utc_offset).start().expect("Couldn't create the second game");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), sb.get_summary());
+		let game = recovered.get_game("Argentina", "Croatia").expect("Couldn't find the recovered game");
+		assert_eq!(get_millis(game.kickoff_time), get_millis(kickoff));
+		assert_eq!((game.venue, game.group, game.utc_offset), (Some(String::from("Lusail")), Some('C'), utc_offset));
+		let game = recovered.get_game("Morocco", "Portugal").expect("Couldn't find the recovered game");
+		assert_eq!((game.venue, game.group, game.utc_offset), (None, Some('F'), UtcOffset::UTC));
+	}
+
+	#[test]
+	fn staged_changes_are_recovered() {
+		let path = get_path("staged");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		let fixtures = generate_round_robin(&["Ajax", "PSV", "Feyenoord"], false);
+		sb.schedule_round_robin(&fixtures, SystemTime::now(), Duration::from_secs(7 * 24 * 3600)).expect("Couldn't schedule the fixtures");
+		let mut transaction = sb.begin();
+		transaction.start_game("Japan", "Indonesia").expect("Couldn't stage the start");
+		transaction.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't stage the update");
+		transaction.commit().expect("Couldn't commit the transaction");
+
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Japan 1 - Indonesia 0")]);
+		assert_eq!(recovered.get_schedule(), sb.get_schedule());
+	}
+
+	#[test]
+	fn change_that_cant_be_made_again_is_reported() {
+		let path = get_path("inconsistent");
+		fs::write(&path, "1671376200000\tstarted\tJapan\tIndonesia\n1671376260000\tscore\tSpain\tBrazil\t1\t0\n").expect("Couldn't write the journal");
+		let result = ScoreBoard::recover(&path);
+		fs::remove_file(&path).ok();
+
+		assert!(result.err().is_some_and(|error| error.to_string().starts_with("Couldn't replay line 2 of the journal")));
+	}
+
+	#[test]
+	fn incomplete_last_line_is_dropped() {
+		let path = get_path("incomplete");
+		let mut sb = ScoreBoard::new();
+		sb.set_journal(&path).expect("Couldn't open the journal");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		OpenOptions::new().append(true).open(&path).and_then(|mut file| file.write_all(b"1671376200000\tscore\tJap")).expect("Couldn't cut the journal");
+
+		let mut recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		recovered.update_score("Japan", 3, "Indonesia", 0).expect("Couldn't update the game");
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board again");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(recovered.get_summary(), vec![String::from("Japan 3 - Indonesia 0")]);
+	}
+
+	#[test]
+	#[cfg(target_os = "linux")]
+	fn change_is_rejected_when_the_journal_cant_be_written() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		// Every write to /dev/full fails, as on a full disk
+		sb.set_journal("/dev/full").expect("Couldn't open the journal");
+
+		assert!(matches!(sb.update_score("Japan", 1, "Indonesia", 0), Err(ScoreBoardError::Storage(_))));
+		assert!(matches!(sb.start_game("Spain", "Brazil"), Err(ScoreBoardError::Storage(_))));
+		assert!(matches!(sb.finish_game("Japan", "Indonesia"), Err(ScoreBoardError::Storage(_))));
+		assert!(matches!(sb.rename_team("Japan", "Nippon"), Err(ScoreBoardError::Storage(_))));
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		assert!(matches!(transaction.commit(), Err(ScoreBoardError::Storage(_))));
+
+		assert_eq!(sb.get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+		assert_eq!(sb.get_events().len(), 1);
+		assert!(sb.get_archive().is_empty());
+	}
+
+	#[test]
+	fn missing_journal_gives_an_empty_board() {
+		let path = get_path("missing");
+		let recovered = ScoreBoard::recover(&path).expect("Couldn't recover the board");
+		fs::remove_file(&path).ok();
+
+		assert!(recovered.get_summary().is_empty());
+	}
+
+	#[test]
+	fn corrupted_line_is_reported() {
+		let path = get_path("corrupted");
+		fs::write(&path, "1671376200000\tstarted\tJapan\tIndonesia\nnot a journal line\n").expect("Couldn't write the journal");
+		let result = ScoreBoard::recover(&path);
+		fs::remove_file(&path).ok();
+
+		assert_eq!(result.err(), Some(ScoreBoardError::Encoding(String::from("Couldn't read line 2 of the journal"))));
+	}
+
+	#[test]
+	fn names_are_escaped() {
+		let name = "Tab\tNew\nBack\\slash";
+
+		assert_eq!(unescape(&escape(name)), name);
+		assert_eq!(unescape("\\\\t"), "\\t");
+	}
+}
@@ -0,0 +1,176 @@
+//! # JSON Lines streaming
+//!
+//! Writes every change of the score board as one line of JSON to a file, a socket or any other sink, so the board can be followed with the standard log tools, like `tail -f` and `jq`
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use log::{trace, warn};
+
+use crate::events::EventRecord;
+use crate::ScoreBoard;
+
+/// Writes the events to the sink
+#[derive(Clone)]
+pub(crate) struct JsonLinesSink {
+	/// The sink, shared by the copies of the score board
+	writer: Arc<Mutex<dyn io::Write + Send>>,
+}
+
+impl JsonLinesSink {
+	/// Writes an event as a line of JSON and flushes the sink, so the line can be read straight away
+	///
+	/// # Arguments
+	///
+	/// * `record` - the event to write
+	///
+	pub(crate) fn notify(&self, record: &EventRecord) {
+		let mut writer = match self.writer.lock() {
+			Ok(writer) => writer,
+			Err(poisoned) => poisoned.into_inner(),
+		};
+
+		if let Err(error) = writeln!(writer, "{}", record.to_json()).and_then(|_| writer.flush()) {
+			warn!("Couldn't write event {} to the JSON Lines sink: {}", record.sequence, error);
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Turns on the streaming of the events as JSON Lines. From now on, every recorded event (the start of a game, a score update, the end of a game, ...) is written to the sink as one JSON object (see `EventRecord::to_json()`) followed by a new line. The lines are written in the order of the events, and changes of batches and transactions are written once they are applied. A failed write is logged and the sink is still used for the next events
+	///
+	/// Calling it again replaces the previous sink
+	///
+	/// # Arguments
+	///
+	/// * `writer` - the sink of the lines, ex. a file, a socket or the standard output
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_json_lines(std::io::stdout());
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_json_lines<W: io::Write + Send + 'static>(&mut self, writer: W) {
+		trace!("Streaming the events as JSON Lines");
+
+		self.json_lines = Some(JsonLinesSink { writer: Arc::new(Mutex::new(writer)) });
+	}
+
+	/// Turns off the streaming of the events as JSON Lines
+	pub fn clear_json_lines(&mut self) {
+		trace!("Turning off the JSON Lines streaming");
+
+		self.json_lines = None;
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::time::Duration;
+	use std::vec::Vec;
+
+	use crate::debounce::DebounceMode;
+	use crate::events::ScoreBoardEvent;
+
+	/// A sink keeping the written lines, readable while the score board owns it
+	#[derive(Clone, Default)]
+	struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+	impl SharedBuffer {
+		fn get_lines(&self) -> Vec<String> {
+			let buffer = self.0.lock().expect("Couldn't lock the buffer");
+			String::from_utf8(buffer.clone()).expect("Couldn't read the buffer").lines().map(String::from).collect()
+		}
+	}
+
+	impl io::Write for SharedBuffer {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().expect("Couldn't lock the buffer").write(buf)
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn every_event_is_one_line() {
+		let buffer = SharedBuffer::default();
+		let mut sb = ScoreBoard::new();
+		sb.set_json_lines(buffer.clone());
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		sb.finish_game("Japan", "Indonesia").expect("Couldn't finish the game");
+
+		let expected: Vec<String> = sb.get_events().iter().map(|record| record.to_json()).collect();
+		assert_eq!(buffer.get_lines(), expected);
+	}
+
+	#[test]
+	fn staged_events_are_written_once_applied() {
+		let buffer = SharedBuffer::default();
+		let mut sb = ScoreBoard::new();
+		sb.set_json_lines(buffer.clone());
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.finish_all().expect("Couldn't finish the games");
+
+		let lines = buffer.get_lines();
+		assert_eq!(lines.len(), 6);
+		assert!(lines[4].contains("\"type\":\"game_finished\""));
+		assert!(lines[5].contains("\"type\":\"result_finalized\""));
+	}
+
+	#[test]
+	fn merged_updates_are_written_with_new_sequences() {
+		let buffer = SharedBuffer::default();
+		let mut sb = ScoreBoard::new();
+		sb.set_json_lines(buffer.clone());
+		sb.set_debounce(Duration::from_secs(60), DebounceMode::Coalesce);
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		sb.update_score("Japan", 2, "Indonesia", 0).expect("Couldn't update the game again");
+
+		let lines = buffer.get_lines();
+		assert_eq!(lines.len(), 3);
+		assert!(lines[1].starts_with("{\"sequence\":2,") && lines[1].contains("\"revision\":2,"));
+		assert!(lines[2].starts_with("{\"sequence\":3,") && lines[2].contains("\"revision\":3,"));
+		assert_eq!(sb.get_events().last().map(|record| record.to_json()).as_ref(), lines.last());
+	}
+
+	#[test]
+	fn renamed_team_is_written() {
+		let buffer = SharedBuffer::default();
+		let mut sb = ScoreBoard::new();
+		sb.set_json_lines(buffer.clone());
+		sb.start_game("Japan", "Indonezia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonezia", 0).expect("Couldn't update the game");
+		sb.rename_team("Indonezia", "Indonesia").expect("Couldn't rename the team");
+
+		let lines = buffer.get_lines();
+		assert_eq!(lines.len(), 3);
+		assert!(lines[2].contains("\"type\":\"team_renamed\",\"old\":\"Indonezia\",\"new\":\"Indonesia\",\"revision\":2,\"state\":\"live\""));
+		assert!(matches!(&sb.get_events()[2].event, ScoreBoardEvent::TeamRenamed { old, new } if old == "Indonezia" && new == "Indonesia"));
+	}
+
+	#[test]
+	fn cleared_sink_gets_no_more_lines() {
+		let buffer = SharedBuffer::default();
+		let mut sb = ScoreBoard::new();
+		sb.set_json_lines(buffer.clone());
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.clear_json_lines();
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+
+		assert_eq!(buffer.get_lines().len(), 1);
+	}
+}
@@ -0,0 +1,231 @@
+//! # Kafka producer
+//!
+//! Publishes every change of the score board to a Kafka topic, keyed by the game, so the analytics pipelines ingest the live score changes directly
+
+use std::string::{String, ToString};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+use std::vec::Vec;
+
+use kafka::producer::{Producer, Record, RequiredAcks};
+use log::{debug, trace, warn};
+
+use crate::events::EventRecord;
+use crate::text::get_game_id;
+use crate::ScoreBoard;
+
+/// Settings of the Kafka producer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KafkaConfig {
+	/// Addresses of the bootstrap brokers, ex. "kafka-1:9092"
+	pub brokers: Vec<String>,
+	/// The topic the events are published to
+	pub topic: String,
+	/// Identifier of the score board at the brokers
+	pub client_id: String,
+	/// How many times a failed publishing is retried before the event is dropped
+	pub max_retries: u32,
+	/// Delay before the first retry. It doubles with every following retry
+	pub initial_backoff: Duration,
+	/// How long the brokers have to acknowledge an event
+	pub ack_timeout: Duration,
+}
+
+impl KafkaConfig {
+	/// Returns settings publishing to the given topic, with 3 retries starting after 500 ms and a 5 second acknowledgement time limit
+	///
+	/// # Arguments
+	///
+	/// * `brokers` - addresses of the bootstrap brokers
+	/// * `topic` - the topic the events are published to
+	pub fn new<T: ToString>(brokers: Vec<String>, topic: T) -> KafkaConfig {
+		KafkaConfig {
+			brokers,
+			topic: topic.to_string(),
+			client_id: String::from("scoreboard_world_cup"),
+			max_retries: 3,
+			initial_backoff: Duration::from_millis(500),
+			ack_timeout: Duration::from_secs(5),
+		}
+	}
+}
+
+/// Hands the events over to the background thread publishing them
+#[derive(Clone)]
+pub(crate) struct KafkaNotifier {
+	/// Sending end of the queue of the keys and the JSON payloads
+	sender: Sender<(String, String)>,
+}
+
+impl KafkaNotifier {
+	/// Queues an event for publishing. Never blocks the score board operation
+	///
+	/// # Arguments
+	///
+	/// * `record` - the event to publish
+	///
+	pub(crate) fn notify(&self, record: &EventRecord) {
+		let (home, away) = record.event.get_teams();
+
+		if self.sender.send((get_game_id(home, away), record.to_json())).is_err() {
+			warn!("The Kafka producer thread has stopped, event {} is dropped", record.sequence);
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Turns on the Kafka producer. From now on, every recorded event (the start of a game, a score update, a change of the state and the end of a game) is published as JSON (see `EventRecord::to_json()`) to the configured topic. The key of a message identifies the game, ex. "spain-brazil", so all the events of a game land in the same partition, in their order. The publishing happens on a background thread, and failed attempts are retried with an exponential backoff. Changes of batches and transactions are published once they are applied
+	///
+	/// Calling it again replaces the previous settings. The events queued with the previous settings are still published
+	///
+	/// # Arguments
+	///
+	/// * `config` - settings of the producer
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use scoreboard_world_cup::KafkaConfig;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_kafka(KafkaConfig::new(vec![String::from("kafka-1:9092")], "worldcup.scores"));
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_kafka(&mut self, config: KafkaConfig) {
+		trace!("Publishing to Kafka topic {}", config.topic);
+
+		let (sender, receiver) = mpsc::channel::<(String, String)>();
+
+		thread::spawn(move || {
+			let mut producer = None;
+
+			for (key, payload) in receiver {
+				deliver(&mut producer, &config, &key, &payload);
+			}
+
+			trace!("Kafka producer thread stopped");
+		});
+
+		self.kafka = Some(KafkaNotifier { sender });
+	}
+
+	/// Turns off the Kafka producer. The already queued events are still published
+	pub fn clear_kafka(&mut self) {
+		trace!("Clearing Kafka");
+
+		self.kafka = None;
+	}
+}
+
+/// Publishes a single event, connecting to the brokers when there is no connection, and retrying with an exponential backoff
+///
+/// # Arguments
+///
+/// * `producer` - the connected producer, if there is one. It is dropped when publishing fails, so the next attempt connects again
+/// * `config` - settings of the producer
+/// * `key` - key of the message
+/// * `payload` - the JSON payload
+///
+fn deliver(producer: &mut Option<Producer>, config: &KafkaConfig, key: &str, payload: &str) {
+	let mut backoff = config.initial_backoff;
+
+	for attempt in 0..=config.max_retries {
+		if attempt > 0 {
+			thread::sleep(backoff);
+			backoff = backoff.saturating_mul(2);
+		}
+
+		let connected = match producer {
+			Some(connected) => connected,
+			None => match connect(config) {
+				Ok(connected) => producer.insert(connected),
+				Err(error) => {
+					debug!("Connecting attempt {} to Kafka failed: {}", attempt + 1, error);
+					continue;
+				},
+			},
+		};
+
+		match connected.send(&Record::from_key_value(&config.topic, key, payload)) {
+			Ok(()) => {
+				debug!("Event of {} published to Kafka", key);
+				return;
+			},
+			Err(error) => {
+				debug!("Publishing attempt {} to Kafka failed: {}", attempt + 1, error);
+				*producer = None;
+			},
+		}
+	}
+
+	warn!("Couldn't publish an event of {} to Kafka after {} attempts", key, config.max_retries + 1);
+}
+
+/// Connects a producer to the brokers
+fn connect(config: &KafkaConfig) -> kafka::Result<Producer> {
+	Producer::from_hosts(config.brokers.clone())
+		.with_client_id(config.client_id.clone())
+		.with_ack_timeout(config.ack_timeout)
+		.with_required_acks(RequiredAcks::One)
+		.create()
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::net::TcpListener;
+	use std::time::Instant;
+
+	#[test]
+	fn unreachable_brokers_dont_block_the_board() {
+		let address = TcpListener::bind("127.0.0.1:0").expect("Couldn't reserve a port").local_addr().expect("Couldn't get the address");
+		let config = KafkaConfig { initial_backoff: Duration::from_millis(1), ..KafkaConfig::new(vec![address.to_string()], "scores") };
+
+		let mut sb = ScoreBoard::new();
+		sb.set_kafka(config);
+		let start = Instant::now();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+
+		assert!(start.elapsed() < Duration::from_secs(1));
+		assert_eq!(sb.get_events().len(), 2);
+	}
+
+	#[test]
+	fn failed_publishing_gives_up_after_the_retries() {
+		let address = TcpListener::bind("127.0.0.1:0").expect("Couldn't reserve a port").local_addr().expect("Couldn't get the address");
+		let config = KafkaConfig { max_retries: 2, initial_backoff: Duration::from_millis(1), ..KafkaConfig::new(vec![address.to_string()], "scores") };
+		let mut producer = None;
+
+		deliver(&mut producer, &config, "spain-brazil", "{}");
+		assert!(producer.is_none());
+	}
+
+	#[test]
+	fn events_are_keyed_by_the_game() {
+		let (sender, receiver) = mpsc::channel();
+		let mut sb = ScoreBoard::new();
+		sb.kafka = Some(KafkaNotifier { sender });
+		sb.start_game("South Korea", "Japan").expect("Couldn't create the game");
+
+		let (key, payload) = receiver.try_recv().expect("The start wasn't queued");
+		assert_eq!(key, "south-korea-japan");
+		assert_eq!(payload, sb.get_events()[0].to_json());
+	}
+
+	#[test]
+	fn config_has_sensible_defaults() {
+		let config = KafkaConfig::new(vec![String::from("localhost:9092")], "scores");
+
+		assert_eq!(config.topic, "scores");
+		assert_eq!(config.max_retries, 3);
+		assert_eq!(config.initial_backoff, Duration::from_millis(500));
+		assert_eq!(config.ack_timeout, Duration::from_secs(5));
+	}
+}
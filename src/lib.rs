@@ -3,27 +3,398 @@
 //! Provides a simple score board for following the results of the currently played games in a World Cup
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
+use std::io;
 use std::string::{String, ToString};
-use std::time::Instant;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 use std::vec::Vec;
 
 use log::{debug, trace, warn};
 
+use archive::PlayDetails;
+use builder::GameDetails;
+use cards::BookedCard;
+use debounce::DebouncePolicy;
+use minute::{MatchClock, DEFAULT_HALF_LENGTH};
+use notes::GameNote;
+use order::RankKey;
+use template::SummaryTemplate;
+use transaction::ArchiveChange;
+
+#[macro_use]
+mod instrument;
+mod interceptor;
+
+mod alias;
+#[cfg(feature = "actor")]
+mod actor;
+mod api;
+mod archive;
+mod atom;
+mod board_view;
+mod builder;
+mod bulk;
+mod called_off;
+mod cards;
+mod chat;
+mod command;
+mod config;
+mod conflict;
+mod countdown;
+mod country;
+#[cfg(feature = "egui")]
+mod dashboard;
+mod datetime;
+mod debounce;
+mod draw;
+mod error;
+mod events;
+mod feed;
+#[cfg(feature = "football-data")]
+mod football_data;
+mod forfeit;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+mod goal;
+#[cfg(feature = "graphql")]
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod history;
+mod ics;
+mod journal;
+mod jsonl;
+#[cfg(feature = "kafka")]
+mod kafka;
+mod limit;
+mod locale;
+mod manager;
+#[cfg(feature = "embedded-graphics")]
+mod matrix;
+mod minute;
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod notes;
+mod odds;
+mod order;
+#[cfg(feature = "page-templates")]
+mod page;
+mod period;
+mod player;
+#[cfg(feature = "image")]
+mod png;
+mod policy;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+mod random;
+#[cfg(feature = "redis")]
+mod redis_board;
+mod rename;
+#[cfg(feature = "repl")]
+mod repl;
+mod reopen;
+mod round_robin;
+mod scorers;
+mod script;
+mod sharded;
+#[cfg(feature = "simulate")]
+mod simulate;
+mod snapshot;
+mod sport;
+mod state;
+mod stats;
+mod svg;
+mod swap;
+mod table;
+mod text;
+mod template;
+mod tie;
+mod timeline;
+mod timezone;
+mod transaction;
+mod validator;
+mod view;
+#[cfg(feature = "webhooks")]
+mod webhook;
+mod xg;
+mod xml;
+
+#[cfg(feature = "actor")]
+pub use actor::ScoreBoardHandle;
+pub use api::ScoreBoardApi;
+pub use archive::{ArchivedGame, Decision};
+pub use board_view::ScoreBoardView;
+pub use builder::GameBuilder;
+pub use bulk::GameId;
+pub use called_off::CalledOffGame;
+pub use cards::CardKind;
+pub use chat::{format_discord_embed, format_slack_blocks};
+pub use command::Command;
+pub use config::ScoreBoardConfig;
+pub use conflict::{ScheduleConflict, DEFAULT_FIXTURE_WINDOW};
+pub use country::get_flag;
+#[cfg(feature = "egui")]
+pub use dashboard::ScoreBoardWidget;
+pub use debounce::DebounceMode;
+pub use draw::{draw_groups, Confederation, DrawRules, DrawTeam, Group};
+pub use error::{Operation, Rejection, ScoreBoardError};
+pub use events::{EventRecord, ScoreBoardEvent};
+pub use forfeit::DEFAULT_FORFEIT_SCORE;
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::OperationSequence;
+pub use goal::{Goal, GoalCounts, GoalKind, Side};
+#[cfg(feature = "graphql")]
+pub use graphql::{graphql_schema, GraphQlMutation, GraphQlQuery, GraphQlSubscription, ScoreBoardSchema};
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcScoreBoard;
+pub use history::LogCheckpoint;
+pub use interceptor::{Interceptor, Next};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaConfig;
+pub use limit::DEFAULT_MAX_SCORE;
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap::{MappedBoardReader, MappedBoardWriter};
+pub use locale::{Locale, Numerals};
+pub use manager::BoardManager;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttConfig;
+pub use odds::Odds;
+pub use order::SortOrder;
+#[cfg(feature = "page-templates")]
+pub use page::PageTemplate;
+pub use period::{Period, PeriodScore};
+pub use player::Player;
+#[cfg(feature = "image")]
+pub use png::PngStyle;
+pub use policy::{MatchupPolicy, PolicyViolation};
+#[cfg(feature = "protobuf")]
+pub use protobuf::{score_event, BoardState, Decision as ProtoDecision, FinishGameRequest, Game as ProtoGame, GameFinished, GameReopened, GameStarted, GameState as ProtoGameState, Goal as ProtoGoal, GoalKind as ProtoGoalKind, OperationReply, Period as ProtoPeriod, PeriodStarted, Player as ProtoPlayer, ResultFinalized, ScoreEvent, ScoreUpdated, SidesSwapped, StartGameRequest, StateChanged, StreamSummaryRequest, TeamRenamed, UpdateScoreRequest};
+#[cfg(feature = "redis")]
+pub use redis_board::RedisScoreBoard;
+#[cfg(feature = "repl")]
+pub use repl::Repl;
+pub use round_robin::{generate_round_robin, RoundRobinFixture};
+pub use scorers::ScorerStanding;
+pub use sharded::ShardedScoreBoard;
+#[cfg(feature = "simulate")]
+pub use simulate::{SimulatedGame, SimulatedGoal, Simulation};
+pub use snapshot::{BoardSnapshot, GameSnapshot, SNAPSHOT_VERSION};
+pub use sport::{Basketball, Football, ScoreModel, SportBoard, Tennis, Volleyball};
+pub use state::GameState;
+pub use stats::TeamStanding;
+pub use svg::SvgStyle;
+pub use table::TableStyle;
+pub use tie::{Tie, TieResolution};
+pub use timeline::{TimelineEntry, TimelineKind};
+pub use timezone::UtcOffset;
+pub use transaction::Transaction;
+pub use validator::Validator;
+pub use view::{GameRef, GameView};
+#[cfg(feature = "webhooks")]
+pub use webhook::WebhookConfig;
+pub use xg::ExpectedGoals;
+
 // *********************
 // Public API functions
 // *********************
 
 /// Score board representation
+#[derive(Clone)]
 pub struct ScoreBoard {
-	/// In-memory data storage, using `Game` struct as a representation of a single ongoing game
-	data: Vec<Game>
+	/// In-memory data storage, using `Game` struct as a representation of a single ongoing game. Always kept in the order of the board
+	data: Vec<Game>,
+	/// Ranking keys of the running games by the names of their teams, so a game is found with a binary search of `data`
+	team_index: HashMap<String, RankKey>,
+	/// Games that are planned but not started yet, in the order they were scheduled
+	schedule: Vec<Fixture>,
+	/// History of all the changes of the score board since the last `compact_log()`, the oldest coming first
+	events: Vec<EventRecord>,
+	/// The state of the board after the last event removed by `compact_log()`
+	checkpoint: Option<LogCheckpoint>,
+	/// Results of the finished games, the earliest finished coming first
+	archive: Vec<ArchivedGame>,
+	/// Abandoned games and postponed fixtures, the earliest called off coming first
+	called_off: Vec<CalledOffGame>,
+	/// Notes of the commentators, the oldest coming first
+	notes: Vec<GameNote>,
+	/// Cards shown to the players, the oldest coming first
+	cards: Vec<BookedCard>,
+	/// Identity of the operator making the changes, recorded with every event
+	operator: Option<String>,
+	/// User supplied rules, checked before every start, update and finish of a game
+	validators: Vec<Arc<dyn Validator + Send + Sync>>,
+	/// User supplied layers around the applied commands, the outermost coming first
+	interceptors: Vec<Arc<dyn Interceptor + Send + Sync>>,
+	/// Other names of the teams, with the names shown on the board
+	aliases: HashMap<String, String>,
+	/// Registered players by the names of their teams, ordered by the shirt numbers
+	players: HashMap<String, Vec<Player>>,
+	/// Order of the games on the board and in the summary
+	sort_order: SortOrder,
+	/// Whether only the known national teams can play, see `set_strict_countries()`
+	strict_countries: bool,
+	/// Optional limit of the games played at the same time
+	max_games: Option<usize>,
+	/// Optional restriction of the pairings that may start, see `set_matchup_policy()`
+	matchup_policy: Option<MatchupPolicy>,
+	/// Time taken by a game, within which the fixtures of the same team or stadium clash, see `set_fixture_window()`
+	fixture_window: Duration,
+	/// Length of a half of a match, for the added time in the minutes, see `set_half_length()`
+	half_length: Duration,
+	/// Optional limit of the score of a team, see `set_max_score()`
+	max_score: Option<u8>,
+	/// Score of the winner of an awarded game, see `set_forfeit_score()`
+	forfeit_score: u8,
+	/// Optional protection against updates of the same game coming too fast
+	debounce: Option<DebouncePolicy>,
+	/// The summary of the running games, rendered by the first `get_summary()` after a change of the games
+	summary: OnceLock<Vec<String>>,
+	/// Optional format of the summary lines, see `set_summary_template()`
+	summary_template: Option<SummaryTemplate>,
+	/// Regional conventions of the summaries, see `set_locale()`
+	locale: Locale,
+	/// Optional notifications about every recorded event
+	#[cfg(feature = "webhooks")]
+	webhooks: Option<webhook::WebhookNotifier>,
+	/// Optional publishing of every recorded event to an MQTT broker
+	#[cfg(feature = "mqtt")]
+	mqtt: Option<mqtt::MqttPublisher>,
+	/// Optional publishing of every recorded event to a Kafka topic
+	#[cfg(feature = "kafka")]
+	kafka: Option<kafka::KafkaNotifier>,
+	/// Optional streaming of every recorded event as JSON Lines
+	json_lines: Option<jsonl::JsonLinesSink>,
+	/// Optional journal of every recorded event, for the recovery after a crash
+	journal: Option<journal::Journal>,
+	/// The state from before the staged changes, while the changes of a transaction or a batch are staged
+	savepoint: Option<transaction::Savepoint>,
 }
 
 impl ScoreBoard {
 	/// Returns a newly created, empty score board
 	pub fn new() -> ScoreBoard {
-		ScoreBoard { data: Vec::new() }
+		ScoreBoard {
+			data: Vec::new(),
+			team_index: HashMap::new(),
+			schedule: Vec::new(),
+			events: Vec::new(),
+			checkpoint: None,
+			archive: Vec::new(),
+			called_off: Vec::new(),
+			notes: Vec::new(),
+			cards: Vec::new(),
+			operator: None,
+			validators: Vec::new(),
+			interceptors: Vec::new(),
+			aliases: HashMap::new(),
+			players: HashMap::new(),
+			sort_order: SortOrder::Ranking,
+			strict_countries: false,
+			max_games: None,
+			matchup_policy: None,
+			fixture_window: DEFAULT_FIXTURE_WINDOW,
+			half_length: DEFAULT_HALF_LENGTH,
+			max_score: Some(DEFAULT_MAX_SCORE),
+			forfeit_score: DEFAULT_FORFEIT_SCORE,
+			debounce: None,
+			summary: OnceLock::new(),
+			summary_template: None,
+			locale: Locale::default(),
+			#[cfg(feature = "webhooks")]
+			webhooks: None,
+			#[cfg(feature = "mqtt")]
+			mqtt: None,
+			#[cfg(feature = "kafka")]
+			kafka: None,
+			json_lines: None,
+			journal: None,
+			savepoint: None,
+		}
+	}
+
+	/// Schedules a future game between two teams. Scheduled games are not shown in the summary until they are started with `start_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `kickoff` - Planned start time of the game
+	///
+	/// # Errors
+	///
+	/// * When the two provided names are the same
+	/// * When any of the teams isn't a known country, in the strict mode (see `set_strict_countries()`)
+	/// * When the same game is already scheduled for the same kickoff time
+	/// * When any of the teams already plays a fixture too close to the kickoff (see `set_fixture_window()`)
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// Fixtures at a stadium, or in its time zone, are scheduled with `GameBuilder::schedule()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Japan", "Indonesia", SystemTime::now() + Duration::from_secs(3600)).unwrap();
+	/// assert!(sb.get_summary().is_empty());
+	/// ```
+	pub fn schedule_game<T: ToString, U: ToString>(&mut self, home: T, away: U, kickoff: SystemTime) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		self.schedule_fixture(home_name, away_name, kickoff, GameDetails::default())
+	}
+
+	/// Schedules a future game between two teams, see `schedule_game()` and `GameBuilder::schedule()`
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team, after following the aliases
+	/// * `away_name` - name of the away team, after following the aliases
+	/// * `kickoff` - planned start time of the game
+	/// * `details` - the venue, the group and the time zone of the game, if they're known. The time of a past start isn't used
+	///
+	pub(crate) fn schedule_fixture(&mut self, home_name: String, away_name: String, kickoff: SystemTime, details: GameDetails) -> Result<(), ScoreBoardError> {
+		operation_span!(tracing::Level::INFO, "schedule_game", home = %home_name, away = %away_name);
+
+		trace!("Trying to schedule a game for teams: '{}' and '{}'", home_name, away_name);
+
+		if home_name == away_name {
+			warn!("{} cannot play with itself", home_name);
+			return Err(ScoreBoardError::SameTeam(home_name));
+		}
+
+		self.check_countries(&home_name, &away_name)?;
+
+		if self.schedule.iter().any(|fixture| fixture.home_name == home_name && fixture.away_name == away_name && fixture.kickoff == kickoff) {
+			warn!("Game between {} and {} is already scheduled", home_name, away_name);
+			return Err(ScoreBoardError::AlreadyScheduled { home: home_name, away: away_name });
+		}
+
+		let GameDetails { venue, group, utc_offset, .. } = details;
+		let utc_offset = utc_offset.unwrap_or(UtcOffset::UTC);
+
+		self.check_schedule_conflicts(&home_name, &away_name, kickoff, venue.as_deref())?;
+
+		let kickoff_millis = journal::get_millis(kickoff).to_string();
+		let utc_offset_minutes = utc_offset.get_minutes().to_string();
+		let group_letter = group.map(String::from);
+		let mut fields: Vec<&str> = vec!["scheduled", &home_name, &away_name, &kickoff_millis, &utc_offset_minutes];
+		journal::push_place(&mut fields, venue.as_deref(), group_letter.as_deref());
+		self.write_ahead_change(&fields)?;
+
+		self.schedule.push(Fixture { home_name, away_name, kickoff, venue, group, utc_offset });
+
+		trace!("Game scheduled");
+
+		Ok(())
 	}
 
 	/// Starts a game between two teams, with initial score 0 - 0
@@ -36,7 +407,13 @@ impl ScoreBoard {
 	/// # Errors
 	///
 	/// * When the two provided names are the same
+	/// * When any of the teams isn't a known country, in the strict mode (see `set_strict_countries()`)
 	/// * When any of the provided team is currently playing a match
+	/// * When the score board already has as many running games as allowed (see `set_max_games()`)
+	/// * When the matchup policy doesn't allow the game (see `set_matchup_policy()`)
+	/// * When any of the validators rejects the game
+	///
+	/// If the game was scheduled with `schedule_game()`, its earliest fixture is removed from the schedule, and the game is played at the venue, in the group and in the time zone of the fixture
 	///
 	/// # Examples
 	///
@@ -49,31 +426,82 @@ impl ScoreBoard {
 	/// let summary = sb.get_summary();
 	/// assert_eq!(summary, expected_result);
 	/// ```
-	pub fn start_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), String> {
+	pub fn start_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
 
-		let home_name = home.to_string();
-		let away_name = away.to_string();
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		self.start_new_game(home_name, away_name, GameDetails::default())
+	}
+
+	/// Starts a game between two teams with the given details, see `start_game()` and `new_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team, after following the aliases
+	/// * `away_name` - name of the away team, after following the aliases
+	/// * `details` - the optional details of the game
+	///
+	pub(crate) fn start_new_game(&mut self, home_name: String, away_name: String, details: GameDetails) -> Result<(), ScoreBoardError> {
+		operation_span!(tracing::Level::INFO, "start_game", home = %home_name, away = %away_name);
 
 		trace!("Trying to start a game for teams: '{}' and '{}'", home_name, away_name);
 
 		if home_name == away_name {
 			warn!("{} cannot play with itself", home_name);
-			return Err(format!("{} cannot play with itself", home_name));
+			return Err(ScoreBoardError::SameTeam(home_name));
 		}
 
+		self.check_countries(&home_name, &away_name)?;
+
 		self.check_if_currently_playing(&home_name, &away_name)?;
 
+		self.check_max_games()?;
+
+		self.check_matchup_policy(&home_name, &away_name)?;
+
+		self.validate(|| Command::StartGame { home: home_name.clone(), away: away_name.clone() })?;
+
+		let kickoff_time = details.kickoff.unwrap_or_else(SystemTime::now);
+		self.write_ahead_start(&home_name, &away_name, kickoff_time, &details)?;
+
+		let fixture = self.remove_from_schedule(&home_name, &away_name);
+		// The details given at the start win over the ones of the fixture
+		let (venue, group, utc_offset, scheduled_kickoff) = match fixture {
+			Some(fixture) => (details.venue.or(fixture.venue), details.group.or(fixture.group), details.utc_offset.unwrap_or(fixture.utc_offset), Some(fixture.kickoff)),
+			None => (details.venue, details.group, details.utc_offset.unwrap_or(UtcOffset::UTC), None),
+		};
+
+		self.record_event(ScoreBoardEvent::GameStarted { home: home_name.clone(), away: away_name.clone() }, 1);
+
+		let elapsed = SystemTime::now().duration_since(kickoff_time).unwrap_or(Duration::ZERO);
+		let start_time = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
 		self.data.push(
 			Game {
 				home_team : Team { name: home_name, score: 0 },
 				away_team : Team { name: away_name, score: 0 },
-				start_time: Instant::now(),
+				start_time,
+				kickoff_time,
+				scheduled_kickoff,
+				last_update: None,
+				revision: 1,
+				goals: Vec::new(),
+				periods: vec![(Period::FirstHalf, (0, 0))],
+				half_time_score: None,
+				pause_score: None,
+				state: GameState::Live,
+				clock: MatchClock::new(start_time),
+				venue,
+				utc_offset,
+				group,
+				odds: None,
+				xg: ExpectedGoals::default(),
 			}
 		);
 
 		trace!("Game started");
 
-		self.sort();
+		self.reposition(self.data.len() - 1);
 
 		Ok(())
 	}
@@ -90,6 +518,9 @@ impl ScoreBoard {
 	/// # Errors
 	///
 	/// * When there is no active match between the given teams
+	/// * When any of the scores is above the limit, see `set_max_score()`
+	/// * When any of the validators rejects the update
+	/// * When the update comes too soon after the previous one and debouncing is set to reject it, see `set_debounce()`
 	///
 	/// # Examples
 	///
@@ -103,36 +534,56 @@ impl ScoreBoard {
 	/// let summary = sb.get_summary();
 	/// assert_eq!(summary, expected_result);
 	/// ```
-	pub fn update_score<T: ToString, U: ToString>(&mut self, home: T, new_home_score: u8, away: U, new_away_score: u8) -> Result<(), String> {
-		let home_name = home.to_string();
-		let away_name = away.to_string();
+	pub fn update_score<T: ToString, U: ToString>(&mut self, home: T, new_home_score: u8, away: U, new_away_score: u8) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "update_score", home = %home_name, home_score = new_home_score, away = %away_name, away_score = new_away_score);
 
 		trace!("Updating score to: {} {} - {} {}", home_name, new_home_score, away_name, new_away_score);
 
-		match self.find_game_index(&home_name, &away_name) {
-			Ok(game_index) => {
-				let new_game_result = Game {
-					home_team : Team { name: home_name, score: new_home_score },
-					away_team : Team { name: away_name, score: new_away_score },
-					start_time : self.data[game_index].start_time,
-				};
-
-				let _ = std::mem::replace(&mut self.data[game_index], new_game_result);
+		self.set_score(home_name, new_home_score, away_name, new_away_score, None)
+	}
+
+	/// Sets the score of a running match and records the change, optionally with the goal that caused it
+	fn set_score(&mut self, home_name: String, new_home_score: u8, away_name: String, new_away_score: u8, goal: Option<Goal>) -> Result<(), ScoreBoardError> {
+		let event = ScoreBoardEvent::ScoreUpdated { home: home_name.clone(), home_score: new_home_score, away: away_name.clone(), away_score: new_away_score, goal: goal.clone() };
+
+		let (game_index, coalesce, revision) = match self.find_game_index(&home_name, &away_name) {
+			Some(game_index) => {
+				let coalesce = self.check_debounce(game_index)?;
+				self.check_max_score(new_home_score, new_away_score)?;
+				self.validate(|| Command::UpdateScore { home: home_name.clone(), home_score: new_home_score, away: away_name.clone(), away_score: new_away_score })?;
+				self.write_ahead(&event)?;
+				self.mark_updated(game_index);
+				let game = &mut self.data[game_index];
+				game.home_team.score = new_home_score;
+				game.away_team.score = new_away_score;
+				goal::trim_goals(&mut game.goals, new_home_score, new_away_score);
+				game.goals.extend(goal);
+				game.revision += 1;
+				(game_index, coalesce, game.revision)
 			},
-			Err(_) => {
+			None => {
 				warn!("Couldn't find a game for update");
-				return Err(String::from("Couldn't find a game for update"))
+				return Err(self.game_not_found(home_name, away_name, Operation::UpdateScore))
 			},
+		};
+
+		if coalesce {
+			self.coalesce_event(event, revision);
+		} else {
+			self.record_event(event, revision);
 		}
 
 		trace!("Update successful");
 
-		self.sort();
+		self.reposition(game_index);
 
 		Ok(())
 	}
 
-	/// Finishes a match and removes it from the score board
+	/// Finishes a match decided in the regular time, removes it from the score board and moves it to the archive. Same as `finish_game_after()` with `Decision::RegularTime`
 	///
 	/// # Arguments
 	///
@@ -142,6 +593,8 @@ impl ScoreBoard {
 	/// # Errors
 	///
 	/// * When there is no active match between the given teams
+	/// * When the game is paused, since a paused game can't be finished (see the table at `GameState`)
+	/// * When any of the validators rejects the end of the game
 	///
 	/// # Examples
 	///
@@ -155,28 +608,194 @@ impl ScoreBoard {
 	/// let summary = sb.get_summary();
 	/// assert_eq!(summary, expected_result);
 	/// ```
-	pub fn finish_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), String> {
-		let home_name = home.to_string();
-		let away_name = away.to_string();
+	pub fn finish_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		self.finish_game_after(home, away, Decision::RegularTime)
+	}
+
+	/// Finishes a match, removes it from the score board and moves it to the archive, recording the way its result was decided
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `decision` - The way the result was decided, ex. `Decision::AfterExtraTime`
+	///
+	/// # Errors
+	///
+	/// * When the penalty shootout has no winner
+	/// * When there is no active match between the given teams
+	/// * When the game is paused, since a paused game can't be finished (see the table at `GameState`)
+	/// * When any of the validators rejects the end of the game
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Decision;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Italy").unwrap();
+	/// sb.update_score("Spain", 1, "Italy", 1).unwrap();
+	/// sb.finish_game_after("Spain", "Italy", Decision::AfterPenalties { home: 4, away: 2 }).unwrap();
+	/// assert_eq!(sb.get_archive()[0].to_string(), "Spain 1 - 1 Italy (a.e.t., 4-2 pen.)");
+	/// ```
+	pub fn finish_game_after<T: ToString, U: ToString>(&mut self, home: T, away: U, decision: Decision) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "finish_game", home = %home_name, away = %away_name);
 
 		trace!("Ending a game bewteen '{}' and '{}'", home_name, away_name);
 
+		if let Decision::AfterPenalties { home, away } = decision {
+			if home == away {
+				warn!("Penalty shootout without a winner");
+				return Err(ScoreBoardError::DrawnShootout(home, away));
+			}
+		}
+
 		match self.find_game_index(&home_name, &away_name) {
-			Ok(game_index) => { let _ = self.data.remove(game_index); },
-			Err(_) => {
+			Some(game_index) => {
+				self.data[game_index].state.transition(GameState::Finished)?;
+				self.validate(|| Command::FinishGame { home: home_name.clone(), away: away_name.clone() })?;
+				let game = &self.data[game_index];
+				// The journal gets the final result only, without the duration
+				self.write_ahead(&ScoreBoardEvent::ResultFinalized {
+					home: home_name.clone(),
+					home_score: game.home_team.score,
+					away: away_name.clone(),
+					away_score: game.away_team.score,
+					duration: Duration::ZERO,
+					decision,
+				})?;
+				let mut game = self.remove_game(game_index);
+				// The clock stays stopped until the game is reopened, if ever
+				game.clock.pause(Instant::now());
+				self.archive.push(ArchivedGame {
+					home_team: game.home_team.name.clone(),
+					home_score: game.home_team.score,
+					away_team: game.away_team.name.clone(),
+					away_score: game.away_team.score,
+					kickoff_time: game.kickoff_time,
+					finish_time: SystemTime::now(),
+					decision,
+					goals: game.goals,
+					details: Some(PlayDetails {
+						periods: game.periods,
+						half_time_score: game.half_time_score,
+						clock: game.clock,
+						venue: game.venue,
+						utc_offset: game.utc_offset,
+						scheduled_kickoff: game.scheduled_kickoff,
+						group: game.group,
+						odds: game.odds,
+						xg: game.xg,
+					}),
+				});
+				self.stage_archive_change(ArchiveChange::Added);
+				self.record_finish(game.revision + 1);
+			},
+			None => {
 				warn!("Couldn't find a game for removal");
-				return Err(String::from("Couldn't find a game for removal"))
+				return Err(self.game_not_found(home_name, away_name, Operation::FinishGame))
 			},
 		}
 
 		trace!("Game removed successfully");
 
-		self.sort();
-
 		Ok(())
 	}
 
-	/// Provides the current status of the scoreboard, with all current matches listed. The matches are ordered by total score (the highest coming first) and, in the case of the same score, by start time (the earliest match coming first)
+	/// Updates a score of a running match, like `update_score()`, when there is one. Meant for the feeds repeating updates of games that may not be on the board: a missing game is reported without allocating anything, while `update_score()` converts the names and builds an error
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name or alias of the home team
+	/// * `new_home_score` - A new score to be set for the home team
+	/// * `away` - Name or alias of the away team
+	/// * `new_away_score` - A new score to be set for the away team
+	///
+	/// # Returns
+	///
+	/// * `true` when the score was updated, and `false` when there is no active match between the given teams
+	///
+	/// # Errors
+	///
+	/// * When the update is rejected, the same way as by `update_score()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(sb.try_update_score("Japan", 1, "Indonesia", 0), Ok(true));
+	/// assert_eq!(sb.try_update_score("Spain", 1, "Brazil", 0), Ok(false));
+	/// ```
+	pub fn try_update_score(&mut self, home: &str, new_home_score: u8, away: &str, new_away_score: u8) -> Result<bool, ScoreBoardError> {
+		if self.find_game_index(self.resolve_name(home), self.resolve_name(away)).is_none() {
+			trace!("No game to update");
+			return Ok(false);
+		}
+
+		self.update_score(home, new_home_score, away, new_away_score).map(|_| true)
+	}
+
+	/// Finishes a running match, like `finish_game()`, when there is one. A missing game is reported without allocating anything, see `try_update_score()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name or alias of the home team
+	/// * `away` - Name or alias of the away team
+	///
+	/// # Returns
+	///
+	/// * `true` when the game was finished, and `false` when there is no active match between the given teams
+	///
+	/// # Errors
+	///
+	/// * When the end of the game is rejected, the same way as by `finish_game()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(sb.try_finish_game("Japan", "Indonesia"), Ok(true));
+	/// assert_eq!(sb.try_finish_game("Japan", "Indonesia"), Ok(false));
+	/// ```
+	pub fn try_finish_game(&mut self, home: &str, away: &str) -> Result<bool, ScoreBoardError> {
+		if self.find_game_index(self.resolve_name(home), self.resolve_name(away)).is_none() {
+			trace!("No game to finish");
+			return Ok(false);
+		}
+
+		self.finish_game(home, away).map(|_| true)
+	}
+
+	/// Provides the current score of a running match
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	///
+	/// # Returns
+	///
+	/// * The scores of the home and the away team, or `None` if there is no active match between the given teams
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 2, "Indonesia", 0).unwrap();
+	/// assert_eq!(sb.get_score("Japan", "Indonesia"), Some((2, 0)));
+	/// assert_eq!(sb.get_score("Indonesia", "Japan"), None);
+	/// ```
+	pub fn get_score(&self, home: &str, away: &str) -> Option<(u8, u8)> {
+		self.find_game(home, away).map(|game| (game.home_team.score, game.away_team.score))
+	}
+
+	/// Provides the current status of the scoreboard, with all current matches listed. The matches are ordered by total score (the highest coming first) and, in the case of the same score, by start time (the earliest match coming first). The lines follow the format set with `set_summary_template()`, if any
 	///
 	/// # Returns
 	///
@@ -194,15 +813,69 @@ impl ScoreBoard {
 	/// assert_eq!(summary, expected_result);
 	/// ```
 	pub fn get_summary(&self) -> Vec<String> {
+		operation_span!(tracing::Level::TRACE, "get_summary", games = self.data.len());
+
 		trace!("Getting the score board summary");
-		
-		let mut result = Vec::new();
+
+		let render = || {
+			trace!("Rendering the summary");
+
+			let now = Instant::now();
+			let mut result = Vec::new();
+
+			for game in &self.data {
+				result.push(self.render_line(game, now));
+			}
+
+			result
+		};
+
+		// The minutes of the matches change with the time, so their lines are never cached
+		match &self.summary_template {
+			Some(template) if template.is_timed() => render(),
+			_ => self.summary.get_or_init(render).clone(),
+		}
+	}
+
+	/// Writes the current status of the scoreboard to the given sink, one match per line, in the order of `get_summary()`. The lines are formatted straight into the sink, without building the summary first, so it suits large boards served over sockets or written to logs
+	///
+	/// # Arguments
+	///
+	/// * `w` - the sink of the summary, ex. a file, a socket or a buffer
+	///
+	/// # Errors
+	///
+	/// * When writing to the sink fails
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 1, "Brazil", 0).unwrap();
+	///
+	/// let mut output = Vec::new();
+	/// sb.write_summary(&mut output).unwrap();
+	/// assert_eq!(String::from_utf8(output).unwrap(), "Spain 1 - Brazil 0\nJapan 0 - Indonesia 0\n");
+	/// ```
+	pub fn write_summary(&self, w: &mut impl io::Write) -> io::Result<()> {
+		trace!("Writing the score board summary");
+
+		let now = Instant::now();
 
 		for game in &self.data {
-			result.push(game.to_string());
+			writeln!(w, "{}", self.render_line(game, now))?;
 		}
 
-		return result;
+		Ok(())
+	}
+}
+
+impl Default for ScoreBoard {
+	/// Returns a newly created, empty score board
+	fn default() -> Self {
+		ScoreBoard::new()
 	}
 }
 
@@ -211,6 +884,7 @@ impl ScoreBoard {
 // *****************************************
 
 /// A representation of a team
+#[derive(Clone)]
 struct Team {
 	/// Team's name
 	name: String,
@@ -226,6 +900,7 @@ impl fmt::Display for Team {
 }
 
 /// A representation of a match
+#[derive(Clone)]
 struct Game {
 	/// Home team structure
 	home_team: Team,
@@ -233,22 +908,79 @@ struct Game {
 	away_team: Team,
 	/// Timestamp of the start of the match
 	start_time: Instant,
+	/// Wall clock time of the start of the match, used when the match is presented outside of the score board (ex. in calendars)
+	kickoff_time: SystemTime,
+	/// Planned start of the match, if it was started from a fixture. Keeps the match the same event in calendars once it is live
+	scheduled_kickoff: Option<SystemTime>,
+	/// Timestamp of the last score update of the match, used for debouncing the updates
+	last_update: Option<Instant>,
+	/// Number of the changes of the match, starting from 1 at the start and increasing by 1 with every score update
+	revision: u64,
+	/// Goals recorded with their kind, the oldest coming first. Goals set with absolute scores are not included
+	goals: Vec<Goal>,
+	/// Every entered period with the score at its start, the earliest coming first. Never empty
+	periods: Vec<(Period, (u8, u8))>,
+	/// Score frozen at the end of the first half, when the half-time break started or, without a break, when the second half started. Never replaced once set
+	half_time_score: Option<(u8, u8)>,
+	/// Score at the start of the ongoing pause, which is the half-time score if the pause is the half-time break
+	pause_score: Option<(u8, u8)>,
+	/// State of the match, either `GameState::Live` or `GameState::Paused`
+	state: GameState,
+	/// Playing time of the current period, used for the match minutes
+	clock: MatchClock,
+	/// Name of the stadium, if it was given with `GameBuilder::venue()`
+	venue: Option<String>,
+	/// Time zone of the stadium, if it was given with `GameBuilder::utc_offset()`, and UTC otherwise
+	utc_offset: UtcOffset,
+	/// Group of the tournament, if it was given with `GameBuilder::group()`
+	group: Option<char>,
+	/// The current betting odds, if they were set with `set_odds()`
+	odds: Option<Odds>,
+	/// Expected goals of both teams, accumulated with `record_xg()`
+	xg: ExpectedGoals,
 }
 
 impl Game {
+	/// Provides the current period of the match
+	fn get_period(&self) -> Period {
+		self.periods.last().map_or(Period::FirstHalf, |(period, _)| *period)
+	}
+
 	/// Calculates a total score of the match, which is a sum of the scores of both teams
-	fn get_total_score(&self) -> u8 {
-		return self.home_team.score + self.away_team.score;
+	fn get_total_score(&self) -> u16 {
+		self.home_team.score as u16 + self.away_team.score as u16
+	}
+
+	/// Calculates the goal difference of the match, regardless of which team leads
+	fn get_goal_difference(&self) -> u8 {
+		self.home_team.score.abs_diff(self.away_team.score)
 	}
 }
 
 impl fmt::Display for Game {
 	/// Implementation of `Display` trait, allowing it to be converted to a String
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} - {}", self.home_team.to_string(), self.away_team.to_string())
+        write!(f, "{} - {}", self.home_team, self.away_team)
     }
 }
 
+/// A representation of a scheduled match that hasn't started yet
+#[derive(Clone)]
+struct Fixture {
+	/// Name of the home team
+	home_name: String,
+	/// Name of the away team
+	away_name: String,
+	/// Planned start time of the match
+	kickoff: SystemTime,
+	/// Name of the stadium, if it was given with `GameBuilder::venue()`
+	venue: Option<String>,
+	/// Group of the tournament, if it was given with `GameBuilder::group()`
+	group: Option<char>,
+	/// Time zone of the stadium, if it was given with `GameBuilder::utc_offset()`, and UTC otherwise
+	utc_offset: UtcOffset,
+}
+
 impl ScoreBoard {
 	/// Finds a match that the given team is currently playing
 	///
@@ -258,25 +990,56 @@ impl ScoreBoard {
 	///
 	/// # Returns
 	///
-	/// * Index to the match in `data` structure that holds the match of a given team
-	///
-	/// # Errors
-	///
-	/// * When the given team is not currently playing any matches
+	/// * Index to the match in `data` structure that holds the match of a given team, or `None` when the team is not currently playing any matches
 	///
-	fn find_game_index_of_team(&self, team_name: &String) -> Result<usize, String> {
+	fn find_game_index_of_team(&self, team_name: &str) -> Option<usize> {
 		trace!("Looking for {} in the score board", team_name);
 
-		for (id, game) in self.data.iter().enumerate() {
-			if &game.home_team.name == team_name || &game.away_team.name == team_name {
+		if let Some(key) = self.team_index.get(team_name) {
+			let sort_order = self.sort_order;
+			let first = self.data.partition_point(|game| order::compare_keys(sort_order, &game.get_rank_key(), key) == Ordering::Less);
+			let found = self.data[first..].iter()
+				.take_while(|game| order::compare_keys(sort_order, &game.get_rank_key(), key) == Ordering::Equal)
+				.position(|game| game.home_team.name == team_name || game.away_team.name == team_name);
+
+			if let Some(offset) = found {
 				debug!("Team {} is currently playing a game", team_name);
-				return Ok(id)
+				return Some(first + offset)
 			}
 		}
 
 		debug!("Couldn't find a game of team {}", team_name);
 
-		Err(format!("Couldn't find a game of team {}", team_name))
+		None
+	}
+
+	/// Finds a running match between the two given teams, following their aliases
+	///
+	/// # Arguments
+	///
+	/// * `home` - name or alias of the home team
+	/// * `away` - name or alias of the away team
+	///
+	fn find_game(&self, home: &str, away: &str) -> Option<&Game> {
+		self.find_game_index(self.resolve_name(home), self.resolve_name(away)).map(|game_index| &self.data[game_index])
+	}
+
+	/// Builds the error of a missing game, suggesting the running team with the name most similar to a team that isn't playing
+	///
+	/// # Arguments
+	///
+	/// * `home` - name of the home team
+	/// * `away` - name of the away team
+	/// * `operation` - the operation that needed the game
+	///
+	pub(crate) fn game_not_found(&self, home: String, away: String, operation: Operation) -> ScoreBoardError {
+		let teams = || self.data.iter().flat_map(|game| [game.home_team.name.as_str(), game.away_team.name.as_str()]);
+		let suggestion = [&home, &away].into_iter()
+			.filter(|name| !teams().any(|team| team == name.as_str()))
+			.find_map(|name| text::find_closest(name, teams()))
+			.map(String::from);
+
+		ScoreBoardError::GameNotFound { home, away, operation, suggestion }
 	}
 
 	/// Finds a match between the two given
@@ -288,56 +1051,115 @@ impl ScoreBoard {
 	///
 	/// # Returns
 	///
-	/// * Index to the match in `data` structure that holds the match of these two teams
-	///
-	/// # Errors
-	///
-	/// * When the given teams are not currently playing any matches
+	/// * Index to the match in `data` structure that holds the match of these two teams, or `None` when the given teams are not currently playing any matches
 	///
-	fn find_game_index(&self, home_name: &String, away_name:&String) -> Result<usize, String> {
+	fn find_game_index(&self, home_name: &str, away_name: &str) -> Option<usize> {
 		trace!("Looking for a game between {} and {}", home_name, away_name);
 
-		match self.find_game_index_of_team(&home_name) {
-			Ok(game_index) => {
-				let game = self.data.get(game_index).unwrap();
-				if &game.home_team.name == home_name && &game.away_team.name == away_name {
+		match self.find_game_index_of_team(home_name) {
+			Some(game_index) => {
+				let game = &self.data[game_index];
+				if game.home_team.name == home_name && game.away_team.name == away_name {
 					debug!("Teams {} and {} are playing a game now", home_name, away_name);
-					return Ok(game_index)
+					Some(game_index)
 				} else {
 					debug!("Team {} isn't playing with {} currently", home_name, away_name);
-					return Err(format!("Team {} isn't playing with {} currently", home_name, away_name))
+					None
 				}
 			},
-			Err(_) => {
+			None => {
 				debug!("Couldn't find a game of teams: {} and {}", home_name, away_name);
-				return Err(format!("Couldn't find a game of teams: {} and {}", home_name, away_name))
+				None
 			},
 		}
 	}
 
-	/// Sorts the `data` structure. Matches with high total scores should come before the ones with low scoring, otherwise matches that started the earliest should come before the matches that started after them
+	/// Sorts the `data` structure. Matches with high total scores should come before the ones with low scoring, otherwise matches that started the earliest should come before the matches that started after them. Used when the whole order changes, while a change of a single game only moves that game, see `reposition()`
 	fn sort(&mut self) {
 		trace!("Sorting the games");
 
-		self.data.sort_by(|a, b| {
-			if a.get_total_score() < b.get_total_score() {
-				Ordering::Greater	// Because reverse order is needed, from greatest to smallest
-			} else if a.get_total_score() > b.get_total_score() {
-				Ordering::Less		// Because reverse order is needed, from greatest to smallest
-			} else {
-				if a.start_time < b.start_time {
-					Ordering::Greater	// TODO Because second ordering is also reversed, from greatest timestamp (i.e. freshest game) to lowest
-				} else if a.start_time > b.start_time {
-					Ordering::Less		// TODO Because second ordering is also reversed, from greatest timestamp (i.e. freshest game) to lowest
-				} else {
-					Ordering::Equal
-				}
-			}
-		});
+		let sort_order = self.sort_order;
+		self.data.sort_by(|a, b| order::compare(sort_order, a, b));
+		self.team_index = self.data.iter()
+			.flat_map(|game| [(game.home_team.name.clone(), game.get_rank_key()), (game.away_team.name.clone(), game.get_rank_key())])
+			.collect();
+		self.invalidate_summary();
 
 		trace!("Games sorted");
 	}
 
+	/// Moves a new or changed game to its place in the order, when all the other games are in order, and updates its key in the team index. The place is found with a binary search, and only the games between the old and the new place are shifted
+	///
+	/// # Arguments
+	///
+	/// * `game_index` - current position of the changed game
+	///
+	fn reposition(&mut self, game_index: usize) {
+		let sort_order = self.sort_order;
+		let game = &self.data[game_index];
+
+		let key = game.get_rank_key();
+		self.team_index.insert(game.home_team.name.clone(), key);
+		self.team_index.insert(game.away_team.name.clone(), key);
+
+		let before = self.data[..game_index].partition_point(|other| order::compare(sort_order, other, game) != Ordering::Greater);
+		let after = self.data[game_index + 1..].partition_point(|other| order::compare(sort_order, other, game) != Ordering::Greater);
+
+		if before < game_index {
+			self.data[before..=game_index].rotate_right(1);
+		} else if after > 0 {
+			self.data[game_index..=game_index + after].rotate_left(1);
+		}
+
+		self.invalidate_summary();
+	}
+
+	/// Removes a game from the board and from the team index
+	///
+	/// # Arguments
+	///
+	/// * `game_index` - position of the game
+	///
+	pub(crate) fn remove_game(&mut self, game_index: usize) -> Game {
+		let game = self.data.remove(game_index);
+		self.team_index.remove(&game.home_team.name);
+		self.team_index.remove(&game.away_team.name);
+		self.invalidate_summary();
+
+		game
+	}
+
+	/// Drops the rendered summary after a change of the games
+	pub(crate) fn invalidate_summary(&mut self) {
+		self.summary = OnceLock::new();
+	}
+
+	/// Removes the earliest scheduled fixture between the two given teams, if there is any
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team
+	/// * `away_name` - name of the away team
+	///
+	/// # Returns
+	///
+	/// * The removed fixture, or `None` when the teams had no fixture
+	///
+	fn remove_from_schedule(&mut self, home_name: &String, away_name: &String) -> Option<Fixture> {
+		let earliest = self.schedule.iter()
+			.enumerate()
+			.filter(|(_, fixture)| &fixture.home_name == home_name && &fixture.away_name == away_name)
+			.min_by_key(|(_, fixture)| fixture.kickoff)
+			.map(|(id, _)| id);
+
+		if let Some(id) = earliest {
+			debug!("Removing the fixture of {} and {} from the schedule", home_name, away_name);
+			return Some(self.schedule.remove(id));
+		}
+
+		None
+	}
+
 	/// Checks if any of the two given teams are currently in any matches
 	///
 	/// # Arguments
@@ -349,23 +1171,17 @@ impl ScoreBoard {
 	///
 	/// * When any of the given teams is currently in any active matches
 	///
-	fn check_if_currently_playing(&self, name_1: &String, name_2:&String) -> Result<(), String> {
+	fn check_if_currently_playing(&self, name_1: &String, name_2:&String) -> Result<(), ScoreBoardError> {
 		trace!("Checking if teams {} and {} are currently playing a game", name_1, name_2);
 
-		match self.find_game_index_of_team(&name_1) {
-			Ok(_) => {
-				debug!("Team {} is currently playing a game", name_1);
-				return Err(format!("{} is currently playing a game", name_1))
-			},
-			Err(_) => ()
+		if self.team_index.contains_key(name_1) {
+			debug!("Team {} is currently playing a game", name_1);
+			return Err(ScoreBoardError::AlreadyPlaying(name_1.clone()))
 		}
 
-		match self.find_game_index_of_team(&name_2) {
-			Ok(_) => {
-				debug!("Team {} is currently playing a game", name_2);
-				return Err(format!("{} is currently playing a game", name_2));
-			}
-			Err(_) => ()
+		if self.team_index.contains_key(name_2) {
+			debug!("Team {} is currently playing a game", name_2);
+			return Err(ScoreBoardError::AlreadyPlaying(name_2.clone()));
 		}
 
 		trace!("Teams {} and {} are not playing any games", name_1, name_2);
@@ -380,6 +1196,7 @@ impl ScoreBoard {
 // ***********
 
 #[cfg(test)]
+#[allow(clippy::needless_return, clippy::get_first)]
 mod tests {
     use super::*;
 
@@ -424,7 +1241,7 @@ mod tests {
 
 		assert!(result.is_ok());
 		assert_eq!(sb.data.len(), 1);
-		let Game { home_team: h, away_team: a, start_time: _} = sb.data.first().expect("First element is not available.");
+		let Game { home_team: h, away_team: a, start_time: _, ..} = sb.data.first().expect("First element is not available.");
 		assert_eq!(h.name, HOME_TEAM_NAME);
 		assert_eq!(h.score, 0);
 		assert_eq!(a.name, AWAY_TEAM_NAME);
@@ -439,7 +1256,7 @@ mod tests {
 		let result = sb.start_game(HOME_TEAM_NAME, HOME_TEAM_NAME);
 
 		assert!(result.is_err());
-		assert!(result.err().is_some_and(|result| result == expected_error_message));
+		assert!(result.err().is_some_and(|result| result.to_string() == expected_error_message));
 		assert!(sb.data.is_empty());
 	}
 
@@ -452,12 +1269,12 @@ mod tests {
 		assert!(result_1.is_ok());
 		assert!(result_2.is_ok());
 		assert_eq!(sb.data.len(), 2);
-		let Game { home_team: h_1, away_team: a_1, start_time: _} = sb.data.get(0).expect("First element is not available.");
+		let Game { home_team: h_1, away_team: a_1, start_time: _, ..} = sb.data.get(0).expect("First element is not available.");
 		assert_eq!(h_1.name, HOME_TEAM_NAME_2);
 		assert_eq!(h_1.score, 0);
 		assert_eq!(a_1.name, AWAY_TEAM_NAME_2);
 		assert_eq!(a_1.score, 0);
-		let Game { home_team: h_2, away_team: a_2, start_time: _} = sb.data.get(1).expect("Second element is not available.");
+		let Game { home_team: h_2, away_team: a_2, start_time: _, ..} = sb.data.get(1).expect("Second element is not available.");
 		assert_eq!(h_2.name, HOME_TEAM_NAME_1);
 		assert_eq!(h_2.score, 0);
 		assert_eq!(a_2.name, AWAY_TEAM_NAME_1);
@@ -529,7 +1346,7 @@ mod tests {
 		let result_2 = sb.get_summary();
 
 		assert!(sb.data.is_empty());
-		assert!(result_1.err().is_some_and(|result| result == REMOVAL_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == REMOVAL_ERROR_MESSAGE));
 		assert_eq!(result_2, NOTHING_TO_SHOW);
 	}
 
@@ -541,7 +1358,7 @@ mod tests {
 		let result_2 = sb.get_summary();
 
 		assert_eq!(sb.data.len(), 1);
-		assert!(result_1.err().is_some_and(|result| result == REMOVAL_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == REMOVAL_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(0));
 	}
 
@@ -553,7 +1370,7 @@ mod tests {
 		let result_2 = sb.get_summary();
 
 		assert_eq!(sb.data.len(), 1);
-		assert!(result_1.err().is_some_and(|result| result == REMOVAL_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == REMOVAL_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
@@ -565,7 +1382,7 @@ mod tests {
 		let result_2 = sb.get_summary();
 
 		assert_eq!(sb.data.len(), 1);
-		assert!(result_1.err().is_some_and(|result| result == REMOVAL_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == REMOVAL_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
@@ -577,7 +1394,7 @@ mod tests {
 		let result_2 = sb.get_summary();
 
 		assert_eq!(sb.data.len(), 1);
-		assert!(result_1.err().is_some_and(|result| result == REMOVAL_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == REMOVAL_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
@@ -686,7 +1503,7 @@ mod tests {
 		let result_1 = sb.update_score(HOME_TEAM_NAME, 0, AWAY_TEAM_NAME, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, NOTHING_TO_SHOW);
 	}
 
@@ -697,7 +1514,7 @@ mod tests {
 		let result_1 = sb.update_score(HOME_TEAM_NAME_2, 0, AWAY_TEAM_NAME_2, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
@@ -708,7 +1525,7 @@ mod tests {
 		let result_1 = sb.update_score(HOME_TEAM_NAME_2, 0, AWAY_TEAM_NAME_1, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
@@ -719,10 +1536,26 @@ mod tests {
 		let result_1 = sb.update_score(HOME_TEAM_NAME_1, 0, AWAY_TEAM_NAME_2, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(1));
 	}
 
+	#[test]
+	fn misspelled_team_is_suggested_when_the_game_is_not_found() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME_1, AWAY_TEAM_NAME_1).expect("Couldn't create the game");
+		let result = sb.update_score("Nigeira", 0, AWAY_TEAM_NAME_1, 1);
+
+		assert_eq!(result, Err(ScoreBoardError::GameNotFound {
+			home: String::from("Nigeira"),
+			away: String::from(AWAY_TEAM_NAME_1),
+			operation: Operation::UpdateScore,
+			suggestion: Some(String::from(HOME_TEAM_NAME_1)),
+		}));
+		let result = sb.finish_game(HOME_TEAM_NAME_1, "chad ");
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for removal, did you mean Chad?"));
+	}
+
 	#[test]
 	fn changing_the_score_for_mismatched_home_and_away_teams_is_an_error() {
 		let mut sb = ScoreBoard::new();
@@ -730,7 +1563,7 @@ mod tests {
 		let result_1 = sb.update_score(AWAY_TEAM_NAME, 0, HOME_TEAM_NAME, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, get_summary_of_scoreless_game(0));
 	}
 
@@ -783,10 +1616,114 @@ mod tests {
 		let result_1 = sb.update_score(HOME_TEAM_NAME, 0, AWAY_TEAM_NAME, 1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == UPDATE_ERROR_MESSAGE));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == UPDATE_ERROR_MESSAGE));
 		assert_eq!(result_2, NOTHING_TO_SHOW);
 	}
 
+	#[test]
+	fn summary_is_rendered_once() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		assert!(sb.summary.get().is_none());
+
+		let summary = sb.get_summary();
+		assert_eq!(sb.summary.get(), Some(&summary));
+		assert_eq!(sb.get_summary(), summary);
+	}
+
+	#[test]
+	fn summary_follows_the_changes() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		let _ = sb.get_summary();
+
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the second game");
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 1 - Brazil 0"), String::from("Mexico 0 - Canada 0")]);
+
+		sb.finish_game("Spain", "Brazil").expect("Couldn't finish the second game");
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0")]);
+
+		sb.set_sort_order(SortOrder::Kickoff);
+		let mut transaction = sb.begin();
+		transaction.start_game("Japan", "Indonesia").expect("Couldn't stage the start");
+		transaction.commit().expect("Couldn't commit the transaction");
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 0"), String::from("Japan 0 - Indonesia 0")]);
+	}
+
+	#[test]
+	fn repositioned_games_match_a_full_sort() {
+		let teams = ["Spain", "Brazil", "Mexico", "Canada", "Germany", "France", "Japan", "Indonesia", "Qatar", "Ecuador", "Italy", "Wales"];
+		let mut sb = ScoreBoard::new();
+		for pair in teams.chunks(2) {
+			sb.start_game(pair[0], pair[1]).expect("Couldn't create a game");
+		}
+
+		let mut seed: u32 = 7;
+		for _ in 0..200 {
+			seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+			let pair = (seed >> 16) as usize % (teams.len() / 2);
+			let (home_score, away_score) = sb.get_score(teams[2 * pair], teams[2 * pair + 1]).expect("Couldn't find a game");
+			let (home_score, away_score) = match (seed >> 8) % 3 {
+				0 => (home_score + 1, away_score),
+				1 => (home_score, away_score + 1),
+				_ => (home_score.saturating_sub(1), away_score),
+			};
+			sb.update_score(teams[2 * pair], home_score, teams[2 * pair + 1], away_score).expect("Couldn't update a game");
+
+			let mut sorted = sb.clone();
+			sorted.sort();
+			assert_eq!(sb.get_summary(), sorted.get_summary());
+		}
+	}
+
+	#[test]
+	fn try_operations_skip_missing_games() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		sb.add_alias("MEX", "Mexico");
+
+		assert_eq!(sb.try_update_score("Canada", 1, "Mexico", 0), Ok(false));
+		assert_eq!(sb.try_update_score("MEX", 0, "Canada", 1), Ok(true));
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 1")]);
+		assert_eq!(sb.try_finish_game("Spain", "Brazil"), Ok(false));
+		assert_eq!(sb.get_events().len(), 2);
+
+		assert_eq!(sb.try_finish_game("MEX", "Canada"), Ok(true));
+		assert!(sb.get_summary().is_empty());
+	}
+
+	#[test]
+	fn try_operations_report_rejections() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		sb.set_debounce(Duration::from_secs(60), DebounceMode::Reject);
+		sb.update_score("Mexico", 1, "Canada", 0).expect("Couldn't update the game");
+
+		assert!(sb.try_update_score("Mexico", 2, "Canada", 0).is_err());
+	}
+
+	#[test]
+	fn team_index_follows_the_games() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		sb.set_sort_order(SortOrder::Kickoff);
+		sb.update_score("Germany", 2, "France", 2).expect("Couldn't update the third game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+
+		assert_eq!(sb.team_index.len(), 2 * sb.data.len());
+		for (game_index, game) in sb.data.iter().enumerate() {
+			assert_eq!(sb.team_index.get(&game.home_team.name), Some(&game.get_rank_key()));
+			assert_eq!(sb.find_game_index(&game.home_team.name, &game.away_team.name), Some(game_index));
+			assert_eq!(sb.find_game_index_of_team(&game.away_team.name), Some(game_index));
+		}
+		assert_eq!(sb.find_game_index_of_team("Mexico"), None);
+		assert!(sb.start_game("Canada", "Mexico").is_ok());
+	}
+
 	#[test]
 	fn sorting_of_updated_games_works() {
 		let expected_summary_1 = vec![format!("{} 0 - {} 1", HOME_TEAM_NAME_2, AWAY_TEAM_NAME_2), String::from(SCORELESS_GAME_1)];
@@ -836,7 +1773,7 @@ mod tests {
 		let result_1 = sb.start_game(HOME_TEAM_NAME_1, AWAY_TEAM_NAME_2);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(HOME_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(HOME_TEAM_NAME_1)));
 		assert_eq!(result_2,get_summary_of_scoreless_game(1));
 	}
 
@@ -847,7 +1784,7 @@ mod tests {
 		let result_1 = sb.start_game(HOME_TEAM_NAME_2, AWAY_TEAM_NAME_1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
 		assert_eq!(result_2,get_summary_of_scoreless_game(1));
 	}
 
@@ -858,7 +1795,7 @@ mod tests {
 		let result_1 = sb.start_game(HOME_TEAM_NAME_2, HOME_TEAM_NAME_1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(HOME_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(HOME_TEAM_NAME_1)));
 		assert_eq!(result_2,get_summary_of_scoreless_game(1));
 	}
 
@@ -869,7 +1806,7 @@ mod tests {
 		let result_1 = sb.start_game(AWAY_TEAM_NAME_1, AWAY_TEAM_NAME_2);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
 		assert_eq!(result_2,get_summary_of_scoreless_game(1));
 	}
 
@@ -880,7 +1817,7 @@ mod tests {
 		let result_1 = sb.start_game(AWAY_TEAM_NAME_1, HOME_TEAM_NAME_1);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(AWAY_TEAM_NAME_1)));
 		assert_eq!(result_2,get_summary_of_scoreless_game(1));
 	}
 
@@ -894,10 +1831,108 @@ mod tests {
 		let result_1 = sb.start_game(HOME_TEAM_NAME_1, AWAY_TEAM_NAME_2);
 		let result_2 = sb.get_summary();
 
-		assert!(result_1.err().is_some_and(|result| result == get_team_already_paying_message(HOME_TEAM_NAME_1)));
+		assert!(result_1.err().is_some_and(|result| result.to_string() == get_team_already_paying_message(HOME_TEAM_NAME_1)));
 		assert_eq!(result_2, expected_summary);
 	}
 
+	#[test]
+	fn written_summary_matches_the_summary() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME_1, AWAY_TEAM_NAME_1).expect("Couldn't create the first game");
+		sb.start_game(HOME_TEAM_NAME_2, AWAY_TEAM_NAME_2).expect("Couldn't create the second game");
+
+		let mut output = Vec::new();
+		sb.write_summary(&mut output).expect("Couldn't write the summary");
+
+		let expected: String = sb.get_summary().iter().map(|line| format!("{}\n", line)).collect();
+		assert_eq!(String::from_utf8(output).expect("Couldn't read the summary"), expected);
+	}
+
+	#[test]
+	fn empty_board_writes_nothing() {
+		let sb = ScoreBoard::new();
+
+		let mut output = Vec::new();
+		sb.write_summary(&mut output).expect("Couldn't write the summary");
+		assert!(output.is_empty());
+	}
+
+	#[test]
+	fn failing_sink_error_is_returned() {
+		struct FailingSink;
+
+		impl io::Write for FailingSink {
+			fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+				Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+			}
+
+			fn flush(&mut self) -> io::Result<()> {
+				Ok(())
+			}
+		}
+
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		let result = sb.write_summary(&mut FailingSink);
+		assert!(result.err().is_some_and(|result| result.kind() == io::ErrorKind::BrokenPipe));
+	}
+
+	#[test]
+	fn game_scheduled_correctly() {
+		let kickoff = SystemTime::now();
+
+		let mut sb = ScoreBoard::new();
+		let result = sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff);
+
+		assert!(result.is_ok());
+		assert_eq!(sb.schedule.len(), 1);
+		assert!(sb.data.is_empty());
+		assert_eq!(sb.get_summary(), NOTHING_TO_SHOW);
+	}
+
+	#[test]
+	fn game_not_scheduled_when_both_teams_have_the_same_name() {
+		let expected_error_message = format!("{} cannot play with itself", HOME_TEAM_NAME);
+
+		let mut sb = ScoreBoard::new();
+		let result = sb.schedule_game(HOME_TEAM_NAME, HOME_TEAM_NAME, SystemTime::now());
+
+		assert!(result.err().is_some_and(|result| result.to_string() == expected_error_message));
+		assert!(sb.schedule.is_empty());
+	}
+
+	#[test]
+	fn the_same_game_cannot_be_scheduled_twice_at_the_same_time() {
+		let expected_error_message = format!("Game between {} and {} is already scheduled", HOME_TEAM_NAME, AWAY_TEAM_NAME);
+		let kickoff = SystemTime::now();
+
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff).expect("Couldn't schedule the game");
+		let result = sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == expected_error_message));
+		assert_eq!(sb.schedule.len(), 1);
+	}
+
+	#[test]
+	fn starting_a_scheduled_game_removes_its_earliest_fixture() {
+		let kickoff = SystemTime::now();
+		let rematch = kickoff + std::time::Duration::from_secs(3600 * 24);
+
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, rematch).expect("Couldn't schedule the rematch");
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, kickoff).expect("Couldn't schedule the game");
+		sb.schedule_game(HOME_TEAM_NAME_1, AWAY_TEAM_NAME_1, kickoff).expect("Couldn't schedule the other game");
+		let result = sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		assert!(result.is_ok());
+		assert_eq!(sb.schedule.len(), 2);
+		assert!(sb.schedule.iter().any(|fixture| fixture.home_name == HOME_TEAM_NAME && fixture.kickoff == rematch));
+		assert!(sb.schedule.iter().any(|fixture| fixture.home_name == HOME_TEAM_NAME_1));
+		assert_eq!(sb.get_summary(), get_summary_of_scoreless_game(0));
+	}
+
 	#[test]
 	fn grand_example() {
 		let expected_summary = vec![
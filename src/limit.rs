@@ -0,0 +1,185 @@
+//! # Limits of the games
+//!
+//! Caps the number of the games played at the same time and the scores of the games, since more parallel games than a tournament can have or a score like 255 - 0 point to a malfunction of the feed
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// The highest score of a team on a new score board, see `ScoreBoard::set_max_score()`
+pub const DEFAULT_MAX_SCORE: u8 = 30;
+
+impl ScoreBoard {
+	/// Limits the number of the games played at the same time. Starting a game over the limit fails, while the games already running are kept
+	///
+	/// # Arguments
+	///
+	/// * `max_games` - the highest number of the running games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_max_games(1);
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let result = sb.start_game("Spain", "Brazil");
+	/// assert_eq!(result.unwrap_err().to_string(), "The score board cannot have more than 1 games at the same time");
+	/// ```
+	pub fn set_max_games(&mut self, max_games: usize) {
+		trace!("Limiting the running games to {}", max_games);
+
+		self.max_games = Some(max_games);
+	}
+
+	/// Removes the limit of the games played at the same time
+	pub fn clear_max_games(&mut self) {
+		trace!("Removing the limit of the running games");
+
+		self.max_games = None;
+	}
+
+	/// Limits the score of a team in a game. Updates of a score over the limit fail, as they are most likely glitches of the feed. The default limit is `DEFAULT_MAX_SCORE`
+	///
+	/// # Arguments
+	///
+	/// * `max_score` - the highest plausible score of a team
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// assert!(sb.update_score("Spain", 255, "Brazil", 0).is_err());
+	///
+	/// sb.set_max_score(10);
+	/// let result = sb.update_score("Spain", 11, "Brazil", 0);
+	/// assert_eq!(result.unwrap_err().to_string(), "Score 11 is above the maximum of 10");
+	/// ```
+	pub fn set_max_score(&mut self, max_score: u8) {
+		trace!("Limiting the scores to {}", max_score);
+
+		self.max_score = Some(max_score);
+	}
+
+	/// Removes the limit of the scores, allowing any score up to 255
+	pub fn clear_max_score(&mut self) {
+		trace!("Removing the limit of the scores");
+
+		self.max_score = None;
+	}
+
+	/// Checks if the scores are within the limit
+	///
+	/// # Arguments
+	///
+	/// * `home_score` - the new score of the home team
+	/// * `away_score` - the new score of the away team
+	///
+	/// # Errors
+	///
+	/// * When any of the scores is over the limit
+	///
+	pub(crate) fn check_max_score(&self, home_score: u8, away_score: u8) -> Result<(), ScoreBoardError> {
+		match self.max_score {
+			Some(max_score) if home_score.max(away_score) > max_score => {
+				warn!("Score {} is above the maximum of {}", home_score.max(away_score), max_score);
+				Err(ScoreBoardError::ImplausibleScore { score: home_score.max(away_score), max_score })
+			},
+			_ => Ok(()),
+		}
+	}
+
+	/// Checks if one more game can be started
+	///
+	/// # Errors
+	///
+	/// * When the score board already has as many running games as the limit allows
+	///
+	pub(crate) fn check_max_games(&self) -> Result<(), ScoreBoardError> {
+		match self.max_games {
+			Some(max_games) if self.data.len() >= max_games => {
+				warn!("The score board cannot have more than {} games at the same time", max_games);
+				Err(ScoreBoardError::TooManyGames(max_games))
+			},
+			_ => Ok(()),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::ToString;
+
+	use crate::goal::{GoalKind, Side};
+
+	#[test]
+	fn games_over_the_limit_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.set_max_games(2);
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+
+		let result = sb.start_game("Germany", "France");
+		assert_eq!(result, Err(ScoreBoardError::TooManyGames(2)));
+		assert_eq!(sb.get_summary().len(), 2);
+		assert_eq!(sb.get_events().len(), 2);
+	}
+
+	#[test]
+	fn finished_game_frees_a_place() {
+		let mut sb = ScoreBoard::new();
+		sb.set_max_games(1);
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+	}
+
+	#[test]
+	fn lower_limit_keeps_the_running_games() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.set_max_games(1);
+
+		assert_eq!(sb.get_summary().len(), 2);
+		assert!(sb.start_game("Germany", "France").err().is_some_and(|result| result.to_string() == "The score board cannot have more than 1 games at the same time"));
+
+		sb.clear_max_games();
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		assert_eq!(sb.get_summary().len(), 3);
+	}
+
+	#[test]
+	fn scores_over_the_limit_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 2, "Brazil", DEFAULT_MAX_SCORE).expect("Couldn't update the game");
+
+		let result = sb.update_score("Spain", 2, "Brazil", DEFAULT_MAX_SCORE + 1);
+		assert_eq!(result, Err(ScoreBoardError::ImplausibleScore { score: DEFAULT_MAX_SCORE + 1, max_score: DEFAULT_MAX_SCORE }));
+		assert_eq!(sb.get_summary(), vec![format!("Spain 2 - Brazil {}", DEFAULT_MAX_SCORE)]);
+		assert_eq!(sb.get_events().len(), 2);
+	}
+
+	#[test]
+	fn goals_over_the_limit_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.set_max_score(1);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.record_goal("Spain", "Brazil", Side::Home, GoalKind::OpenPlay).expect("Couldn't record the first goal");
+
+		let result = sb.record_goal("Spain", "Brazil", Side::Home, GoalKind::Penalty);
+		assert!(result.err().is_some_and(|result| result.to_string() == "Score 2 is above the maximum of 1"));
+
+		sb.clear_max_score();
+		sb.update_score("Spain", 255, "Brazil", 0).expect("Couldn't update the game without the limit");
+	}
+}
@@ -0,0 +1,199 @@
+//! # Locales
+//!
+//! Regional conventions of the summaries, like the dash between the teams and the digits of the scores, so boards in different regions read naturally without reworking their output
+
+use std::string::{String, ToString};
+
+use log::trace;
+
+use crate::archive::{ArchivedGame, Decision};
+use crate::{Game, ScoreBoard};
+
+/// The digits the numbers are written with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Numerals {
+	/// The Western Arabic digits, ex. "2"
+	#[default]
+	Western,
+	/// The Eastern Arabic digits, used in the Arab world, ex. "٢"
+	EasternArabic,
+	/// The Persian variant of the Eastern Arabic digits, used in Iran and Afghanistan, ex. "۲"
+	Persian,
+}
+
+impl Numerals {
+	/// Rewrites the Western digits of a text with these digits. Other characters stay as they are
+	///
+	/// # Arguments
+	///
+	/// * `text` - the text to rewrite, ex. "78'"
+	///
+	pub(crate) fn localize(&self, text: &str) -> String {
+		let zero = match self {
+			Numerals::Western => return text.to_string(),
+			Numerals::EasternArabic => 0x0660,
+			Numerals::Persian => 0x06F0,
+		};
+
+		text.chars()
+			.map(|c| match c.to_digit(10) {
+				Some(digit) if c.is_ascii_digit() => char::from_u32(zero + digit).unwrap_or(c),
+				_ => c,
+			})
+			.collect()
+	}
+}
+
+/// Regional conventions of the summaries. The default locale gives the summaries of `ScoreBoard::new()`
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{Locale, Numerals};
+///
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.set_locale(Locale { dash: String::from(" – "), numerals: Numerals::EasternArabic, ..Locale::default() });
+/// sb.start_game("Saudi Arabia", "Argentina").unwrap();
+/// sb.update_score("Saudi Arabia", 2, "Argentina", 1).unwrap();
+/// assert_eq!(sb.get_summary(), vec![String::from("Saudi Arabia ٢ – Argentina ١")]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locale {
+	/// The text between the home and the away team of a running game, as in `ScoreBoard::get_summary()`, ex. "Japan 2 - Indonesia 0"
+	pub dash: String,
+	/// The text between the scores of a finished game, as in `ScoreBoard::get_archive_summary()`, ex. "Spain 2 - 1 Italy"
+	pub score_separator: String,
+	/// The digits of the scores and the minutes
+	pub numerals: Numerals,
+}
+
+impl Default for Locale {
+	/// Implementation of `Default` trait, with the conventions of `ScoreBoard::new()`
+	fn default() -> Locale {
+		Locale { dash: String::from(" - "), score_separator: String::from(" - "), numerals: Numerals::default() }
+	}
+}
+
+impl Locale {
+	/// Renders the summary line of a running game, ex. "Japan 2 - Indonesia 0"
+	///
+	/// # Arguments
+	///
+	/// * `game` - the rendered game
+	///
+	pub(crate) fn render_game(&self, game: &Game) -> String {
+		format!("{} {}{}{} {}",
+			game.home_team.name, self.numerals.localize(&game.home_team.score.to_string()),
+			self.dash,
+			game.away_team.name, self.numerals.localize(&game.away_team.score.to_string()))
+	}
+
+	/// Renders the result of a finished game, ex. "Spain 2 - 1 Italy (a.e.t.)"
+	///
+	/// # Arguments
+	///
+	/// * `game` - the rendered game
+	///
+	pub(crate) fn render_result(&self, game: &ArchivedGame) -> String {
+		let result = format!("{} {}{}{} {}",
+			game.home_team, self.numerals.localize(&game.home_score.to_string()),
+			self.score_separator,
+			self.numerals.localize(&game.away_score.to_string()), game.away_team);
+
+		match game.decision {
+			Decision::RegularTime => result,
+			decision => format!("{} ({})", result, decision),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Sets the regional conventions of the summaries: `get_summary()` and the other summaries of the running games, `get_summary_with_minutes()` and `get_archive_summary()`. The numerals also apply to the scores and the minutes of the template set with `set_summary_template()`
+	///
+	/// # Arguments
+	///
+	/// * `locale` - the conventions of the summaries
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Locale, Numerals};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_locale(Locale { score_separator: String::from(":"), numerals: Numerals::Persian, ..Locale::default() });
+	/// sb.start_game("Iran", "Wales").unwrap();
+	/// sb.update_score("Iran", 2, "Wales", 0).unwrap();
+	/// sb.finish_game("Iran", "Wales").unwrap();
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Iran ۲:۰ Wales")]);
+	/// ```
+	pub fn set_locale(&mut self, locale: Locale) {
+		trace!("Rendering the summaries with {:?}", locale);
+
+		self.locale = locale;
+		self.invalidate_summary();
+	}
+
+	/// Provides the regional conventions of the summaries
+	pub fn get_locale(&self) -> &Locale {
+		&self.locale
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::vec::Vec;
+
+	use crate::period::Period;
+	use crate::state::GameState;
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Morocco", "Spain").expect("Couldn't create the game");
+		sb.update_score("Morocco", 10, "Spain", 3).expect("Couldn't update the game");
+		sb
+	}
+
+	#[test]
+	fn default_locale_keeps_the_summaries() {
+		let mut sb = get_board();
+		let summary = sb.get_summary();
+		sb.set_locale(Locale::default());
+
+		assert_eq!(sb.get_summary(), summary);
+		assert_eq!(sb.get_locale(), &Locale::default());
+		assert_eq!(Numerals::default().localize("90+4'"), "90+4'");
+	}
+
+	#[test]
+	fn all_the_digits_are_localized() {
+		assert_eq!(Numerals::EasternArabic.localize("0123456789"), "٠١٢٣٤٥٦٧٨٩");
+		assert_eq!(Numerals::Persian.localize("0123456789"), "۰۱۲۳۴۵۶۷۸۹");
+	}
+
+	#[test]
+	fn locale_is_applied_to_every_summary() {
+		let mut sb = get_board();
+		sb.set_locale(Locale { dash: String::from(" — "), score_separator: String::from("–"), numerals: Numerals::EasternArabic });
+
+		assert_eq!(sb.get_summary(), vec![String::from("Morocco ١٠ — Spain ٣")]);
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Morocco ١٠ — Spain ٣ (١')")]);
+		let mut output = Vec::new();
+		sb.write_summary(&mut output).expect("Couldn't write the summary");
+		assert_eq!(String::from_utf8(output).expect("Couldn't read the summary"), "Morocco ١٠ — Spain ٣\n");
+
+		sb.set_game_state("Morocco", "Spain", GameState::Paused).expect("Couldn't pause the game");
+		sb.start_period("Morocco", "Spain", Period::SecondHalf).expect("Couldn't start the second half");
+		sb.set_summary_template("{home} {home_score} {clock}").expect("Couldn't set the template");
+		assert_eq!(sb.get_summary(), vec![String::from("Morocco ١٠ HT")]);
+
+		sb.set_game_state("Morocco", "Spain", GameState::Live).expect("Couldn't resume the game");
+		sb.finish_game_after("Morocco", "Spain", Decision::AfterExtraTime).expect("Couldn't finish the game");
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Morocco ١٠–٣ Spain (a.e.t.)")]);
+	}
+}
@@ -0,0 +1,311 @@
+//! # Board manager
+//!
+//! Hosts several independent score boards, one per competition, so a single service instance can follow many tournaments at once
+
+use std::collections::BTreeMap;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// A collection of independent score boards, keyed by competition ID (ex. "World Cup", "U-20 World Cup", "Women's World Cup")
+pub struct BoardManager {
+	/// Score boards of all the registered competitions. `BTreeMap` keeps the competitions ordered by their IDs, which keeps the combined summary stable
+	boards: BTreeMap<String, ScoreBoard>,
+}
+
+impl BoardManager {
+	/// Returns a newly created manager, without any competitions
+	pub fn new() -> BoardManager {
+		BoardManager { boards: BTreeMap::new() }
+	}
+
+	/// Registers a new competition with its own, empty score board
+	///
+	/// # Arguments
+	///
+	/// * `competition` - ID of the competition. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When a competition with the same ID is already registered
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut manager = scoreboard_world_cup::BoardManager::new();
+	/// manager.add_competition("World Cup").unwrap();
+	/// assert_eq!(manager.get_competitions(), vec![String::from("World Cup")]);
+	/// ```
+	pub fn add_competition<T: ToString>(&mut self, competition: T) -> Result<(), ScoreBoardError> {
+		let competition_id = competition.to_string();
+
+		operation_span!(tracing::Level::INFO, "add_competition", competition = %competition_id);
+
+		trace!("Adding competition '{}'", competition_id);
+
+		if self.boards.contains_key(&competition_id) {
+			warn!("Competition {} is already registered", competition_id);
+			return Err(ScoreBoardError::CompetitionAlreadyRegistered(competition_id));
+		}
+
+		self.boards.insert(competition_id, ScoreBoard::new());
+
+		trace!("Competition added");
+
+		Ok(())
+	}
+
+	/// Unregisters a competition and hands its score board back to the caller
+	///
+	/// # Arguments
+	///
+	/// * `competition` - ID of the competition
+	///
+	/// # Returns
+	///
+	/// * The score board of the removed competition, with all its games
+	///
+	/// # Errors
+	///
+	/// * When there is no competition with the given ID
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut manager = scoreboard_world_cup::BoardManager::new();
+	/// manager.add_competition("World Cup").unwrap();
+	/// let board = manager.remove_competition("World Cup").unwrap();
+	/// assert!(board.get_summary().is_empty());
+	/// assert!(manager.get_competitions().is_empty());
+	/// ```
+	pub fn remove_competition(&mut self, competition: &str) -> Result<ScoreBoard, ScoreBoardError> {
+		operation_span!(tracing::Level::INFO, "remove_competition", competition = %competition);
+
+		trace!("Removing competition '{}'", competition);
+
+		match self.boards.remove(competition) {
+			Some(board) => {
+				trace!("Competition removed");
+				Ok(board)
+			},
+			None => {
+				warn!("Couldn't find competition {}", competition);
+				Err(ScoreBoardError::CompetitionNotFound(competition.to_string()))
+			},
+		}
+	}
+
+	/// Provides read access to the score board of a competition
+	///
+	/// # Arguments
+	///
+	/// * `competition` - ID of the competition
+	///
+	/// # Returns
+	///
+	/// * The score board, or `None` if there is no competition with the given ID
+	pub fn get_board(&self, competition: &str) -> Option<&ScoreBoard> {
+		self.boards.get(competition)
+	}
+
+	/// Provides write access to the score board of a competition, so its games can be started, updated and finished
+	///
+	/// # Arguments
+	///
+	/// * `competition` - ID of the competition
+	///
+	/// # Returns
+	///
+	/// * The score board, or `None` if there is no competition with the given ID
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut manager = scoreboard_world_cup::BoardManager::new();
+	/// manager.add_competition("World Cup").unwrap();
+	/// manager.get_board_mut("World Cup").unwrap().start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(manager.get_board("World Cup").unwrap().get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// ```
+	pub fn get_board_mut(&mut self, competition: &str) -> Option<&mut ScoreBoard> {
+		self.boards.get_mut(competition)
+	}
+
+	/// Lists IDs of all the registered competitions, in alphabetical order
+	pub fn get_competitions(&self) -> Vec<String> {
+		self.boards.keys().cloned().collect()
+	}
+
+	/// Provides a combined summary of all the competitions. Competitions are listed in alphabetical order of their IDs, and the games of every competition are ordered the same way as in `ScoreBoard::get_summary()`
+	///
+	/// # Returns
+	///
+	/// * A vector of strings, each string containing the competition ID followed by the home team, its score, the away team and its score
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut expected_result: Vec<String> = Vec::new();
+	/// expected_result.push(String::from("U-20 World Cup: Brazil 0 - Chile 0"));
+	/// expected_result.push(String::from("World Cup: Japan 0 - Indonesia 0"));
+	///
+	/// let mut manager = scoreboard_world_cup::BoardManager::new();
+	/// manager.add_competition("World Cup").unwrap();
+	/// manager.add_competition("U-20 World Cup").unwrap();
+	/// manager.get_board_mut("World Cup").unwrap().start_game("Japan", "Indonesia").unwrap();
+	/// manager.get_board_mut("U-20 World Cup").unwrap().start_game("Brazil", "Chile").unwrap();
+	/// assert_eq!(manager.get_summary(), expected_result);
+	/// ```
+	pub fn get_summary(&self) -> Vec<String> {
+		trace!("Getting the combined summary of all competitions");
+
+		let mut result = Vec::new();
+
+		for (competition, board) in &self.boards {
+			for game in board.get_summary() {
+				result.push(format!("{}: {}", competition, game));
+			}
+		}
+
+		result
+	}
+}
+
+impl Default for BoardManager {
+	/// Returns a newly created manager, without any competitions
+	fn default() -> Self {
+		BoardManager::new()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const WORLD_CUP: &str = "World Cup";
+	const U20_WORLD_CUP: &str = "U-20 World Cup";
+	const WOMENS_WORLD_CUP: &str = "Women's World Cup";
+
+	const NOTHING_TO_SHOW: Vec<String> = Vec::new();
+
+	#[test]
+	fn manager_is_empty_at_start() {
+		let manager = BoardManager::new();
+
+		assert!(manager.boards.is_empty());
+		assert_eq!(manager.get_summary(), NOTHING_TO_SHOW);
+	}
+
+	#[test]
+	fn competition_added_correctly() {
+		let mut manager = BoardManager::new();
+		let result = manager.add_competition(WORLD_CUP);
+
+		assert!(result.is_ok());
+		assert_eq!(manager.get_competitions(), vec![WORLD_CUP]);
+		assert!(manager.get_board(WORLD_CUP).is_some());
+	}
+
+	#[test]
+	fn competition_cannot_be_added_twice() {
+		let expected_error_message = format!("Competition {} is already registered", WORLD_CUP);
+
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the competition");
+		manager.get_board_mut(WORLD_CUP).unwrap().start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		let result = manager.add_competition(WORLD_CUP);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == expected_error_message));
+		assert_eq!(manager.get_summary(), vec![String::from("World Cup: Japan 0 - Indonesia 0")]);
+	}
+
+	#[test]
+	fn removing_a_competition_returns_its_board() {
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the competition");
+		manager.get_board_mut(WORLD_CUP).unwrap().start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		let result = manager.remove_competition(WORLD_CUP);
+
+		assert!(result.is_ok_and(|board| board.get_summary() == vec![String::from("Japan 0 - Indonesia 0")]));
+		assert!(manager.get_board(WORLD_CUP).is_none());
+		assert_eq!(manager.get_summary(), NOTHING_TO_SHOW);
+	}
+
+	#[test]
+	fn removing_an_unknown_competition_is_an_error() {
+		let expected_error_message = format!("Couldn't find competition {}", WORLD_CUP);
+
+		let mut manager = BoardManager::new();
+		let result = manager.remove_competition(WORLD_CUP);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == expected_error_message));
+	}
+
+	#[test]
+	fn unknown_competition_has_no_board() {
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the competition");
+
+		assert!(manager.get_board(U20_WORLD_CUP).is_none());
+		assert!(manager.get_board_mut(U20_WORLD_CUP).is_none());
+	}
+
+	#[test]
+	fn the_same_team_can_play_in_different_competitions() {
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the first competition");
+		manager.add_competition(U20_WORLD_CUP).expect("Couldn't add the second competition");
+		let result_1 = manager.get_board_mut(WORLD_CUP).unwrap().start_game("Brazil", "Chile");
+		let result_2 = manager.get_board_mut(U20_WORLD_CUP).unwrap().start_game("Brazil", "Chile");
+
+		assert!(result_1.is_ok());
+		assert!(result_2.is_ok());
+	}
+
+	#[test]
+	fn boards_of_competitions_are_independent() {
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the first competition");
+		manager.add_competition(U20_WORLD_CUP).expect("Couldn't add the second competition");
+		manager.get_board_mut(WORLD_CUP).unwrap().start_game("Brazil", "Chile").expect("Couldn't create the first game");
+		manager.get_board_mut(U20_WORLD_CUP).unwrap().start_game("Brazil", "Chile").expect("Couldn't create the second game");
+		manager.get_board_mut(WORLD_CUP).unwrap().update_score("Brazil", 2, "Chile", 0).expect("Couldn't update the first game");
+		manager.get_board_mut(U20_WORLD_CUP).unwrap().finish_game("Brazil", "Chile").expect("Couldn't finish the second game");
+
+		assert_eq!(manager.get_board(WORLD_CUP).unwrap().get_summary(), vec![String::from("Brazil 2 - Chile 0")]);
+		assert_eq!(manager.get_board(U20_WORLD_CUP).unwrap().get_summary(), NOTHING_TO_SHOW);
+	}
+
+	#[test]
+	fn combined_summary_lists_competitions_alphabetically() {
+		let expected_summary = vec![
+			String::from("U-20 World Cup: Argentina 1 - Uruguay 0"),
+			String::from("Women's World Cup: Spain 0 - England 0"),
+			String::from("World Cup: Mexico 0 - Canada 5"),
+			String::from("World Cup: Germany 2 - France 2"),
+		];
+
+		let mut manager = BoardManager::new();
+		manager.add_competition(WORLD_CUP).expect("Couldn't add the first competition");
+		manager.add_competition(WOMENS_WORLD_CUP).expect("Couldn't add the second competition");
+		manager.add_competition(U20_WORLD_CUP).expect("Couldn't add the third competition");
+		let world_cup = manager.get_board_mut(WORLD_CUP).unwrap();
+		world_cup.start_game("Germany", "France").expect("Couldn't create the first game");
+		world_cup.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+		world_cup.update_score("Germany", 2, "France", 2).expect("Couldn't update the first game");
+		world_cup.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the second game");
+		manager.get_board_mut(WOMENS_WORLD_CUP).unwrap().start_game("Spain", "England").expect("Couldn't create the third game");
+		let u20_world_cup = manager.get_board_mut(U20_WORLD_CUP).unwrap();
+		u20_world_cup.start_game("Argentina", "Uruguay").expect("Couldn't create the fourth game");
+		u20_world_cup.update_score("Argentina", 1, "Uruguay", 0).expect("Couldn't update the fourth game");
+
+		assert_eq!(manager.get_summary(), expected_summary);
+	}
+}
@@ -0,0 +1,160 @@
+//! # LED matrix displays
+//!
+//! Draws the top games of the score board on any `embedded-graphics` draw target, so the crate can drive small monochrome or RGB matrices of the physical score boards
+
+use std::string::String;
+
+use embedded_graphics::mono_font::ascii::FONT_4X6;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::prelude::{DrawTarget, Point};
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::Drawable;
+use log::trace;
+
+use crate::country::get_team_code;
+use crate::{Game, ScoreBoard};
+
+impl Game {
+	/// Provides the line of the game for a matrix display, with the three letter codes of the teams, ex. "ESP 10-2 BRA"
+	fn get_matrix_line(&self) -> String {
+		format!("{} {}-{} {}", get_team_code(&self.home_team.name), self.home_team.score, self.away_team.score, get_team_code(&self.away_team.name))
+	}
+}
+
+impl ScoreBoard {
+	/// Draws the top games of the board on a display, one line per game in the order of the summary, ex. "ESP 10-2 BRA". The teams are shown with their FIFA codes, or the first three letters of the names that aren't known countries. The lines use a 4x6 pixels font, so a 64x32 matrix shows 5 games of up to 16 characters, and the lines are cut at the right edge of the display. Only the text is drawn, so the display should be cleared first
+	///
+	/// # Arguments
+	///
+	/// * `target` - the display, or any other `embedded-graphics` draw target
+	/// * `max_games` - the most games to draw. Fewer are drawn when the board has fewer games or they don't fit the height of the display
+	/// * `color` - color of the text
+	///
+	/// # Returns
+	///
+	/// * Number of the drawn games
+	///
+	/// # Errors
+	///
+	/// * The error of the draw target
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use embedded_graphics::mock_display::MockDisplay;
+	/// use embedded_graphics::pixelcolor::BinaryColor;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// let mut display = MockDisplay::<BinaryColor>::new();
+	/// assert_eq!(sb.draw_games(&mut display, 3, BinaryColor::On), Ok(1));
+	/// ```
+	pub fn draw_games<D: DrawTarget>(&self, target: &mut D, max_games: usize, color: D::Color) -> Result<usize, D::Error> {
+		trace!("Drawing up to {} games", max_games);
+
+		let style = MonoTextStyle::new(&FONT_4X6, color);
+		let area = target.bounding_box();
+		let line_height = FONT_4X6.character_size.height;
+		let rows = usize::try_from(area.size.height / line_height).unwrap_or(usize::MAX);
+		let columns = usize::try_from(area.size.width / FONT_4X6.character_size.width).unwrap_or(usize::MAX);
+
+		let mut position = area.top_left;
+		let mut drawn = 0;
+
+		for game in self.data.iter().take(max_games.min(rows)) {
+			let line: String = game.get_matrix_line().chars().take(columns).collect();
+			Text::with_baseline(&line, position, style, Baseline::Top).draw(target)?;
+
+			position += Point::new(0, i32::try_from(line_height).unwrap_or(i32::MAX));
+			drawn += 1;
+		}
+
+		Ok(drawn)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use embedded_graphics::draw_target::DrawTargetExt;
+	use embedded_graphics::mock_display::MockDisplay;
+	use embedded_graphics::pixelcolor::{BinaryColor, Rgb565};
+	use embedded_graphics::prelude::{RgbColor, Size};
+	use embedded_graphics::primitives::Rectangle;
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		sb.update_score("Germany", 2, "France", 2).expect("Couldn't update the third game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		sb
+	}
+
+	/// Draws the expected lines, one below another
+	fn get_expected(lines: &[&str]) -> MockDisplay<BinaryColor> {
+		let mut display = MockDisplay::new();
+		let style = MonoTextStyle::new(&FONT_4X6, BinaryColor::On);
+
+		for (index, line) in (0..).zip(lines) {
+			Text::with_baseline(line, Point::new(0, index * 6), style, Baseline::Top).draw(&mut display).expect("Couldn't draw the expected line");
+		}
+
+		display
+	}
+
+	#[test]
+	fn games_are_drawn_in_the_summary_order() {
+		let sb = get_board();
+		let mut display = MockDisplay::new();
+
+		assert_eq!(sb.draw_games(&mut display, 5, BinaryColor::On), Ok(3));
+		display.assert_eq(&get_expected(&["ESP 10-2 BRA", "GER 2-2 FRA", "MEX 0-0 CAN"]));
+	}
+
+	#[test]
+	fn only_the_top_games_are_drawn() {
+		let sb = get_board();
+		let mut display = MockDisplay::new();
+
+		assert_eq!(sb.draw_games(&mut display, 1, BinaryColor::On), Ok(1));
+		display.assert_eq(&get_expected(&["ESP 10-2 BRA"]));
+	}
+
+	#[test]
+	fn games_below_the_display_are_not_drawn() {
+		let sb = get_board();
+		let mut display = MockDisplay::new();
+		let mut matrix = display.cropped(&Rectangle::new(Point::zero(), Size::new(64, 13)));
+
+		assert_eq!(sb.draw_games(&mut matrix, 5, BinaryColor::On), Ok(2));
+		// The glyphs leave the bottom row of the line empty
+		assert_eq!(display.affected_area().size.height, 11);
+	}
+
+	#[test]
+	fn long_lines_are_cut_at_the_edge() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let mut display = MockDisplay::new();
+		let mut matrix = display.cropped(&Rectangle::new(Point::zero(), Size::new(32, 6)));
+
+		assert_eq!(sb.draw_games(&mut matrix, 5, BinaryColor::On), Ok(1));
+		display.assert_eq(&get_expected(&["ESP 0-0 "]));
+	}
+
+	#[test]
+	fn color_displays_are_supported() {
+		let sb = get_board();
+		let mut display = MockDisplay::<Rgb565>::new();
+
+		assert_eq!(sb.draw_games(&mut display, 5, Rgb565::GREEN), Ok(3));
+		assert_eq!(display.affected_area().size.height, 17);
+	}
+}
@@ -0,0 +1,372 @@
+//! # Match minutes
+//!
+//! Keeps the playing time of the running games, without the pauses, so the summary can show the official minute of every match, with the added time, for ticker displays
+
+use std::string::String;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+
+use crate::period::Period;
+use crate::state::GameState;
+use crate::{Game, ScoreBoard};
+
+/// Length of a half of a match on a new score board, see `ScoreBoard::set_half_length()`
+pub const DEFAULT_HALF_LENGTH: Duration = Duration::from_secs(45 * 60);
+
+/// Length of the extra time, both of its halves, in minutes
+const EXTRA_TIME_MINUTES: u64 = 30;
+
+/// Playing time of a match
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MatchClock {
+	/// Start of the current period
+	period_start: Instant,
+	/// Time spent paused in the current period, without the ongoing pause
+	paused_time: Duration,
+	/// Start of the ongoing pause, if the match is paused
+	paused_since: Option<Instant>,
+}
+
+impl MatchClock {
+	/// Returns a running clock of a period started at the given time
+	pub(crate) fn new(period_start: Instant) -> MatchClock {
+		MatchClock { period_start, paused_time: Duration::ZERO, paused_since: None }
+	}
+
+	/// Stops the clock, at the start of a pause
+	pub(crate) fn pause(&mut self, now: Instant) {
+		self.paused_since.get_or_insert(now);
+	}
+
+	/// Starts the clock again, at the end of a pause
+	pub(crate) fn resume(&mut self, now: Instant) {
+		if let Some(paused_since) = self.paused_since.take() {
+			self.paused_time += now.saturating_duration_since(paused_since);
+		}
+	}
+
+	/// Restarts the clock for a new period. The ongoing pause, if any, continues
+	pub(crate) fn start_period(&mut self, now: Instant) {
+		self.period_start = now;
+		self.paused_time = Duration::ZERO;
+		self.paused_since = self.paused_since.map(|_| now);
+	}
+
+	/// Checks if the current period was entered during a pause and hasn't been played yet, ex. the second half during the half-time break
+	fn is_waiting_for_period(&self) -> bool {
+		self.paused_since == Some(self.period_start) && self.paused_time.is_zero()
+	}
+
+	/// Provides the playing time of the current period
+	fn get_playing_time(&self, now: Instant) -> Duration {
+		let ongoing_pause = self.paused_since.map_or(Duration::ZERO, |paused_since| now.saturating_duration_since(paused_since));
+
+		now.saturating_duration_since(self.period_start).saturating_sub(self.paused_time).saturating_sub(ongoing_pause)
+	}
+}
+
+impl Game {
+	/// Provides the minute of the match as shown on the tickers: "HT" in the break after the first half, "FT" in the break after the second half, and the current minute otherwise, ex. "78'". A pause is the break when the length of the period was played, or when the next period was started during it. Other pauses, ex. for an injury, show the minute the clock stopped at. The minutes played over the length of a period are shown as the added time, ex. "45+3'" or "120+1'"
+	///
+	/// # Arguments
+	///
+	/// * `now` - the current time
+	/// * `half_minutes` - length of a half, in minutes
+	///
+	pub(crate) fn get_minute(&self, now: Instant, half_minutes: u64) -> String {
+		let period = self.get_period();
+		let last_minute = match period {
+			Period::FirstHalf => half_minutes,
+			Period::SecondHalf => 2 * half_minutes,
+			Period::ExtraTime => 2 * half_minutes + EXTRA_TIME_MINUTES,
+		};
+
+		let minute = self.get_minute_number(now, half_minutes);
+
+		if self.is_half_time_break(now, half_minutes) {
+			return String::from("HT");
+		}
+
+		match (self.state, period) {
+			(GameState::Paused, Period::ExtraTime) if self.clock.is_waiting_for_period() => String::from("FT"),
+			(GameState::Paused, Period::SecondHalf) if minute > last_minute => String::from("FT"),
+			_ if minute > last_minute => format!("{}+{}'", last_minute, minute - last_minute),
+			_ => format!("{}'", minute),
+		}
+	}
+
+	/// Checks if the match is in the break after the first half: paused after the length of the first half was played, or paused when the second half was started
+	///
+	/// # Arguments
+	///
+	/// * `now` - the current time
+	/// * `half_minutes` - length of a half, in minutes
+	///
+	pub(crate) fn is_half_time_break(&self, now: Instant, half_minutes: u64) -> bool {
+		self.state == GameState::Paused && match self.get_period() {
+			Period::FirstHalf => self.get_minute_number(now, half_minutes) > half_minutes,
+			Period::SecondHalf => self.clock.is_waiting_for_period(),
+			Period::ExtraTime => false,
+		}
+	}
+
+	/// Provides the current minute of the match as a number, also in the breaks and the added time, ex. 78. The minutes of the second half start right after the first half, ex. from 46, and of the extra time right after the second half, ex. from 91
+	///
+	/// # Arguments
+	///
+	/// * `now` - the current time
+	/// * `half_minutes` - length of a half, in minutes
+	///
+	pub(crate) fn get_minute_number(&self, now: Instant, half_minutes: u64) -> u64 {
+		let elapsed_minutes = self.clock.get_playing_time(now).as_secs() / 60;
+		let first_minute = match self.get_period() {
+			Period::FirstHalf => 1,
+			Period::SecondHalf => half_minutes + 1,
+			Period::ExtraTime => 2 * half_minutes + 1,
+		};
+
+		first_minute + elapsed_minutes
+	}
+}
+
+impl ScoreBoard {
+	/// Sets the length of a half of every match, 45 minutes by default. The extra time is always two halves of 15 minutes. The minutes restart after the length of the previous periods when a period begins, and the minutes played over the length of a period are shown as the added time, ex. "45+3'"
+	///
+	/// # Arguments
+	///
+	/// * `length` - length of a half, in whole minutes
+	///
+	/// # Errors
+	///
+	/// * When the length is shorter than a minute
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::Duration;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_half_length(Duration::from_secs(40 * 60)).unwrap();
+	/// assert_eq!(sb.get_half_length(), Duration::from_secs(40 * 60));
+	/// assert!(sb.set_half_length(Duration::from_secs(30)).is_err());
+	/// ```
+	pub fn set_half_length(&mut self, length: Duration) -> Result<(), ScoreBoardError> {
+		trace!("Setting the half length to {:?}", length);
+
+		if length.as_secs() < 60 {
+			warn!("A half can't be shorter than a minute");
+			return Err(ScoreBoardError::InvalidHalfLength(length));
+		}
+
+		self.half_length = Duration::from_secs(length.as_secs() / 60 * 60);
+		self.invalidate_summary();
+
+		Ok(())
+	}
+
+	/// Provides the length of a half of every match, see `set_half_length()`
+	pub fn get_half_length(&self) -> Duration {
+		self.half_length
+	}
+
+	/// Provides the length of a half in minutes
+	pub(crate) fn get_half_minutes(&self) -> u64 {
+		self.half_length.as_secs() / 60
+	}
+
+	/// Provides the official minute of a running match, ex. "78'", or "45+3'" in the added time, see `get_summary_with_minutes()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name or alias of the home team
+	/// * `away` - Name or alias of the away team
+	///
+	/// # Returns
+	///
+	/// * The minute of the match, or `None` if there is no active match between the given teams
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Germany", "France").unwrap();
+	/// assert_eq!(sb.get_match_minute("Germany", "France"), Some(String::from("1'")));
+	/// assert_eq!(sb.get_match_minute("Germany", "Spain"), None);
+	/// ```
+	pub fn get_match_minute(&self, home: &str, away: &str) -> Option<String> {
+		let now = Instant::now();
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		self.find_game_index(&home_name, &away_name).map(|game_index| self.data[game_index].get_minute(now, self.get_half_minutes()))
+	}
+
+	/// Provides the summary of the running games, like `get_summary()`, with the minute of every match appended, ex. "Germany 2 - France 2 (78')". A match in the break after the first half shows "HT" and after the second half "FT", while a pause within a half, ex. for an injury, keeps showing the minute. The minutes come from the start time of the match, without the pauses (see `set_game_state()`), and restart from 46 and 91 when the second half and the extra time begin (see `start_period()`). The minutes played over the length of a period are shown as the added time, ex. "45+3'" (see `set_half_length()`)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{GameState, Period};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Germany", "France").unwrap();
+	/// assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 0 - France 0 (1')")]);
+	/// sb.set_game_state("Germany", "France", GameState::Paused).unwrap();
+	/// assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 0 - France 0 (1')")]);
+	/// sb.start_period("Germany", "France", Period::SecondHalf).unwrap();
+	/// assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 0 - France 0 (HT)")]);
+	/// ```
+	pub fn get_summary_with_minutes(&self) -> Vec<String> {
+		trace!("Getting the score board summary with minutes");
+
+		let now = Instant::now();
+
+		self.data.iter().map(|game| format!("{} ({})", self.locale.render_game(game), self.locale.numerals.localize(&game.get_minute(now, self.get_half_minutes())))).collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HOME_TEAM_NAME: &str = "Germany";
+	const AWAY_TEAM_NAME: &str = "France";
+
+	const MINUTE: Duration = Duration::from_secs(60);
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 2, AWAY_TEAM_NAME, 2).expect("Couldn't update the game");
+		sb
+	}
+
+	fn get_time(start: Instant, minutes: u32) -> Instant {
+		start + MINUTE * minutes
+	}
+
+	#[test]
+	fn minute_counts_from_the_kickoff() {
+		let sb = get_board();
+		let game = &sb.data[0];
+		let kickoff = game.clock.period_start;
+
+		assert_eq!(game.get_minute(kickoff, 45), "1'");
+		assert_eq!(game.get_minute(get_time(kickoff, 44) + Duration::from_secs(59), 45), "45'");
+	}
+
+	#[test]
+	fn pauses_are_not_counted() {
+		let mut sb = get_board();
+		let kickoff = sb.data[0].clock.period_start;
+		let game = &mut sb.data[0];
+		game.clock.pause(get_time(kickoff, 30));
+		game.clock.resume(get_time(kickoff, 40));
+
+		assert_eq!(game.get_minute(get_time(kickoff, 50), 45), "41'");
+	}
+
+	#[test]
+	fn half_time_and_full_time_are_shown_in_the_breaks() {
+		let mut sb = get_board();
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf).expect("Couldn't start the second half");
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 2 - France 2 (HT)")]);
+
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Live).expect("Couldn't resume the game");
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 2 - France 2 (46')")]);
+
+		let start = sb.data[0].clock.period_start;
+		sb.data[0].state = GameState::Paused;
+		sb.data[0].clock.pause(get_time(start, 47));
+		assert_eq!(sb.data[0].get_minute(get_time(start, 55), 45), "FT");
+	}
+
+	#[test]
+	fn pause_within_a_half_keeps_the_minute() {
+		let mut sb = get_board();
+		let kickoff = sb.data[0].clock.period_start;
+		sb.data[0].state = GameState::Paused;
+		sb.data[0].clock.pause(get_time(kickoff, 20));
+		assert_eq!(sb.data[0].get_minute(get_time(kickoff, 25), 45), "21'");
+
+		sb.data[0].clock.resume(get_time(kickoff, 25));
+		sb.data[0].clock.pause(get_time(kickoff, 72));
+		assert_eq!(sb.data[0].get_minute(get_time(kickoff, 80), 45), "HT");
+	}
+
+	#[test]
+	fn minute_follows_the_aliases() {
+		let mut sb = get_board();
+		sb.add_alias("GER", HOME_TEAM_NAME);
+
+		assert_eq!(sb.get_match_minute("GER", AWAY_TEAM_NAME), Some(String::from("1'")));
+	}
+
+	#[test]
+	fn extra_time_counts_from_91() {
+		let mut sb = get_board();
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::ExtraTime).expect("Couldn't start the extra time");
+		let start = sb.data[0].clock.period_start;
+
+		assert_eq!(sb.data[0].get_minute(get_time(start, 14), 45), "105'");
+	}
+
+	#[test]
+	fn pause_of_the_extra_time_keeps_the_minute() {
+		let mut sb = get_board();
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::ExtraTime).expect("Couldn't start the extra time");
+		let start = sb.data[0].clock.period_start;
+		sb.data[0].state = GameState::Paused;
+		sb.data[0].clock.pause(get_time(start, 15));
+
+		assert_eq!(sb.data[0].get_minute(get_time(start, 20), 45), "106'");
+	}
+
+	#[test]
+	fn restored_game_keeps_its_minute() {
+		let mut snapshot = get_board().snapshot();
+		snapshot.games[0].kickoff_time -= MINUTE * 20;
+		let sb = ScoreBoard::from_snapshot(snapshot).expect("Couldn't restore the board");
+
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Germany 2 - France 2 (21')")]);
+	}
+
+	#[test]
+	fn added_time_is_shown_after_the_length_of_the_period() {
+		let mut sb = get_board();
+		let kickoff = sb.data[0].clock.period_start;
+		assert_eq!(sb.data[0].get_minute(get_time(kickoff, 47), 45), "45+3'");
+
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf).expect("Couldn't start the second half");
+		let start = sb.data[0].clock.period_start;
+		assert_eq!(sb.data[0].get_minute(get_time(start, 44), 45), "90'");
+		assert_eq!(sb.data[0].get_minute(get_time(start, 48), 45), "90+4'");
+
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::ExtraTime).expect("Couldn't start the extra time");
+		let start = sb.data[0].clock.period_start;
+		assert_eq!(sb.data[0].get_minute(get_time(start, 31), 45), "120+2'");
+		assert_eq!(sb.data[0].get_minute_number(get_time(start, 31), 45), 122);
+	}
+
+	#[test]
+	fn half_length_moves_the_minutes_of_the_later_periods() {
+		let mut sb = get_board();
+		sb.set_half_length(Duration::from_secs(40 * 60 + 59)).expect("Couldn't set the half length");
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf).expect("Couldn't start the second half");
+		let start = sb.data[0].clock.period_start;
+
+		assert_eq!(sb.get_half_length(), MINUTE * 40);
+		assert_eq!(sb.data[0].get_minute(start, sb.get_half_minutes()), "41'");
+		assert_eq!(sb.data[0].get_minute(get_time(start, 42), sb.get_half_minutes()), "80+3'");
+		assert_eq!(sb.set_half_length(Duration::ZERO), Err(ScoreBoardError::InvalidHalfLength(Duration::ZERO)));
+	}
+}
@@ -0,0 +1,440 @@
+//! # Memory-mapped board
+//!
+//! Shares the summary of a board between processes through a memory-mapped file: one writer process keeps the summary in the file, and any number of viewer processes read it without locks, ex. the screens of a stadium
+//!
+//! The file starts with a header of 24 bytes: the magic `SCOREBRD`, a sequence number and the length of the summary, followed by the summary as UTF-8 lines. The writer makes the sequence number odd while writing, so the readers retry when they catch a write in progress
+//!
+//! Every byte of the file is read and written as an atomic, since the readers and the writer access the same bytes at the same time. A process touching a byte past the end of the file dies with `SIGBUS`, so the file is never shrunk once it was created, nor should any other program truncate it while the board is in use
+
+use std::fs::{File, OpenOptions};
+use std::hint;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+use std::string::String;
+use std::sync::atomic::{fence, AtomicU64, AtomicU8, Ordering};
+use std::thread;
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// The first bytes of a mapped board file
+const MAGIC: &[u8; 8] = b"SCOREBRD";
+/// Size of the header, before the summary
+const HEADER_SIZE: usize = 24;
+/// Position of the sequence number in the header
+const SEQUENCE_OFFSET: usize = 8;
+/// Position of the length of the summary in the header
+const LENGTH_OFFSET: usize = 16;
+/// How many times a reader retries before giving up on a write in progress, ex. of a writer that died in the middle of it
+const MAX_READ_ATTEMPTS: usize = 100_000;
+
+/// A memory mapping of a whole file
+struct Mapping {
+	/// Start of the mapping
+	address: *mut u8,
+	/// Size of the mapping, the same as the size of the file
+	size: usize,
+}
+
+// SAFETY: the mapping is owned by the `Mapping` and unmapped only when it's dropped, so it may be moved to another thread
+unsafe impl Send for Mapping {}
+// SAFETY: every byte of the mapping is only accessed through `AtomicU64` and `AtomicU8`, so the threads sharing it don't race
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+	/// Maps the whole file into the memory, shared with the other processes mapping it
+	///
+	/// # Arguments
+	///
+	/// * `file` - the mapped file
+	/// * `size` - size of the file
+	/// * `writable` - whether the mapping can be written
+	///
+	/// # Errors
+	///
+	/// * When the file can't be mapped
+	fn new(file: &File, size: usize, writable: bool) -> Result<Mapping, ScoreBoardError> {
+		let protection = if writable { libc::PROT_READ | libc::PROT_WRITE } else { libc::PROT_READ };
+
+		// SAFETY: a new mapping of an open file doesn't alias any memory of the process. The callers map at most the size of the file, so no byte of the mapping is past its end
+		let address = unsafe { libc::mmap(ptr::null_mut(), size, protection, libc::MAP_SHARED, file.as_raw_fd(), 0) };
+
+		if address == libc::MAP_FAILED {
+			return Err(ScoreBoardError::Storage(format!("Couldn't map the board file: {}", std::io::Error::last_os_error())));
+		}
+
+		Ok(Mapping { address: address.cast(), size })
+	}
+
+	/// Provides an atomic number of the header
+	///
+	/// # Arguments
+	///
+	/// * `offset` - position of the number in the header
+	///
+	fn get_atomic(&self, offset: usize) -> &AtomicU64 {
+		// SAFETY: the mapping is page aligned and at least as big as the header, so the number is aligned and inside of it
+		unsafe { &*self.address.add(offset).cast::<AtomicU64>() }
+	}
+
+	/// Provides the bytes of the mapping as atomics
+	///
+	/// # Arguments
+	///
+	/// * `offset` - position of the first byte
+	/// * `length` - number of the bytes
+	///
+	fn get_bytes(&self, offset: usize, length: usize) -> &[AtomicU8] {
+		assert!(offset + length <= self.size, "The bytes are outside of the mapping");
+
+		// SAFETY: the bytes are inside the mapping, which lives as long as the returned slice. `AtomicU8` has the size and the alignment of `u8`, and the bytes are never accessed as anything but atomics
+		unsafe { slice::from_raw_parts(self.address.add(offset).cast::<AtomicU8>(), length) }
+	}
+
+	/// Checks if the mapping starts with the magic of a board file
+	fn has_magic(&self) -> bool {
+		self.size >= HEADER_SIZE && self.get_bytes(0, MAGIC.len()).iter().zip(MAGIC).all(|(byte, magic)| byte.load(Ordering::Relaxed) == *magic)
+	}
+
+	/// Provides the capacity of the summary
+	fn get_capacity(&self) -> usize {
+		self.size - HEADER_SIZE
+	}
+}
+
+impl Drop for Mapping {
+	/// Implementation of `Drop` trait, unmapping the file
+	fn drop(&mut self) {
+		// SAFETY: the mapping was made by `Mapping::new()` and nothing refers to it anymore
+		unsafe { libc::munmap(self.address.cast(), self.size) };
+	}
+}
+
+/// The writing side of a memory-mapped board. It keeps the summary of a board in the file for the `MappedBoardReader`s
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{MappedBoardReader, MappedBoardWriter};
+///
+/// let path = std::env::temp_dir().join("scoreboard-mapped-example.board");
+/// let mut writer = MappedBoardWriter::create(&path, 4096).unwrap();
+/// let reader = MappedBoardReader::open(&path).unwrap();
+///
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// sb.start_game("Japan", "Indonesia").unwrap();
+/// writer.publish(&sb).unwrap();
+///
+/// assert_eq!(reader.get_summary().unwrap(), vec![String::from("Japan 0 - Indonesia 0")]);
+/// # std::fs::remove_file(&path).ok();
+/// ```
+pub struct MappedBoardWriter {
+	/// The mapping of the board file
+	mapping: Mapping,
+}
+
+impl MappedBoardWriter {
+	/// Creates the board file with an empty summary. There should be only one writer of a file at a time
+	///
+	/// An existing board file is taken over with its published summary instead, since its readers may have it mapped: it's never shrunk nor cleared, and it grows when the capacity is bigger. The readers that opened it before see the summaries bigger than the previous capacity once they open it again
+	///
+	/// # Arguments
+	///
+	/// * `path` - path of the board file
+	/// * `capacity` - the largest size of a summary in bytes
+	///
+	/// # Errors
+	///
+	/// * When the file can't be created or mapped
+	/// * When the file exists, but isn't a board file
+	pub fn create<P: AsRef<Path>>(path: P, capacity: usize) -> Result<MappedBoardWriter, ScoreBoardError> {
+		let path = path.as_ref();
+
+		trace!("Creating the mapped board {} for {} bytes", path.display(), capacity);
+
+		let storage_error = |error: std::io::Error| ScoreBoardError::Storage(format!("Couldn't create the board file {}: {}", path.display(), error));
+
+		// Truncating the file would kill its readers
+		let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path).map_err(storage_error)?;
+		let existing_size = file.metadata().map_err(storage_error)?.len() as usize;
+
+		if existing_size > 0 {
+			let existing = Mapping::new(&file, existing_size, false)?;
+			if !existing.has_magic() {
+				warn!("{} isn't a board file", path.display());
+				return Err(ScoreBoardError::Storage(format!("{} exists and isn't a board file", path.display())));
+			}
+		}
+
+		let size = existing_size.max(HEADER_SIZE + capacity);
+		if size > existing_size {
+			file.set_len(size as u64).map_err(storage_error)?;
+		}
+
+		let mapping = Mapping::new(&file, size, true)?;
+		let writer = MappedBoardWriter { mapping };
+
+		if existing_size == 0 {
+			// The readers don't trust the file until the magic is there
+			for (byte, magic) in writer.mapping.get_bytes(0, MAGIC.len()).iter().zip(MAGIC) {
+				byte.store(*magic, Ordering::Relaxed);
+			}
+		} else if !writer.mapping.get_atomic(SEQUENCE_OFFSET).load(Ordering::Acquire).is_multiple_of(2) {
+			// The previous writer died in the middle of a write
+			writer.write(b"");
+		}
+
+		Ok(writer)
+	}
+
+	/// Writes the summary of the board into the file, as in `ScoreBoard::get_summary()`. The readers see either the previous summary or the new one, never a mix of them
+	///
+	/// # Arguments
+	///
+	/// * `board` - the published board
+	///
+	/// # Errors
+	///
+	/// * When the summary is bigger than the capacity of the file. The previous summary stays then
+	pub fn publish(&mut self, board: &ScoreBoard) -> Result<(), ScoreBoardError> {
+		let summary = board.get_summary().join("\n");
+
+		trace!("Publishing {} bytes of the summary", summary.len());
+
+		if summary.len() > self.mapping.get_capacity() {
+			warn!("The summary doesn't fit the board file");
+			return Err(ScoreBoardError::Storage(format!("The summary of {} bytes doesn't fit the board file of {} bytes", summary.len(), self.mapping.get_capacity())));
+		}
+
+		self.write(summary.as_bytes());
+
+		Ok(())
+	}
+
+	/// Writes a summary that fits the capacity after the header. The odd sequence number keeps the readers from using the bytes until the write is done
+	///
+	/// # Arguments
+	///
+	/// * `summary` - the bytes of the summary
+	///
+	fn write(&self, summary: &[u8]) {
+		let sequence = self.mapping.get_atomic(SEQUENCE_OFFSET);
+		// Odd already, when the previous writer didn't finish
+		let writing = sequence.load(Ordering::Relaxed) | 1;
+
+		sequence.store(writing, Ordering::Relaxed);
+		fence(Ordering::Release);
+
+		for (byte, value) in self.mapping.get_bytes(HEADER_SIZE, summary.len()).iter().zip(summary) {
+			byte.store(*value, Ordering::Relaxed);
+		}
+		self.mapping.get_atomic(LENGTH_OFFSET).store(summary.len() as u64, Ordering::Relaxed);
+
+		sequence.store(writing + 1, Ordering::Release);
+	}
+}
+
+/// The reading side of a memory-mapped board, for the viewer processes. Reads never block the writer nor the other readers
+pub struct MappedBoardReader {
+	/// The mapping of the board file
+	mapping: Mapping,
+}
+
+impl MappedBoardReader {
+	/// Opens a board file created with `MappedBoardWriter::create()`
+	///
+	/// # Arguments
+	///
+	/// * `path` - path of the board file
+	///
+	/// # Errors
+	///
+	/// * When the file can't be opened or mapped
+	/// * When the file isn't a board file
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedBoardReader, ScoreBoardError> {
+		let path = path.as_ref();
+
+		trace!("Opening the mapped board {}", path.display());
+
+		let storage_error = |error: std::io::Error| ScoreBoardError::Storage(format!("Couldn't open the board file {}: {}", path.display(), error));
+
+		let file = File::open(path).map_err(storage_error)?;
+		let size = file.metadata().map_err(storage_error)?.len() as usize;
+
+		if size < HEADER_SIZE {
+			return Err(ScoreBoardError::Storage(format!("{} isn't a board file", path.display())));
+		}
+
+		let mapping = Mapping::new(&file, size, false)?;
+
+		if !mapping.has_magic() {
+			return Err(ScoreBoardError::Storage(format!("{} isn't a board file", path.display())));
+		}
+
+		Ok(MappedBoardReader { mapping })
+	}
+
+	/// Provides the number of the summaries published so far. It changes with every `MappedBoardWriter::publish()`, so a viewer can skip the reading when nothing changed
+	pub fn get_version(&self) -> u64 {
+		self.mapping.get_atomic(SEQUENCE_OFFSET).load(Ordering::Acquire) / 2
+	}
+
+	/// Provides the summary last published by the writer, one game per line as in `ScoreBoard::get_summary()`
+	///
+	/// # Errors
+	///
+	/// * When the writer doesn't finish its write, ex. because it died in the middle of it
+	/// * When the summary has outgrown the file as it was opened, see `MappedBoardWriter::create()`
+	pub fn get_summary(&self) -> Result<Vec<String>, ScoreBoardError> {
+		let sequence = self.mapping.get_atomic(SEQUENCE_OFFSET);
+
+		for _ in 0..MAX_READ_ATTEMPTS {
+			let start = sequence.load(Ordering::Acquire);
+
+			if start.is_multiple_of(2) {
+				let length = self.mapping.get_atomic(LENGTH_OFFSET).load(Ordering::Relaxed) as usize;
+				let bytes: Vec<u8> = self.mapping.get_bytes(HEADER_SIZE, length.min(self.mapping.get_capacity())).iter()
+					.map(|byte| byte.load(Ordering::Relaxed))
+					.collect();
+
+				// The bytes are thrown away if the writer changed them meanwhile
+				fence(Ordering::Acquire);
+				if sequence.load(Ordering::Relaxed) == start {
+					if length > self.mapping.get_capacity() {
+						warn!("The summary doesn't fit the mapped board anymore");
+						return Err(ScoreBoardError::Storage(String::from("The board file has grown since it was opened")));
+					}
+					let summary = String::from_utf8_lossy(&bytes);
+					return Ok(summary.lines().map(String::from).collect());
+				}
+			}
+
+			hint::spin_loop();
+			thread::yield_now();
+		}
+
+		warn!("Couldn't read the mapped board, the writer didn't finish");
+
+		Err(ScoreBoardError::Storage(String::from("The board file is still being written")))
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::fs;
+	use std::path::PathBuf;
+
+	/// Provides a path of a board file in the temporary directory, without the file
+	fn get_path(name: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("scoreboard-{}-{}.board", name, std::process::id()));
+		fs::remove_file(&path).ok();
+		path
+	}
+
+	#[test]
+	fn readers_follow_the_writer() {
+		let path = get_path("follow");
+		let mut writer = MappedBoardWriter::create(&path, 1024).expect("Couldn't create the board file");
+		let reader = MappedBoardReader::open(&path).expect("Couldn't open the board file");
+		assert_eq!((reader.get_version(), reader.get_summary()), (0, Ok(Vec::new())));
+
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the second game");
+		writer.publish(&sb).expect("Couldn't publish the board");
+
+		let second_reader = MappedBoardReader::open(&path).expect("Couldn't open the board file again");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(reader.get_version(), 1);
+		assert_eq!(reader.get_summary(), Ok(sb.get_summary()));
+		assert_eq!(second_reader.get_summary(), Ok(sb.get_summary()));
+	}
+
+	#[test]
+	fn too_big_summary_keeps_the_previous_one() {
+		let path = get_path("too-big");
+		let mut writer = MappedBoardWriter::create(&path, 30).expect("Couldn't create the board file");
+		let reader = MappedBoardReader::open(&path).expect("Couldn't open the board file");
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the first game");
+		writer.publish(&sb).expect("Couldn't publish the board");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		let result = writer.publish(&sb);
+		fs::remove_file(&path).ok();
+
+		assert!(matches!(result, Err(ScoreBoardError::Storage(_))));
+		assert_eq!(reader.get_summary(), Ok(vec![String::from("Japan 0 - Indonesia 0")]));
+	}
+
+	#[test]
+	fn existing_board_is_taken_over_without_shrinking() {
+		let path = get_path("taken-over");
+		let mut writer = MappedBoardWriter::create(&path, 1024).expect("Couldn't create the board file");
+		let reader = MappedBoardReader::open(&path).expect("Couldn't open the board file");
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		writer.publish(&sb).expect("Couldn't publish the board");
+		drop(writer);
+
+		let mut writer = MappedBoardWriter::create(&path, 16).expect("Couldn't take over the board file");
+		let size = fs::metadata(&path).map(|metadata| metadata.len());
+		assert_eq!(reader.get_summary(), Ok(sb.get_summary()));
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		writer.publish(&sb).expect("Couldn't publish the board again");
+		fs::remove_file(&path).ok();
+
+		assert_eq!(size.ok(), Some((HEADER_SIZE + 1024) as u64));
+		assert_eq!((reader.get_version(), reader.get_summary()), (2, Ok(sb.get_summary())));
+	}
+
+	#[test]
+	fn other_files_are_not_replaced() {
+		let path = get_path("not-replaced");
+		fs::write(&path, "Japan 0 - Indonesia 0 and some more text").expect("Couldn't write the file");
+		let result = MappedBoardWriter::create(&path, 64);
+		let content = fs::read_to_string(&path);
+		fs::remove_file(&path).ok();
+
+		assert!(matches!(result, Err(ScoreBoardError::Storage(_))));
+		assert_eq!(content.ok().as_deref(), Some("Japan 0 - Indonesia 0 and some more text"));
+	}
+
+	#[test]
+	fn other_files_are_rejected() {
+		let path = get_path("other");
+		fs::write(&path, "Japan 0 - Indonesia 0 and some more text").expect("Couldn't write the file");
+		let result = MappedBoardReader::open(&path);
+		fs::remove_file(&path).ok();
+
+		assert!(matches!(result, Err(ScoreBoardError::Storage(_))));
+	}
+
+	#[test]
+	fn reads_dont_see_a_write_in_progress() {
+		let path = get_path("in-progress");
+		let _writer = MappedBoardWriter::create(&path, 64).expect("Couldn't create the board file");
+		let reader = MappedBoardReader::open(&path).expect("Couldn't open the board file");
+		let file = OpenOptions::new().read(true).write(true).open(&path).expect("Couldn't open the board file for writing");
+		let mapping = Mapping::new(&file, HEADER_SIZE + 64, true).expect("Couldn't map the board file");
+		mapping.get_atomic(SEQUENCE_OFFSET).store(1, Ordering::Release);
+		assert!(reader.get_summary().is_err());
+
+		let _writer = MappedBoardWriter::create(&path, 64).expect("Couldn't take over the board file");
+		fs::remove_file(&path).ok();
+
+		assert_eq!((reader.get_version(), reader.get_summary()), (1, Ok(Vec::new())));
+	}
+}
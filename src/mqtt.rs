@@ -0,0 +1,244 @@
+//! # MQTT publisher
+//!
+//! Publishes every change of the score board to an MQTT broker, so the displays subscribed to the topics of the games update as soon as the score changes
+
+use std::string::{String, ToString};
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, trace, warn};
+use rumqttc::{Client, MqttOptions, QoS};
+
+use crate::events::EventRecord;
+use crate::text::get_game_id;
+use crate::ScoreBoard;
+
+/// Number of the messages waiting for the connection to the broker. Messages published while the queue is full are dropped
+const QUEUE_CAPACITY: usize = 256;
+
+/// Delay before connecting to the broker again after the connection failed
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Settings of the MQTT publisher
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MqttConfig {
+	/// Host name or address of the broker
+	pub host: String,
+	/// Port of the broker
+	pub port: u16,
+	/// Identifier of the score board at the broker
+	pub client_id: String,
+	/// The topic of a game. Every `{id}` is replaced with the identifier of the game, built from the team names, ex. "worldcup/games/{id}/score" becomes "worldcup/games/spain-brazil/score"
+	pub topic: String,
+	/// Whether the broker keeps the last message of every topic, so the displays subscribing later get the latest state of the game right away
+	pub retain: bool,
+}
+
+impl MqttConfig {
+	/// Returns settings publishing retained messages to "worldcup/games/{id}/score" at the given broker
+	///
+	/// # Arguments
+	///
+	/// * `host` - host name or address of the broker
+	/// * `port` - port of the broker, usually 1883
+	pub fn new<T: ToString>(host: T, port: u16) -> MqttConfig {
+		MqttConfig {
+			host: host.to_string(),
+			port,
+			client_id: String::from("scoreboard_world_cup"),
+			topic: String::from("worldcup/games/{id}/score"),
+			retain: true,
+		}
+	}
+}
+
+/// Hands the events over to the background thread keeping the connection to the broker
+#[derive(Clone)]
+pub(crate) struct MqttPublisher {
+	/// Client queueing the messages for the connection
+	client: Client,
+	/// The topic of a game, see `MqttConfig::topic`
+	topic: String,
+	/// Whether the messages are retained, see `MqttConfig::retain`
+	retain: bool,
+}
+
+impl MqttPublisher {
+	/// Queues an event for publishing, as JSON (see `EventRecord::to_json()`) on the topic of its game. Never blocks the score board operation
+	///
+	/// # Arguments
+	///
+	/// * `record` - the event to publish
+	///
+	pub(crate) fn notify(&self, record: &EventRecord) {
+		let (home, away) = record.event.get_teams();
+		let topic = self.topic.replace("{id}", &get_game_id(home, away));
+
+		if let Err(error) = self.client.try_publish(topic, QoS::AtLeastOnce, self.retain, record.to_json()) {
+			warn!("Couldn't queue event {} for MQTT: {}", record.sequence, error);
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Turns on the MQTT publisher. From now on, every recorded event (the start of a game, a score update, a change of the state and the end of a game) is published as JSON (see `EventRecord::to_json()`) to the topic of its game, with the QoS 1 (at least once). The connection is kept by a background thread, which connects again when it fails. Changes of batches and transactions are published once they are applied
+	///
+	/// Calling it again replaces the previous settings
+	///
+	/// # Arguments
+	///
+	/// * `config` - settings of the publisher
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use scoreboard_world_cup::MqttConfig;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_mqtt(MqttConfig::new("broker.stadium.local", 1883));
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_mqtt(&mut self, config: MqttConfig) {
+		trace!("Publishing to MQTT broker {}:{}", config.host, config.port);
+
+		let options = MqttOptions::new(config.client_id, config.host, config.port);
+		let (client, mut connection) = Client::new(options, QUEUE_CAPACITY);
+
+		thread::spawn(move || {
+			// Ends when all the clients are dropped and the queued messages are sent
+			for notification in connection.iter() {
+				if let Err(error) = notification {
+					debug!("MQTT connection failed: {}", error);
+					thread::sleep(RECONNECT_DELAY);
+				}
+			}
+
+			trace!("MQTT connection thread stopped");
+		});
+
+		self.mqtt = Some(MqttPublisher { client, topic: config.topic, retain: config.retain });
+	}
+
+	/// Turns off the MQTT publisher
+	pub fn clear_mqtt(&mut self) {
+		trace!("Clearing MQTT");
+
+		self.mqtt = None;
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::io::{Read, Write};
+	use std::net::{TcpListener, TcpStream};
+	use std::sync::mpsc::{self, Receiver};
+	use std::vec::Vec;
+
+	const WAIT_TIME: Duration = Duration::from_secs(5);
+
+	/// A message received by the test broker
+	#[derive(Debug)]
+	struct Message {
+		topic: String,
+		retain: bool,
+		payload: String,
+	}
+
+	/// Reads an MQTT packet, returning its first byte and the rest of the packet after the length
+	fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+		let mut byte = [0; 1];
+		stream.read_exact(&mut byte).ok()?;
+		let kind = byte[0];
+
+		let mut length = 0;
+		let mut shift = 0;
+		loop {
+			stream.read_exact(&mut byte).ok()?;
+			length |= usize::from(byte[0] & 0x7f) << shift;
+			shift += 7;
+			if byte[0] & 0x80 == 0 {
+				break;
+			}
+		}
+
+		let mut body = vec![0; length];
+		stream.read_exact(&mut body).ok()?;
+		Some((kind, body))
+	}
+
+	/// Starts a local broker accepting a single connection and passing the published messages on
+	fn start_broker() -> (u16, Receiver<Message>) {
+		let listener = TcpListener::bind("127.0.0.1:0").expect("Couldn't start the broker");
+		let port = listener.local_addr().expect("Couldn't get the broker address").port();
+		let (sender, receiver) = mpsc::channel();
+
+		thread::spawn(move || {
+			let (mut stream, _) = listener.accept().expect("Couldn't accept a connection");
+
+			while let Some((kind, body)) = read_packet(&mut stream) {
+				match kind >> 4 {
+					// CONNECT, answered with an accepting CONNACK
+					1 => stream.write_all(&[0x20, 0x02, 0x00, 0x00]).expect("Couldn't accept the connection"),
+					// PUBLISH of QoS 1, answered with a PUBACK
+					3 => {
+						let topic_length = usize::from(u16::from_be_bytes([body[0], body[1]]));
+						let topic = String::from_utf8_lossy(&body[2..2 + topic_length]).into_owned();
+						let packet_id = &body[2 + topic_length..4 + topic_length];
+						let payload = String::from_utf8_lossy(&body[4 + topic_length..]).into_owned();
+						stream.write_all(&[0x40, 0x02, packet_id[0], packet_id[1]]).expect("Couldn't acknowledge the message");
+						let _ = sender.send(Message { topic, retain: kind & 0x01 == 1, payload });
+					},
+					// PINGREQ, answered with a PINGRESP
+					12 => stream.write_all(&[0xd0, 0x00]).expect("Couldn't answer the ping"),
+					_ => {},
+				}
+			}
+		});
+
+		(port, receiver)
+	}
+
+	#[test]
+	fn every_event_is_published_to_the_game_topic() {
+		let (port, messages) = start_broker();
+
+		let mut sb = ScoreBoard::new();
+		sb.set_mqtt(MqttConfig::new("127.0.0.1", port));
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+
+		let start = messages.recv_timeout(WAIT_TIME).expect("The start wasn't published");
+		let update = messages.recv_timeout(WAIT_TIME).expect("The update wasn't published");
+		assert_eq!(start.topic, "worldcup/games/spain-brazil/score");
+		assert!(start.retain);
+		assert_eq!(start.payload, sb.get_events()[0].to_json());
+		assert_eq!(update.payload, sb.get_events()[1].to_json());
+	}
+
+	#[test]
+	fn messages_can_be_not_retained() {
+		let (port, messages) = start_broker();
+
+		let mut sb = ScoreBoard::new();
+		sb.set_mqtt(MqttConfig { topic: String::from("stadium/{id}"), retain: false, ..MqttConfig::new("127.0.0.1", port) });
+		sb.start_game("South Korea", "Japan").expect("Couldn't create the game");
+
+		let start = messages.recv_timeout(WAIT_TIME).expect("The start wasn't published");
+		assert_eq!(start.topic, "stadium/south-korea-japan");
+		assert!(!start.retain);
+	}
+
+	#[test]
+	fn config_has_sensible_defaults() {
+		let config = MqttConfig::new("localhost", 1883);
+
+		assert_eq!(config.topic, "worldcup/games/{id}/score");
+		assert!(config.retain);
+	}
+}
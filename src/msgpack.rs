@@ -0,0 +1,176 @@
+//! # MessagePack encoding
+//!
+//! Compact binary encoding of the board snapshots, for low bandwidth links between the score board instances. Available with the `msgpack` feature
+
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::snapshot::BoardSnapshot;
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Encodes a snapshot of all the currently played games with MessagePack
+	///
+	/// # Errors
+	///
+	/// * When the snapshot can't be encoded
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let bytes = sb.encode_msgpack().unwrap();
+	/// let restored = scoreboard_world_cup::ScoreBoard::decode_msgpack(&bytes).unwrap();
+	/// assert_eq!(restored.get_summary(), sb.get_summary());
+	/// ```
+	pub fn encode_msgpack(&self) -> Result<Vec<u8>, ScoreBoardError> {
+		trace!("Encoding the score board with MessagePack");
+
+		match rmp_serde::to_vec(&self.snapshot()) {
+			Ok(bytes) => Ok(bytes),
+			Err(error) => {
+				warn!("Couldn't encode the snapshot: {}", error);
+				Err(ScoreBoardError::Encoding(format!("Couldn't encode the snapshot: {}", error)))
+			},
+		}
+	}
+
+	/// Creates a score board from a MessagePack encoded snapshot. The restored board has no event history. The snapshots saved by the older releases are migrated, see `SNAPSHOT_VERSION`
+	///
+	/// # Arguments
+	///
+	/// * `bytes` - the encoded snapshot
+	///
+	/// # Errors
+	///
+	/// * When the snapshot can't be decoded
+	/// * When the version of the snapshot is not supported
+	/// * When a team plays with itself or in more than one game
+	pub fn decode_msgpack(bytes: &[u8]) -> Result<ScoreBoard, ScoreBoardError> {
+		trace!("Decoding the score board from MessagePack");
+
+		let snapshot: BoardSnapshot = match rmp_serde::from_slice(bytes) {
+			Ok(snapshot) => snapshot,
+			Err(error) => {
+				warn!("Couldn't decode the snapshot: {}", error);
+				return Err(ScoreBoardError::Encoding(format!("Couldn't decode the snapshot: {}", error)));
+			},
+		};
+
+		ScoreBoard::from_snapshot(snapshot)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::SystemTime;
+
+	use crate::period::Period;
+	use crate::snapshot::GameSnapshot;
+	use crate::state::GameState;
+	use crate::timezone::UtcOffset;
+
+	#[test]
+	fn empty_board_is_encoded_and_decoded() {
+		let sb = ScoreBoard::new();
+		let bytes = sb.encode_msgpack().expect("Couldn't encode the board");
+		let result = ScoreBoard::decode_msgpack(&bytes);
+
+		assert!(result.is_ok_and(|restored| restored.get_summary().is_empty()));
+	}
+
+	#[test]
+	fn board_survives_encoding() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		let bytes = sb.encode_msgpack().expect("Couldn't encode the board");
+		let restored = ScoreBoard::decode_msgpack(&bytes).expect("Couldn't decode the board");
+
+		assert_eq!(restored.snapshot(), sb.snapshot());
+	}
+
+	#[test]
+	fn encoding_is_compact() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		let bytes = sb.encode_msgpack().expect("Couldn't encode the board");
+
+		assert!(bytes.len() < 60);
+	}
+
+	#[test]
+	fn garbage_is_not_decoded() {
+		let result = ScoreBoard::decode_msgpack(&[0xc1, 0x00]);
+
+		assert!(result.err().is_some_and(|result| result.to_string().starts_with("Couldn't decode the snapshot")));
+	}
+
+	#[test]
+	fn invalid_snapshot_is_rejected() {
+		let snapshot = BoardSnapshot::new(vec![GameSnapshot {
+			home_team: String::from("Spain"),
+			home_score: 0,
+			away_team: String::from("Spain"),
+			away_score: 0,
+			kickoff_time: SystemTime::now(),
+			revision: 1,
+			state: GameState::Live,
+			period: Period::FirstHalf,
+			venue: None,
+			group: None,
+			utc_offset: UtcOffset::UTC,
+		}]);
+		let bytes = rmp_serde::to_vec(&snapshot).expect("Couldn't encode the snapshot");
+		let result = ScoreBoard::decode_msgpack(&bytes);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Spain cannot play with itself"));
+	}
+
+	#[test]
+	fn snapshot_of_the_first_version_is_decoded() {
+		// The format before the revisions and the version were added
+		#[derive(serde::Serialize)]
+		struct LegacyGame {
+			home_team: String,
+			home_score: u8,
+			away_team: String,
+			away_score: u8,
+			kickoff_time: SystemTime,
+		}
+		#[derive(serde::Serialize)]
+		struct LegacyBoard {
+			games: Vec<LegacyGame>,
+		}
+
+		let legacy = LegacyBoard { games: vec![LegacyGame {
+			home_team: String::from("Spain"),
+			home_score: 2,
+			away_team: String::from("Brazil"),
+			away_score: 1,
+			kickoff_time: SystemTime::now(),
+		}] };
+		let bytes = rmp_serde::to_vec(&legacy).expect("Couldn't encode the snapshot");
+		let restored = ScoreBoard::decode_msgpack(&bytes).expect("Couldn't decode the snapshot");
+
+		assert_eq!(restored.get_summary(), vec![String::from("Spain 2 - Brazil 1")]);
+		assert_eq!(restored.snapshot().games[0].revision, 1);
+	}
+
+	#[test]
+	fn snapshot_of_a_newer_version_is_rejected() {
+		let bytes = rmp_serde::to_vec(&BoardSnapshot { games: Vec::new(), version: 99 }).expect("Couldn't encode the snapshot");
+
+		assert_eq!(ScoreBoard::decode_msgpack(&bytes).err(), Some(ScoreBoardError::UnsupportedSnapshotVersion(99)));
+	}
+}
@@ -0,0 +1,139 @@
+//! # Commentary notes
+//!
+//! Free-text notes of the commentators, attached to the running games and shown in their timelines
+
+use std::string::{String, ToString};
+use std::time::SystemTime;
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::ScoreBoard;
+
+/// A note attached to a game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct GameNote {
+	/// Name of the home team
+	pub(crate) home: String,
+	/// Name of the away team
+	pub(crate) away: String,
+	/// Sequence number of the last event recorded before the note, placing the note among the events
+	pub(crate) after_sequence: u64,
+	/// Wall clock time when the note was added
+	pub(crate) time: SystemTime,
+	/// Text of the note
+	pub(crate) text: String,
+}
+
+impl ScoreBoard {
+	/// Attaches a free-text note to a running game, ex. "Yellow card for Mitoma". The notes don't change the game, so they are neither recorded as events nor change its revision, but they are shown in the timeline of the game, see `get_timeline()`, also after it finishes
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `text` - Text of the note. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is no running game between the given teams
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::TimelineKind;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.add_note("Japan", "Indonesia", "Heavy rain in the stadium").unwrap();
+	/// assert_eq!(sb.get_timeline("Japan", "Indonesia")[1].kind, TimelineKind::Note(String::from("Heavy rain in the stadium")));
+	/// ```
+	pub fn add_note<T: ToString, U: ToString, V: ToString>(&mut self, home: T, away: U, text: V) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+		let text = text.to_string();
+
+		trace!("Adding a note to the game between '{}' and '{}': {}", home_name, away_name, text);
+
+		if self.find_game_index(&home_name, &away_name).is_none() {
+			warn!("Couldn't find a game for the note");
+			return Err(self.game_not_found(home_name, away_name, Operation::UpdateScore));
+		}
+
+		self.write_ahead_change(&["note", &home_name, &away_name, &text])?;
+
+		let after_sequence = self.get_last_sequence();
+		self.notes.push(GameNote { home: home_name, away: away_name, after_sequence, time: SystemTime::now(), text });
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::vec::Vec;
+
+	use crate::timeline::TimelineKind;
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	#[test]
+	fn notes_are_shown_in_the_timeline_in_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.add_note(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Kickoff delayed").expect("Couldn't add the first note");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		sb.add_note(HOME_TEAM_NAME, AWAY_TEAM_NAME, "What a strike!").expect("Couldn't add the second note");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+
+		let entries: Vec<(TimelineKind, u8)> = sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).into_iter().map(|entry| (entry.kind, entry.home_score)).collect();
+		assert_eq!(entries, vec![
+			(TimelineKind::Kickoff, 0),
+			(TimelineKind::Note(String::from("Kickoff delayed")), 0),
+			(TimelineKind::ScoreChange(None), 1),
+			(TimelineKind::Note(String::from("What a strike!")), 1),
+			(TimelineKind::Finished, 1),
+		]);
+	}
+
+	#[test]
+	fn note_needs_a_running_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		let result = sb.add_note(AWAY_TEAM_NAME, HOME_TEAM_NAME, "Wrong way around");
+		assert!(matches!(result, Err(ScoreBoardError::GameNotFound { .. })));
+		assert_eq!(sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).len(), 1);
+	}
+
+	#[test]
+	fn notes_dont_change_the_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		let before = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+		sb.add_note(HOME_TEAM_NAME, AWAY_TEAM_NAME, "Substitution").expect("Couldn't add the note");
+
+		assert_eq!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME), before);
+		assert_eq!(sb.get_events().len(), 1);
+	}
+
+	#[test]
+	fn notes_of_earlier_games_are_left_out() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first game");
+		sb.add_note(HOME_TEAM_NAME, AWAY_TEAM_NAME, "First leg").expect("Couldn't add the note");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the first game");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the second game");
+
+		let kinds: Vec<TimelineKind> = sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).into_iter().map(|entry| entry.kind).collect();
+		assert_eq!(kinds, vec![TimelineKind::Kickoff]);
+	}
+}
@@ -0,0 +1,233 @@
+//! # Betting odds
+//!
+//! The current odds of the running games, with the probabilities they imply, for the second-screen apps
+
+use std::hash::{Hash, Hasher};
+use std::string::{String, ToString};
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::ScoreBoard;
+
+/// Decimal odds of the three results of a game, ex. 2.10 for the home win pays 2.10 for every 1 staked
+#[derive(Clone, Copy, Debug)]
+pub struct Odds {
+	/// Odds of the home team winning
+	pub home: f64,
+	/// Odds of a draw
+	pub draw: f64,
+	/// Odds of the away team winning
+	pub away: f64,
+}
+
+impl Odds {
+	/// Returns the odds of the three results
+	///
+	/// # Arguments
+	///
+	/// * `home` - odds of the home team winning
+	/// * `draw` - odds of a draw
+	/// * `away` - odds of the away team winning
+	pub fn new(home: f64, draw: f64, away: f64) -> Odds {
+		Odds { home, draw, away }
+	}
+
+	/// Provides the probabilities of the home win, the draw and the away win implied by the odds. The bookmaker margin is removed, so the probabilities add up to 1
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Odds;
+	///
+	/// let (home, draw, away) = Odds::new(2.0, 4.0, 4.0).get_implied_probabilities();
+	/// assert_eq!((home, draw, away), (0.5, 0.25, 0.25));
+	/// ```
+	pub fn get_implied_probabilities(&self) -> (f64, f64, f64) {
+		let (home, draw, away) = (1.0 / self.home, 1.0 / self.draw, 1.0 / self.away);
+		let total = home + draw + away;
+
+		(home / total, draw / total, away / total)
+	}
+
+	/// Provides the bookmaker margin of the odds, ex. 0.05 for a 5% margin
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Odds;
+	///
+	/// assert!(Odds::new(1.8, 3.5, 4.5).get_margin() > 0.0);
+	/// ```
+	pub fn get_margin(&self) -> f64 {
+		1.0 / self.home + 1.0 / self.draw + 1.0 / self.away - 1.0
+	}
+
+	/// Finds the odds that can't be paid out: not a number, infinite, or not above 1
+	fn find_invalid(&self) -> Option<f64> {
+		[self.home, self.draw, self.away].into_iter().find(|odds| !odds.is_finite() || *odds <= 1.0)
+	}
+}
+
+impl PartialEq for Odds {
+	/// Compares the exact values of the odds, so the odds can be a part of `GameView`, which is `Eq` and `Hash`
+	fn eq(&self, other: &Odds) -> bool {
+		[self.home, self.draw, self.away].map(f64::to_bits) == [other.home, other.draw, other.away].map(f64::to_bits)
+	}
+}
+
+impl Eq for Odds {}
+
+impl Hash for Odds {
+	/// Hashes the exact values of the odds, consistently with `PartialEq`
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		[self.home, self.draw, self.away].map(f64::to_bits).hash(state);
+	}
+}
+
+impl ScoreBoard {
+	/// Sets the current odds of a running game, replacing the previous ones. The odds are shown in `GameView::odds`. They don't change the game, so they are neither recorded as events nor change its revision
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `odds` - the current odds
+	///
+	/// # Errors
+	///
+	/// * When any of the odds is not a finite number above 1
+	/// * When there is no running game between the given teams
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Odds;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.set_odds("Japan", "Indonesia", Odds::new(1.5, 4.0, 6.5)).unwrap();
+	/// assert_eq!(sb.get_game("Japan", "Indonesia").unwrap().odds, Some(Odds::new(1.5, 4.0, 6.5)));
+	/// assert!(sb.set_odds("Japan", "Indonesia", Odds::new(0.5, 4.0, 6.5)).is_err());
+	/// ```
+	pub fn set_odds<T: ToString, U: ToString>(&mut self, home: T, away: U, odds: Odds) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		trace!("Setting the odds of the game between '{}' and '{}' to {:?}", home_name, away_name, odds);
+
+		if let Some(invalid) = odds.find_invalid() {
+			warn!("Odds {} are invalid", invalid);
+			return Err(ScoreBoardError::InvalidOdds(invalid.to_string()));
+		}
+
+		self.replace_odds(home_name, away_name, Some(odds))
+	}
+
+	/// Removes the odds of a running game, ex. when the betting is suspended
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is no running game between the given teams
+	/// * When the journal can't be written, see `set_journal()`
+	pub fn clear_odds<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		trace!("Clearing the odds of the game between '{}' and '{}'", home_name, away_name);
+
+		self.replace_odds(home_name, away_name, None)
+	}
+
+	/// Replaces the odds of a running game
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team, after following the aliases
+	/// * `away_name` - name of the away team, after following the aliases
+	/// * `odds` - the new odds, if any
+	///
+	fn replace_odds(&mut self, home_name: String, away_name: String, odds: Option<Odds>) -> Result<(), ScoreBoardError> {
+		match self.find_game_index(&home_name, &away_name) {
+			Some(game_index) => {
+				match odds {
+					Some(odds) => self.write_ahead_change(&["odds", &home_name, &away_name, &odds.home.to_string(), &odds.draw.to_string(), &odds.away.to_string()])?,
+					None => self.write_ahead_change(&["odds", &home_name, &away_name])?,
+				}
+				self.data[game_index].odds = odds;
+				Ok(())
+			},
+			None => {
+				warn!("Couldn't find a game for update");
+				Err(self.game_not_found(home_name, away_name, Operation::UpdateScore))
+			},
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	#[test]
+	fn probabilities_are_normalized() {
+		let (home, draw, away) = Odds::new(1.8, 3.5, 4.5).get_implied_probabilities();
+
+		assert!((home + draw + away - 1.0).abs() < 1e-12);
+		assert!(home > draw && draw > away);
+	}
+
+	#[test]
+	fn fair_odds_have_no_margin() {
+		assert!(Odds::new(2.0, 4.0, 4.0).get_margin().abs() < 1e-12);
+		assert!((Odds::new(1.8, 3.5, 4.5).get_margin() - 0.0635).abs() < 1e-3);
+	}
+
+	#[test]
+	fn invalid_odds_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		for odds in [Odds::new(1.0, 3.0, 3.0), Odds::new(2.0, f64::NAN, 3.0), Odds::new(2.0, 3.0, f64::INFINITY)] {
+			assert!(matches!(sb.set_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME, odds), Err(ScoreBoardError::InvalidOdds(_))));
+		}
+		assert_eq!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).and_then(|game| game.odds), None);
+	}
+
+	#[test]
+	fn odds_follow_the_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.set_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME, Odds::new(2.1, 3.4, 3.25)).expect("Couldn't set the odds");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't update the game");
+		sb.set_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME, Odds::new(1.4, 4.5, 8.0)).expect("Couldn't set the odds again");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.odds, Some(Odds::new(1.4, 4.5, 8.0)));
+		assert_eq!(game.revision, 2);
+
+		sb.clear_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't clear the odds");
+		assert_eq!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).and_then(|game| game.odds), None);
+	}
+
+	#[test]
+	fn odds_need_a_running_game() {
+		let mut sb = ScoreBoard::new();
+
+		assert!(matches!(sb.set_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME, Odds::new(2.0, 3.0, 4.0)), Err(ScoreBoardError::GameNotFound { .. })));
+		assert_eq!(sb.clear_odds(HOME_TEAM_NAME, AWAY_TEAM_NAME).map_err(|error| error.to_string()), Err(String::from("Couldn't find a game for update")));
+	}
+}
@@ -0,0 +1,271 @@
+//! # Summary orders
+//!
+//! Alternative orders of the summary, for the screens that don't want the games ranked by their score
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::string::String;
+use std::time::Instant;
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::{Game, ScoreBoard};
+
+/// The order of the games in a summary
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+	/// By the total score (the highest coming first) and then by the start time (the most recent coming first), the same as `ScoreBoard::get_summary()`
+	#[default]
+	Ranking,
+	/// By the start time, the earliest coming first, regardless of the scores
+	Kickoff,
+	/// By the total score (the highest coming first), then by the goal difference (the most lopsided game coming first) and then by the start time (the most recent coming first)
+	GoalDifference,
+}
+
+/// The values of a game the orders are based on. Every change of them is followed by moving the game to its place on the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct RankKey {
+	/// Sum of the scores of both teams
+	total_score: u16,
+	/// Difference of the scores, regardless of which team leads
+	goal_difference: u8,
+	/// Timestamp of the start of the game
+	start_time: Instant,
+}
+
+impl Game {
+	/// Provides the values of the game the orders are based on
+	pub(crate) fn get_rank_key(&self) -> RankKey {
+		RankKey { total_score: self.get_total_score(), goal_difference: self.get_goal_difference(), start_time: self.start_time }
+	}
+}
+
+/// Compares the keys of two games for the given order. The smaller game comes first
+pub(crate) fn compare_keys(order: SortOrder, a: &RankKey, b: &RankKey) -> Ordering {
+	match order {
+		SortOrder::Ranking => b.total_score.cmp(&a.total_score)
+			.then_with(|| b.start_time.cmp(&a.start_time)),
+		SortOrder::Kickoff => a.start_time.cmp(&b.start_time),
+		SortOrder::GoalDifference => b.total_score.cmp(&a.total_score)
+			.then_with(|| b.goal_difference.cmp(&a.goal_difference))
+			.then_with(|| b.start_time.cmp(&a.start_time)),
+	}
+}
+
+/// Compares two games for the given order. The smaller game comes first
+pub(crate) fn compare(order: SortOrder, a: &Game, b: &Game) -> Ordering {
+	compare_keys(order, &a.get_rank_key(), &b.get_rank_key())
+}
+
+impl ScoreBoard {
+	/// Sets the order of the games on the board. It is used by `get_summary()` and all the other presentations of the board, like `get_games()` and the exports. The default is `SortOrder::Ranking`
+	///
+	/// # Arguments
+	///
+	/// * `order` - the order of the games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::SortOrder;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Mexico", "Canada").unwrap();
+	/// sb.update_score("Mexico", 0, "Canada", 4).unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 2, "Brazil", 2).unwrap();
+	/// sb.set_sort_order(SortOrder::GoalDifference);
+	/// assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 4"), String::from("Spain 2 - Brazil 2")]);
+	/// ```
+	pub fn set_sort_order(&mut self, order: SortOrder) {
+		trace!("Ordering the games by {:?}", order);
+
+		self.sort_order = order;
+		self.sort();
+	}
+
+	/// Provides the order of the games on the board
+	pub fn get_sort_order(&self) -> SortOrder {
+		self.sort_order
+	}
+
+	/// Provides the current status of the score board, with the games in the given order, regardless of the order set with `set_sort_order()`. The order of the board isn't changed, so screens with different orders can share one board. The summary in the order of the board is the cached one of `get_summary()`
+	///
+	/// # Arguments
+	///
+	/// * `order` - the order of the games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::SortOrder;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Mexico", "Canada").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 1, "Brazil", 0).unwrap();
+	/// assert_eq!(sb.get_summary_sorted(SortOrder::Kickoff), vec![String::from("Mexico 0 - Canada 0"), String::from("Spain 1 - Brazil 0")]);
+	/// ```
+	pub fn get_summary_sorted(&self, order: SortOrder) -> Vec<String> {
+		trace!("Getting the score board summary in the {:?} order", order);
+
+		if order == self.sort_order {
+			return self.get_summary();
+		}
+
+		let now = Instant::now();
+
+		self.get_sorted_games(order).into_iter().map(|game| self.render_line(game, now)).collect()
+	}
+
+	/// Provides the current status of the score board split by the groups of the tournament, one summary per group, so every group gets its own table. Every summary is in the order of the board, as in `get_summary()`
+	///
+	/// # Returns
+	///
+	/// * The summaries by the group letters, given with `GameBuilder::group()`, in the alphabetical order. The games without a group are under `None`, which comes first. Groups without running games are left out
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.new_game("Qatar", "Ecuador").group('A').start().unwrap();
+	/// sb.new_game("England", "Iran").group('B').start().unwrap();
+	/// sb.new_game("Senegal", "Netherlands").group('A').start().unwrap();
+	/// sb.update_score("Senegal", 0, "Netherlands", 2).unwrap();
+	///
+	/// let groups = sb.get_summary_grouped();
+	/// assert_eq!(groups[&Some('A')], vec![String::from("Senegal 0 - Netherlands 2"), String::from("Qatar 0 - Ecuador 0")]);
+	/// assert_eq!(groups[&Some('B')], vec![String::from("England 0 - Iran 0")]);
+	/// ```
+	pub fn get_summary_grouped(&self) -> BTreeMap<Option<char>, Vec<String>> {
+		trace!("Getting the score board summary by the groups");
+
+		let now = Instant::now();
+		let mut groups: BTreeMap<Option<char>, Vec<String>> = BTreeMap::new();
+
+		for game in &self.data {
+			groups.entry(game.group).or_default().push(self.render_line(game, now));
+		}
+
+		groups
+	}
+
+	/// Provides the running games in the given order
+	pub(crate) fn get_sorted_games(&self, order: SortOrder) -> Vec<&Game> {
+		let mut games: Vec<&Game> = self.data.iter().collect();
+
+		if order != self.sort_order {
+			games.sort_by(|a, b| compare(order, a, b));
+		}
+
+		games
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		sb.update_score("Germany", 2, "France", 2).expect("Couldn't update the third game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		sb
+	}
+
+	#[test]
+	fn ranking_order_is_the_default_summary() {
+		let sb = get_board();
+
+		assert_eq!(SortOrder::default(), SortOrder::Ranking);
+		assert_eq!(sb.get_summary_sorted(SortOrder::Ranking), sb.get_summary());
+	}
+
+	#[test]
+	fn goal_difference_breaks_the_ties() {
+		let mut sb = get_board();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the fourth game");
+		sb.update_score("Japan", 4, "Indonesia", 0).expect("Couldn't update the fourth game");
+		sb.start_game("Qatar", "Ecuador").expect("Couldn't create the fifth game");
+		sb.update_score("Qatar", 1, "Ecuador", 3).expect("Couldn't update the fifth game");
+
+		let expected = vec![
+			String::from("Spain 10 - Brazil 2"),
+			String::from("Japan 4 - Indonesia 0"),
+			String::from("Qatar 1 - Ecuador 3"),
+			String::from("Germany 2 - France 2"),
+			String::from("Mexico 0 - Canada 0"),
+		];
+		assert_eq!(sb.get_summary_sorted(SortOrder::GoalDifference), expected);
+		assert_eq!(sb.get_summary()[1], "Qatar 1 - Ecuador 3");
+
+		sb.set_sort_order(SortOrder::GoalDifference);
+		assert_eq!(sb.get_sort_order(), SortOrder::GoalDifference);
+		assert_eq!(sb.get_summary(), expected);
+		assert_eq!(sb.get_summary_sorted(SortOrder::Ranking)[1], "Qatar 1 - Ecuador 3");
+	}
+
+	#[test]
+	fn set_order_is_kept_after_updates() {
+		let mut sb = get_board();
+		sb.set_sort_order(SortOrder::Kickoff);
+		sb.update_score("Mexico", 0, "Canada", 1).expect("Couldn't update the first game");
+
+		assert_eq!(sb.get_summary()[0], "Mexico 0 - Canada 1");
+	}
+
+	#[test]
+	fn games_without_a_group_come_first() {
+		let mut sb = get_board();
+		sb.new_game("Qatar", "Ecuador").group('A').start().expect("Couldn't create the grouped game");
+		sb.set_sort_order(SortOrder::Kickoff);
+
+		let groups = sb.get_summary_grouped();
+		assert_eq!(groups.keys().copied().collect::<Vec<Option<char>>>(), vec![None, Some('A')]);
+		assert_eq!(groups[&None], vec![
+			String::from("Mexico 0 - Canada 0"),
+			String::from("Spain 10 - Brazil 2"),
+			String::from("Germany 2 - France 2"),
+		]);
+		assert!(ScoreBoard::new().get_summary_grouped().is_empty());
+	}
+
+	#[test]
+	fn kickoff_order_ignores_the_scores() {
+		let sb = get_board();
+
+		assert_eq!(sb.get_summary_sorted(SortOrder::Kickoff), vec![
+			String::from("Mexico 0 - Canada 0"),
+			String::from("Spain 10 - Brazil 2"),
+			String::from("Germany 2 - France 2"),
+		]);
+	}
+
+	#[test]
+	fn kickoff_order_doesnt_change_the_board() {
+		let sb = get_board();
+		let _ = sb.get_summary_sorted(SortOrder::Kickoff);
+
+		assert_eq!(sb.get_summary()[0], "Spain 10 - Brazil 2");
+	}
+
+	#[test]
+	fn board_order_follows_the_updates() {
+		let mut sb = get_board();
+		sb.set_sort_order(SortOrder::Kickoff);
+		let _ = sb.get_summary_sorted(SortOrder::Kickoff);
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the fourth game");
+
+		assert_eq!(sb.get_summary_sorted(SortOrder::Kickoff)[3], "Japan 0 - Indonesia 0");
+		assert_eq!(sb.get_summary_sorted(SortOrder::Ranking)[0], "Spain 10 - Brazil 2");
+	}
+}
@@ -0,0 +1,308 @@
+//! # Page templates
+//!
+//! Renders whole pages and reports, ex. the nightly results bulletin, from the Handlebars templates of the users, filled with the structured data of the board: the running games of `get_games()` and the finished ones of `get_archive()`
+
+use std::string::{String, ToString};
+use std::time::Instant;
+use std::vec::Vec;
+
+use handlebars::Handlebars;
+use log::{trace, warn};
+use serde::Serialize;
+
+use crate::archive::{ArchivedGame, Decision};
+use crate::datetime::UtcDateTime;
+use crate::error::ScoreBoardError;
+use crate::view::GameView;
+use crate::ScoreBoard;
+
+/// Name of the single template of a `PageTemplate`
+const PAGE: &str = "page";
+
+/// The data of the board given to a page template
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct PageData {
+	/// Number of the running games
+	game_count: usize,
+	/// Number of the finished games
+	result_count: usize,
+	/// The running games, in the order of `get_games()`
+	games: Vec<GameData>,
+	/// The finished games, the earliest finished coming first
+	results: Vec<ResultData>,
+}
+
+/// The data of a running game given to a page template
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct GameData {
+	/// Name of the home team
+	home: String,
+	/// Goals of the home team
+	home_score: u8,
+	/// Name of the away team
+	away: String,
+	/// Goals of the away team
+	away_score: u8,
+	/// State of the game, ex. "live"
+	state: &'static str,
+	/// Period of the game, ex. "second half"
+	period: String,
+	/// Minute of the match as shown on the tickers, ex. "45+2'"
+	clock: String,
+	/// Score at the half-time break, ex. "1-0", `None` before it
+	half_time: Option<String>,
+	/// Kick-off time in UTC, in the RFC 3339 format
+	kickoff: String,
+	/// Stadium of the game, when it was given
+	venue: Option<String>,
+	/// Group of the tournament, when it was given
+	group: Option<String>,
+}
+
+/// The data of a finished game given to a page template
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct ResultData {
+	/// Name of the home team
+	home: String,
+	/// Final goals of the home team
+	home_score: u8,
+	/// Name of the away team
+	away: String,
+	/// Final goals of the away team
+	away_score: u8,
+	/// How the game was decided, ex. "a.e.t.", `None` for the regular time
+	decision: Option<String>,
+	/// The result as a line, ex. "Spain 2 - 1 Italy (a.e.t.)"
+	result: String,
+	/// Kick-off time in UTC, in the RFC 3339 format
+	kickoff: String,
+	/// Finish time in UTC, in the RFC 3339 format
+	finish: String,
+}
+
+impl GameData {
+	/// Provides the data of a running game
+	///
+	/// # Arguments
+	///
+	/// * `game` - the running game
+	/// * `clock` - minute of the match as shown on the tickers
+	///
+	fn new(game: GameView, clock: String) -> GameData {
+		GameData {
+			state: game.state.get_name(),
+			period: game.period.to_string(),
+			clock,
+			half_time: game.half_time_score.map(|(home, away)| format!("{}-{}", home, away)),
+			kickoff: UtcDateTime::from_system_time(game.kickoff_time).to_rfc3339(),
+			group: game.group.map(String::from),
+			home_score: game.home_score,
+			away_score: game.away_score,
+			home: game.home_team,
+			away: game.away_team,
+			venue: game.venue,
+		}
+	}
+}
+
+impl ResultData {
+	/// Provides the data of a finished game
+	///
+	/// # Arguments
+	///
+	/// * `game` - the finished game
+	///
+	fn new(game: &ArchivedGame) -> ResultData {
+		ResultData {
+			home: game.home_team.clone(),
+			home_score: game.home_score,
+			away: game.away_team.clone(),
+			away_score: game.away_score,
+			decision: (game.decision != Decision::RegularTime).then(|| game.decision.to_string()),
+			result: game.to_string(),
+			kickoff: UtcDateTime::from_system_time(game.kickoff_time).to_rfc3339(),
+			finish: UtcDateTime::from_system_time(game.finish_time).to_rfc3339(),
+		}
+	}
+}
+
+/// A Handlebars page template, for `ScoreBoard::render_page()`. The whole Handlebars syntax of the `handlebars` crate can be used, ex. `{{value}}` with the HTML special characters escaped, `{{{value}}}` as it is, `{{#each}}`, `{{#if}}`/`{{else}}`, `{{#with}}` and `{{! comments}}`. The templates are strict: a value the board doesn't have is an error, not an empty text
+///
+/// The data of the board is:
+///
+/// * `games` - the running games, each with `home`, `home_score`, `away`, `away_score`, `state`, `period`, `clock`, `half_time` (ex. "1-0", null before the half-time break), `kickoff`, `venue` and `group`, the last two null when they weren't given
+/// * `results` - the finished games, the earliest finished coming first, each with `home`, `home_score`, `away`, `away_score`, `decision` (ex. "a.e.t.", null for the regular time), `result` (ex. "Spain 2 - 1 Italy (a.e.t.)"), `kickoff` and `finish`
+/// * `game_count` and `result_count` - the numbers of the running and the finished games
+///
+/// The times are in UTC, in the RFC 3339 format, ex. "2022-12-18T15:00:00Z". Inside of `{{#each}}`, the data of the board is reached with `@root`, ex. `{{@root.game_count}}`
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::PageTemplate;
+///
+/// assert!(PageTemplate::parse("Results:\n{{#each results}}{{{result}}}\n{{/each}}").is_ok());
+/// assert!(PageTemplate::parse("{{#each games}}{{home}}").is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PageTemplate {
+	/// The registry holding the parsed template
+	registry: Handlebars<'static>,
+}
+
+impl PageTemplate {
+	/// Parses a page template
+	///
+	/// # Arguments
+	///
+	/// * `text` - the text of the template
+	///
+	/// # Errors
+	///
+	/// * When the template isn't valid Handlebars, ex. `{{home` or `{{#each games}}` without `{{/each}}`
+	pub fn parse(text: &str) -> Result<PageTemplate, ScoreBoardError> {
+		trace!("Parsing a page template of {} bytes", text.len());
+
+		let mut registry = Handlebars::new();
+		registry.set_strict_mode(true);
+
+		if let Err(error) = registry.register_template_string(PAGE, text) {
+			warn!("Couldn't parse the page template: {}", error);
+			return Err(ScoreBoardError::InvalidPageTemplate(error.to_string()));
+		}
+
+		Ok(PageTemplate { registry })
+	}
+}
+
+impl ScoreBoard {
+	/// Renders a page from a template, filled with the running games and the finished ones, see `PageTemplate` for the data of the board
+	///
+	/// # Arguments
+	///
+	/// * `template` - the template of the page
+	///
+	/// # Errors
+	///
+	/// * When the template uses a value the board doesn't have, or a helper fails, ex. `{{#each}}` over a number
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Decision, PageTemplate};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Italy").unwrap();
+	/// sb.update_score("Spain", 2, "Italy", 1).unwrap();
+	/// sb.finish_game_after("Spain", "Italy", Decision::AfterExtraTime).unwrap();
+	/// sb.start_game("Bosnia & Herzegovina", "Japan").unwrap();
+	///
+	/// let template = PageTemplate::parse("<h1>{{result_count}} result(s)</h1>\n{{#each results}}<p>{{result}}</p>\n{{/each}}{{#each games}}<p>Live: {{home}} {{home_score}} - {{away}} {{away_score}}</p>{{/each}}").unwrap();
+	/// assert_eq!(sb.render_page(&template).unwrap(), "<h1>1 result(s)</h1>\n<p>Spain 2 - 1 Italy (a.e.t.)</p>\n<p>Live: Bosnia &amp; Herzegovina 0 - Japan 0</p>");
+	/// ```
+	pub fn render_page(&self, template: &PageTemplate) -> Result<String, ScoreBoardError> {
+		trace!("Rendering a page of {} running and {} finished games", self.data.len(), self.archive.len());
+
+		let now = Instant::now();
+		let half_minutes = self.get_half_minutes();
+		let games: Vec<GameData> = self.get_games().into_iter()
+			.zip(&self.data)
+			.map(|(view, game)| GameData::new(view, game.get_minute(now, half_minutes)))
+			.collect();
+		let results: Vec<ResultData> = self.archive.iter().map(ResultData::new).collect();
+
+		let data = PageData {
+			game_count: games.len(),
+			result_count: results.len(),
+			games,
+			results,
+		};
+
+		template.registry.render(PAGE, &data).map_err(|error| {
+			warn!("Couldn't render the page: {}", error);
+			ScoreBoardError::InvalidPageTemplate(error.to_string())
+		})
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::archive::Decision;
+	use crate::period::Period;
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Croatia", "Brazil").expect("Couldn't create the first game");
+		sb.update_score("Croatia", 1, "Brazil", 1).expect("Couldn't update the first game");
+		sb.finish_game_after("Croatia", "Brazil", Decision::AfterPenalties { home: 4, away: 2 }).expect("Couldn't finish the first game");
+		sb.start_game("Morocco", "Portugal").expect("Couldn't create the second game");
+		sb.update_score("Morocco", 1, "Portugal", 0).expect("Couldn't update the second game");
+		sb.start_period("Morocco", "Portugal", Period::SecondHalf).expect("Couldn't start the second half");
+		sb
+	}
+
+	fn render(sb: &ScoreBoard, template: &str) -> Result<String, ScoreBoardError> {
+		sb.render_page(&PageTemplate::parse(template).expect("Couldn't parse the template"))
+	}
+
+	#[test]
+	fn bulletin_lists_the_results_and_the_games() {
+		let sb = get_board();
+		let page = render(&sb, "{{! nightly bulletin }}{{#each results}}{{home}} {{home_score}}-{{away_score}} {{away}}{{#if decision}} ({{decision}}){{/if}}\n{{/each}}{{#each games}}{{home}} - {{away}}, {{period}}, HT {{half_time}}, {{state}}{{/each}}");
+
+		assert_eq!(page, Ok(String::from("Croatia 1-1 Brazil (a.e.t., 4-2 pen.)\nMorocco - Portugal, second half, HT 1-0, live")));
+	}
+
+	#[test]
+	fn empty_lists_show_the_other_part() {
+		let sb = ScoreBoard::new();
+
+		assert_eq!(render(&sb, "{{#if games}}Live{{else}}No games, {{game_count}} running{{/if}}"), Ok(String::from("No games, 0 running")));
+		assert_eq!(render(&sb, "[{{#each results}}{{result}}{{/each}}]"), Ok(String::from("[]")));
+	}
+
+	#[test]
+	fn values_are_escaped_unless_raw() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Trinidad & Tobago", "<Haiti>").expect("Couldn't create the game");
+
+		assert_eq!(render(&sb, "{{#each games}}{{home}} {{{away}}}{{/each}}"), Ok(String::from("Trinidad &amp; Tobago <Haiti>")));
+	}
+
+	#[test]
+	fn board_values_are_seen_inside_the_lists() {
+		let sb = get_board();
+
+		assert_eq!(render(&sb, "{{#each games}}{{home}} of {{@root.game_count}}{{/each}}"), Ok(String::from("Morocco of 1")));
+	}
+
+	#[test]
+	fn unclosed_parts_are_rejected() {
+		for template in ["{{home", "{{{home}}", "{{#each games}}{{home}}", "{{#if games}}{{else}}", "{{/each}}", "{{#each games}}{{/if}}"] {
+			assert!(matches!(PageTemplate::parse(template), Err(ScoreBoardError::InvalidPageTemplate(_))), "{} was accepted", template);
+		}
+	}
+
+	#[test]
+	fn missing_details_are_null() {
+		let sb = get_board();
+
+		assert_eq!(render(&sb, "{{#each games}}{{#if venue}}at {{venue}}{{else}}no venue{{/if}}, {{home_score}}{{/each}}"), Ok(String::from("no venue, 1")));
+		assert_eq!(render(&sb, "{{#each results}}{{#with decision}}{{this}}{{/with}}{{/each}}"), Ok(String::from("a.e.t., 4-2 pen.")));
+	}
+
+	#[test]
+	fn unknown_values_are_rejected_at_rendering() {
+		let sb = get_board();
+
+		assert!(matches!(render(&sb, "{{score}}"), Err(ScoreBoardError::InvalidPageTemplate(reason)) if reason.contains("score")));
+		assert!(render(&sb, "{{#each games}}{{kickof}}{{/each}}").is_err());
+		assert!(render(&sb, "{{home score}}").is_err());
+	}
+}
@@ -0,0 +1,392 @@
+//! # Periods of a game
+//!
+//! Tracks the score at the start of every period (half, extra time), so the score can be split per period, ex. for the half-time score
+
+use std::fmt;
+use std::string::ToString;
+use std::time::Instant;
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::events::ScoreBoardEvent;
+use crate::{Game, ScoreBoard};
+
+/// A period of a game. Every game starts in the first half and the periods can only move forward
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Period {
+	/// The first half, from the kickoff
+	FirstHalf,
+	/// The second half, after the half-time break
+	SecondHalf,
+	/// Both halves of the extra time
+	ExtraTime,
+}
+
+impl fmt::Display for Period {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Period::FirstHalf => write!(f, "first half"),
+			Period::SecondHalf => write!(f, "second half"),
+			Period::ExtraTime => write!(f, "extra time"),
+		}
+	}
+}
+
+impl Period {
+	/// Name of the period, as used in the JSON presentation of the events and in the journal
+	pub(crate) fn get_name(&self) -> &'static str {
+		match self {
+			Period::FirstHalf => "first_half",
+			Period::SecondHalf => "second_half",
+			Period::ExtraTime => "extra_time",
+		}
+	}
+}
+
+/// The goals scored in a single period
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PeriodScore {
+	/// The period
+	pub period: Period,
+	/// Goals of the home team in the period
+	pub home_score: u8,
+	/// Goals of the away team in the period
+	pub away_score: u8,
+}
+
+/// Splits the score of a game into periods
+///
+/// # Arguments
+///
+/// * `starts` - every entered period with the score at its start, the earliest coming first
+/// * `score` - the current score of the home and the away team
+///
+pub(crate) fn split_periods(starts: &[(Period, (u8, u8))], score: (u8, u8)) -> Vec<PeriodScore> {
+	starts.iter().enumerate().map(|(index, (period, (home_start, away_start)))| {
+		let (home_end, away_end) = starts.get(index + 1).map_or(score, |(_, start)| *start);
+
+		PeriodScore { period: *period, home_score: home_end.saturating_sub(*home_start), away_score: away_end.saturating_sub(*away_start) }
+	}).collect()
+}
+
+impl Game {
+	/// Freezes the half-time score, when the half-time break starts or, without a break, when the second half starts: the score at the start of the break, if the game is paused, otherwise the current score. A score frozen once is never replaced
+	pub(crate) fn freeze_half_time_score(&mut self) {
+		if self.half_time_score.is_none() {
+			self.half_time_score = Some(self.pause_score.unwrap_or((self.home_team.score, self.away_team.score)));
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Provides the half-time score of a running game, apart from its live score. The score is frozen as the half-time break starts, when the game is paused after the length of the first half was played (see `set_game_state()` and `set_half_length()`), the same moment the minute of the match turns to "HT". A game that goes to the second half without such a break (see `start_period()`) has the score frozen then: the score at the start of the pause, if it is paused, otherwise the current score. Once frozen, the score stays, also when the second half starts after the break was resumed in the added time of the first half. The earlier pauses of the first half, ex. for an injury, don't count, and the later corrections of the score don't change it
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name or alias of the home team
+	/// * `away` - Name or alias of the away team
+	///
+	/// # Returns
+	///
+	/// * The scores of the home and the away team at the half-time, or `None` if the game hasn't reached the break nor the second half, or there is no active match between the given teams
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// use scoreboard_world_cup::{GameState, Period};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.new_game("Japan", "Indonesia").kickoff_at(SystemTime::now() - Duration::from_secs(47 * 60)).start().unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// assert_eq!(sb.half_time_score("Japan", "Indonesia"), None);
+	///
+	/// sb.set_game_state("Japan", "Indonesia", GameState::Paused).unwrap();
+	/// assert_eq!(sb.half_time_score("Japan", "Indonesia"), Some((1, 0)));
+	/// sb.update_score("Japan", 1, "Indonesia", 1).unwrap();
+	/// sb.start_period("Japan", "Indonesia", Period::SecondHalf).unwrap();
+	/// assert_eq!(sb.half_time_score("Japan", "Indonesia"), Some((1, 0)));
+	/// assert_eq!(sb.get_score("Japan", "Indonesia"), Some((1, 1)));
+	/// ```
+	pub fn half_time_score(&self, home: &str, away: &str) -> Option<(u8, u8)> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		self.find_game_index(&home_name, &away_name).and_then(|game_index| self.data[game_index].half_time_score)
+	}
+
+	/// Moves a running match to the next period, ex. to the second half after the break. The goals scored from now on count for the new period. Periods can be skipped (ex. when the first half wasn't followed live), but not entered again. The change is recorded as `ScoreBoardEvent::PeriodStarted`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `period` - The period that begins
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	/// * When the match has already reached the period or a later one
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Period;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// sb.start_period("Japan", "Indonesia", Period::SecondHalf).unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 1).unwrap();
+	/// assert_eq!(sb.half_time_score("Japan", "Indonesia"), Some((1, 0)));
+	/// ```
+	pub fn start_period<T: ToString, U: ToString>(&mut self, home: T, away: U, period: Period) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "start_period", home = %home_name, away = %away_name, period = %period);
+
+		trace!("Starting the {} of '{}' - '{}'", period, home_name, away_name);
+
+		let game_index = match self.find_game_index(&home_name, &away_name) {
+			Some(game_index) => game_index,
+			None => {
+				warn!("Couldn't find a game for period change");
+				return Err(self.game_not_found(home_name, away_name, Operation::StartPeriod));
+			},
+		};
+
+		let game = &self.data[game_index];
+		let current = game.get_period();
+		if period <= current {
+			warn!("The {} cannot follow the {}", period, current);
+			return Err(ScoreBoardError::PeriodOrder { current, requested: period });
+		}
+
+		let event = ScoreBoardEvent::PeriodStarted { home: home_name, home_score: game.home_team.score, away: away_name, away_score: game.away_team.score, period };
+		self.write_ahead(&event)?;
+
+		let game = &mut self.data[game_index];
+		game.periods.push((period, (game.home_team.score, game.away_team.score)));
+		if period == Period::SecondHalf {
+			game.freeze_half_time_score();
+		}
+		game.clock.start_period(Instant::now());
+		game.revision += 1;
+		let revision = game.revision;
+
+		self.record_event(event, revision);
+		self.invalidate_summary();
+
+		trace!("Period started");
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+	use std::time::{Duration, SystemTime};
+
+	use crate::state::GameState;
+
+	const HOME_TEAM_NAME: &str = "Spain";
+	const AWAY_TEAM_NAME: &str = "Italy";
+
+	fn update(sb: &mut ScoreBoard, home_score: u8, away_score: u8) {
+		sb.update_score(HOME_TEAM_NAME, home_score, AWAY_TEAM_NAME, away_score).expect("Couldn't update the game");
+	}
+
+	fn next_period(sb: &mut ScoreBoard, period: Period) {
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, period).expect("Couldn't start the period");
+	}
+
+	#[test]
+	fn score_is_split_per_period() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+		next_period(&mut sb, Period::SecondHalf);
+		update(&mut sb, 1, 1);
+		next_period(&mut sb, Period::ExtraTime);
+		update(&mut sb, 2, 1);
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.period, Period::ExtraTime);
+		assert_eq!(game.half_time_score, Some((1, 0)));
+		assert_eq!(game.periods, vec![
+			PeriodScore { period: Period::FirstHalf, home_score: 1, away_score: 0 },
+			PeriodScore { period: Period::SecondHalf, home_score: 0, away_score: 1 },
+			PeriodScore { period: Period::ExtraTime, home_score: 1, away_score: 0 },
+		]);
+	}
+
+	#[test]
+	fn first_half_has_no_half_time_score() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.period, Period::FirstHalf);
+		assert_eq!(game.half_time_score, None);
+		assert_eq!(game.get_half_time_caption(), None);
+	}
+
+	#[test]
+	fn periods_cannot_go_back() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		next_period(&mut sb, Period::SecondHalf);
+
+		let result = sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::FirstHalf);
+		assert!(result.err().is_some_and(|result| result.to_string() == "The first half cannot follow the second half"));
+		let result = sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn skipped_periods_have_no_split() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 1, 1);
+		next_period(&mut sb, Period::ExtraTime);
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.half_time_score, None);
+		assert_eq!(game.periods.len(), 2);
+	}
+
+	#[test]
+	fn lowered_score_doesnt_underflow() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 2, 0);
+		next_period(&mut sb, Period::SecondHalf);
+		update(&mut sb, 1, 0);
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(game.periods[1], PeriodScore { period: Period::SecondHalf, home_score: 0, away_score: 0 });
+	}
+
+	#[test]
+	fn period_of_missing_game_is_rejected() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for period change"));
+	}
+
+	#[test]
+	fn period_change_is_recorded() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Spain 1 - Italy 0 (1')")]);
+		next_period(&mut sb, Period::SecondHalf);
+
+		let record = sb.get_events().last().expect("Couldn't find the event");
+		assert_eq!(record.event, ScoreBoardEvent::PeriodStarted { home: String::from(HOME_TEAM_NAME), home_score: 1, away: String::from(AWAY_TEAM_NAME), away_score: 0, period: Period::SecondHalf });
+		assert_eq!(record.revision, 3);
+		assert!(record.to_json().contains("\"type\":\"period_started\""));
+		assert!(record.to_json().ends_with(",\"period\":\"second_half\"}"));
+		assert_eq!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).map(|game| game.revision), Some(3));
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Spain 1 - Italy 0 (46')")]);
+	}
+
+	#[test]
+	fn half_time_score_is_frozen_at_the_break() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 2, 1);
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+		update(&mut sb, 2, 2);
+		next_period(&mut sb, Period::SecondHalf);
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Live).expect("Couldn't resume the game");
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game");
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((2, 1)));
+		assert_eq!(game.get_half_time_caption(), Some(String::from("HT: 2-1")));
+		assert_eq!((game.home_score, game.away_score), (2, 2));
+	}
+
+	#[test]
+	fn half_time_score_is_known_during_the_break() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).kickoff_at(SystemTime::now() - Duration::from_secs(47 * 60)).start().expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+
+		assert_eq!(sb.get_summary_with_minutes(), vec![String::from("Spain 1 - Italy 0 (HT)")]);
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+		update(&mut sb, 1, 1);
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+
+		next_period(&mut sb, Period::SecondHalf);
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+	}
+
+	#[test]
+	fn second_half_doesnt_replace_the_frozen_score() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).kickoff_at(SystemTime::now() - Duration::from_secs(47 * 60)).start().expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Live).expect("Couldn't resume the game");
+		update(&mut sb, 1, 1);
+		next_period(&mut sb, Period::SecondHalf);
+
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+	}
+
+	#[test]
+	fn half_time_score_is_frozen_at_the_second_half_without_a_break() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 0, 1);
+		next_period(&mut sb, Period::SecondHalf);
+		update(&mut sb, 3, 1);
+
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((0, 1)));
+		assert_eq!(sb.half_time_score(AWAY_TEAM_NAME, HOME_TEAM_NAME), None);
+	}
+
+	#[test]
+	fn pause_within_the_first_half_isnt_the_break() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Paused).expect("Couldn't pause the game");
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), None);
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Live).expect("Couldn't resume the game");
+		update(&mut sb, 1, 0);
+		next_period(&mut sb, Period::SecondHalf);
+
+		assert_eq!(sb.half_time_score(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some((1, 0)));
+	}
+
+	#[test]
+	fn half_time_score_follows_the_aliases() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("ESP", HOME_TEAM_NAME);
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		update(&mut sb, 1, 0);
+		next_period(&mut sb, Period::SecondHalf);
+
+		assert_eq!(sb.half_time_score("ESP", AWAY_TEAM_NAME), Some((1, 0)));
+	}
+}
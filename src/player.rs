@@ -0,0 +1,142 @@
+//! # Players
+//!
+//! The players of the teams, registered once and referenced by the goals, so the statistics of a player are consistent across the games
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// A player of a team
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Player {
+	/// Name of the player
+	pub name: String,
+	/// Shirt number of the player, if the player was registered with `ScoreBoard::register_player()`
+	pub number: Option<u8>,
+	/// Name of the player's team
+	pub team: String,
+}
+
+impl ScoreBoard {
+	/// Registers a player of a team with the shirt number. Registering a player again changes the number. The goals recorded with `record_goal_by()` refer to the registered player
+	///
+	/// # Arguments
+	///
+	/// * `team` - Name of the team. Must be either a `String` or a type that is convertable to `String`
+	/// * `name` - Name of the player. Must be either a `String` or a type that is convertable to `String`
+	/// * `number` - Shirt number of the player
+	///
+	/// # Errors
+	///
+	/// * When another player of the team already has the number
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.register_player("Argentina", "Messi", 10).unwrap();
+	/// sb.register_player("Argentina", "Alvarez", 9).unwrap();
+	/// assert!(sb.register_player("Argentina", "Otamendi", 10).is_err());
+	///
+	/// let numbers: Vec<Option<u8>> = sb.get_players("Argentina").iter().map(|player| player.number).collect();
+	/// assert_eq!(numbers, vec![Some(9), Some(10)]);
+	/// ```
+	pub fn register_player<T: ToString, U: ToString>(&mut self, team: T, name: U, number: u8) -> Result<(), ScoreBoardError> {
+		let team_name = self.resolve_team(team);
+		let name = name.to_string();
+
+		trace!("Registering {} of {} with number {}", name, team_name, number);
+
+		if self.players.get(&team_name).is_some_and(|players| players.iter().any(|player| player.number == Some(number) && player.name != name)) {
+			warn!("Number {} of {} is already taken", number, team_name);
+			return Err(ScoreBoardError::NumberTaken { team: team_name, number });
+		}
+
+		self.write_ahead_change(&["player", &team_name, &name, &number.to_string()])?;
+
+		let players = self.players.entry(team_name.clone()).or_default();
+		players.retain(|player| player.name != name);
+		let position = players.partition_point(|player| player.number < Some(number));
+		players.insert(position, Player { name, number: Some(number), team: team_name });
+
+		Ok(())
+	}
+
+	/// Provides the registered players of a team, ordered by their shirt numbers
+	///
+	/// # Arguments
+	///
+	/// * `team` - Name of the team
+	///
+	pub fn get_players(&self, team: &str) -> &[Player] {
+		self.players.get(self.resolve_name(team)).map_or(&[], Vec::as_slice)
+	}
+
+	/// Provides a player of a team: the registered one, or an unregistered one without a shirt number
+	///
+	/// # Arguments
+	///
+	/// * `team` - name of the team, after following the aliases
+	/// * `name` - name of the player
+	///
+	pub(crate) fn get_player(&self, team: &str, name: String) -> Player {
+		match self.get_players(team).iter().find(|player| player.name == name) {
+			Some(player) => player.clone(),
+			None => Player { name, number: None, team: String::from(team) },
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn registering_again_changes_the_number() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player("Argentina", "Messi", 19).expect("Couldn't register the player");
+		sb.register_player("Argentina", "Messi", 10).expect("Couldn't register the player again");
+
+		assert_eq!(sb.get_players("Argentina"), [Player { name: String::from("Messi"), number: Some(10), team: String::from("Argentina") }]);
+	}
+
+	#[test]
+	fn numbers_are_unique_per_team() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player("Argentina", "Messi", 10).expect("Couldn't register the first player");
+		sb.register_player("France", "Benzema", 10).expect("Couldn't register the second player");
+
+		let result = sb.register_player("Argentina", "Dybala", 10);
+		assert_eq!(result.map_err(|error| error.to_string()), Err(String::from("Number 10 of Argentina is already taken")));
+		assert_eq!(sb.get_players("Argentina").len(), 1);
+	}
+
+	#[test]
+	fn players_follow_the_aliases() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("ARG", "Argentina");
+		sb.register_player("ARG", "Messi", 10).expect("Couldn't register the player");
+
+		assert_eq!(sb.get_players("Argentina").len(), 1);
+		assert_eq!(sb.get_players("ARG").len(), 1);
+		assert!(sb.get_players("France").is_empty());
+	}
+
+	#[test]
+	fn unregistered_player_has_no_number() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player("Argentina", "Messi", 10).expect("Couldn't register the player");
+
+		assert_eq!(sb.get_player("Argentina", String::from("Messi")).number, Some(10));
+		assert_eq!(sb.get_player("Argentina", String::from("Alvarez")), Player { name: String::from("Alvarez"), number: None, team: String::from("Argentina") });
+	}
+}
@@ -0,0 +1,306 @@
+//! # PNG rendering
+//!
+//! Draws the summary of the board into a PNG image of a requested resolution, for the signage players that only show static images refreshed every few seconds. Available with the `image` feature
+
+use std::convert::Infallible;
+use std::vec::Vec;
+
+use embedded_graphics::mono_font::iso_8859_1::FONT_10X20;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::{Rgb888, RgbColor};
+use embedded_graphics::prelude::{DrawTarget, OriginDimensions, Pixel, Point, Size};
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::Drawable;
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// Largest number of the pixels of an image, so a mistyped resolution doesn't exhaust the memory
+const MAX_PIXELS: u64 = 8192 * 8192;
+
+/// Signature at the start of every PNG file
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Compression level of the image data, from 0 (none) to 10 (best)
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// Colors of the PNG image of the board, see `ScoreBoard::render_png()`
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use scoreboard_world_cup::PngStyle;
+///
+/// let style = PngStyle { text_color: Rgb888::new(241, 196, 15), ..PngStyle::default() };
+/// assert_eq!(style.background_color, Rgb888::new(11, 30, 63));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PngStyle {
+	/// Color of the background of the image
+	pub background_color: Rgb888,
+	/// Colors of the odd and the even rows, counting from 1
+	pub row_colors: (Rgb888, Rgb888),
+	/// Color of the text
+	pub text_color: Rgb888,
+}
+
+impl Default for PngStyle {
+	/// Implementation of `Default` trait, with the colors of `SvgStyle::default()`
+	fn default() -> PngStyle {
+		PngStyle {
+			background_color: Rgb888::new(11, 30, 63),
+			row_colors: (Rgb888::new(20, 48, 95), Rgb888::new(16, 40, 81)),
+			text_color: Rgb888::WHITE,
+		}
+	}
+}
+
+/// RGB pixels of an image, drawn on by `embedded-graphics` through a magnifying glass: every drawn pixel becomes a square of `scale` pixels, placed from `origin`
+struct Canvas {
+	/// Width of the image
+	width: u32,
+	/// Height of the image
+	height: u32,
+	/// Rows of the pixels, from the top
+	pixels: Vec<Rgb888>,
+	/// Side of the square of a drawn pixel
+	scale: u32,
+	/// Place of the drawn point (0, 0) in the image
+	origin: Point,
+}
+
+impl Canvas {
+	/// Paints a rectangle of the image, cut at the edges
+	///
+	/// # Arguments
+	///
+	/// * `x`, `y` - the top left corner
+	/// * `width`, `height` - the size
+	/// * `color` - the paint
+	///
+	fn fill(&mut self, x: i64, y: i64, width: u32, height: u32, color: Rgb888) {
+		let left = x.clamp(0, i64::from(self.width)) as usize;
+		let right = (x + i64::from(width)).clamp(0, i64::from(self.width)) as usize;
+		let top = y.clamp(0, i64::from(self.height)) as usize;
+		let bottom = (y + i64::from(height)).clamp(0, i64::from(self.height)) as usize;
+
+		for row in top..bottom {
+			let start = row * self.width as usize;
+			self.pixels[start + left..start + right].fill(color);
+		}
+	}
+
+	/// Encodes the image as PNG, with 8 bits per color and no transparency
+	fn encode(&self) -> Vec<u8> {
+		let mut data = Vec::with_capacity((self.width as usize * 3 + 1) * self.height as usize);
+		for row in self.pixels.chunks(self.width as usize) {
+			// Every row starts with its filter, none here
+			data.push(0);
+			for pixel in row {
+				data.extend_from_slice(&[pixel.r(), pixel.g(), pixel.b()]);
+			}
+		}
+
+		let mut header = Vec::with_capacity(13);
+		header.extend_from_slice(&self.width.to_be_bytes());
+		header.extend_from_slice(&self.height.to_be_bytes());
+		// Bit depth, color type (RGB), compression, filter and interlace methods
+		header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+		let mut png = Vec::from(PNG_SIGNATURE);
+		push_chunk(&mut png, b"IHDR", &header);
+		push_chunk(&mut png, b"IDAT", &miniz_oxide::deflate::compress_to_vec_zlib(&data, COMPRESSION_LEVEL));
+		push_chunk(&mut png, b"IEND", &[]);
+
+		png
+	}
+}
+
+impl OriginDimensions for Canvas {
+	/// Implementation of `OriginDimensions` trait, with the size in the drawn pixels
+	fn size(&self) -> Size {
+		Size::new(self.width / self.scale, self.height / self.scale)
+	}
+}
+
+impl DrawTarget for Canvas {
+	type Color = Rgb888;
+	type Error = Infallible;
+
+	/// Implementation of `DrawTarget` trait, painting the squares of the drawn pixels
+	fn draw_iter<I: IntoIterator<Item = Pixel<Rgb888>>>(&mut self, pixels: I) -> Result<(), Infallible> {
+		let scale = i64::from(self.scale);
+
+		for Pixel(point, color) in pixels {
+			self.fill(i64::from(self.origin.x) + i64::from(point.x) * scale, i64::from(self.origin.y) + i64::from(point.y) * scale, self.scale, self.scale, color);
+		}
+
+		Ok(())
+	}
+}
+
+/// Appends a chunk to a PNG file: its length, type, data and checksum
+///
+/// # Arguments
+///
+/// * `png` - the file
+/// * `kind` - type of the chunk
+/// * `data` - content of the chunk
+///
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+	png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+	let start = png.len();
+	png.extend_from_slice(kind);
+	png.extend_from_slice(data);
+	let checksum = crc32(&png[start..]);
+	png.extend_from_slice(&checksum.to_be_bytes());
+}
+
+/// Computes the CRC-32 checksum of the PNG chunks
+fn crc32(bytes: &[u8]) -> u32 {
+	let mut crc = u32::MAX;
+
+	for byte in bytes {
+		crc ^= u32::from(*byte);
+		for _ in 0..8 {
+			crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+		}
+	}
+
+	!crc
+}
+
+impl ScoreBoard {
+	/// Renders the summary of the running games as a PNG image of the given resolution, one row per game in the order of `get_summary()`. The text is as large as the image allows, with the same size for all the games, and the lines that are too long for the width are cut
+	///
+	/// # Arguments
+	///
+	/// * `width` - width of the image in pixels
+	/// * `height` - height of the image in pixels
+	/// * `style` - the colors of the image
+	///
+	/// # Errors
+	///
+	/// * When the resolution is empty or larger than 8192 x 8192 pixels
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::PngStyle;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	///
+	/// let png = sb.render_png(1920, 1080, &PngStyle::default()).unwrap();
+	/// assert!(png.starts_with(b"\x89PNG"));
+	/// ```
+	pub fn render_png(&self, width: u32, height: u32, style: &PngStyle) -> Result<Vec<u8>, ScoreBoardError> {
+		trace!("Rendering the score board as a {}x{} PNG image", width, height);
+
+		if width == 0 || height == 0 || u64::from(width) * u64::from(height) > MAX_PIXELS {
+			warn!("Images of {}x{} pixels can't be rendered", width, height);
+			return Err(ScoreBoardError::InvalidImageSize { width, height });
+		}
+
+		let summary = self.get_summary();
+		let rows = summary.len().max(1) as u32;
+		let row_height = (height / rows).max(1);
+		let margin = width / 40;
+		let columns = summary.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u32;
+		let font_size = FONT_10X20.character_size;
+		// The largest magnification of the font that fits both a row and the longest line, but at least 1
+		let scale = (row_height / (font_size.height + 2)).min((width - 2 * margin) / (font_size.width * columns).max(1)).max(1);
+
+		let mut canvas = Canvas { width, height, pixels: vec![style.background_color; width as usize * height as usize], scale, origin: Point::zero() };
+		let text_style = MonoTextStyle::new(&FONT_10X20, style.text_color);
+
+		for (id, line) in summary.iter().enumerate() {
+			let top = id as u32 * row_height;
+			let row_color = if id % 2 == 0 { style.row_colors.0 } else { style.row_colors.1 };
+			canvas.fill(0, i64::from(top), width, row_height, row_color);
+
+			let text_top = top + row_height.saturating_sub(font_size.height * scale) / 2;
+			canvas.origin = Point::new(margin as i32, text_top as i32);
+			let Ok(_) = Text::with_baseline(line, Point::zero(), text_style, Baseline::Top).draw(&mut canvas);
+		}
+
+		Ok(canvas.encode())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Reads the width and the height of a PNG image from its header
+	fn get_size(png: &[u8]) -> (u32, u32) {
+		let width = u32::from_be_bytes(png[16..20].try_into().expect("Couldn't read the width"));
+		let height = u32::from_be_bytes(png[20..24].try_into().expect("Couldn't read the height"));
+		(width, height)
+	}
+
+	/// Decodes the pixels of a PNG image made by `render_png()`
+	fn get_pixels(png: &[u8]) -> Vec<u8> {
+		let length = u32::from_be_bytes(png[33..37].try_into().expect("Couldn't read the data length")) as usize;
+		assert_eq!(&png[37..41], b"IDAT");
+		miniz_oxide::inflate::decompress_to_vec_zlib(&png[41..41 + length]).expect("Couldn't decompress the data")
+	}
+
+	#[test]
+	fn image_has_the_requested_resolution() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let png = sb.render_png(320, 200, &PngStyle::default()).expect("Couldn't render the image");
+
+		assert_eq!(&png[..8], &PNG_SIGNATURE);
+		assert_eq!(&png[12..16], b"IHDR");
+		assert_eq!(get_size(&png), (320, 200));
+		assert_eq!(get_pixels(&png).len(), (320 * 3 + 1) * 200);
+		assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+	}
+
+	#[test]
+	fn rows_and_text_are_painted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the first game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the second game");
+		let style = PngStyle { background_color: Rgb888::BLACK, row_colors: (Rgb888::RED, Rgb888::BLUE), text_color: Rgb888::WHITE };
+		let pixels = get_pixels(&sb.render_png(400, 100, &style).expect("Couldn't render the image"));
+		let get_pixel = |x: usize, y: usize| &pixels[y * (400 * 3 + 1) + 1 + x * 3..][..3];
+
+		assert_eq!(get_pixel(0, 0), [255, 0, 0]);
+		assert_eq!(get_pixel(0, 99), [0, 0, 255]);
+		assert!((0..50).any(|y| (0..400).any(|x| get_pixel(x, y) == [255, 255, 255])));
+		assert!((50..100).any(|y| (0..400).any(|x| get_pixel(x, y) == [255, 255, 255])));
+	}
+
+	#[test]
+	fn empty_board_has_only_the_background() {
+		let sb = ScoreBoard::new();
+		let style = PngStyle { background_color: Rgb888::BLACK, row_colors: (Rgb888::RED, Rgb888::BLUE), text_color: Rgb888::WHITE };
+		let pixels = get_pixels(&sb.render_png(2, 2, &style).expect("Couldn't render the image"));
+
+		assert_eq!(pixels, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn invalid_resolutions_are_rejected() {
+		let sb = ScoreBoard::new();
+
+		for (width, height) in [(0, 100), (100, 0), (8193, 8192)] {
+			assert_eq!(sb.render_png(width, height, &PngStyle::default()), Err(ScoreBoardError::InvalidImageSize { width, height }));
+		}
+	}
+
+	#[test]
+	fn checksum_is_the_standard_one() {
+		assert_eq!(crc32(b"IEND"), 0xae42_6082);
+		assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+	}
+}
@@ -0,0 +1,268 @@
+//! # Matchup policy
+//!
+//! Restricts the pairings that may start on the board, ex. to the fixtures of the loaded schedule or to the teams of the same group, so a typo or a wrong feed can't start a game that isn't part of the tournament
+
+use std::collections::HashMap;
+use std::fmt;
+use std::string::String;
+
+use log::{trace, warn};
+
+use crate::draw::Group;
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// The pairings allowed to start on the score board, see `ScoreBoard::set_matchup_policy()`. A new policy allows all the pairings, and every rule added to it has to be met
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{draw_groups, Confederation, DrawRules, DrawTeam, MatchupPolicy};
+///
+/// let pots = vec![
+///     vec![DrawTeam::new("Qatar", Confederation::Afc), DrawTeam::new("Brazil", Confederation::Conmebol)],
+///     vec![DrawTeam::new("Ecuador", Confederation::Conmebol), DrawTeam::new("Serbia", Confederation::Uefa)],
+/// ];
+/// let groups = draw_groups(&pots, &DrawRules::new(), 2022).unwrap();
+///
+/// let policy = MatchupPolicy::new().within_groups(&groups);
+/// assert_eq!(policy.get_group("Qatar"), policy.get_group("Ecuador"));
+/// assert_ne!(policy.get_group("Qatar"), policy.get_group("Brazil"));
+/// assert!(!policy.is_scheduled_only());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MatchupPolicy {
+	/// Whether only the games from the schedule can start
+	scheduled_only: bool,
+	/// Letters of the groups by the names of their teams, when only the teams of the same group can play
+	groups: Option<HashMap<String, char>>,
+}
+
+impl MatchupPolicy {
+	/// Returns a policy allowing all the pairings
+	pub fn new() -> MatchupPolicy {
+		MatchupPolicy::default()
+	}
+
+	/// Allows only the games scheduled with `ScoreBoard::schedule_game()`, with the same home and away team
+	pub fn scheduled_only(mut self) -> MatchupPolicy {
+		self.scheduled_only = true;
+		self
+	}
+
+	/// Allows only the games between the teams of the same group, ex. during the group stage. Teams outside all the groups can't play
+	///
+	/// # Arguments
+	///
+	/// * `groups` - the groups of the tournament, see `draw_groups()`
+	pub fn within_groups(mut self, groups: &[Group]) -> MatchupPolicy {
+		self.groups = Some(groups.iter()
+			.flat_map(|group| group.teams.iter().map(move |team| (team.name.clone(), group.letter)))
+			.collect());
+		self
+	}
+
+	/// Checks if only the scheduled games can start
+	pub fn is_scheduled_only(&self) -> bool {
+		self.scheduled_only
+	}
+
+	/// Provides the letter of the group of a team, when the policy keeps the games within the groups
+	///
+	/// # Arguments
+	///
+	/// * `team` - name of the team
+	pub fn get_group(&self, team: &str) -> Option<char> {
+		self.groups.as_ref().and_then(|groups| groups.get(team).copied())
+	}
+}
+
+/// The rule of a `MatchupPolicy` that doesn't allow a game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+	/// The game isn't in the schedule
+	NotScheduled {
+		/// Name of the home team
+		home: String,
+		/// Name of the away team
+		away: String,
+	},
+	/// The team isn't in any of the groups
+	NoGroup(String),
+	/// The teams are in different groups
+	DifferentGroups {
+		/// Name of the home team
+		home: String,
+		/// Letter of the group of the home team
+		home_group: char,
+		/// Name of the away team
+		away: String,
+		/// Letter of the group of the away team
+		away_group: char,
+	},
+}
+
+impl fmt::Display for PolicyViolation {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			PolicyViolation::NotScheduled { home, away } => write!(f, "The game between {} and {} is not scheduled", home, away),
+			PolicyViolation::NoGroup(team) => write!(f, "{} is not in any of the groups", team),
+			PolicyViolation::DifferentGroups { home, home_group, away, away_group } => {
+				write!(f, "{} from group {} cannot play {} from group {}", home, home_group, away, away_group)
+			},
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Restricts the pairings that may start on the board. Starting any other game fails with `ScoreBoardError::PolicyViolation`, while the games already running are kept
+	///
+	/// # Arguments
+	///
+	/// * `policy` - the allowed pairings
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	///
+	/// use scoreboard_world_cup::{MatchupPolicy, PolicyViolation, ScoreBoardError};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.schedule_game("Japan", "Indonesia", SystemTime::now() + Duration::from_secs(3600)).unwrap();
+	/// sb.set_matchup_policy(MatchupPolicy::new().scheduled_only());
+	///
+	/// let result = sb.start_game("Japan", "Spain");
+	/// assert_eq!(result, Err(ScoreBoardError::PolicyViolation(PolicyViolation::NotScheduled { home: String::from("Japan"), away: String::from("Spain") })));
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_matchup_policy(&mut self, policy: MatchupPolicy) {
+		trace!("Restricting the pairings to {:?}", policy);
+
+		self.matchup_policy = Some(policy);
+	}
+
+	/// Allows all the pairings to start again
+	pub fn clear_matchup_policy(&mut self) {
+		trace!("Removing the matchup policy");
+
+		self.matchup_policy = None;
+	}
+
+	/// Provides the policy of the pairings, if one is set
+	pub fn get_matchup_policy(&self) -> Option<&MatchupPolicy> {
+		self.matchup_policy.as_ref()
+	}
+
+	/// Checks if the matchup policy allows a new game
+	///
+	/// # Arguments
+	///
+	/// * `home` - name of the home team
+	/// * `away` - name of the away team
+	///
+	/// # Errors
+	///
+	/// * When the game isn't scheduled, and only the scheduled games can start
+	/// * When any of the teams isn't in a group, or they are in different groups, and the games are kept within the groups
+	///
+	pub(crate) fn check_matchup_policy(&self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		let policy = match &self.matchup_policy {
+			Some(policy) => policy,
+			None => return Ok(()),
+		};
+
+		let violation = if policy.scheduled_only && !self.schedule.iter().any(|fixture| fixture.home_name == home && fixture.away_name == away) {
+			Some(PolicyViolation::NotScheduled { home: String::from(home), away: String::from(away) })
+		} else if let Some(groups) = &policy.groups {
+			match (groups.get(home), groups.get(away)) {
+				(None, _) => Some(PolicyViolation::NoGroup(String::from(home))),
+				(_, None) => Some(PolicyViolation::NoGroup(String::from(away))),
+				(Some(home_group), Some(away_group)) if home_group != away_group => Some(PolicyViolation::DifferentGroups {
+					home: String::from(home),
+					home_group: *home_group,
+					away: String::from(away),
+					away_group: *away_group,
+				}),
+				_ => None,
+			}
+		} else {
+			None
+		};
+
+		match violation {
+			Some(violation) => {
+				warn!("{}", violation);
+				Err(ScoreBoardError::PolicyViolation(violation))
+			},
+			None => Ok(()),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::{Duration, SystemTime};
+	use std::vec::Vec;
+
+	use crate::draw::{Confederation, DrawTeam};
+
+	fn get_groups() -> Vec<Group> {
+		vec![
+			Group { letter: 'A', teams: vec![DrawTeam::new("Qatar", Confederation::Afc), DrawTeam::new("Ecuador", Confederation::Conmebol)] },
+			Group { letter: 'B', teams: vec![DrawTeam::new("England", Confederation::Uefa), DrawTeam::new("Iran", Confederation::Afc)] },
+		]
+	}
+
+	#[test]
+	fn all_pairings_are_allowed_without_a_policy() {
+		let mut sb = ScoreBoard::new();
+		sb.set_matchup_policy(MatchupPolicy::new().scheduled_only());
+		sb.clear_matchup_policy();
+
+		assert!(sb.start_game("Spain", "Brazil").is_ok());
+		assert_eq!(sb.get_matchup_policy(), None);
+	}
+
+	#[test]
+	fn only_scheduled_games_start() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("Spain", "Brazil", SystemTime::now() + Duration::from_secs(60)).expect("Couldn't schedule the game");
+		sb.set_matchup_policy(MatchupPolicy::new().scheduled_only());
+
+		assert_eq!(sb.start_game("Brazil", "Spain"), Err(ScoreBoardError::PolicyViolation(PolicyViolation::NotScheduled {
+			home: String::from("Brazil"),
+			away: String::from("Spain"),
+		})));
+		assert!(sb.start_game("Spain", "Brazil").is_ok());
+		assert!(sb.get_events().iter().all(|record| record.event.get_teams() == ("Spain", "Brazil")));
+	}
+
+	#[test]
+	fn games_are_kept_within_the_groups() {
+		let mut sb = ScoreBoard::new();
+		sb.set_matchup_policy(MatchupPolicy::new().within_groups(&get_groups()));
+
+		let result = sb.start_game("England", "Ecuador");
+		assert!(result.is_err_and(|error| error.to_string() == "England from group B cannot play Ecuador from group A"));
+		assert_eq!(sb.start_game("England", "Wales"), Err(ScoreBoardError::PolicyViolation(PolicyViolation::NoGroup(String::from("Wales")))));
+		assert!(sb.start_game("Qatar", "Ecuador").is_ok());
+	}
+
+	#[test]
+	fn rules_are_combined() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game("England", "Qatar", SystemTime::now() + Duration::from_secs(60)).expect("Couldn't schedule the game");
+		sb.set_matchup_policy(MatchupPolicy::new().scheduled_only().within_groups(&get_groups()));
+
+		assert!(sb.start_game("England", "Qatar").is_err_and(|error| matches!(error, ScoreBoardError::PolicyViolation(PolicyViolation::DifferentGroups { .. }))));
+		assert!(sb.start_game("England", "Iran").is_err_and(|error| matches!(error, ScoreBoardError::PolicyViolation(PolicyViolation::NotScheduled { .. }))));
+	}
+}
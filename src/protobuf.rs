@@ -0,0 +1,930 @@
+//! # Protocol Buffers encoding
+//!
+//! Encodes the score board state and its events with Protocol Buffers, following the schema in `proto/scoreboard.proto`. Available with the `protobuf` feature. The messages are exported from the crate root, the ones named like the types of the board with a `Proto` prefix
+
+use std::string::String;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::vec::Vec;
+
+use log::{trace, warn};
+use prost::Message;
+
+use crate::archive;
+use crate::error::ScoreBoardError;
+use crate::events::{EventRecord, ScoreBoardEvent};
+use crate::goal::{self, Side};
+use crate::period;
+use crate::player;
+use crate::state;
+use crate::snapshot::{BoardSnapshot, GameSnapshot};
+use crate::timezone::UtcOffset;
+use crate::ScoreBoard;
+
+/// A single game that is currently played
+#[derive(Clone, PartialEq, Message)]
+pub struct Game {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Score of the home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Score of the away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+	/// Wall clock time of the start of the game, in milliseconds since the Unix epoch
+	#[prost(int64, tag = "5")]
+	pub kickoff_unix_millis: i64,
+	/// Revision of the game, increasing by 1 with every score update
+	#[prost(uint64, tag = "6")]
+	pub revision: u64,
+}
+
+/// All the games that are currently played, in the order of the summary
+#[derive(Clone, PartialEq, Message)]
+pub struct BoardState {
+	/// The games
+	#[prost(message, repeated, tag = "1")]
+	pub games: Vec<Game>,
+}
+
+/// A game has started, with the score 0 - 0
+#[derive(Clone, PartialEq, Message)]
+pub struct GameStarted {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Name of the away team
+	#[prost(string, tag = "2")]
+	pub away_team: String,
+}
+
+/// The way a goal was scored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum GoalKind {
+	/// A goal from open play
+	OpenPlay = 0,
+	/// A penalty kick
+	Penalty = 1,
+	/// A goal scored by a player of the other team
+	OwnGoal = 2,
+	/// A direct free kick
+	FreeKick = 3,
+}
+
+/// A single goal
+#[derive(Clone, PartialEq, Message)]
+pub struct Goal {
+	/// Whether the goal counts for the away team, instead of the home team
+	#[prost(bool, tag = "1")]
+	pub away: bool,
+	/// The way the goal was scored
+	#[prost(enumeration = "GoalKind", tag = "2")]
+	pub kind: i32,
+	/// The player who scored the goal, if it was recorded
+	#[prost(message, optional, tag = "3")]
+	pub scorer: Option<Player>,
+}
+
+/// A player of a team
+#[derive(Clone, PartialEq, Message)]
+pub struct Player {
+	/// Name of the player
+	#[prost(string, tag = "1")]
+	pub name: String,
+	/// Shirt number of the player, if the player is registered
+	#[prost(uint32, optional, tag = "2")]
+	pub number: Option<u32>,
+	/// Name of the player's team
+	#[prost(string, tag = "3")]
+	pub team: String,
+}
+
+/// A score of a running game has changed
+#[derive(Clone, PartialEq, Message)]
+pub struct ScoreUpdated {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// New score of the home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// New score of the away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+	/// The goal that changed the score, if it was recorded
+	#[prost(message, optional, tag = "5")]
+	pub goal: Option<Goal>,
+}
+
+/// A game has finished and was removed from the score board
+#[derive(Clone, PartialEq, Message)]
+pub struct GameFinished {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Final score of the home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Final score of the away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+}
+
+/// The state of a game
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum GameState {
+	/// Planned, not started yet
+	Scheduled = 0,
+	/// Being played
+	Live = 1,
+	/// Interrupted
+	Paused = 2,
+	/// Ended with a result
+	Finished = 3,
+	/// Ended without a result
+	Abandoned = 4,
+	/// Put off before the start
+	Postponed = 5,
+}
+
+/// A game was paused, resumed or abandoned
+#[derive(Clone, PartialEq, Message)]
+pub struct StateChanged {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Score of the home team at the change
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Score of the away team at the change
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+	/// The new state of the game
+	#[prost(enumeration = "GameState", tag = "5")]
+	pub state: i32,
+}
+
+/// A finished game was brought back onto the score board
+#[derive(Clone, PartialEq, Message)]
+pub struct GameReopened {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Score of the home team, as it was at the finish
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Score of the away team, as it was at the finish
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+}
+
+/// The way the result of a game was decided
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Decision {
+	/// In the regular time
+	RegularTime = 0,
+	/// In the extra time
+	ExtraTime = 1,
+	/// In a penalty shootout
+	Penalties = 2,
+	/// Awarded to one of the teams
+	Forfeit = 3,
+}
+
+/// The final result of a game, recorded right after its finish
+#[derive(Clone, PartialEq, Message)]
+pub struct ResultFinalized {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Final score of the home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Final score of the away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+	/// Whole seconds from the kickoff to the finish of the game
+	#[prost(uint64, tag = "5")]
+	pub duration_seconds: u64,
+	/// Nanoseconds from the kickoff to the finish of the game, on top of the whole seconds
+	#[prost(uint32, tag = "6")]
+	pub duration_nanos: u32,
+	/// The way the result was decided
+	#[prost(enumeration = "Decision", tag = "7")]
+	pub decision: i32,
+	/// Penalties scored by the home team in the shootout
+	#[prost(uint32, tag = "8")]
+	pub home_penalties: u32,
+	/// Penalties scored by the away team in the shootout
+	#[prost(uint32, tag = "9")]
+	pub away_penalties: u32,
+}
+
+/// The home and the away team of a running game were swapped
+#[derive(Clone, PartialEq, Message)]
+pub struct SidesSwapped {
+	/// Name of the new home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Score of the new home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the new away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Score of the new away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+}
+
+/// A period of a game
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Period {
+	/// The first half
+	FirstHalf = 0,
+	/// The second half
+	SecondHalf = 1,
+	/// The extra time
+	ExtraTime = 2,
+}
+
+/// A running game moved to the next period
+#[derive(Clone, PartialEq, Message)]
+pub struct PeriodStarted {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Score of the home team at the start of the period
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// Score of the away team at the start of the period
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+	/// The period that began
+	#[prost(enumeration = "Period", tag = "5")]
+	pub period: i32,
+}
+
+/// A team was renamed
+#[derive(Clone, PartialEq, Message)]
+pub struct TeamRenamed {
+	/// The name the team had before
+	#[prost(string, tag = "1")]
+	pub old_name: String,
+	/// The new name of the team
+	#[prost(string, tag = "2")]
+	pub new_name: String,
+}
+
+/// A single change of the score board, as it was recorded
+#[derive(Clone, PartialEq, Message)]
+pub struct ScoreEvent {
+	/// Number of the event, increasing by 1 with every recorded event
+	#[prost(uint64, tag = "1")]
+	pub sequence: u64,
+	/// Wall clock time when the event was recorded, in milliseconds since the Unix epoch
+	#[prost(int64, tag = "2")]
+	pub time_unix_millis: i64,
+	/// The recorded change
+	#[prost(oneof = "score_event::Event", tags = "3, 4, 5, 9, 10, 11, 12, 13, 14")]
+	pub event: Option<score_event::Event>,
+	/// Identity of the operator who made the change
+	#[prost(string, optional, tag = "6")]
+	pub operator: Option<String>,
+	/// Revision of the changed game, after the change
+	#[prost(uint64, tag = "7")]
+	pub revision: u64,
+	/// State of the changed game, after the change
+	#[prost(enumeration = "GameState", tag = "8")]
+	pub state: i32,
+}
+
+/// Nested types of `ScoreEvent`
+pub mod score_event {
+	/// The recorded change
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Event {
+		/// A game has started
+		#[prost(message, tag = "3")]
+		GameStarted(super::GameStarted),
+		/// A score has changed
+		#[prost(message, tag = "4")]
+		ScoreUpdated(super::ScoreUpdated),
+		/// A game has finished
+		#[prost(message, tag = "5")]
+		GameFinished(super::GameFinished),
+		/// The state of a game has changed
+		#[prost(message, tag = "9")]
+		StateChanged(super::StateChanged),
+		/// A finished game was reopened
+		#[prost(message, tag = "10")]
+		GameReopened(super::GameReopened),
+		/// The result of a game is final
+		#[prost(message, tag = "11")]
+		ResultFinalized(super::ResultFinalized),
+		/// The sides of a game were swapped
+		#[prost(message, tag = "12")]
+		SidesSwapped(super::SidesSwapped),
+		/// A game moved to the next period
+		#[prost(message, tag = "13")]
+		PeriodStarted(super::PeriodStarted),
+		/// A team was renamed
+		#[prost(message, tag = "14")]
+		TeamRenamed(super::TeamRenamed),
+	}
+}
+
+/// Request of the `StartGame` call of the `ScoreBoardService`
+#[derive(Clone, PartialEq, Message)]
+pub struct StartGameRequest {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Name of the away team
+	#[prost(string, tag = "2")]
+	pub away_team: String,
+}
+
+/// Request of the `UpdateScore` call of the `ScoreBoardService`
+#[derive(Clone, PartialEq, Message)]
+pub struct UpdateScoreRequest {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// New score of the home team
+	#[prost(uint32, tag = "2")]
+	pub home_score: u32,
+	/// Name of the away team
+	#[prost(string, tag = "3")]
+	pub away_team: String,
+	/// New score of the away team
+	#[prost(uint32, tag = "4")]
+	pub away_score: u32,
+}
+
+/// Request of the `FinishGame` call of the `ScoreBoardService`
+#[derive(Clone, PartialEq, Message)]
+pub struct FinishGameRequest {
+	/// Name of the home team
+	#[prost(string, tag = "1")]
+	pub home_team: String,
+	/// Name of the away team
+	#[prost(string, tag = "2")]
+	pub away_team: String,
+}
+
+/// Reply to a successful change of the score board
+#[derive(Clone, PartialEq, Message)]
+pub struct OperationReply {}
+
+/// Request of the `StreamSummary` call of the `ScoreBoardService`
+#[derive(Clone, PartialEq, Message)]
+pub struct StreamSummaryRequest {}
+
+impl ScoreBoard {
+	/// Encodes all the currently played games as a `BoardState` Protocol Buffers message
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let bytes = sb.encode_protobuf();
+	/// let restored = scoreboard_world_cup::ScoreBoard::decode_protobuf(&bytes).unwrap();
+	/// assert_eq!(restored.get_summary(), sb.get_summary());
+	/// ```
+	pub fn encode_protobuf(&self) -> Vec<u8> {
+		trace!("Encoding the score board with Protocol Buffers");
+
+		self.to_board_state().encode_to_vec()
+	}
+
+	/// Provides all the currently played games as a `BoardState` message
+	pub(crate) fn to_board_state(&self) -> BoardState {
+		BoardState {
+			games: self.data.iter().map(|game| Game {
+				home_team: game.home_team.name.clone(),
+				home_score: game.home_team.score as u32,
+				away_team: game.away_team.name.clone(),
+				away_score: game.away_team.score as u32,
+				kickoff_unix_millis: to_unix_millis(game.kickoff_time),
+				revision: game.revision,
+			}).collect(),
+		}
+	}
+
+	/// Creates a score board from a `BoardState` Protocol Buffers message. The restored board has no event history
+	///
+	/// # Arguments
+	///
+	/// * `bytes` - the encoded message
+	///
+	/// # Errors
+	///
+	/// * When the message can't be decoded
+	/// * When a score doesn't fit the score board range (0 - 255)
+	/// * When a team plays with itself or in more than one game
+	pub fn decode_protobuf(bytes: &[u8]) -> Result<ScoreBoard, ScoreBoardError> {
+		trace!("Decoding the score board from Protocol Buffers");
+
+		let state = match BoardState::decode(bytes) {
+			Ok(state) => state,
+			Err(error) => {
+				warn!("Couldn't decode the board state: {}", error);
+				return Err(ScoreBoardError::Encoding(format!("Couldn't decode the board state: {}", error)));
+			},
+		};
+
+		let mut games = Vec::with_capacity(state.games.len());
+
+		for game in state.games {
+			games.push(GameSnapshot {
+				home_score: to_score(game.home_score)?,
+				away_score: to_score(game.away_score)?,
+				home_team: game.home_team,
+				away_team: game.away_team,
+				kickoff_time: from_unix_millis(game.kickoff_unix_millis),
+				revision: game.revision,
+				state: state::GameState::Live,
+				period: period::Period::FirstHalf,
+				venue: None,
+				group: None,
+				utc_offset: UtcOffset::UTC,
+			});
+		}
+
+		let sb = ScoreBoard::from_snapshot(BoardSnapshot::new(games))?;
+
+		trace!("Score board decoded");
+
+		Ok(sb)
+	}
+}
+
+impl EventRecord {
+	/// Encodes the event as a `ScoreEvent` Protocol Buffers message
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let bytes = sb.get_events()[0].encode_protobuf();
+	/// let event = scoreboard_world_cup::EventRecord::decode_protobuf(&bytes).unwrap();
+	/// assert_eq!(event.event, sb.get_events()[0].event);
+	/// ```
+	pub fn encode_protobuf(&self) -> Vec<u8> {
+		let event = match &self.event {
+			ScoreBoardEvent::GameStarted { home, away } => score_event::Event::GameStarted(GameStarted {
+				home_team: home.clone(),
+				away_team: away.clone(),
+			}),
+			ScoreBoardEvent::ScoreUpdated { home, home_score, away, away_score, goal } => score_event::Event::ScoreUpdated(ScoreUpdated {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+				goal: goal.clone().map(to_goal_message),
+			}),
+			ScoreBoardEvent::GameFinished { home, home_score, away, away_score } => score_event::Event::GameFinished(GameFinished {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+			}),
+			ScoreBoardEvent::StateChanged { home, home_score, away, away_score, state } => score_event::Event::StateChanged(StateChanged {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+				state: to_state_message(*state) as i32,
+			}),
+			ScoreBoardEvent::GameReopened { home, home_score, away, away_score } => score_event::Event::GameReopened(GameReopened {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+			}),
+			ScoreBoardEvent::ResultFinalized { home, home_score, away, away_score, duration, decision } => {
+				let (decision, home_penalties, away_penalties) = to_decision_message(*decision);
+				score_event::Event::ResultFinalized(ResultFinalized {
+					home_team: home.clone(),
+					home_score: *home_score as u32,
+					away_team: away.clone(),
+					away_score: *away_score as u32,
+					duration_seconds: duration.as_secs(),
+					duration_nanos: duration.subsec_nanos(),
+					decision: decision as i32,
+					home_penalties,
+					away_penalties,
+				})
+			},
+			ScoreBoardEvent::SidesSwapped { home, home_score, away, away_score } => score_event::Event::SidesSwapped(SidesSwapped {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+			}),
+			ScoreBoardEvent::PeriodStarted { home, home_score, away, away_score, period } => score_event::Event::PeriodStarted(PeriodStarted {
+				home_team: home.clone(),
+				home_score: *home_score as u32,
+				away_team: away.clone(),
+				away_score: *away_score as u32,
+				period: to_period_message(*period) as i32,
+			}),
+			ScoreBoardEvent::TeamRenamed { old, new } => score_event::Event::TeamRenamed(TeamRenamed {
+				old_name: old.clone(),
+				new_name: new.clone(),
+			}),
+		};
+
+		ScoreEvent {
+			sequence: self.sequence,
+			time_unix_millis: to_unix_millis(self.time),
+			event: Some(event),
+			operator: self.operator.clone(),
+			revision: self.revision,
+			state: to_state_message(self.state) as i32,
+		}.encode_to_vec()
+	}
+
+	/// Creates an event from a `ScoreEvent` Protocol Buffers message
+	///
+	/// # Arguments
+	///
+	/// * `bytes` - the encoded message
+	///
+	/// # Errors
+	///
+	/// * When the message can't be decoded or has no event
+	/// * When a score doesn't fit the score board range (0 - 255)
+	pub fn decode_protobuf(bytes: &[u8]) -> Result<EventRecord, ScoreBoardError> {
+		let message = match ScoreEvent::decode(bytes) {
+			Ok(message) => message,
+			Err(error) => {
+				warn!("Couldn't decode the event: {}", error);
+				return Err(ScoreBoardError::Encoding(format!("Couldn't decode the event: {}", error)));
+			},
+		};
+
+		let event = match message.event {
+			Some(score_event::Event::GameStarted(started)) => ScoreBoardEvent::GameStarted {
+				home: started.home_team,
+				away: started.away_team,
+			},
+			Some(score_event::Event::ScoreUpdated(updated)) => ScoreBoardEvent::ScoreUpdated {
+				home: updated.home_team,
+				home_score: to_score(updated.home_score)?,
+				away: updated.away_team,
+				away_score: to_score(updated.away_score)?,
+				goal: updated.goal.map(from_goal_message).transpose()?,
+			},
+			Some(score_event::Event::GameFinished(finished)) => ScoreBoardEvent::GameFinished {
+				home: finished.home_team,
+				home_score: to_score(finished.home_score)?,
+				away: finished.away_team,
+				away_score: to_score(finished.away_score)?,
+			},
+			Some(score_event::Event::StateChanged(changed)) => ScoreBoardEvent::StateChanged {
+				home: changed.home_team,
+				home_score: to_score(changed.home_score)?,
+				away: changed.away_team,
+				away_score: to_score(changed.away_score)?,
+				state: from_state_message(changed.state)?,
+			},
+			Some(score_event::Event::GameReopened(reopened)) => ScoreBoardEvent::GameReopened {
+				home: reopened.home_team,
+				home_score: to_score(reopened.home_score)?,
+				away: reopened.away_team,
+				away_score: to_score(reopened.away_score)?,
+			},
+			Some(score_event::Event::ResultFinalized(result)) => ScoreBoardEvent::ResultFinalized {
+				home: result.home_team,
+				home_score: to_score(result.home_score)?,
+				away: result.away_team,
+				away_score: to_score(result.away_score)?,
+				duration: Duration::new(result.duration_seconds, result.duration_nanos),
+				decision: from_decision_message(result.decision, result.home_penalties, result.away_penalties)?,
+			},
+			Some(score_event::Event::SidesSwapped(swapped)) => ScoreBoardEvent::SidesSwapped {
+				home: swapped.home_team,
+				home_score: to_score(swapped.home_score)?,
+				away: swapped.away_team,
+				away_score: to_score(swapped.away_score)?,
+			},
+			Some(score_event::Event::PeriodStarted(started)) => ScoreBoardEvent::PeriodStarted {
+				home: started.home_team,
+				home_score: to_score(started.home_score)?,
+				away: started.away_team,
+				away_score: to_score(started.away_score)?,
+				period: from_period_message(started.period)?,
+			},
+			Some(score_event::Event::TeamRenamed(renamed)) => ScoreBoardEvent::TeamRenamed {
+				old: renamed.old_name,
+				new: renamed.new_name,
+			},
+			None => {
+				warn!("The event message has no event");
+				return Err(ScoreBoardError::Encoding(String::from("The event message has no event")));
+			},
+		};
+
+		Ok(EventRecord { sequence: message.sequence, time: from_unix_millis(message.time_unix_millis), event, operator: message.operator, revision: message.revision, state: from_state_message(message.state)? })
+	}
+}
+
+/// Converts a goal to its message
+fn to_goal_message(goal: goal::Goal) -> Goal {
+	let kind = match goal.kind {
+		goal::GoalKind::OpenPlay => GoalKind::OpenPlay,
+		goal::GoalKind::Penalty => GoalKind::Penalty,
+		goal::GoalKind::OwnGoal => GoalKind::OwnGoal,
+		goal::GoalKind::FreeKick => GoalKind::FreeKick,
+	};
+
+	let scorer = goal.scorer.map(|scorer| Player { name: scorer.name, number: scorer.number.map(u32::from), team: scorer.team });
+
+	Goal { away: goal.side == Side::Away, kind: kind as i32, scorer }
+}
+
+/// Converts a goal message to a goal
+fn from_goal_message(message: Goal) -> Result<goal::Goal, ScoreBoardError> {
+	let kind = match GoalKind::try_from(message.kind) {
+		Ok(GoalKind::OpenPlay) => goal::GoalKind::OpenPlay,
+		Ok(GoalKind::Penalty) => goal::GoalKind::Penalty,
+		Ok(GoalKind::OwnGoal) => goal::GoalKind::OwnGoal,
+		Ok(GoalKind::FreeKick) => goal::GoalKind::FreeKick,
+		Err(_) => {
+			warn!("Unknown goal kind {}", message.kind);
+			return Err(ScoreBoardError::Encoding(format!("Unknown goal kind {}", message.kind)));
+		},
+	};
+
+	let scorer = match message.scorer {
+		Some(scorer) => {
+			let number = match scorer.number.map(u8::try_from).transpose() {
+				Ok(number) => number,
+				Err(_) => {
+					warn!("Shirt number of {} is out of range", scorer.name);
+					return Err(ScoreBoardError::Encoding(format!("Shirt number of {} is out of range", scorer.name)));
+				},
+			};
+			Some(player::Player { name: scorer.name, number, team: scorer.team })
+		},
+		None => None,
+	};
+
+	Ok(goal::Goal { side: if message.away { Side::Away } else { Side::Home }, kind, scorer })
+}
+
+/// Converts a game state to its message value
+fn to_state_message(state: state::GameState) -> GameState {
+	match state {
+		state::GameState::Scheduled => GameState::Scheduled,
+		state::GameState::Live => GameState::Live,
+		state::GameState::Paused => GameState::Paused,
+		state::GameState::Finished => GameState::Finished,
+		state::GameState::Abandoned => GameState::Abandoned,
+		state::GameState::Postponed => GameState::Postponed,
+	}
+}
+
+/// Converts a message value to a game state
+fn from_state_message(value: i32) -> Result<state::GameState, ScoreBoardError> {
+	match GameState::try_from(value) {
+		Ok(GameState::Scheduled) => Ok(state::GameState::Scheduled),
+		Ok(GameState::Live) => Ok(state::GameState::Live),
+		Ok(GameState::Paused) => Ok(state::GameState::Paused),
+		Ok(GameState::Finished) => Ok(state::GameState::Finished),
+		Ok(GameState::Abandoned) => Ok(state::GameState::Abandoned),
+		Ok(GameState::Postponed) => Ok(state::GameState::Postponed),
+		Err(_) => {
+			warn!("Unknown game state {}", value);
+			Err(ScoreBoardError::Encoding(format!("Unknown game state {}", value)))
+		},
+	}
+}
+
+/// Converts a period to its message value
+fn to_period_message(period: period::Period) -> Period {
+	match period {
+		period::Period::FirstHalf => Period::FirstHalf,
+		period::Period::SecondHalf => Period::SecondHalf,
+		period::Period::ExtraTime => Period::ExtraTime,
+	}
+}
+
+/// Converts a message value to a period
+fn from_period_message(value: i32) -> Result<period::Period, ScoreBoardError> {
+	match Period::try_from(value) {
+		Ok(Period::FirstHalf) => Ok(period::Period::FirstHalf),
+		Ok(Period::SecondHalf) => Ok(period::Period::SecondHalf),
+		Ok(Period::ExtraTime) => Ok(period::Period::ExtraTime),
+		Err(_) => {
+			warn!("Unknown period {}", value);
+			Err(ScoreBoardError::Encoding(format!("Unknown period {}", value)))
+		},
+	}
+}
+
+/// Converts a decision to its message value, with the penalties of a shootout
+fn to_decision_message(decision: archive::Decision) -> (Decision, u32, u32) {
+	match decision {
+		archive::Decision::RegularTime => (Decision::RegularTime, 0, 0),
+		archive::Decision::AfterExtraTime => (Decision::ExtraTime, 0, 0),
+		archive::Decision::AfterPenalties { home, away } => (Decision::Penalties, home as u32, away as u32),
+		archive::Decision::Forfeit => (Decision::Forfeit, 0, 0),
+	}
+}
+
+/// Converts a message value and the penalties of a shootout to a decision
+fn from_decision_message(value: i32, home_penalties: u32, away_penalties: u32) -> Result<archive::Decision, ScoreBoardError> {
+	match Decision::try_from(value) {
+		Ok(Decision::RegularTime) => Ok(archive::Decision::RegularTime),
+		Ok(Decision::ExtraTime) => Ok(archive::Decision::AfterExtraTime),
+		Ok(Decision::Penalties) => Ok(archive::Decision::AfterPenalties { home: to_score(home_penalties)?, away: to_score(away_penalties)? }),
+		Ok(Decision::Forfeit) => Ok(archive::Decision::Forfeit),
+		Err(_) => {
+			warn!("Unknown decision {}", value);
+			Err(ScoreBoardError::Encoding(format!("Unknown decision {}", value)))
+		},
+	}
+}
+
+/// Converts a score from the message to the score board range
+pub(crate) fn to_score(score: u32) -> Result<u8, ScoreBoardError> {
+	match u8::try_from(score) {
+		Ok(score) => Ok(score),
+		Err(_) => {
+			warn!("Score {} is out of range", score);
+			Err(ScoreBoardError::ScoreOutOfRange(score))
+		},
+	}
+}
+
+/// Converts a wall clock time to milliseconds since the Unix epoch
+fn to_unix_millis(time: SystemTime) -> i64 {
+	match time.duration_since(UNIX_EPOCH) {
+		Ok(duration) => duration.as_millis() as i64,
+		Err(error) => -(error.duration().as_millis() as i64),
+	}
+}
+
+/// Converts milliseconds since the Unix epoch to a wall clock time
+fn from_unix_millis(millis: i64) -> SystemTime {
+	if millis >= 0 {
+		UNIX_EPOCH + Duration::from_millis(millis as u64)
+	} else {
+		UNIX_EPOCH - Duration::from_millis(millis.unsigned_abs())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_board_is_encoded_and_decoded() {
+		let sb = ScoreBoard::new();
+		let result = ScoreBoard::decode_protobuf(&sb.encode_protobuf());
+
+		assert!(result.is_ok_and(|restored| restored.get_summary().is_empty()));
+	}
+
+	#[test]
+	fn board_state_survives_encoding() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		sb.update_score("Spain", 10, "Brazil", 2).expect("Couldn't update the second game");
+		let restored = ScoreBoard::decode_protobuf(&sb.encode_protobuf()).expect("Couldn't decode the board");
+
+		assert_eq!(restored.get_summary(), sb.get_summary());
+		assert!(restored.get_events().is_empty());
+	}
+
+	#[test]
+	fn encoded_state_matches_the_schema() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 2, "Indonesia", 1).expect("Couldn't update the game");
+		let state = BoardState::decode(sb.encode_protobuf().as_slice()).expect("Couldn't decode the message");
+
+		assert_eq!(state.games.len(), 1);
+		assert_eq!(state.games[0].home_team, "Japan");
+		assert_eq!(state.games[0].home_score, 2);
+		assert_eq!(state.games[0].away_team, "Indonesia");
+		assert_eq!(state.games[0].away_score, 1);
+	}
+
+	#[test]
+	fn garbage_is_not_decoded() {
+		let result = ScoreBoard::decode_protobuf(&[0xff, 0xff, 0xff]);
+
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn out_of_range_score_is_rejected() {
+		let state = BoardState { games: vec![Game { home_team: String::from("Japan"), home_score: 256, away_team: String::from("Indonesia"), away_score: 0, kickoff_unix_millis: 0, revision: 1 }] };
+		let result = ScoreBoard::decode_protobuf(&state.encode_to_vec());
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Score 256 is out of range"));
+	}
+
+	#[test]
+	fn team_playing_twice_is_rejected() {
+		let state = BoardState { games: vec![
+			Game { home_team: String::from("Japan"), home_score: 0, away_team: String::from("Indonesia"), away_score: 0, kickoff_unix_millis: 0, revision: 1 },
+			Game { home_team: String::from("Qatar"), home_score: 0, away_team: String::from("Japan"), away_score: 0, kickoff_unix_millis: 0, revision: 1 },
+		] };
+		let result = ScoreBoard::decode_protobuf(&state.encode_to_vec());
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Japan is currently playing a game"));
+	}
+
+	#[test]
+	fn all_events_survive_encoding() {
+		let mut sb = ScoreBoard::new();
+		sb.set_operator("alice");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		sb.record_goal("Japan", "Indonesia", Side::Away, goal::GoalKind::Penalty).expect("Couldn't record the goal");
+		sb.register_player("Japan", "Mitoma", 9).expect("Couldn't register the player");
+		sb.record_goal_by("Japan", "Indonesia", Side::Home, goal::GoalKind::OpenPlay, "Mitoma").expect("Couldn't record the goal with the scorer");
+		sb.record_goal_by("Japan", "Indonesia", Side::Away, goal::GoalKind::OpenPlay, "Struick").expect("Couldn't record the goal with an unregistered scorer");
+		sb.set_game_state("Japan", "Indonesia", state::GameState::Paused).expect("Couldn't pause the game");
+		sb.set_game_state("Japan", "Indonesia", state::GameState::Live).expect("Couldn't resume the game");
+		sb.start_period("Japan", "Indonesia", period::Period::ExtraTime).expect("Couldn't start the extra time");
+		sb.rename_team("Japan", "Nippon").expect("Couldn't rename the team");
+		sb.finish_game_after("Nippon", "Indonesia", archive::Decision::AfterPenalties { home: 4, away: 2 }).expect("Couldn't finish the game");
+		assert!(matches!(sb.get_events().last().map(|record| &record.event), Some(ScoreBoardEvent::ResultFinalized { .. })));
+
+		for record in sb.get_events() {
+			let decoded = EventRecord::decode_protobuf(&record.encode_protobuf()).expect("Couldn't decode the event");
+			assert_eq!(decoded.sequence, record.sequence);
+			assert_eq!(decoded.event, record.event);
+			assert_eq!(decoded.operator, record.operator);
+			assert_eq!(decoded.revision, record.revision);
+			assert_eq!(decoded.state, record.state);
+			assert_eq!(to_unix_millis(decoded.time), to_unix_millis(record.time));
+		}
+	}
+
+	#[test]
+	fn event_without_payload_is_rejected() {
+		let message = ScoreEvent { sequence: 1, time_unix_millis: 0, event: None, operator: None, revision: 1, state: GameState::Live as i32 };
+		let result = EventRecord::decode_protobuf(&message.encode_to_vec());
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "The event message has no event"));
+	}
+
+	#[test]
+	fn unknown_goal_kind_is_rejected() {
+		let updated = ScoreUpdated { home_team: String::from("Japan"), home_score: 1, away_team: String::from("Indonesia"), away_score: 0, goal: Some(Goal { away: false, kind: 9, scorer: None }) };
+		let message = ScoreEvent { sequence: 2, time_unix_millis: 0, event: Some(score_event::Event::ScoreUpdated(updated)), operator: None, revision: 2, state: GameState::Live as i32 };
+		let result = EventRecord::decode_protobuf(&message.encode_to_vec());
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Unknown goal kind 9"));
+	}
+
+	#[test]
+	fn times_before_epoch_are_converted() {
+		let time = UNIX_EPOCH - Duration::from_millis(1500);
+
+		assert_eq!(to_unix_millis(time), -1500);
+		assert_eq!(from_unix_millis(-1500), time);
+	}
+}
@@ -0,0 +1,45 @@
+//! # Seeded random numbers
+//!
+//! A small generator of random numbers, reproducible from the seed, for the group draw and the simulated games
+
+/// Generator of the random numbers, reproducible from the seed (xorshift64*)
+pub(crate) struct SeededRandom {
+	/// The current state, never 0
+	state: u64,
+}
+
+impl SeededRandom {
+	/// Returns a generator started from the seed
+	pub(crate) fn new(seed: u64) -> SeededRandom {
+		// Spreads the bits of small seeds, and keeps the state away from 0
+		SeededRandom { state: seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1 }
+	}
+
+	/// Provides the next random number
+	fn next(&mut self) -> u64 {
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+
+		self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+	}
+
+	/// Provides a number from 0 to `bound` - 1
+	pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+		(self.next() % bound as u64) as usize
+	}
+
+	/// Provides a number from 0 up to, but not including 1
+	#[cfg(feature = "simulate")]
+	pub(crate) fn next_f64(&mut self) -> f64 {
+		// The 53 highest bits fill the mantissa of the number exactly
+		(self.next() >> 11) as f64 / (1u64 << 53) as f64
+	}
+
+	/// Shuffles the items (Fisher-Yates)
+	pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+		for index in (1..items.len()).rev() {
+			items.swap(index, self.next_below(index + 1));
+		}
+	}
+}
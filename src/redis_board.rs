@@ -0,0 +1,391 @@
+//! # Redis-backed score board
+//!
+//! Keeps the live games in Redis, so several stateless service replicas can share one score board
+//!
+//! Every board uses a few keys with a common prefix, the ID of the board:
+//!
+//! * `{board}:game:{home name length}:{home}:{away}` - a hash per game, with the `home`, `away`, `home_score`, `away_score`, `kickoff` (milliseconds since the Unix epoch) and `revision` fields
+//! * `{board}:games` - a sorted set of the game keys, ranked so that the reversed order is the order of the summary
+//! * `{board}:teams` - a hash of the currently playing teams, pointing to the keys of their games
+//!
+//! All the changes are made by Lua scripts, so they are atomic even with many replicas working on the same board
+
+use std::string::{String, ToString};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::vec::Vec;
+
+use log::{trace, warn};
+use redis::{Client, Connection, Script};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::period::Period;
+use crate::snapshot::{BoardSnapshot, GameSnapshot};
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+
+/// Multiplier of the total score in the rank of a game. It's bigger than any kickoff time in milliseconds, so the total score decides first and the kickoff time second
+const TOTAL_SCORE_WEIGHT: f64 = 1e13;
+
+/// Starts a game, if none of the teams is playing. Returns 0 on success, 1 when the home team is playing and 2 when the away team is playing
+const START_SCRIPT: &str = r"
+if redis.call('HEXISTS', KEYS[2], ARGV[1]) == 1 then return 1 end
+if redis.call('HEXISTS', KEYS[2], ARGV[2]) == 1 then return 2 end
+redis.call('HSET', KEYS[2], ARGV[1], KEYS[3], ARGV[2], KEYS[3])
+redis.call('HSET', KEYS[3], 'home', ARGV[1], 'away', ARGV[2], 'home_score', 0, 'away_score', 0, 'kickoff', ARGV[3], 'revision', 1)
+redis.call('ZADD', KEYS[1], ARGV[3], KEYS[3])
+return 0
+";
+
+/// Updates the score of a game and its rank. Returns the new revision of the game, or 0 when there is no such game
+const UPDATE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[2]) == 0 then return 0 end
+redis.call('HSET', KEYS[2], 'home_score', ARGV[1], 'away_score', ARGV[2])
+local kickoff = tonumber(redis.call('HGET', KEYS[2], 'kickoff'))
+local rank = (tonumber(ARGV[1]) + tonumber(ARGV[2])) * tonumber(ARGV[3]) + kickoff
+redis.call('ZADD', KEYS[1], string.format('%.0f', rank), KEYS[2])
+return redis.call('HINCRBY', KEYS[2], 'revision', 1)
+";
+
+/// Finishes a game. Returns 1 on success, or 0 when there is no such game
+const FINISH_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[3]) == 0 then return 0 end
+local teams = redis.call('HMGET', KEYS[3], 'home', 'away')
+redis.call('HDEL', KEYS[2], teams[1], teams[2])
+redis.call('ZREM', KEYS[1], KEYS[3])
+redis.call('DEL', KEYS[3])
+return 1
+";
+
+/// Reads all the games in the order of the summary, each as a list of its `home`, `home_score`, `away`, `away_score`, `kickoff` and `revision` fields
+const READ_SCRIPT: &str = r"
+local result = {}
+for _, key in ipairs(redis.call('ZREVRANGE', KEYS[1], 0, -1)) do
+	table.insert(result, redis.call('HMGET', key, 'home', 'home_score', 'away', 'away_score', 'kickoff', 'revision'))
+end
+return result
+";
+
+/// Removes all the games of the board
+const CLEAR_SCRIPT: &str = r"
+for _, key in ipairs(redis.call('ZRANGE', KEYS[1], 0, -1)) do
+	redis.call('DEL', key)
+end
+redis.call('DEL', KEYS[1], KEYS[2])
+return 1
+";
+
+/// A score board kept in Redis. It offers the same operations as `ScoreBoard`, but every call goes to Redis, so all the replicas using the same board ID see the same games. Validators, debouncing, events and the other extras of `ScoreBoard` are not available here; use `snapshot()` and `ScoreBoard::from_snapshot()` to use the exports on a local copy
+pub struct RedisScoreBoard {
+	/// Connection to the Redis server
+	connection: Connection,
+	/// ID of the board, the prefix of all its keys
+	board_id: String,
+}
+
+impl RedisScoreBoard {
+	/// Connects to a board in Redis. The board doesn't need to exist before, it is created with the first game
+	///
+	/// # Arguments
+	///
+	/// * `url` - URL of the Redis server, ex. "redis://127.0.0.1/"
+	/// * `board_id` - ID of the board, shared by all the replicas. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When the server can't be reached
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// let mut sb = scoreboard_world_cup::RedisScoreBoard::connect("redis://127.0.0.1/", "world-cup").unwrap();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(sb.get_summary().unwrap(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// ```
+	pub fn connect<T: ToString>(url: &str, board_id: T) -> Result<RedisScoreBoard, ScoreBoardError> {
+		let board_id = board_id.to_string();
+
+		trace!("Connecting to board '{}' in Redis", board_id);
+
+		let connection = Client::open(url).and_then(|client| client.get_connection()).map_err(get_storage_error)?;
+
+		Ok(RedisScoreBoard { connection, board_id })
+	}
+
+	/// Starts a game between two teams, with initial score 0 - 0. See `ScoreBoard::start_game()`
+	///
+	/// # Errors
+	///
+	/// * When the two provided names are the same
+	/// * When any of the provided team is currently playing a match, on any of the replicas
+	/// * When Redis can't be reached
+	pub fn start_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		operation_span!(tracing::Level::INFO, "start_game", home = %home_name, away = %away_name);
+
+		trace!("Trying to start a game for teams: '{}' and '{}' in Redis", home_name, away_name);
+
+		if home_name == away_name {
+			warn!("{} cannot play with itself", home_name);
+			return Err(ScoreBoardError::SameTeam(home_name));
+		}
+
+		let result: i64 = Script::new(START_SCRIPT)
+			.key(self.get_games_key())
+			.key(self.get_teams_key())
+			.key(self.get_game_key(&home_name, &away_name))
+			.arg(&home_name)
+			.arg(&away_name)
+			.arg(to_unix_millis(SystemTime::now()))
+			.invoke(&mut self.connection)
+			.map_err(get_storage_error)?;
+
+		match result {
+			1 => Err(ScoreBoardError::AlreadyPlaying(home_name)),
+			2 => Err(ScoreBoardError::AlreadyPlaying(away_name)),
+			_ => {
+				trace!("Game started");
+				Ok(())
+			},
+		}
+	}
+
+	/// Updates a score of a running match with absolute values. See `ScoreBoard::update_score()`
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	/// * When Redis can't be reached
+	pub fn update_score<T: ToString, U: ToString>(&mut self, home: T, new_home_score: u8, away: U, new_away_score: u8) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		operation_span!(tracing::Level::INFO, "update_score", home = %home_name, home_score = new_home_score, away = %away_name, away_score = new_away_score);
+
+		trace!("Updating score in Redis to: {} {} - {} {}", home_name, new_home_score, away_name, new_away_score);
+
+		let revision: i64 = Script::new(UPDATE_SCRIPT)
+			.key(self.get_games_key())
+			.key(self.get_game_key(&home_name, &away_name))
+			.arg(new_home_score)
+			.arg(new_away_score)
+			.arg(TOTAL_SCORE_WEIGHT)
+			.invoke(&mut self.connection)
+			.map_err(get_storage_error)?;
+
+		if revision == 0 {
+			warn!("Couldn't find a game for update");
+			return Err(ScoreBoardError::GameNotFound { home: home_name, away: away_name, operation: Operation::UpdateScore, suggestion: None });
+		}
+
+		trace!("Update successful");
+
+		Ok(())
+	}
+
+	/// Finishes a match and removes it from the score board. See `ScoreBoard::finish_game()`
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	/// * When Redis can't be reached
+	pub fn finish_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		operation_span!(tracing::Level::INFO, "finish_game", home = %home_name, away = %away_name);
+
+		trace!("Ending a game between '{}' and '{}' in Redis", home_name, away_name);
+
+		let result: i64 = Script::new(FINISH_SCRIPT)
+			.key(self.get_games_key())
+			.key(self.get_teams_key())
+			.key(self.get_game_key(&home_name, &away_name))
+			.invoke(&mut self.connection)
+			.map_err(get_storage_error)?;
+
+		if result == 0 {
+			warn!("Couldn't find a game for removal");
+			return Err(ScoreBoardError::GameNotFound { home: home_name, away: away_name, operation: Operation::FinishGame, suggestion: None });
+		}
+
+		trace!("Game removed successfully");
+
+		Ok(())
+	}
+
+	/// Provides the current status of the scoreboard, in the same order as `ScoreBoard::get_summary()`
+	///
+	/// # Errors
+	///
+	/// * When Redis can't be reached
+	pub fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+		trace!("Getting the score board summary from Redis");
+
+		let snapshot = self.snapshot()?;
+
+		Ok(snapshot.games.iter().map(|game| format!("{} {} - {} {}", game.home_team, game.home_score, game.away_team, game.away_score)).collect())
+	}
+
+	/// Takes a copy of all the currently played games, in the order of the summary. The copy can be turned into a local `ScoreBoard` with `ScoreBoard::from_snapshot()`
+	///
+	/// # Errors
+	///
+	/// * When Redis can't be reached or holds invalid data
+	pub fn snapshot(&mut self) -> Result<BoardSnapshot, ScoreBoardError> {
+		let games: Vec<Vec<String>> = Script::new(READ_SCRIPT)
+			.key(self.get_games_key())
+			.invoke(&mut self.connection)
+			.map_err(get_storage_error)?;
+
+		let games = games.into_iter().map(parse_game).collect::<Result<Vec<GameSnapshot>, ScoreBoardError>>()?;
+
+		Ok(BoardSnapshot::new(games))
+	}
+
+	/// Removes all the games of the board, for all the replicas
+	///
+	/// # Errors
+	///
+	/// * When Redis can't be reached
+	pub fn clear(&mut self) -> Result<(), ScoreBoardError> {
+		trace!("Clearing board '{}' in Redis", self.board_id);
+
+		let _: i64 = Script::new(CLEAR_SCRIPT)
+			.key(self.get_games_key())
+			.key(self.get_teams_key())
+			.invoke(&mut self.connection)
+			.map_err(get_storage_error)?;
+
+		Ok(())
+	}
+
+	/// Key of the sorted set of the games
+	fn get_games_key(&self) -> String {
+		format!("{}:games", self.board_id)
+	}
+
+	/// Key of the hash of the playing teams
+	fn get_teams_key(&self) -> String {
+		format!("{}:teams", self.board_id)
+	}
+
+	/// Key of the hash of a game. The length of the home team name keeps the keys of different pairs of teams apart, even if the names contain colons
+	fn get_game_key(&self, home_name: &str, away_name: &str) -> String {
+		format!("{}:game:{}:{}:{}", self.board_id, home_name.len(), home_name, away_name)
+	}
+}
+
+/// Builds a game from the fields read by `READ_SCRIPT`
+fn parse_game(fields: Vec<String>) -> Result<GameSnapshot, ScoreBoardError> {
+	let invalid = || ScoreBoardError::Storage(String::from("Redis error: invalid game data"));
+
+	match fields.as_slice() {
+		[home, home_score, away, away_score, kickoff, revision] => Ok(GameSnapshot {
+			home_team: home.clone(),
+			home_score: home_score.parse().map_err(|_| invalid())?,
+			away_team: away.clone(),
+			away_score: away_score.parse().map_err(|_| invalid())?,
+			kickoff_time: from_unix_millis(kickoff.parse().map_err(|_| invalid())?),
+			revision: revision.parse().map_err(|_| invalid())?,
+			state: GameState::Live,
+			period: Period::FirstHalf,
+			venue: None,
+			group: None,
+			utc_offset: UtcOffset::UTC,
+		}),
+		_ => Err(invalid()),
+	}
+}
+
+/// Converts a wall clock time to the milliseconds since the Unix epoch
+fn to_unix_millis(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_millis() as u64)
+}
+
+/// Converts the milliseconds since the Unix epoch to a wall clock time
+fn from_unix_millis(millis: u64) -> SystemTime {
+	UNIX_EPOCH + std::time::Duration::from_millis(millis)
+}
+
+/// Wraps a Redis error
+fn get_storage_error(error: redis::RedisError) -> ScoreBoardError {
+	warn!("Redis error: {}", error);
+	ScoreBoardError::Storage(format!("Redis error: {}", error))
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::ScoreBoard;
+
+	#[test]
+	fn stored_game_is_parsed() {
+		let fields = vec![String::from("Spain"), String::from("2"), String::from("Brazil"), String::from("1"), String::from("1671375600000"), String::from("3")];
+		let expected = GameSnapshot {
+			home_team: String::from("Spain"),
+			home_score: 2,
+			away_team: String::from("Brazil"),
+			away_score: 1,
+			kickoff_time: UNIX_EPOCH + std::time::Duration::from_secs(1_671_375_600),
+			revision: 3,
+			state: GameState::Live,
+			period: Period::FirstHalf,
+			venue: None,
+			group: None,
+			utc_offset: UtcOffset::UTC,
+		};
+
+		assert_eq!(parse_game(fields), Ok(expected));
+	}
+
+	#[test]
+	fn invalid_stored_game_is_rejected() {
+		let fields = vec![String::from("Spain"), String::from("300"), String::from("Brazil"), String::from("1"), String::from("0"), String::from("1")];
+
+		assert!(parse_game(fields).err().is_some_and(|result| result.to_string() == "Redis error: invalid game data"));
+		assert!(parse_game(vec![String::from("Spain")]).is_err());
+	}
+
+	#[test]
+	fn rank_orders_by_total_score_then_kickoff() {
+		let rank = |total: u32, kickoff: u64| total as f64 * TOTAL_SCORE_WEIGHT + kickoff as f64;
+		let kickoff = to_unix_millis(SystemTime::now());
+
+		assert!(rank(1, 0) > rank(0, kickoff));
+		assert!(rank(3, kickoff + 1) > rank(3, kickoff));
+		assert!(rank(510, kickoff) < 2f64.powi(53));
+	}
+
+	/// Needs a Redis server, given with the `REDIS_URL` environment variable. Run with `cargo test --features redis -- --ignored`
+	#[test]
+	#[ignore]
+	fn replicas_share_the_board() {
+		let url = std::env::var("REDIS_URL").unwrap_or_else(|_| String::from("redis://127.0.0.1/"));
+		let mut replica_1 = RedisScoreBoard::connect(&url, "scoreboard-test").expect("Couldn't connect the first replica");
+		let mut replica_2 = RedisScoreBoard::connect(&url, "scoreboard-test").expect("Couldn't connect the second replica");
+		replica_1.clear().expect("Couldn't clear the board");
+
+		replica_1.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		replica_2.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		replica_1.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		let result_1 = replica_2.start_game("Brazil", "Germany");
+		let result_2 = replica_2.update_score("Brazil", 1, "Spain", 0);
+
+		assert!(result_1.err().is_some_and(|result| result.to_string() == "Brazil is currently playing a game"));
+		assert!(result_2.err().is_some_and(|result| result.to_string() == "Couldn't find a game for update"));
+		assert_eq!(replica_2.get_summary().expect("Couldn't get the summary"), vec![String::from("Mexico 0 - Canada 5"), String::from("Spain 0 - Brazil 0")]);
+
+		replica_2.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+		let local = ScoreBoard::from_snapshot(replica_1.snapshot().expect("Couldn't take a snapshot")).expect("Couldn't restore the board");
+		assert_eq!(local.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+
+		replica_1.clear().expect("Couldn't clear the board");
+	}
+}
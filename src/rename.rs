@@ -0,0 +1,271 @@
+//! # Team renames
+//!
+//! Corrects the name of a team everywhere on the board at once, so a misspelled name can be fixed minutes after the kickoff without finishing and recreating the game
+
+use std::string::{String, ToString};
+
+use log::{debug, trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::events::ScoreBoardEvent;
+use crate::goal::Goal;
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Renames a team and records a `ScoreBoardEvent::TeamRenamed`. The running game, the schedule, the archive, the called off games, the notes, the cards, the players and the history of the events all get the new name, so the game goes on with its score, goals and revision. The aliases of the old name lead to the new one
+	///
+	/// # Arguments
+	///
+	/// * `old` - Current name or alias of the team. Must be either a `String` or a type that is convertable to `String`
+	/// * `new` - The corrected name of the team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When the board doesn't know the team
+	/// * When a team with the new name is currently playing a game
+	/// * When the board already knows a team with the new name, from the schedule, the archive, the called off games or the players, since their histories would be merged
+	/// * When only the known national teams can play and the new name isn't one of them
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Agrentina", "France").unwrap();
+	/// sb.update_score("Agrentina", 1, "France", 0).unwrap();
+	/// sb.rename_team("Agrentina", "Argentina").unwrap();
+	///
+	/// assert_eq!(sb.get_summary(), vec![String::from("Argentina 1 - France 0")]);
+	/// assert_eq!(sb.get_audit_log("Argentina", "France").len(), 2);
+	/// ```
+	pub fn rename_team<T: ToString, U: ToString>(&mut self, old: T, new: U) -> Result<(), ScoreBoardError> {
+		let old_name = self.resolve_team(old);
+		let new_name = new.to_string();
+
+		operation_span!(tracing::Level::INFO, "rename_team", old = %old_name, new = %new_name);
+
+		trace!("Renaming '{}' to '{}'", old_name, new_name);
+
+		if !self.is_known_team(&old_name) {
+			warn!("Couldn't find the team {}", old_name);
+			return Err(ScoreBoardError::UnknownTeam(old_name));
+		}
+
+		if old_name == new_name {
+			return Ok(());
+		}
+
+		if self.team_index.contains_key(&new_name) {
+			warn!("{} is currently playing a game", new_name);
+			return Err(ScoreBoardError::AlreadyPlaying(new_name));
+		}
+
+		if self.is_known_team(&new_name) {
+			warn!("The name {} is already taken by another team", new_name);
+			return Err(ScoreBoardError::TeamNameTaken(new_name));
+		}
+
+		self.check_countries(&new_name, &new_name)?;
+
+		self.write_ahead_change(&["renamed", &old_name, &new_name])?;
+
+		let rename = |name: &mut String| if *name == old_name { name.clone_from(&new_name) };
+		let rename_goals = |goals: &mut [Goal]| {
+			for scorer in goals.iter_mut().filter_map(|goal| goal.scorer.as_mut()) {
+				rename(&mut scorer.team);
+			}
+		};
+
+		for game in &mut self.data {
+			rename(&mut game.home_team.name);
+			rename(&mut game.away_team.name);
+			rename_goals(&mut game.goals);
+		}
+		if let Some(key) = self.team_index.remove(&old_name) {
+			self.team_index.insert(new_name.clone(), key);
+		}
+
+		for fixture in &mut self.schedule {
+			rename(&mut fixture.home_name);
+			rename(&mut fixture.away_name);
+		}
+
+		for record in &mut self.events {
+			match &mut record.event {
+				ScoreBoardEvent::ScoreUpdated { home, away, goal, .. } => {
+					rename(home);
+					rename(away);
+					rename_goals(goal.as_mut_slice());
+				},
+				ScoreBoardEvent::GameStarted { home, away }
+				| ScoreBoardEvent::GameFinished { home, away, .. }
+				| ScoreBoardEvent::StateChanged { home, away, .. }
+				| ScoreBoardEvent::GameReopened { home, away, .. }
+				| ScoreBoardEvent::ResultFinalized { home, away, .. }
+				| ScoreBoardEvent::SidesSwapped { home, away, .. }
+				| ScoreBoardEvent::PeriodStarted { home, away, .. } => {
+					rename(home);
+					rename(away);
+				},
+				ScoreBoardEvent::TeamRenamed { .. } => (),
+			}
+		}
+		if let Some(checkpoint) = &mut self.checkpoint {
+			for game in &mut checkpoint.snapshot.games {
+				rename(&mut game.home_team);
+				rename(&mut game.away_team);
+			}
+		}
+
+		for game in &mut self.archive {
+			rename(&mut game.home_team);
+			rename(&mut game.away_team);
+			rename_goals(&mut game.goals);
+		}
+
+		for game in &mut self.called_off {
+			rename(&mut game.home_team);
+			rename(&mut game.away_team);
+		}
+
+		for note in &mut self.notes {
+			rename(&mut note.home);
+			rename(&mut note.away);
+		}
+
+		for card in &mut self.cards {
+			rename(&mut card.player.team);
+		}
+
+		if let Some(mut players) = self.players.remove(&old_name) {
+			players.iter_mut().for_each(|player| rename(&mut player.team));
+			self.players.insert(new_name.clone(), players);
+		}
+
+		for name in self.aliases.values_mut() {
+			rename(name);
+		}
+
+		self.invalidate_summary();
+
+		let revision = self.find_game_index_of_team(&new_name).map_or(0, |index| self.data[index].revision);
+		self.record_event(ScoreBoardEvent::TeamRenamed { old: old_name.clone(), new: new_name.clone() }, revision);
+
+		debug!("Renamed {} to {}", old_name, new_name);
+
+		Ok(())
+	}
+
+	/// Checks if the team plays, is scheduled, played or was called off, or has registered players
+	///
+	/// # Arguments
+	///
+	/// * `name` - name of the team
+	///
+	fn is_known_team(&self, name: &str) -> bool {
+		self.team_index.contains_key(name)
+			|| self.schedule.iter().any(|fixture| fixture.home_name == name || fixture.away_name == name)
+			|| self.archive.iter().any(|game| game.home_team == name || game.away_team == name)
+			|| self.called_off.iter().any(|game| game.home_team == name || game.away_team == name)
+			|| self.players.contains_key(name)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::{Duration, SystemTime};
+
+	use crate::goal::{GoalKind, Side};
+
+	const OLD_NAME: &str = "Agrentina";
+	const NEW_NAME: &str = "Argentina";
+
+	#[test]
+	fn running_game_keeps_its_score_and_rank() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game(OLD_NAME, "France").expect("Couldn't create the second game");
+		sb.update_score(OLD_NAME, 2, "France", 1).expect("Couldn't update the second game");
+		sb.rename_team(OLD_NAME, NEW_NAME).expect("Couldn't rename the team");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Argentina 2 - France 1"), String::from("Mexico 0 - Canada 0")]);
+		assert!(sb.update_score(NEW_NAME, 3, "France", 1).is_ok());
+		assert!(sb.update_score(OLD_NAME, 4, "France", 1).is_err());
+		assert_eq!(sb.get_game(NEW_NAME, "France").expect("Couldn't find the game").revision, 3);
+	}
+
+	#[test]
+	fn history_follows_the_new_name() {
+		let mut sb = ScoreBoard::new();
+		sb.register_player(OLD_NAME, "Messi", 10).expect("Couldn't register the player");
+		sb.start_game(OLD_NAME, "France").expect("Couldn't create the game");
+		sb.record_goal_by(OLD_NAME, "France", Side::Home, GoalKind::Penalty, "Messi").expect("Couldn't record the goal");
+		sb.finish_game(OLD_NAME, "France").expect("Couldn't finish the game");
+		sb.rename_team(OLD_NAME, NEW_NAME).expect("Couldn't rename the team");
+
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Argentina 1 - 0 France")]);
+		assert_eq!(sb.get_audit_log(NEW_NAME, "France").len(), 4);
+		assert_eq!(sb.top_scorers(1)[0].player.team, NEW_NAME);
+		assert_eq!(sb.get_players(NEW_NAME)[0].name, "Messi");
+		assert!(sb.get_players(OLD_NAME).is_empty());
+	}
+
+	#[test]
+	fn fixtures_and_aliases_follow_the_new_name() {
+		let mut sb = ScoreBoard::new();
+		sb.add_alias("ARG", OLD_NAME);
+		sb.schedule_game(OLD_NAME, "France", SystemTime::now() + Duration::from_secs(3600)).expect("Couldn't schedule the game");
+		sb.rename_team("ARG", NEW_NAME).expect("Couldn't rename the team");
+		sb.start_game("ARG", "France").expect("Couldn't start the scheduled game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Argentina 0 - France 0")]);
+		assert!(sb.schedule.is_empty());
+	}
+
+	#[test]
+	fn unknown_team_cannot_be_renamed() {
+		let mut sb = ScoreBoard::new();
+
+		assert_eq!(sb.rename_team(OLD_NAME, NEW_NAME), Err(ScoreBoardError::UnknownTeam(String::from(OLD_NAME))));
+	}
+
+	#[test]
+	fn playing_team_name_cannot_be_taken() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(OLD_NAME, "France").expect("Couldn't create the first game");
+		sb.start_game(NEW_NAME, "Croatia").expect("Couldn't create the second game");
+
+		assert_eq!(sb.rename_team(OLD_NAME, NEW_NAME), Err(ScoreBoardError::AlreadyPlaying(String::from(NEW_NAME))));
+		assert!(sb.get_game(OLD_NAME, "France").is_some());
+	}
+
+	#[test]
+	fn known_team_name_cannot_be_taken() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("France", "Croatia").expect("Couldn't create the first game");
+		sb.finish_game("France", "Croatia").expect("Couldn't finish the first game");
+		sb.schedule_game("Morocco", "Spain", SystemTime::now() + Duration::from_secs(3600)).expect("Couldn't schedule the game");
+		sb.register_player("Portugal", "Ronaldo", 7).expect("Couldn't register the player");
+		sb.start_game(OLD_NAME, "Mexico").expect("Couldn't create the second game");
+
+		for name in ["France", "Morocco", "Portugal"] {
+			assert_eq!(sb.rename_team(OLD_NAME, name), Err(ScoreBoardError::TeamNameTaken(String::from(name))));
+		}
+		assert!(sb.get_game(OLD_NAME, "Mexico").is_some());
+		assert_eq!(sb.get_archive_summary(), vec![String::from("France 0 - 0 Croatia")]);
+	}
+
+	#[test]
+	fn new_name_must_be_a_country_on_a_strict_board() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(NEW_NAME, "France").expect("Couldn't create the game");
+		sb.set_strict_countries(true);
+
+		assert!(matches!(sb.rename_team(NEW_NAME, OLD_NAME), Err(ScoreBoardError::UnknownCountry { .. })));
+	}
+}
@@ -0,0 +1,230 @@
+//! # Reopened games
+//!
+//! Brings a finished game back from the archive onto the board, for the times when the full time was signaled too early
+
+use std::string::ToString;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::{trace, warn};
+
+use crate::archive::PlayDetails;
+use crate::error::{Operation, ScoreBoardError};
+use crate::events::ScoreBoardEvent;
+use crate::minute::MatchClock;
+use crate::period::Period;
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+use crate::xg::ExpectedGoals;
+use crate::transaction::ArchiveChange;
+use crate::{Game, ScoreBoard, Team};
+
+impl ScoreBoard {
+	/// Reopens the most recently finished game between the teams. The game is taken out of the archive and goes on as if it wasn't finished: with its score, goals, kickoff time, periods, half-time score, venue, group, odds and expected goals. The match clock goes on from the minute of the finish. A game awarded with `award_forfeit()` goes on in the first period instead
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is no finished game between the given teams
+	/// * When any of the teams is currently playing a match
+	/// * When the score board already has as many running games as allowed (see `set_max_games()`)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// sb.finish_game("Japan", "Indonesia").unwrap();
+	/// sb.reopen_game("Japan", "Indonesia").unwrap();
+	///
+	/// assert!(sb.get_archive().is_empty());
+	/// assert_eq!(sb.get_summary(), vec![String::from("Japan 1 - Indonesia 0")]);
+	/// ```
+	pub fn reopen_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "reopen_game", home = %home_name, away = %away_name);
+
+		trace!("Reopening the game between '{}' and '{}'", home_name, away_name);
+
+		let archive_index = match self.archive.iter().rposition(|game| game.home_team == home_name && game.away_team == away_name) {
+			Some(archive_index) => archive_index,
+			None => {
+				warn!("Couldn't find a finished game to reopen");
+				return Err(ScoreBoardError::GameNotFound { home: home_name, away: away_name, operation: Operation::ReopenGame, suggestion: None });
+			},
+		};
+
+		self.check_if_currently_playing(&home_name, &away_name)?;
+
+		self.check_max_games()?;
+
+		// The game goes on from the revision of its finish, unless the finish was compacted out of the log
+		let revision = self.events.iter()
+			.rev()
+			.find(|record| matches!(&record.event, ScoreBoardEvent::GameFinished { home, away, .. } if *home == home_name && *away == away_name))
+			.map_or(1, |record| record.revision + 1);
+
+		let event = ScoreBoardEvent::GameReopened {
+			home: home_name.clone(),
+			home_score: self.archive[archive_index].home_score,
+			away: away_name.clone(),
+			away_score: self.archive[archive_index].away_score,
+		};
+		self.write_ahead(&event)?;
+
+		let archived = self.archive.remove(archive_index);
+		self.stage_archive_change(ArchiveChange::Removed(archive_index, Box::new(archived.clone())));
+
+		self.record_event(event, revision);
+
+		let now = Instant::now();
+		let elapsed = SystemTime::now().duration_since(archived.kickoff_time).unwrap_or(Duration::ZERO);
+		let start_time = now.checked_sub(elapsed).unwrap_or(now);
+		let mut details = archived.details.unwrap_or_else(|| PlayDetails {
+			periods: vec![(Period::FirstHalf, (0, 0))],
+			half_time_score: None,
+			clock: MatchClock::new(start_time),
+			venue: None,
+			utc_offset: UtcOffset::UTC,
+			scheduled_kickoff: None,
+			group: None,
+			odds: None,
+			xg: ExpectedGoals::default(),
+		});
+		// The time since the finish doesn't count as played
+		details.clock.resume(now);
+		self.data.push(
+			Game {
+				home_team: Team { name: home_name, score: archived.home_score },
+				away_team: Team { name: away_name, score: archived.away_score },
+				start_time,
+				kickoff_time: archived.kickoff_time,
+				scheduled_kickoff: details.scheduled_kickoff,
+				last_update: None,
+				revision,
+				goals: archived.goals,
+				periods: details.periods,
+				half_time_score: details.half_time_score,
+				pause_score: None,
+				state: GameState::Live,
+				clock: details.clock,
+				venue: details.venue,
+				utc_offset: details.utc_offset,
+				group: details.group,
+				odds: details.odds,
+				xg: details.xg,
+			}
+		);
+
+		trace!("Game reopened");
+
+		self.reposition(self.data.len() - 1);
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+
+	use crate::goal::{GoalKind, Side};
+	use crate::timeline::TimelineKind;
+
+	const HOME_TEAM_NAME: &str = "Morocco";
+	const AWAY_TEAM_NAME: &str = "Portugal";
+
+	fn finish(sb: &mut ScoreBoard) {
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OpenPlay).expect("Couldn't record the goal");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+	}
+
+	#[test]
+	fn reopened_game_goes_on_with_its_score_and_kickoff() {
+		let mut sb = ScoreBoard::new();
+		finish(&mut sb);
+		let kickoff_time = sb.get_archive()[0].kickoff_time;
+		sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't reopen the game");
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Away, GoalKind::Penalty).expect("Couldn't record the goal");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the reopened game");
+		assert_eq!((game.home_score, game.away_score, game.kickoff_time), (1, 1, kickoff_time));
+		assert_eq!(game.revision, 5);
+		assert_eq!(sb.get_goal_counts(HOME_TEAM_NAME, AWAY_TEAM_NAME).map(|(home, away)| (home.open_play, away.penalty)), Some((1, 1)));
+	}
+
+	#[test]
+	fn reopened_game_goes_on_with_its_timing_and_details() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).venue("Al Thumama Stadium").group('F').start().expect("Couldn't create the game");
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Home, GoalKind::OpenPlay).expect("Couldn't record the goal");
+		sb.start_period(HOME_TEAM_NAME, AWAY_TEAM_NAME, Period::SecondHalf).expect("Couldn't start the second half");
+		// 47 minutes of the second half are played
+		sb.data[0].clock = MatchClock::new(Instant::now() - Duration::from_secs(47 * 60));
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the game");
+		sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't reopen the game");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the reopened game");
+		assert_eq!((game.period, game.half_time_score), (Period::SecondHalf, Some((1, 0))));
+		assert_eq!((game.venue.as_deref(), game.group), (Some("Al Thumama Stadium"), Some('F')));
+		assert_eq!(sb.get_match_minute(HOME_TEAM_NAME, AWAY_TEAM_NAME), Some(String::from("90+3'")));
+	}
+
+	#[test]
+	fn reopening_is_recorded_in_the_history() {
+		let mut sb = ScoreBoard::new();
+		finish(&mut sb);
+		sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't reopen the game");
+
+		let record = sb.get_events().last().expect("Couldn't find the event");
+		assert_eq!((record.revision, record.state), (4, GameState::Live));
+		assert!(record.to_json().contains("\"type\":\"game_reopened\""));
+		assert_eq!(sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).last().map(|entry| entry.kind.clone()), Some(TimelineKind::Reopened));
+
+		let snapshot = sb.state_at(SystemTime::now());
+		assert_eq!((snapshot.games[0].home_score, snapshot.games[0].kickoff_time), (1, sb.get_events()[0].time));
+	}
+
+	#[test]
+	fn only_the_latest_game_is_reopened() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first game");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the first game");
+		finish(&mut sb);
+		sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't reopen the game");
+
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Morocco 0 - 0 Portugal")]);
+		assert_eq!(sb.get_summary(), vec![String::from("Morocco 1 - Portugal 0")]);
+	}
+
+	#[test]
+	fn game_cannot_be_reopened_while_a_team_plays() {
+		let mut sb = ScoreBoard::new();
+		finish(&mut sb);
+		sb.start_game("Spain", AWAY_TEAM_NAME).expect("Couldn't create the next game");
+
+		assert_eq!(sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME), Err(ScoreBoardError::AlreadyPlaying(String::from(AWAY_TEAM_NAME))));
+		assert_eq!(sb.get_archive().len(), 1);
+	}
+
+	#[test]
+	fn unfinished_game_cannot_be_reopened() {
+		let mut sb = ScoreBoard::new();
+		let result = sb.reopen_game(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		assert_eq!(result.unwrap_err().to_string(), "Couldn't find a game for reopening");
+	}
+}
@@ -0,0 +1,272 @@
+//! # Interactive shell
+//!
+//! A prompt over a score board, taking the lines of the command scripts, so the staff can manage the scores live from a terminal session. Available with the `repl` feature
+
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::command::Command;
+use crate::ScoreBoard;
+
+/// Prompt shown before every line
+const PROMPT: &str = "> ";
+
+/// Ending of a line asking for the completions of its last word, as typed with the Tab key
+const COMPLETION_KEY: char = '\t';
+
+/// Keywords of the shell, next to the ones of `Command::parse()`
+const SHELL_KEYWORDS: [&str; 4] = ["summary", "history", "help", "quit"];
+
+/// Keywords of the commands, see `Command::parse()`
+const COMMAND_KEYWORDS: [&str; 6] = ["start", "score", "finish", "pause", "resume", "reopen"];
+
+/// Description of the shell, shown by `help`
+const HELP: &str = "\
+start <home> vs <away> - start a game
+score <home> <goals> <away> <goals> - set the score of a game
+finish|pause|resume|reopen <home> vs <away> - change a game
+summary - show the running games
+history - show the entered lines
+!! or !<number> - run the last or the numbered line again
+<start of a word><Tab><Enter> - show the completions of the word
+quit - leave the shell";
+
+/// An interactive shell over a score board. Every line is either a command of a script (see `Command::parse()`) or one of the shell keywords: `summary`, `history`, `help` and `quit`. The entered lines are kept in the history, and can be run again with `!!` or `!<number>`. A line ending with a Tab lists the completions of its last word, from the keywords and the names of the teams on the board
+///
+/// # Examples
+///
+/// ```
+/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+/// let mut output = Vec::new();
+/// sb.repl().run("start Spain vs Brazil\nscore Spain 1 Br\t\nsummary\n".as_bytes(), &mut output).unwrap();
+///
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.contains("score Spain 1 Brazil"));
+/// assert!(output.contains("Spain 0 - Brazil 0"));
+/// ```
+pub struct Repl<'a> {
+	/// The managed board
+	board: &'a mut ScoreBoard,
+	/// The entered lines, the oldest coming first
+	history: Vec<String>,
+}
+
+impl ScoreBoard {
+	/// Provides an interactive shell over the score board, see `Repl`
+	pub fn repl(&mut self) -> Repl<'_> {
+		Repl { board: self, history: Vec::new() }
+	}
+
+	/// Provides the names of all the teams known to the board: playing, scheduled and finished, together with their aliases, in the alphabetical order
+	fn get_team_names(&self) -> BTreeSet<&str> {
+		self.data.iter().flat_map(|game| [game.home_team.name.as_str(), game.away_team.name.as_str()])
+			.chain(self.schedule.iter().flat_map(|fixture| [fixture.home_name.as_str(), fixture.away_name.as_str()]))
+			.chain(self.archive.iter().flat_map(|game| [game.home_team.as_str(), game.away_team.as_str()]))
+			.chain(self.aliases.keys().map(String::as_str))
+			.collect()
+	}
+}
+
+impl<'a> Repl<'a> {
+	/// Runs the shell until `quit` or the end of the input, showing the prompt and the replies on the output
+	///
+	/// # Arguments
+	///
+	/// * `input` - the typed lines, ex. `std::io::stdin().lock()`
+	/// * `output` - the sink of the prompts and the replies, ex. `std::io::stdout()`
+	///
+	/// # Errors
+	///
+	/// * When reading the input or writing the output fails
+	pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+		trace!("Starting the shell");
+
+		loop {
+			write!(output, "{}", PROMPT)?;
+			output.flush()?;
+
+			let mut line = String::new();
+			if input.read_line(&mut line)? == 0 {
+				break;
+			}
+
+			let line = line.trim_end_matches(['\n', '\r']);
+			if line.trim().eq_ignore_ascii_case("quit") {
+				break;
+			}
+
+			for reply in self.execute(line) {
+				writeln!(output, "{}", reply)?;
+			}
+		}
+
+		trace!("Leaving the shell");
+
+		Ok(())
+	}
+
+	/// Runs a single line of the shell
+	///
+	/// # Arguments
+	///
+	/// * `line` - the line, without the line break
+	///
+	/// # Returns
+	///
+	/// * The lines of the reply
+	pub fn execute(&mut self, line: &str) -> Vec<String> {
+		if let Some(start) = line.strip_suffix(COMPLETION_KEY) {
+			return self.complete(start);
+		}
+
+		let line = line.trim();
+		let line = match self.recall(line) {
+			Some(Ok(recalled)) => recalled,
+			Some(Err(reply)) => return vec![reply],
+			None => line.to_string(),
+		};
+
+		if line.is_empty() {
+			return Vec::new();
+		}
+
+		let reply = match line.to_lowercase().as_str() {
+			"summary" => self.get_summary(),
+			"history" => self.history.iter().enumerate().map(|(id, entry)| format!("{:>4}  {}", id + 1, entry)).collect(),
+			"help" => HELP.lines().map(String::from).collect(),
+			_ => vec![match Command::parse(&line).and_then(|command| self.board.apply(command)) {
+				Ok(()) => String::from("OK"),
+				Err(error) => format!("Error: {}", error),
+			}],
+		};
+
+		self.history.push(line);
+
+		reply
+	}
+
+	/// Provides the completions of the last word of a line, from the keywords at the start of the line and from the team names after it
+	///
+	/// # Arguments
+	///
+	/// * `line` - the line typed so far
+	///
+	/// # Returns
+	///
+	/// * The whole lines with the last word completed, in the alphabetical order
+	pub fn complete(&self, line: &str) -> Vec<String> {
+		let (start, word) = match line.rfind(' ') {
+			Some(id) => line.split_at(id + 1),
+			None => ("", line),
+		};
+		let word = word.to_lowercase();
+
+		let candidates: BTreeSet<&str> = if start.trim().is_empty() {
+			COMMAND_KEYWORDS.into_iter().chain(SHELL_KEYWORDS).collect()
+		} else {
+			self.board.get_team_names()
+		};
+
+		candidates.into_iter()
+			.filter(|candidate| candidate.to_lowercase().starts_with(&word))
+			.map(|candidate| format!("{}{}", start, candidate))
+			.collect()
+	}
+
+	/// Provides the entered lines, the oldest coming first
+	pub fn get_history(&self) -> &[String] {
+		&self.history
+	}
+
+	/// Provides the summary of the board with a note for an empty board
+	fn get_summary(&self) -> Vec<String> {
+		match self.board.get_summary() {
+			summary if summary.is_empty() => vec![String::from("No games are played")],
+			summary => summary,
+		}
+	}
+
+	/// Finds the line from the history that a line refers to, with `!!` or `!<number>`
+	///
+	/// # Arguments
+	///
+	/// * `line` - the entered line
+	///
+	/// # Returns
+	///
+	/// * `None` when the line isn't a reference to the history, or the referred line, or the reply when there is no such line
+	fn recall(&self, line: &str) -> Option<Result<String, String>> {
+		let reference = line.strip_prefix('!')?;
+
+		let entry = match reference {
+			"!" => self.history.last(),
+			number => number.parse::<usize>().ok().and_then(|number| number.checked_sub(1)).and_then(|id| self.history.get(id)),
+		};
+
+		Some(entry.cloned().ok_or_else(|| format!("Error: no line {} in the history", line)))
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn commands_change_the_board() {
+		let mut sb = ScoreBoard::new();
+		let mut repl = sb.repl();
+
+		assert_eq!(repl.execute("summary"), vec![String::from("No games are played")]);
+		assert_eq!(repl.execute("start Costa Rica vs South Korea"), vec![String::from("OK")]);
+		assert_eq!(repl.execute("score Costa Rica 1 South Korea 0"), vec![String::from("OK")]);
+		assert_eq!(repl.execute("finish Spain Brazil"), vec![String::from("Error: Couldn't find a game for removal")]);
+		assert_eq!(repl.execute("kick Spain"), vec![String::from("Error: Couldn't parse the command 'kick Spain'")]);
+		assert_eq!(repl.execute("SUMMARY"), vec![String::from("Costa Rica 1 - South Korea 0")]);
+	}
+
+	#[test]
+	fn history_lines_are_run_again() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let mut repl = sb.repl();
+
+		repl.execute("pause Spain Brazil");
+		repl.execute("resume Spain Brazil");
+		assert_eq!(repl.execute("!1"), vec![String::from("OK")]);
+		assert_eq!(repl.execute("!!"), vec![String::from("Error: A game cannot go from paused to paused")]);
+		assert_eq!(repl.execute("!9"), vec![String::from("Error: no line !9 in the history")]);
+		assert_eq!(repl.get_history(), ["pause Spain Brazil", "resume Spain Brazil", "pause Spain Brazil", "pause Spain Brazil"]);
+		assert_eq!(repl.execute("history")[1], "   2  resume Spain Brazil");
+	}
+
+	#[test]
+	fn words_are_completed() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.start_game("Saudi Arabia", "Senegal").expect("Couldn't create the game");
+		let repl = sb.repl();
+
+		assert_eq!(repl.complete("s"), vec![String::from("score"), String::from("start"), String::from("summary")]);
+		assert_eq!(repl.complete("finish s"), vec![String::from("finish Saudi Arabia"), String::from("finish Senegal"), String::from("finish Spain")]);
+		assert_eq!(repl.complete("finish Spain vs Br"), vec![String::from("finish Spain vs Brazil")]);
+		assert!(repl.complete("finish x").is_empty());
+	}
+
+	#[test]
+	fn shell_runs_until_quit() {
+		let mut sb = ScoreBoard::new();
+		let mut output = Vec::new();
+		sb.repl().run("start Spain vs Brazil\r\nquit\nstart Mexico vs Canada\n".as_bytes(), &mut output).expect("Couldn't run the shell");
+
+		assert_eq!(String::from_utf8(output).expect("Couldn't read the output"), "> OK\n> ");
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+	}
+}
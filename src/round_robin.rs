@@ -0,0 +1,226 @@
+//! # Round-robin fixtures
+//!
+//! Generates the fixtures of a league where every team plays every other team, split into rounds, and puts them on the schedule of the board
+
+use std::string::{String, ToString};
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+use log::{debug, trace};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// A game of a round-robin league
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RoundRobinFixture {
+	/// Number of the round, from 1
+	pub round: u32,
+	/// Name of the home team
+	pub home: String,
+	/// Name of the away team
+	pub away: String,
+}
+
+/// Generates the fixtures of a round-robin league with the circle method. Every team plays once in every round, except for a team resting in every round when the number of teams is odd. The home and away games of every team differ at most by one
+///
+/// # Arguments
+///
+/// * `teams` - the teams of the league. Must be either `String`s or a type that is convertable to `String`
+/// * `double` - whether every pair of teams plays twice, once at home of each team. The second half of the season repeats the rounds of the first half with the home and away teams swapped
+///
+/// # Returns
+///
+/// * The fixtures ordered by the round
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::generate_round_robin;
+///
+/// let fixtures = generate_round_robin(&["Ajax", "PSV", "Feyenoord", "AZ"], true);
+/// assert_eq!(fixtures.len(), 12);
+/// assert_eq!(fixtures.last().unwrap().round, 6);
+/// ```
+pub fn generate_round_robin<T: ToString>(teams: &[T], double: bool) -> Vec<RoundRobinFixture> {
+	let names: Vec<String> = teams.iter().map(ToString::to_string).collect();
+
+	trace!("Generating the fixtures of {} teams", names.len());
+
+	if names.len() < 2 {
+		return Vec::new();
+	}
+
+	// With an even number of teams, the last one stays in the centre of the circle, and plays the team resting in the round otherwise
+	let circle = if names.len().is_multiple_of(2) { names.len() - 1 } else { names.len() };
+	let centre = names.get(circle);
+	let mut fixtures = Vec::new();
+
+	for round in 0..circle {
+		let number = round as u32 + 1;
+
+		if let Some(centre) = centre {
+			let (home, away) = if round.is_multiple_of(2) { (centre, &names[round]) } else { (&names[round], centre) };
+			fixtures.push(RoundRobinFixture { round: number, home: home.clone(), away: away.clone() });
+		}
+
+		for distance in 1..=circle / 2 {
+			let first = (round + distance) % circle;
+			let second = (round + circle - distance) % circle;
+			// Every team of the circle hosts the teams following it by less than half of the circle, which is exactly half of its opponents
+			let (home, away) = if (second + circle - first) % circle <= circle / 2 { (first, second) } else { (second, first) };
+			fixtures.push(RoundRobinFixture { round: number, home: names[home].clone(), away: names[away].clone() });
+		}
+	}
+
+	if double {
+		let return_legs: Vec<RoundRobinFixture> = fixtures.iter()
+			.map(|fixture| RoundRobinFixture { round: fixture.round + circle as u32, home: fixture.away.clone(), away: fixture.home.clone() })
+			.collect();
+		fixtures.extend(return_legs);
+	}
+
+	debug!("Generated {} fixtures", fixtures.len());
+
+	fixtures
+}
+
+impl ScoreBoard {
+	/// Schedules the fixtures of a round-robin league, see `generate_round_robin()`. The games of the same round share the kickoff time. Either all the fixtures are scheduled, or none of them
+	///
+	/// # Arguments
+	///
+	/// * `fixtures` - the fixtures to schedule
+	/// * `first_kickoff` - kickoff time of the games of round 1
+	/// * `round_interval` - time between the kickoffs of the following rounds
+	///
+	/// # Errors
+	///
+	/// * The same as `schedule_game()`, for any of the fixtures
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, SystemTime};
+	/// use scoreboard_world_cup::generate_round_robin;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let fixtures = generate_round_robin(&["Ajax", "PSV", "Feyenoord"], false);
+	/// sb.schedule_round_robin(&fixtures, SystemTime::now(), Duration::from_secs(7 * 24 * 3600)).unwrap();
+	/// ```
+	pub fn schedule_round_robin(&mut self, fixtures: &[RoundRobinFixture], first_kickoff: SystemTime, round_interval: Duration) -> Result<(), ScoreBoardError> {
+		trace!("Scheduling {} round-robin fixtures", fixtures.len());
+
+		self.staged(|staged| {
+			for fixture in fixtures {
+				let kickoff = first_kickoff + round_interval * fixture.round.saturating_sub(1);
+				staged.schedule_game(&fixture.home, &fixture.away, kickoff)?;
+			}
+
+			Ok(())
+		})
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::collections::{HashMap, HashSet};
+
+	const TEAMS: [&str; 6] = ["Ajax", "PSV", "Feyenoord", "AZ", "Twente", "Utrecht"];
+
+	/// Provides the number of home games of every team, minus its away games
+	fn get_home_games(fixtures: Vec<RoundRobinFixture>) -> HashMap<String, i32> {
+		let mut balance = HashMap::new();
+		for fixture in fixtures {
+			*balance.entry(fixture.home).or_insert(0) += 1;
+			*balance.entry(fixture.away).or_insert(0) -= 1;
+		}
+		balance
+	}
+
+	#[test]
+	fn every_pair_plays_once() {
+		for team_count in 2..=TEAMS.len() {
+			let fixtures = generate_round_robin(&TEAMS[..team_count], false);
+			let pairs: HashSet<(&str, &str)> = fixtures.iter().map(|fixture| {
+				let (home, away) = (fixture.home.as_str(), fixture.away.as_str());
+				if home < away { (home, away) } else { (away, home) }
+			}).collect();
+
+			assert_eq!(fixtures.len(), team_count * (team_count - 1) / 2);
+			assert_eq!(pairs.len(), fixtures.len());
+		}
+	}
+
+	#[test]
+	fn teams_play_once_per_round() {
+		for team_count in [5, 6] {
+			let fixtures = generate_round_robin(&TEAMS[..team_count], false);
+			let rounds = fixtures.iter().map(|fixture| fixture.round).max().expect("Couldn't find the last round");
+			assert_eq!(rounds, if team_count.is_multiple_of(2) { team_count - 1 } else { team_count } as u32);
+
+			for round in 1..=rounds {
+				let teams: Vec<&str> = fixtures.iter().filter(|fixture| fixture.round == round).flat_map(|fixture| [fixture.home.as_str(), fixture.away.as_str()]).collect();
+				let unique: HashSet<&str> = teams.iter().copied().collect();
+				assert_eq!(teams.len(), unique.len());
+				assert_eq!(teams.len(), team_count / 2 * 2);
+			}
+		}
+	}
+
+	#[test]
+	fn home_and_away_games_are_balanced() {
+		for team_count in 2..=TEAMS.len() {
+			let balance = get_home_games(generate_round_robin(&TEAMS[..team_count], false));
+			assert!(balance.values().all(|difference| difference.abs() <= 1), "Unbalanced league of {} teams: {:?}", team_count, balance);
+		}
+
+		let balance = get_home_games(generate_round_robin(&TEAMS, true));
+		assert!(balance.values().all(|difference| *difference == 0));
+	}
+
+	#[test]
+	fn second_half_swaps_the_home_teams() {
+		let fixtures = generate_round_robin(&TEAMS[..4], true);
+
+		assert_eq!(fixtures.len(), 12);
+		for (first, second) in fixtures[..6].iter().zip(&fixtures[6..]) {
+			assert_eq!((first.round + 3, &first.home, &first.away), (second.round, &second.away, &second.home));
+		}
+	}
+
+	#[test]
+	fn too_few_teams_have_no_fixtures() {
+		assert!(generate_round_robin::<&str>(&[], false).is_empty());
+		assert!(generate_round_robin(&["Ajax"], true).is_empty());
+	}
+
+	#[test]
+	fn fixtures_are_scheduled_by_round() {
+		let mut sb = ScoreBoard::new();
+		let start = SystemTime::UNIX_EPOCH;
+		let week = Duration::from_secs(7 * 24 * 3600);
+		sb.schedule_round_robin(&generate_round_robin(&TEAMS[..4], false), start, week).expect("Couldn't schedule the fixtures");
+
+		assert_eq!(sb.schedule.len(), 6);
+		assert_eq!(sb.schedule.last().map(|fixture| fixture.kickoff), Some(start + week * 2));
+	}
+
+	#[test]
+	fn failed_scheduling_leaves_the_schedule_unchanged() {
+		let mut sb = ScoreBoard::new();
+		let fixtures = vec![
+			RoundRobinFixture { round: 1, home: String::from("Ajax"), away: String::from("PSV") },
+			RoundRobinFixture { round: 1, home: String::from("AZ"), away: String::from("AZ") },
+		];
+
+		assert_eq!(sb.schedule_round_robin(&fixtures, SystemTime::UNIX_EPOCH, Duration::ZERO), Err(ScoreBoardError::SameTeam(String::from("AZ"))));
+		assert!(sb.schedule.is_empty());
+	}
+}
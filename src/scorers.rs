@@ -0,0 +1,186 @@
+//! # Scorer leaderboard
+//!
+//! Counts the goals of the players across the running and the finished games, so the standings of the tournament's top scorers come straight from the board. With the `parallel` feature, the goals of a large archive are counted in parallel, see the statistics
+
+use std::collections::HashMap;
+use std::string::String;
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::archive::ArchivedGame;
+use crate::goal::{Goal, GoalKind};
+use crate::player::Player;
+use crate::stats::aggregate;
+use crate::ScoreBoard;
+
+/// Goals of the players, by their teams and names
+type ScorerCounts<'a> = HashMap<(&'a str, &'a str), u32>;
+
+/// Goals of a single player, see `ScoreBoard::top_scorers()`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScorerStanding {
+	/// The player, as currently registered with `ScoreBoard::register_player()`
+	pub player: Player,
+	/// Number of the goals of the player
+	pub goals: u32,
+}
+
+impl ScoreBoard {
+	/// Provides the players with the most goals, across the running and the finished games. Only the goals recorded with `record_goal_by()` have a scorer, and own goals are not counted. Players with the same number of goals are ordered by name. Players are told apart by their names and teams, so the goals scored before a player was registered are counted too
+	///
+	/// # Arguments
+	///
+	/// * `n` - the most players to provide
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{GoalKind, Side};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "France").unwrap();
+	/// sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::Penalty, "Messi").unwrap();
+	/// sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::Penalty, "Mbappe").unwrap();
+	/// sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::OpenPlay, "Mbappe").unwrap();
+	/// sb.finish_game("Argentina", "France").unwrap();
+	///
+	/// let top = sb.top_scorers(1);
+	/// assert_eq!((top[0].player.name.as_str(), top[0].player.team.as_str(), top[0].goals), ("Mbappe", "France", 2));
+	/// ```
+	pub fn top_scorers(&self, n: usize) -> Vec<ScorerStanding> {
+		trace!("Getting the top {} scorers", n);
+
+		let mut counts = count_archive_goals(&self.archive);
+		count_goals(self.data.iter().flat_map(|game| &game.goals), &mut counts);
+
+		let mut standings: Vec<ScorerStanding> = counts.into_iter()
+			.map(|((team, name), goals)| ScorerStanding { player: self.get_player(team, String::from(name)), goals })
+			.collect();
+		standings.sort_by(|a, b| b.goals.cmp(&a.goals).then_with(|| a.player.name.cmp(&b.player.name)).then_with(|| a.player.team.cmp(&b.player.team)));
+		standings.truncate(n);
+
+		standings
+	}
+}
+
+/// Adds the goals to the counts of their scorers. Own goals and the goals without a scorer are left out
+///
+/// # Arguments
+///
+/// * `goals` - the counted goals
+/// * `counts` - the counts to add the goals to
+///
+fn count_goals<'a>(goals: impl Iterator<Item = &'a Goal>, counts: &mut ScorerCounts<'a>) {
+	for goal in goals.filter(|goal| goal.kind != GoalKind::OwnGoal) {
+		if let Some(scorer) = &goal.scorer {
+			*counts.entry((scorer.team.as_str(), scorer.name.as_str())).or_insert(0) += 1;
+		}
+	}
+}
+
+/// Counts the goals of the finished games, see `aggregate()`
+///
+/// # Arguments
+///
+/// * `archive` - the finished games
+///
+fn count_archive_goals(archive: &[ArchivedGame]) -> ScorerCounts<'_> {
+	aggregate(
+		archive,
+		|mut counts: ScorerCounts<'_>, game| {
+			count_goals(game.goals.iter(), &mut counts);
+			counts
+		},
+		|mut counts, other| {
+			for (scorer, goals) in other {
+				*counts.entry(scorer).or_insert(0) += goals;
+			}
+			counts
+		},
+	)
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::goal::Side;
+
+	fn get_standings(sb: &ScoreBoard, n: usize) -> Vec<(String, String, u32)> {
+		sb.top_scorers(n).into_iter().map(|standing| (standing.player.name, standing.player.team, standing.goals)).collect()
+	}
+
+	fn standing(scorer: &str, team: &str, goals: u32) -> (String, String, u32) {
+		(String::from(scorer), String::from(team), goals)
+	}
+
+	#[test]
+	fn goals_are_counted_across_games() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "Croatia").expect("Couldn't create the first game");
+		sb.record_goal_by("Argentina", "Croatia", Side::Home, GoalKind::Penalty, "Messi").expect("Couldn't record the first goal");
+		sb.record_goal_by("Argentina", "Croatia", Side::Home, GoalKind::OpenPlay, "Alvarez").expect("Couldn't record the second goal");
+		sb.finish_game("Argentina", "Croatia").expect("Couldn't finish the first game");
+		sb.start_game("France", "Argentina").expect("Couldn't create the second game");
+		sb.record_goal_by("France", "Argentina", Side::Away, GoalKind::OpenPlay, "Messi").expect("Couldn't record the third goal");
+		sb.record_goal_by("France", "Argentina", Side::Home, GoalKind::FreeKick, "Mbappe").expect("Couldn't record the fourth goal");
+
+		assert_eq!(get_standings(&sb, 10), vec![standing("Messi", "Argentina", 2), standing("Alvarez", "Argentina", 1), standing("Mbappe", "France", 1)]);
+		assert_eq!(get_standings(&sb, 1), vec![standing("Messi", "Argentina", 2)]);
+	}
+
+	#[test]
+	fn own_goals_and_anonymous_goals_are_not_counted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		sb.record_goal("Argentina", "France", Side::Home, GoalKind::OpenPlay).expect("Couldn't record the first goal");
+		sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::OwnGoal, "Otamendi").expect("Couldn't record the second goal");
+
+		assert!(sb.top_scorers(5).is_empty());
+	}
+
+	#[test]
+	fn goals_before_the_registration_are_counted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::Penalty, "Messi").expect("Couldn't record the first goal");
+		sb.register_player("Argentina", "Messi", 10).expect("Couldn't register the player");
+		sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::OpenPlay, "Messi").expect("Couldn't record the second goal");
+
+		let top = sb.top_scorers(5);
+		assert_eq!(top, vec![ScorerStanding { player: Player { name: String::from("Messi"), number: Some(10), team: String::from("Argentina") }, goals: 2 }]);
+	}
+
+	#[test]
+	fn disallowed_goals_are_not_counted() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::OpenPlay, "Messi").expect("Couldn't record the first goal");
+		sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::OpenPlay, "Di Maria").expect("Couldn't record the second goal");
+		sb.update_score("Argentina", 1, "France", 0).expect("Couldn't disallow the second goal");
+
+		assert_eq!(get_standings(&sb, 5), vec![standing("Messi", "Argentina", 1)]);
+	}
+
+	#[test]
+	fn goals_of_a_large_archive_are_all_counted() {
+		let mut sb = ScoreBoard::new();
+		for game in 0..3000 {
+			sb.start_game("Argentina", "France").expect("Couldn't create the game");
+			sb.record_goal_by("Argentina", "France", Side::Home, GoalKind::OpenPlay, "Messi").expect("Couldn't record the first goal");
+			if game % 3 == 0 {
+				sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::Penalty, "Mbappe").expect("Couldn't record the second goal");
+			}
+			sb.finish_game("Argentina", "France").expect("Couldn't finish the game");
+		}
+		sb.start_game("Argentina", "France").expect("Couldn't create the running game");
+		sb.record_goal_by("Argentina", "France", Side::Away, GoalKind::OpenPlay, "Mbappe").expect("Couldn't record the goal of the running game");
+
+		assert_eq!(get_standings(&sb, 5), vec![standing("Messi", "Argentina", 3000), standing("Mbappe", "France", 1001)]);
+	}
+}
@@ -0,0 +1,223 @@
+//! # Command scripts
+//!
+//! Parses short text lines, like `start Spain vs Brazil`, into commands, so the operators can drive the board from simple scripts and chat bots
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::command::Command;
+use crate::error::ScoreBoardError;
+
+/// Separator of the teams of a game
+const VERSUS_SEPARATOR: &str = "vs";
+
+/// Start of a comment line in a script
+const COMMENT_START: char = '#';
+
+impl Command {
+	/// Parses a single line of a command script
+	///
+	/// Keywords are case-insensitive, and the words can be separated with any amount of white space. The teams of a game are written as `<home> vs <away>`, or as `<home> <away>` when both names are single words
+	///
+	/// | Line | Example | Command |
+	/// | --- | --- | --- |
+	/// | `start <teams>` | `start Spain vs Brazil` | `Command::StartGame` |
+	/// | `score <home> <home score> <away> <away score>` | `score Costa Rica 2 Brazil 1` | `Command::UpdateScore` |
+	/// | `finish <teams>` | `finish Spain Brazil` | `Command::FinishGame` |
+	/// | `pause <teams>` | `pause Spain Brazil` | `Command::PauseGame` |
+	/// | `resume <teams>` | `resume Spain Brazil` | `Command::ResumeGame` |
+	/// | `reopen <teams>` | `reopen Spain Brazil` | `Command::ReopenGame` |
+	///
+	/// # Arguments
+	///
+	/// * `line` - a single line of a script
+	///
+	/// # Errors
+	///
+	/// * When the line doesn't follow any of the formats
+	/// * When a score doesn't fit the score board range (0 - 255)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Command;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.apply(Command::parse("start Spain vs Brazil").unwrap()).unwrap();
+	/// sb.apply(Command::parse("score Spain 2 Brazil 1").unwrap()).unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("Spain 2 - Brazil 1")]);
+	/// ```
+	pub fn parse(line: &str) -> Result<Command, ScoreBoardError> {
+		trace!("Parsing the command '{}'", line);
+
+		let words: Vec<&str> = line.split_whitespace().collect();
+		let keyword = match words.first() {
+			Some(keyword) => keyword.to_lowercase(),
+			None => return Err(get_invalid_command_error(line)),
+		};
+		let arguments = &words[1..];
+
+		match keyword.as_str() {
+			"start" => parse_teams(line, arguments).map(|(home, away)| Command::StartGame { home, away }),
+			"score" => parse_score(line, arguments),
+			"finish" => parse_teams(line, arguments).map(|(home, away)| Command::FinishGame { home, away }),
+			"pause" => parse_teams(line, arguments).map(|(home, away)| Command::PauseGame { home, away }),
+			"resume" => parse_teams(line, arguments).map(|(home, away)| Command::ResumeGame { home, away }),
+			"reopen" => parse_teams(line, arguments).map(|(home, away)| Command::ReopenGame { home, away }),
+			_ => Err(get_invalid_command_error(line)),
+		}
+	}
+
+	/// Parses a command script, one command per line, see `Command::parse()`. Empty lines and the lines starting with `#` are skipped. The commands can be applied all together with `ScoreBoard::apply_batch()`
+	///
+	/// # Arguments
+	///
+	/// * `script` - the text of the script
+	///
+	/// # Errors
+	///
+	/// * When any of the lines can't be parsed. The error is the one of the first such line
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let script = "
+	///     ## The opening game
+	///     start Qatar vs Ecuador
+	///     score Qatar 0 Ecuador 2
+	///     finish Qatar Ecuador
+	/// ";
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.apply_batch(scoreboard_world_cup::Command::parse_script(script).unwrap()).unwrap();
+	/// assert_eq!(sb.get_archive_summary(), vec![String::from("Qatar 0 - 2 Ecuador")]);
+	/// ```
+	pub fn parse_script(script: &str) -> Result<Vec<Command>, ScoreBoardError> {
+		script.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty() && !line.starts_with(COMMENT_START))
+			.map(Command::parse)
+			.collect()
+	}
+}
+
+/// Parses the `<home> vs <away>` or `<home> <away>` teams of a line
+///
+/// # Arguments
+///
+/// * `line` - the whole line, used in the error message
+/// * `words` - the words after the keyword
+///
+fn parse_teams(line: &str, words: &[&str]) -> Result<(String, String), ScoreBoardError> {
+	match words.iter().position(|word| word.eq_ignore_ascii_case(VERSUS_SEPARATOR)) {
+		Some(id) if id > 0 && id < words.len() - 1 => Ok((words[..id].join(" "), words[id + 1..].join(" "))),
+		None if words.len() == 2 => Ok((words[0].to_string(), words[1].to_string())),
+		_ => Err(get_invalid_command_error(line)),
+	}
+}
+
+/// Parses the `<home> <home score> <away> <away score>` part of a score line
+///
+/// # Arguments
+///
+/// * `line` - the whole line, used in the error message
+/// * `words` - the words after the keyword
+///
+fn parse_score(line: &str, words: &[&str]) -> Result<Command, ScoreBoardError> {
+	let home_score_id = match words.iter().position(|word| is_number(word)) {
+		Some(id) if id > 0 && id < words.len() - 2 => id,
+		_ => return Err(get_invalid_command_error(line)),
+	};
+	let away_score_id = words.len() - 1;
+
+	if !is_number(words[away_score_id]) {
+		return Err(get_invalid_command_error(line));
+	}
+
+	Ok(Command::UpdateScore {
+		home: words[..home_score_id].join(" "),
+		home_score: parse_goals(line, words[home_score_id])?,
+		away: words[home_score_id + 1..away_score_id].join(" "),
+		away_score: parse_goals(line, words[away_score_id])?,
+	})
+}
+
+/// Checks if a word is a number of goals
+fn is_number(word: &str) -> bool {
+	word.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Converts the goals of a team to the score board range
+fn parse_goals(line: &str, goals: &str) -> Result<u8, ScoreBoardError> {
+	match goals.parse::<u32>() {
+		Ok(goals) => u8::try_from(goals).map_err(|_| {
+			warn!("Score {} is out of range", goals);
+			ScoreBoardError::ScoreOutOfRange(goals)
+		}),
+		Err(_) => Err(get_invalid_command_error(line)),
+	}
+}
+
+/// Provides the error of a line that doesn't follow any of the formats
+fn get_invalid_command_error(line: &str) -> ScoreBoardError {
+	warn!("Couldn't parse the command '{}'", line);
+	ScoreBoardError::InvalidCommand(line.to_string())
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn game(home: &str, away: &str) -> (String, String) {
+		(String::from(home), String::from(away))
+	}
+
+	#[test]
+	fn all_the_keywords_are_parsed() {
+		let (home, away) = game("Spain", "Brazil");
+
+		assert_eq!(Command::parse("start Spain vs Brazil"), Ok(Command::StartGame { home: home.clone(), away: away.clone() }));
+		assert_eq!(Command::parse("score Spain 2 Brazil 1"), Ok(Command::UpdateScore { home: home.clone(), home_score: 2, away: away.clone(), away_score: 1 }));
+		assert_eq!(Command::parse("finish Spain Brazil"), Ok(Command::FinishGame { home: home.clone(), away: away.clone() }));
+		assert_eq!(Command::parse("pause Spain Brazil"), Ok(Command::PauseGame { home: home.clone(), away: away.clone() }));
+		assert_eq!(Command::parse("resume Spain Brazil"), Ok(Command::ResumeGame { home: home.clone(), away: away.clone() }));
+		assert_eq!(Command::parse("reopen Spain Brazil"), Ok(Command::ReopenGame { home, away }));
+	}
+
+	#[test]
+	fn names_can_have_several_words_and_keywords_any_case() {
+		let (home, away) = game("Costa Rica", "South Korea");
+
+		assert_eq!(Command::parse("  START  Costa Rica VS South   Korea "), Ok(Command::StartGame { home: home.clone(), away: away.clone() }));
+		assert_eq!(Command::parse("Score Costa Rica 0 South Korea 12"), Ok(Command::UpdateScore { home: home.clone(), home_score: 0, away: away.clone(), away_score: 12 }));
+		assert_eq!(Command::parse("finish Costa Rica vs South Korea"), Ok(Command::FinishGame { home, away }));
+	}
+
+	#[test]
+	fn malformed_lines_are_rejected() {
+		for line in ["", "start", "start Spain", "start Spain vs", "start vs Brazil", "start Costa Rica Brazil", "kickoff Spain Brazil",
+			"score Spain Brazil", "score Spain 2 Brazil", "score 2 Brazil 1", "score Spain 2 1", "score Spain -2 Brazil 1", "finish Spain"] {
+			assert_eq!(Command::parse(line), Err(ScoreBoardError::InvalidCommand(String::from(line))), "Line '{}' should be rejected", line);
+		}
+	}
+
+	#[test]
+	fn out_of_range_score_is_rejected() {
+		assert_eq!(Command::parse("score Spain 256 Brazil 0"), Err(ScoreBoardError::ScoreOutOfRange(256)));
+	}
+
+	#[test]
+	fn script_skips_comments_and_empty_lines() {
+		let commands = Command::parse_script("# Group A\n\nstart Qatar vs Ecuador\n  # Kickoff\nfinish Qatar Ecuador\n").expect("Couldn't parse the script");
+		assert_eq!(commands.len(), 2);
+
+		let result = Command::parse_script("start Qatar vs Ecuador\nend Qatar Ecuador\nkickoff");
+		assert_eq!(result, Err(ScoreBoardError::InvalidCommand(String::from("end Qatar Ecuador"))));
+	}
+}
@@ -0,0 +1,287 @@
+//! # Sharded score board
+//!
+//! Splits the running games between several boards with their own locks, so many operator threads can update different games at the same time instead of waiting for one lock
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::string::{String, ToString};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::api::ScoreBoardApi;
+use crate::error::ScoreBoardError;
+use crate::order::{self, SortOrder};
+use crate::{Game, ScoreBoard};
+
+/// A score board split into shards, for very high update rates. Every game lives in one of the shards, picked by the names of its teams, and the updates of a game lock only its shard. Starting and finishing a game also locks the index of the playing teams, so a team can't play two games in different shards. All the operations take a shared reference, so the board can be shared between threads, ex. in an `Arc`
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let board = Arc::new(scoreboard_world_cup::ShardedScoreBoard::new(4));
+/// board.start_game("Japan", "Indonesia").unwrap();
+/// board.start_game("Spain", "Brazil").unwrap();
+///
+/// let operator = thread::spawn({
+///     let board = Arc::clone(&board);
+///     move || board.update_score("Spain", 1, "Brazil", 0).unwrap()
+/// });
+/// board.update_score("Japan", 0, "Indonesia", 2).unwrap();
+/// operator.join().unwrap();
+///
+/// assert_eq!(board.get_summary(), vec![String::from("Japan 0 - Indonesia 2"), String::from("Spain 1 - Brazil 0")]);
+/// ```
+pub struct ShardedScoreBoard {
+	/// The shards, each a board with some of the running games
+	shards: Vec<Mutex<ScoreBoard>>,
+	/// The shards of the currently playing teams
+	teams: Mutex<HashMap<String, usize>>,
+}
+
+impl ShardedScoreBoard {
+	/// Returns a newly created, empty board with the given number of shards
+	///
+	/// # Arguments
+	///
+	/// * `shard_count` - number of the shards, at least 1. More shards let more games be updated at the same time
+	///
+	pub fn new(shard_count: usize) -> ShardedScoreBoard {
+		ShardedScoreBoard {
+			shards: (0..shard_count.max(1)).map(|_| Mutex::new(ScoreBoard::new())).collect(),
+			teams: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Provides the number of the shards
+	pub fn get_shard_count(&self) -> usize {
+		self.shards.len()
+	}
+
+	/// Starts a new game with the score 0 - 0, as with `ScoreBoard::start_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When the two provided names are the same
+	/// * When any of the teams is currently playing a match, in any of the shards
+	pub fn start_game<T: ToString, U: ToString>(&self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		trace!("Starting a sharded game for teams: '{}' and '{}'", home_name, away_name);
+
+		let mut teams = lock(&self.teams);
+
+		for team_name in [&home_name, &away_name] {
+			if teams.contains_key(team_name) && home_name != away_name {
+				warn!("{} is currently playing a game", team_name);
+				return Err(ScoreBoardError::AlreadyPlaying(team_name.clone()));
+			}
+		}
+
+		let shard_index = self.get_shard_index(&home_name, &away_name);
+		lock(&self.shards[shard_index]).start_game(&home_name, &away_name)?;
+
+		teams.insert(home_name, shard_index);
+		teams.insert(away_name, shard_index);
+
+		Ok(())
+	}
+
+	/// Updates the score of a running game with absolute values, as with `ScoreBoard::update_score()`. Only the shard of the game is locked
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `new_home_score` - A new score to be set for the home team
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `new_away_score` - A new score to be set for the away team
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::update_score()`
+	pub fn update_score<T: ToString, U: ToString>(&self, home: T, new_home_score: u8, away: U, new_away_score: u8) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		let shard_index = self.get_shard_index(&home_name, &away_name);
+
+		lock(&self.shards[shard_index]).update_score(home_name, new_home_score, away_name, new_away_score)
+	}
+
+	/// Finishes a game and removes it from the board, as with `ScoreBoard::finish_game()`
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	pub fn finish_game<T: ToString, U: ToString>(&self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		trace!("Finishing the sharded game between '{}' and '{}'", home_name, away_name);
+
+		// The index of the teams is always locked before the shards, so a start and a finish never wait for each other
+		let mut teams = lock(&self.teams);
+
+		let shard_index = self.get_shard_index(&home_name, &away_name);
+		lock(&self.shards[shard_index]).finish_game(&home_name, &away_name)?;
+
+		teams.remove(&home_name);
+		teams.remove(&away_name);
+
+		Ok(())
+	}
+
+	/// Provides the summary of the running games of all the shards, in the order of `ScoreBoard::get_summary()`. The ordered games of the shards are merged, and all the shards are locked meanwhile, so the summary shows one moment of the board
+	pub fn get_summary(&self) -> Vec<String> {
+		trace!("Getting the sharded score board summary");
+
+		let shards: Vec<MutexGuard<ScoreBoard>> = self.shards.iter().map(lock).collect();
+		let mut heads: Vec<&[Game]> = shards.iter().map(|shard| shard.data.as_slice()).collect();
+		let mut summary = Vec::with_capacity(heads.iter().map(|games| games.len()).sum());
+
+		// Every shard keeps its games in order, so the next game of the summary is the first one of some shard
+		while let Some(next) = heads.iter()
+			.enumerate()
+			.filter_map(|(index, games)| games.first().map(|game| (index, game)))
+			.min_by(|(_, a), (_, b)| order::compare(SortOrder::Ranking, a, b))
+			.map(|(index, _)| index)
+		{
+			summary.push(heads[next][0].to_string());
+			heads[next] = &heads[next][1..];
+		}
+
+		summary
+	}
+
+	/// Provides the shard of the game between the teams. The same game always gets the same shard
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team
+	/// * `away_name` - name of the away team
+	///
+	fn get_shard_index(&self, home_name: &str, away_name: &str) -> usize {
+		let mut hasher = DefaultHasher::new();
+		(home_name, away_name).hash(&mut hasher);
+
+		(hasher.finish() % self.shards.len() as u64) as usize
+	}
+}
+
+/// Locks a mutex, also when another thread panicked while holding it. The boards check everything before changing the games, so a panic doesn't leave a half made change
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+	mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl ScoreBoardApi for ShardedScoreBoard {
+	fn start_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		ShardedScoreBoard::start_game(self, home, away)
+	}
+
+	fn update_score(&mut self, home: &str, home_score: u8, away: &str, away_score: u8) -> Result<(), ScoreBoardError> {
+		ShardedScoreBoard::update_score(self, home, home_score, away, away_score)
+	}
+
+	fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		ShardedScoreBoard::finish_game(self, home, away)
+	}
+
+	fn get_summary(&mut self) -> Result<Vec<String>, ScoreBoardError> {
+		Ok(ShardedScoreBoard::get_summary(self))
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::thread;
+
+	const TEAMS: [&str; 16] = [
+		"Qatar", "Ecuador", "Senegal", "Netherlands", "England", "Iran", "USA", "Wales",
+		"Argentina", "Saudi Arabia", "Mexico", "Poland", "France", "Australia", "Denmark", "Tunisia",
+	];
+
+	#[test]
+	fn summary_is_the_one_of_a_single_board() {
+		let sharded = ShardedScoreBoard::new(3);
+		let mut sb = ScoreBoard::new();
+
+		for (index, teams) in TEAMS.chunks(2).enumerate() {
+			sharded.start_game(teams[0], teams[1]).expect("Couldn't create the sharded game");
+			sb.start_game(teams[0], teams[1]).expect("Couldn't create the game");
+			let score = (index % 3) as u8;
+			sharded.update_score(teams[0], score, teams[1], 1).expect("Couldn't update the sharded game");
+			sb.update_score(teams[0], score, teams[1], 1).expect("Couldn't update the game");
+		}
+		sharded.finish_game("England", "Iran").expect("Couldn't finish the sharded game");
+		sb.finish_game("England", "Iran").expect("Couldn't finish the game");
+
+		assert_eq!(sharded.get_summary(), sb.get_summary());
+	}
+
+	#[test]
+	fn team_cannot_play_in_two_shards() {
+		let sharded = ShardedScoreBoard::new(8);
+		sharded.start_game("Qatar", "Ecuador").expect("Couldn't create the game");
+
+		for (home, away) in TEAMS[2..].iter().map(|team| ("Qatar", *team)).chain(TEAMS[2..].iter().map(|team| (*team, "Ecuador"))) {
+			assert!(matches!(sharded.start_game(home, away), Err(ScoreBoardError::AlreadyPlaying(_))));
+		}
+		assert_eq!(sharded.start_game("Wales", "Wales"), Err(ScoreBoardError::SameTeam(String::from("Wales"))));
+
+		sharded.finish_game("Qatar", "Ecuador").expect("Couldn't finish the game");
+		sharded.start_game("Ecuador", "Senegal").expect("Couldn't create the next game");
+	}
+
+	#[test]
+	fn games_are_updated_from_many_threads() {
+		let sharded = ShardedScoreBoard::new(4);
+		for teams in TEAMS.chunks(2) {
+			sharded.start_game(teams[0], teams[1]).expect("Couldn't create the game");
+		}
+
+		thread::scope(|scope| {
+			for teams in TEAMS.chunks(2) {
+				let sharded = &sharded;
+				scope.spawn(move || {
+					for score in 1..=10 {
+						sharded.update_score(teams[0], score, teams[1], 0).expect("Couldn't update the game");
+					}
+				});
+			}
+		});
+
+		let summary = sharded.get_summary();
+		assert_eq!(summary.len(), 8);
+		assert!(summary.iter().all(|line| line.contains(" 10 - ")));
+	}
+
+	#[test]
+	fn missing_game_cannot_be_finished() {
+		let mut sharded = ShardedScoreBoard::new(0);
+
+		assert_eq!(sharded.get_shard_count(), 1);
+		assert!(matches!(ScoreBoardApi::finish_game(&mut sharded, "Qatar", "Ecuador"), Err(ScoreBoardError::GameNotFound { .. })));
+		assert_eq!(ScoreBoardApi::get_summary(&mut sharded), Ok(Vec::new()));
+	}
+}
@@ -0,0 +1,290 @@
+//! # Simulation
+//!
+//! Plays made-up games on the board, with the goals drawn from a seed, so the displays and the services can be demonstrated and load tested without a live tournament. The same seed and fixtures always give the same games
+
+use std::collections::HashSet;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::{debug, trace};
+
+use crate::error::ScoreBoardError;
+use crate::goal::{GoalKind, Side};
+use crate::random::SeededRandom;
+use crate::ScoreBoard;
+
+/// Length of a simulated game, in minutes
+const GAME_LENGTH: u8 = 90;
+/// Average goals of the home team in a game
+const HOME_GOALS: f64 = 1.5;
+/// Average goals of the away team in a game
+const AWAY_GOALS: f64 = 1.2;
+
+/// A goal of a simulated game
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SimulatedGoal {
+	/// The minute of the goal, from 1 to 90
+	pub minute: u8,
+	/// The team the goal counts for
+	pub side: Side,
+	/// The way the goal was scored
+	pub kind: GoalKind,
+}
+
+/// A simulated game, with all its goals decided before the kickoff
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SimulatedGame {
+	/// Name of the home team
+	pub home: String,
+	/// Name of the away team
+	pub away: String,
+	/// The goals of the game, ordered by the minute
+	pub goals: Vec<SimulatedGoal>,
+}
+
+/// Driver of the simulated games. Every `step()` plays a minute of the running games. The fixtures are played in the given order, every game starting as soon as both teams are free
+#[derive(Clone, Debug)]
+pub struct Simulation {
+	/// All the games of the simulation
+	games: Vec<SimulatedGame>,
+	/// Number of the games started so far
+	started: usize,
+	/// Indexes of the running games
+	running: Vec<usize>,
+	/// The minute played by the running games
+	minute: u8,
+}
+
+impl Simulation {
+	/// Returns a simulation of the fixtures, drawing their goals from the seed. The goals come more often in the second half, and the home team scores slightly more
+	///
+	/// # Arguments
+	///
+	/// * `fixtures` - pairs of the home and the away team names. Must be either `String`s or a type that is convertable to `String`
+	/// * `seed` - the seed of the goals. The same seed gives the same games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Simulation;
+	///
+	/// let first = Simulation::new(&[("Japan", "Indonesia")], 2022);
+	/// let second = Simulation::new(&[("Japan", "Indonesia")], 2022);
+	/// assert_eq!(first.get_games(), second.get_games());
+	/// ```
+	pub fn new<T: ToString, U: ToString>(fixtures: &[(T, U)], seed: u64) -> Simulation {
+		trace!("Simulating {} games with seed {}", fixtures.len(), seed);
+
+		let mut random = SeededRandom::new(seed);
+		let games = fixtures.iter().map(|(home, away)| SimulatedGame {
+			home: home.to_string(),
+			away: away.to_string(),
+			goals: draw_goals(&mut random),
+		}).collect();
+
+		Simulation { games, started: 0, running: Vec::new(), minute: 0 }
+	}
+
+	/// Provides all the games of the simulation, with their goals
+	pub fn get_games(&self) -> &[SimulatedGame] {
+		&self.games
+	}
+
+	/// Plays the next minute on the board. When no games are running, the following fixtures are started instead. The goals of the minute are recorded with `record_goal()`, and the games are finished after 90 minutes. Calling it every second makes a one and a half minute game for a demo
+	///
+	/// # Arguments
+	///
+	/// * `board` - the board to play the games on
+	///
+	/// # Returns
+	///
+	/// * Whether the simulation goes on, `false` once all the games are finished
+	///
+	/// # Errors
+	///
+	/// * When the board rejects any of the operations, ex. when a team is already playing a game started outside the simulation
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::Simulation;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let mut simulation = Simulation::new(&[("Japan", "Indonesia")], 7);
+	/// simulation.step(&mut sb).unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// ```
+	pub fn step(&mut self, board: &mut ScoreBoard) -> Result<bool, ScoreBoardError> {
+		if self.running.is_empty() {
+			return self.kick_off(board);
+		}
+
+		self.minute += 1;
+
+		for &index in &self.running {
+			let game = &self.games[index];
+			for goal in game.goals.iter().filter(|goal| goal.minute == self.minute) {
+				board.record_goal(&game.home, &game.away, goal.side, goal.kind)?;
+			}
+		}
+
+		if self.minute == GAME_LENGTH {
+			for index in std::mem::take(&mut self.running) {
+				board.finish_game(&self.games[index].home, &self.games[index].away)?;
+			}
+		}
+
+		Ok(self.started < self.games.len() || !self.running.is_empty())
+	}
+
+	/// Plays all the games on the board
+	///
+	/// # Arguments
+	///
+	/// * `board` - the board to play the games on
+	///
+	/// # Errors
+	///
+	/// * The same as `step()`
+	pub fn run(&mut self, board: &mut ScoreBoard) -> Result<(), ScoreBoardError> {
+		while self.step(board)? {}
+
+		Ok(())
+	}
+
+	/// Starts the following fixtures, as long as their teams don't play in the fixtures started before them
+	///
+	/// # Arguments
+	///
+	/// * `board` - the board to start the games on
+	///
+	/// # Returns
+	///
+	/// * Whether any games were started
+	///
+	fn kick_off(&mut self, board: &mut ScoreBoard) -> Result<bool, ScoreBoardError> {
+		let mut playing: HashSet<&str> = HashSet::new();
+
+		for (index, game) in self.games.iter().enumerate().skip(self.started) {
+			if playing.contains(game.home.as_str()) || playing.contains(game.away.as_str()) {
+				break;
+			}
+
+			board.start_game(&game.home, &game.away)?;
+			playing.insert(&game.home);
+			playing.insert(&game.away);
+			self.running.push(index);
+		}
+
+		self.started += self.running.len();
+		self.minute = 0;
+
+		debug!("Kicked off {} simulated games", self.running.len());
+
+		Ok(!self.running.is_empty())
+	}
+}
+
+/// Draws the goals of a game, minute by minute
+///
+/// # Arguments
+///
+/// * `random` - the generator of the random numbers
+///
+fn draw_goals(random: &mut SeededRandom) -> Vec<SimulatedGoal> {
+	let mut goals = Vec::new();
+
+	for minute in 1..=GAME_LENGTH {
+		// The chances grow from 0.8 to 1.2 times the average over the game, as the teams tire
+		let weight = 0.8 + 0.4 * f64::from(minute) / f64::from(GAME_LENGTH);
+
+		for (side, average) in [(Side::Home, HOME_GOALS), (Side::Away, AWAY_GOALS)] {
+			if random.next_f64() < average * weight / f64::from(GAME_LENGTH) {
+				let kind = match random.next_below(100) {
+					0..=79 => GoalKind::OpenPlay,
+					80..=89 => GoalKind::Penalty,
+					90..=95 => GoalKind::FreeKick,
+					_ => GoalKind::OwnGoal,
+				};
+				goals.push(SimulatedGoal { minute, side, kind });
+			}
+		}
+	}
+
+	goals
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FIXTURES: [(&str, &str); 3] = [("Qatar", "Ecuador"), ("Senegal", "Netherlands"), ("Qatar", "Senegal")];
+
+	#[test]
+	fn same_seed_gives_the_same_games() {
+		let first = Simulation::new(&FIXTURES, 2022);
+		let second = Simulation::new(&FIXTURES, 2022);
+		let other = Simulation::new(&FIXTURES, 2018);
+
+		assert_eq!(first.get_games(), second.get_games());
+		assert_ne!(first.get_games(), other.get_games());
+	}
+
+	#[test]
+	fn goal_numbers_are_realistic() {
+		let fixtures: Vec<(String, String)> = (0..1000).map(|index| (format!("Home {}", index), format!("Away {}", index))).collect();
+		let simulation = Simulation::new(&fixtures, 1);
+		let goals: Vec<&SimulatedGoal> = simulation.get_games().iter().flat_map(|game| &game.goals).collect();
+		let home_goals = goals.iter().filter(|goal| goal.side == Side::Home).count();
+
+		let average = goals.len() as f64 / fixtures.len() as f64;
+		assert!((2.4..3.0).contains(&average), "{} goals per game", average);
+		assert!(home_goals * 2 > goals.len());
+		assert!(goals.iter().all(|goal| (1..=GAME_LENGTH).contains(&goal.minute)));
+	}
+
+	#[test]
+	fn games_are_played_on_the_board() {
+		let mut sb = ScoreBoard::new();
+		let mut simulation = Simulation::new(&FIXTURES, 3);
+		simulation.run(&mut sb).expect("Couldn't run the simulation");
+
+		assert!(sb.get_summary().is_empty());
+		assert_eq!(sb.get_archive().len(), FIXTURES.len());
+		for (archived, simulated) in sb.get_archive().iter().zip(simulation.get_games()) {
+			let home_goals = simulated.goals.iter().filter(|goal| goal.side == Side::Home).count();
+			assert_eq!((archived.home_team.as_str(), archived.home_score as usize), (simulated.home.as_str(), home_goals));
+			assert_eq!(archived.away_score as usize, simulated.goals.len() - home_goals);
+		}
+	}
+
+	#[test]
+	fn fixtures_wait_for_their_teams() {
+		let mut sb = ScoreBoard::new();
+		let mut simulation = Simulation::new(&FIXTURES, 3);
+		assert!(simulation.step(&mut sb).expect("Couldn't kick off the games"));
+		assert_eq!(sb.get_summary().len(), 2);
+
+		for _ in 0..GAME_LENGTH {
+			simulation.step(&mut sb).expect("Couldn't play a minute");
+		}
+		assert!(sb.get_summary().is_empty());
+
+		simulation.step(&mut sb).expect("Couldn't kick off the last game");
+		let game = sb.get_game("Qatar", "Senegal").expect("Couldn't find the last game");
+		assert_eq!((game.home_score, game.away_score), (0, 0));
+	}
+
+	#[test]
+	fn rejected_operations_stop_the_simulation() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Ecuador", "Mexico").expect("Couldn't create the game");
+		let mut simulation = Simulation::new(&FIXTURES, 3);
+
+		assert_eq!(simulation.step(&mut sb), Err(ScoreBoardError::AlreadyPlaying(String::from("Ecuador"))));
+	}
+}
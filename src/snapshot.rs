@@ -0,0 +1,462 @@
+//! # Board snapshots
+//!
+//! A plain copy of the score board state, which can be stored, sent to another process and turned back into a score board
+
+use std::string::String;
+use std::time::{Duration, Instant, SystemTime};
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::config::ScoreBoardConfig;
+use crate::error::ScoreBoardError;
+use crate::minute::MatchClock;
+use crate::period::Period;
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+use crate::xg::ExpectedGoals;
+use crate::{Game, ScoreBoard, Team};
+
+/// A copy of a single game that is currently played
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+	/// Name of the home team
+	pub home_team: String,
+	/// Score of the home team
+	pub home_score: u8,
+	/// Name of the away team
+	pub away_team: String,
+	/// Score of the away team
+	pub away_score: u8,
+	/// Wall clock time of the start of the game
+	pub kickoff_time: SystemTime,
+	/// Revision of the game, increasing by 1 with every score update. See `EventRecord::revision`
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub revision: u64,
+	/// State of the game, either `GameState::Live` or `GameState::Paused`
+	#[cfg_attr(feature = "serde", serde(default = "get_live_state"))]
+	pub state: GameState,
+	/// The current period of the game
+	#[cfg_attr(feature = "serde", serde(default = "get_first_period"))]
+	pub period: Period,
+	/// Name of the stadium, if it was given
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub venue: Option<String>,
+	/// Group of the tournament, if it was given
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub group: Option<char>,
+	/// Time zone of the stadium
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub utc_offset: UtcOffset,
+}
+
+/// Version of the snapshots taken by this release. The snapshots of the older versions are migrated by `ScoreBoard::from_snapshot()`:
+///
+/// * 1 - the games have no revisions
+/// * 2 - the games have revisions
+/// * 3 - the games have their state, period, venue, group and time zone
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+/// A copy of all the games that are currently played, in the order of the summary
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardSnapshot {
+	/// The games, in the order of the summary
+	pub games: Vec<GameSnapshot>,
+	/// Version of the format of the snapshot, see `SNAPSHOT_VERSION`. The snapshots saved before the version was introduced are of version 1
+	#[cfg_attr(feature = "serde", serde(default = "get_first_version"))]
+	pub version: u32,
+}
+
+impl BoardSnapshot {
+	/// Returns a snapshot of the games, of the current version
+	///
+	/// # Arguments
+	///
+	/// * `games` - the games, in the order of the summary
+	pub fn new(games: Vec<GameSnapshot>) -> BoardSnapshot {
+		BoardSnapshot { games, version: SNAPSHOT_VERSION }
+	}
+
+	/// Brings the snapshot to the current version, one version at a time
+	///
+	/// # Errors
+	///
+	/// * When the version is unknown, ex. the snapshot was taken by a newer release
+	///
+	fn migrate(mut self) -> Result<BoardSnapshot, ScoreBoardError> {
+		while self.version != SNAPSHOT_VERSION {
+			trace!("Migrating a snapshot of version {}", self.version);
+
+			match self.version {
+				1 => {
+					// The revisions of the games before the snapshot are unknown, so they start anew
+					for game in &mut self.games {
+						game.revision = game.revision.max(1);
+					}
+				},
+				2 => {
+					// The games were always restored as live games in the first half, without the details
+					for game in &mut self.games {
+						game.state = GameState::Live;
+						game.period = Period::FirstHalf;
+						game.venue = None;
+						game.group = None;
+						game.utc_offset = UtcOffset::UTC;
+					}
+				},
+				version => {
+					warn!("Snapshot version {} is not supported", version);
+					return Err(ScoreBoardError::UnsupportedSnapshotVersion(version));
+				},
+			}
+
+			self.version += 1;
+		}
+
+		Ok(self)
+	}
+}
+
+/// Provides the version of the snapshots saved without the version
+#[cfg(feature = "serde")]
+fn get_first_version() -> u32 {
+	1
+}
+
+/// Provides the state of the games of the snapshots saved without the state
+#[cfg(feature = "serde")]
+fn get_live_state() -> GameState {
+	GameState::Live
+}
+
+/// Provides the period of the games of the snapshots saved without the period
+#[cfg(feature = "serde")]
+fn get_first_period() -> Period {
+	Period::FirstHalf
+}
+
+impl ScoreBoard {
+	/// Takes a copy of all the currently played games
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let snapshot = sb.snapshot();
+	/// assert_eq!(snapshot.games.len(), 1);
+	/// assert_eq!(snapshot.games[0].home_team, "Japan");
+	/// ```
+	pub fn snapshot(&self) -> BoardSnapshot {
+		trace!("Taking a snapshot of the score board");
+
+		BoardSnapshot::new(self.data.iter().map(|game| GameSnapshot {
+			home_team: game.home_team.name.clone(),
+			home_score: game.home_team.score,
+			away_team: game.away_team.name.clone(),
+			away_score: game.away_team.score,
+			kickoff_time: game.kickoff_time,
+			revision: game.revision,
+			state: game.state,
+			period: game.get_period(),
+			venue: game.venue.clone(),
+			group: game.group,
+			utc_offset: game.utc_offset,
+		}).collect())
+	}
+
+	/// Creates a score board with the default settings and the games from a snapshot, see `from_snapshot_with_config()`
+	///
+	/// # Arguments
+	///
+	/// * `snapshot` - the copy of the games to restore
+	///
+	/// # Errors
+	///
+	/// * The same as of `from_snapshot_with_config()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// let restored = scoreboard_world_cup::ScoreBoard::from_snapshot(sb.snapshot()).unwrap();
+	/// assert_eq!(restored.get_summary(), sb.get_summary());
+	/// ```
+	pub fn from_snapshot(snapshot: BoardSnapshot) -> Result<ScoreBoard, ScoreBoardError> {
+		ScoreBoard::from_snapshot_with_config(snapshot, ScoreBoardConfig::default())
+	}
+
+	/// Creates a score board with the given settings and the games from a snapshot. Every game is checked like a started game and its score like an updated score, so the snapshot can't bring in games the board wouldn't accept. Games with the same total score keep their order thanks to their kickoff times. The restored board has no event history. A snapshot of an older version is migrated first, see `SNAPSHOT_VERSION`
+	///
+	/// The snapshot has only the current period of a game, so the goals scored before the snapshot count in the first half
+	///
+	/// # Arguments
+	///
+	/// * `snapshot` - the copy of the games to restore
+	/// * `config` - settings of the score board
+	///
+	/// # Errors
+	///
+	/// * When the version of the snapshot is not supported
+	/// * When a team plays with itself or in more than one game
+	/// * When any of the teams isn't a known country, in the strict mode
+	/// * When there are more games than allowed
+	/// * When a score is above the highest allowed score
+	/// * When a game is neither live nor paused, or its time zone is over 18 hours from UTC
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{ScoreBoard, ScoreBoardConfig};
+	///
+	/// let mut sb = ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 9, "Indonesia", 0).unwrap();
+	///
+	/// let config = ScoreBoardConfig { max_score: Some(5), ..ScoreBoardConfig::default() };
+	/// assert!(ScoreBoard::from_snapshot_with_config(sb.snapshot(), config).is_err());
+	/// ```
+	pub fn from_snapshot_with_config(snapshot: BoardSnapshot, config: ScoreBoardConfig) -> Result<ScoreBoard, ScoreBoardError> {
+		trace!("Restoring a score board from a snapshot");
+
+		let snapshot = snapshot.migrate()?;
+		let now = SystemTime::now();
+		let mut sb = ScoreBoard::with_config(config);
+
+		for game in snapshot.games {
+			if game.home_team == game.away_team {
+				warn!("{} cannot play with itself", game.home_team);
+				return Err(ScoreBoardError::SameTeam(game.home_team));
+			}
+
+			sb.check_countries(&game.home_team, &game.away_team)?;
+
+			sb.check_if_currently_playing(&game.home_team, &game.away_team)?;
+
+			sb.check_max_games()?;
+
+			sb.check_max_score(game.home_score, game.away_score)?;
+
+			if !matches!(game.state, GameState::Live | GameState::Paused) {
+				warn!("Game between {} and {} is {}", game.home_team, game.away_team, game.state);
+				return Err(ScoreBoardError::Encoding(format!("Game between {} and {} is {}, not running", game.home_team, game.away_team, game.state)));
+			}
+
+			let utc_offset = UtcOffset::from_minutes(game.utc_offset.get_minutes())?;
+
+			let elapsed = now.duration_since(game.kickoff_time).unwrap_or(Duration::ZERO);
+			let start_time = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
+			let score = (game.home_score, game.away_score);
+
+			let mut periods = vec![(Period::FirstHalf, (0, 0))];
+			if game.period != Period::FirstHalf {
+				periods.push((game.period, score));
+			}
+			let mut clock = MatchClock::new(start_time);
+			let pause_score = match game.state {
+				GameState::Paused => {
+					clock.pause(Instant::now());
+					Some(score)
+				},
+				_ => None,
+			};
+
+			sb.data.push(Game {
+				home_team: Team { name: game.home_team, score: game.home_score },
+				away_team: Team { name: game.away_team, score: game.away_score },
+				start_time,
+				kickoff_time: game.kickoff_time,
+				scheduled_kickoff: None,
+				last_update: None,
+				revision: game.revision,
+				goals: Vec::new(),
+				periods,
+				half_time_score: None,
+				pause_score,
+				state: game.state,
+				clock,
+				venue: game.venue,
+				utc_offset,
+				group: game.group,
+				odds: None,
+				xg: ExpectedGoals::default(),
+			});
+			sb.reposition(sb.data.len() - 1);
+		}
+
+		trace!("Score board restored");
+
+		Ok(sb)
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::limit::DEFAULT_MAX_SCORE;
+
+	fn get_game(home: &str, away: &str, kickoff_time: SystemTime) -> GameSnapshot {
+		GameSnapshot {
+			home_team: String::from(home),
+			home_score: 0,
+			away_team: String::from(away),
+			away_score: 0,
+			kickoff_time,
+			revision: 1,
+			state: GameState::Live,
+			period: Period::FirstHalf,
+			venue: None,
+			group: None,
+			utc_offset: UtcOffset::UTC,
+		}
+	}
+
+	#[test]
+	fn empty_board_has_an_empty_snapshot() {
+		let sb = ScoreBoard::new();
+
+		assert_eq!(sb.snapshot(), BoardSnapshot::new(Vec::new()));
+	}
+
+	#[test]
+	fn snapshot_follows_the_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		let snapshot = sb.snapshot();
+
+		assert_eq!(snapshot.games.len(), 2);
+		assert_eq!(snapshot.games[0].home_team, "Mexico");
+		assert_eq!(snapshot.games[0].away_score, 5);
+		assert_eq!(snapshot.games[1].home_team, "Spain");
+	}
+
+	#[test]
+	fn board_is_restored_from_its_snapshot() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Germany", "France").expect("Couldn't create the third game");
+		sb.update_score("Spain", 1, "Brazil", 1).expect("Couldn't update the second game");
+		let restored = ScoreBoard::from_snapshot(sb.snapshot()).expect("Couldn't restore the board");
+
+		assert_eq!(restored.get_summary(), sb.get_summary());
+		assert_eq!(restored.snapshot(), sb.snapshot());
+		assert!(restored.get_events().is_empty());
+	}
+
+	#[test]
+	fn restored_game_continues_its_revisions() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		let mut restored = ScoreBoard::from_snapshot(sb.snapshot()).expect("Couldn't restore the board");
+		restored.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the restored game");
+
+		assert_eq!(sb.snapshot().games[0].revision, 2);
+		assert_eq!(restored.get_events()[0].revision, 3);
+	}
+
+	#[test]
+	fn restored_games_with_equal_scores_are_ordered_by_kickoff() {
+		let kickoff = SystemTime::now() - Duration::from_secs(3600);
+		let snapshot = BoardSnapshot::new(vec![
+			get_game("Mexico", "Canada", kickoff),
+			get_game("Spain", "Brazil", kickoff + Duration::from_secs(60)),
+		]);
+		let restored = ScoreBoard::from_snapshot(snapshot).expect("Couldn't restore the board");
+
+		assert_eq!(restored.get_summary(), vec![String::from("Spain 0 - Brazil 0"), String::from("Mexico 0 - Canada 0")]);
+	}
+
+	#[test]
+	fn snapshot_with_a_team_playing_itself_is_rejected() {
+		let snapshot = BoardSnapshot::new(vec![get_game("Spain", "Spain", SystemTime::now())]);
+		let result = ScoreBoard::from_snapshot(snapshot);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Spain cannot play with itself"));
+	}
+
+	#[test]
+	fn snapshot_with_a_team_playing_twice_is_rejected() {
+		let snapshot = BoardSnapshot::new(vec![
+			get_game("Spain", "Brazil", SystemTime::now()),
+			get_game("Brazil", "Germany", SystemTime::now()),
+		]);
+		let result = ScoreBoard::from_snapshot(snapshot);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Brazil is currently playing a game"));
+	}
+
+	#[test]
+	fn old_snapshot_is_migrated() {
+		let mut game = get_game("Spain", "Brazil", SystemTime::now());
+		game.revision = 0;
+		let restored = ScoreBoard::from_snapshot(BoardSnapshot { games: vec![game], version: 1 }).expect("Couldn't restore the board");
+
+		assert_eq!(restored.snapshot().games[0].revision, 1);
+		assert_eq!(restored.snapshot().version, SNAPSHOT_VERSION);
+	}
+
+	#[test]
+	fn restored_game_keeps_its_state_and_details() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game("Spain", "Brazil").venue("Lusail").group('B').utc_offset(UtcOffset::from_hours(3).expect("Couldn't create the offset")).start().expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.start_period("Spain", "Brazil", Period::SecondHalf).expect("Couldn't start the second half");
+		sb.set_game_state("Spain", "Brazil", GameState::Paused).expect("Couldn't pause the game");
+		let mut restored = ScoreBoard::from_snapshot(sb.snapshot()).expect("Couldn't restore the board");
+
+		let game = restored.get_game("Spain", "Brazil").expect("Couldn't find the restored game");
+		assert_eq!((game.state, game.period), (GameState::Paused, Period::SecondHalf));
+		assert_eq!((game.venue.as_deref(), game.group, game.utc_offset), (Some("Lusail"), Some('B'), UtcOffset::from_hours(3).expect("Couldn't create the offset")));
+		assert_eq!(restored.snapshot(), sb.snapshot());
+		restored.set_game_state("Spain", "Brazil", GameState::Live).expect("Couldn't resume the restored game");
+	}
+
+	#[test]
+	fn restored_games_are_checked_like_started_games() {
+		let mut game = get_game("Spain", "Atlantis", SystemTime::now());
+		let strict = ScoreBoardConfig { strict_countries: true, ..ScoreBoardConfig::default() };
+		let result = ScoreBoard::from_snapshot_with_config(BoardSnapshot::new(vec![game.clone()]), strict);
+		assert!(matches!(result.err(), Some(ScoreBoardError::UnknownCountry { team, .. }) if team == "Atlantis"));
+
+		game.home_score = DEFAULT_MAX_SCORE + 1;
+		let result = ScoreBoard::from_snapshot(BoardSnapshot::new(vec![game.clone()]));
+		assert_eq!(result.err(), Some(ScoreBoardError::ImplausibleScore { score: DEFAULT_MAX_SCORE + 1, max_score: DEFAULT_MAX_SCORE }));
+
+		game.home_score = 0;
+		game.state = GameState::Finished;
+		assert!(ScoreBoard::from_snapshot(BoardSnapshot::new(vec![game])).is_err());
+	}
+
+	#[test]
+	fn snapshot_of_version_2_is_restored_as_live_games() {
+		let mut game = get_game("Spain", "Brazil", SystemTime::now());
+		game.state = GameState::Paused;
+		game.venue = Some(String::from("Lusail"));
+		let restored = ScoreBoard::from_snapshot(BoardSnapshot { games: vec![game], version: 2 }).expect("Couldn't restore the board");
+
+		let game = restored.get_game("Spain", "Brazil").expect("Couldn't find the restored game");
+		assert_eq!((game.state, game.venue), (GameState::Live, None));
+	}
+
+	#[test]
+	fn unknown_version_is_rejected() {
+		for version in [0, SNAPSHOT_VERSION + 1] {
+			let result = ScoreBoard::from_snapshot(BoardSnapshot { games: Vec::new(), version });
+
+			assert_eq!(result.err(), Some(ScoreBoardError::UnsupportedSnapshotVersion(version)));
+		}
+	}
+}
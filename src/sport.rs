@@ -0,0 +1,391 @@
+//! # Multi-sport score boards
+//!
+//! Generalizes the score board over the way a game is scored, so sports with structured scores (sets, quarters) can be tracked next to football
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::string::{String, ToString};
+use std::time::Instant;
+use std::vec::Vec;
+
+use log::{debug, trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::text::find_closest;
+
+/// The way a sport is scored. The headline scores order the games in the summary, the same way the goals do on the football `ScoreBoard`
+pub trait ScoreModel {
+	/// The complete score of a game. The default value is the score at the start
+	type Score: Clone + fmt::Debug + Default + PartialEq;
+
+	/// Provides the headline scores of the home and the away team, ex. the goals or the won sets
+	fn get_headline(&self, score: &Self::Score) -> (u32, u32);
+
+	/// Provides the details shown after the headline score in the summary, ex. "6-4, 3-6". No details by default
+	fn get_details(&self, _score: &Self::Score) -> Option<String> {
+		None
+	}
+
+	/// Checks if a score is possible in the sport. Every score is accepted by default
+	fn check(&self, _score: &Self::Score) -> Result<(), ScoreBoardError> {
+		Ok(())
+	}
+}
+
+/// Football, scored with goals. The score is the goals of the home and the away team
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Football;
+
+impl ScoreModel for Football {
+	type Score = (u8, u8);
+
+	fn get_headline(&self, score: &(u8, u8)) -> (u32, u32) {
+		(score.0 as u32, score.1 as u32)
+	}
+}
+
+/// Tennis, scored with sets. The score is the games of the home and the away player in every set so far, and the headline is the number of won sets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tennis;
+
+impl ScoreModel for Tennis {
+	type Score = Vec<(u16, u16)>;
+
+	fn get_headline(&self, score: &Vec<(u16, u16)>) -> (u32, u32) {
+		count_won_sets(score, |home, away, _| (home.max(away) >= 6 && home.abs_diff(away) >= 2) || home.max(away) == 7)
+	}
+
+	fn get_details(&self, score: &Vec<(u16, u16)>) -> Option<String> {
+		describe_periods(score)
+	}
+
+	/// A set ends at 7 games at the latest, with the tie-break
+	fn check(&self, score: &Vec<(u16, u16)>) -> Result<(), ScoreBoardError> {
+		match score.iter().flat_map(|(home, away)| [*home, *away]).find(|games| *games > 7) {
+			Some(games) => Err(ScoreBoardError::ScoreOutOfRange(games as u32)),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Basketball, scored with points in quarters and overtimes. The score is the points of the home and the away team in every period so far, and the headline is the total of the points
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Basketball;
+
+impl ScoreModel for Basketball {
+	type Score = Vec<(u16, u16)>;
+
+	fn get_headline(&self, score: &Vec<(u16, u16)>) -> (u32, u32) {
+		score.iter().fold((0, 0), |(home, away), period| (home + period.0 as u32, away + period.1 as u32))
+	}
+
+	fn get_details(&self, score: &Vec<(u16, u16)>) -> Option<String> {
+		describe_periods(score)
+	}
+}
+
+/// Volleyball, scored with sets. The score is the points of the home and the away team in every set so far, and the headline is the number of won sets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Volleyball;
+
+impl ScoreModel for Volleyball {
+	type Score = Vec<(u16, u16)>;
+
+	/// A set is won with 25 points (15 in the fifth set) and a lead of 2
+	fn get_headline(&self, score: &Vec<(u16, u16)>) -> (u32, u32) {
+		count_won_sets(score, |home, away, index| home.max(away) >= if index == 4 { 15 } else { 25 } && home.abs_diff(away) >= 2)
+	}
+
+	fn get_details(&self, score: &Vec<(u16, u16)>) -> Option<String> {
+		describe_periods(score)
+	}
+}
+
+/// Counts the sets won by each side
+///
+/// # Arguments
+///
+/// * `score` - the home and the away score of every set
+/// * `is_finished` - tells if a set with the given scores and number (counting from 0) is finished
+///
+fn count_won_sets<F: Fn(u16, u16, usize) -> bool>(score: &[(u16, u16)], is_finished: F) -> (u32, u32) {
+	score.iter()
+		.enumerate()
+		.filter(|(index, (home, away))| is_finished(*home, *away, *index))
+		.fold((0, 0), |(home_sets, away_sets), (_, (home, away))| if home > away { (home_sets + 1, away_sets) } else { (home_sets, away_sets + 1) })
+}
+
+/// Presents the scores of the periods (sets, quarters), ex. "6-4, 3-6"
+fn describe_periods(score: &[(u16, u16)]) -> Option<String> {
+	if score.is_empty() {
+		return None;
+	}
+
+	Some(score.iter().map(|(home, away)| format!("{}-{}", home, away)).collect::<Vec<String>>().join(", "))
+}
+
+/// A game played on a `SportBoard`
+#[derive(Clone, Debug)]
+struct SportGame<S> {
+	home_name: String,
+	away_name: String,
+	score: S,
+	start_time: Instant,
+}
+
+/// A score board of any sport described with a `ScoreModel`. It works like the football `ScoreBoard`: the same team can't play two games at once, and the summary orders the games by the total of the headline scores (the highest coming first) and then by the start time (the most recent coming first)
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::{SportBoard, Tennis};
+///
+/// let mut sb: SportBoard<Tennis> = SportBoard::new();
+/// sb.start_game("Nadal", "Federer").unwrap();
+/// sb.update_score("Nadal", "Federer", vec![(6, 4), (3, 6), (7, 6)]).unwrap();
+/// assert_eq!(sb.get_summary(), vec![String::from("Nadal 2 - Federer 1 (6-4, 3-6, 7-6)")]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SportBoard<M: ScoreModel = Football> {
+	model: M,
+	games: Vec<SportGame<M::Score>>,
+}
+
+impl<M: ScoreModel + Default> SportBoard<M> {
+	/// Returns a newly created, empty score board using the default settings of the model
+	pub fn new() -> SportBoard<M> {
+		SportBoard::with_model(M::default())
+	}
+}
+
+impl<M: ScoreModel + Default> Default for SportBoard<M> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<M: ScoreModel> SportBoard<M> {
+	/// Returns a newly created, empty score board using the given model
+	///
+	/// # Arguments
+	///
+	/// * `model` - the way the games are scored
+	pub fn with_model(model: M) -> SportBoard<M> {
+		SportBoard { model, games: Vec::new() }
+	}
+
+	/// Starts a new game, with the default score of the model
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When the home and the away team are the same
+	/// * When any of the teams is currently playing a game
+	pub fn start_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		let home_name = home.to_string();
+		let away_name = away.to_string();
+
+		trace!("Starting a game between '{}' and '{}'", home_name, away_name);
+
+		if home_name == away_name {
+			warn!("{} cannot play with itself", home_name);
+			return Err(ScoreBoardError::SameTeam(home_name));
+		}
+
+		for name in [&home_name, &away_name] {
+			if self.games.iter().any(|game| &game.home_name == name || &game.away_name == name) {
+				debug!("Team {} is currently playing a game", name);
+				return Err(ScoreBoardError::AlreadyPlaying(name.clone()));
+			}
+		}
+
+		self.games.push(SportGame { home_name, away_name, score: M::Score::default(), start_time: Instant::now() });
+		self.sort();
+
+		Ok(())
+	}
+
+	/// Replaces the score of a running game
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	/// * `score` - the new complete score
+	///
+	/// # Errors
+	///
+	/// * When the score isn't possible in the sport
+	/// * When there is no active game between the given teams
+	pub fn update_score(&mut self, home: &str, away: &str, score: M::Score) -> Result<(), ScoreBoardError> {
+		trace!("Updating score of '{}' - '{}' to {:?}", home, away, score);
+
+		self.model.check(&score)?;
+
+		let index = self.find_game_index(home, away, Operation::UpdateScore)?;
+		self.games[index].score = score;
+		self.sort();
+
+		Ok(())
+	}
+
+	/// Finishes a game and removes it from the score board
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	///
+	/// # Errors
+	///
+	/// * When there is no active game between the given teams
+	pub fn finish_game(&mut self, home: &str, away: &str) -> Result<(), ScoreBoardError> {
+		trace!("Ending a game between '{}' and '{}'", home, away);
+
+		let index = self.find_game_index(home, away, Operation::FinishGame)?;
+		self.games.remove(index);
+
+		Ok(())
+	}
+
+	/// Provides the current score of a running game, or `None` if there is no active game between the given teams
+	pub fn get_score(&self, home: &str, away: &str) -> Option<&M::Score> {
+		self.games.iter()
+			.find(|game| game.home_name == home && game.away_name == away)
+			.map(|game| &game.score)
+	}
+
+	/// Provides the current status of the score board, with all the running games listed as "Home 2 - Away 1", followed by the details of the score in brackets if the model has them
+	pub fn get_summary(&self) -> Vec<String> {
+		self.games.iter().map(|game| {
+			let (home_score, away_score) = self.model.get_headline(&game.score);
+			let line = format!("{} {} - {} {}", game.home_name, home_score, game.away_name, away_score);
+
+			match self.model.get_details(&game.score) {
+				Some(details) => format!("{} ({})", line, details),
+				None => line,
+			}
+		}).collect()
+	}
+
+	/// Finds the index of the game between the given teams
+	fn find_game_index(&self, home: &str, away: &str, operation: Operation) -> Result<usize, ScoreBoardError> {
+		match self.games.iter().position(|game| game.home_name == home && game.away_name == away) {
+			Some(index) => Ok(index),
+			None => {
+				warn!("Couldn't find a game for {}", operation);
+				let teams = || self.games.iter().flat_map(|game| [game.home_name.as_str(), game.away_name.as_str()]);
+				let suggestion = [home, away].into_iter()
+					.filter(|name| !teams().any(|team| team == *name))
+					.find_map(|name| find_closest(name, teams()))
+					.map(String::from);
+
+				Err(ScoreBoardError::GameNotFound { home: home.to_string(), away: away.to_string(), operation, suggestion })
+			},
+		}
+	}
+
+	/// Orders the games by the total of the headline scores and the start time, both descending
+	fn sort(&mut self) {
+		let model = &self.model;
+		let get_total = |game: &SportGame<M::Score>| {
+			let (home, away) = model.get_headline(&game.score);
+			home + away
+		};
+
+		self.games.sort_by(|a, b| match get_total(b).cmp(&get_total(a)) {
+			Ordering::Equal => b.start_time.cmp(&a.start_time),
+			ordering => ordering,
+		});
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn football_is_the_default_model() {
+		let mut sb: SportBoard = SportBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", "Canada", (0, 5)).expect("Couldn't update the first game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 0 - Canada 5"), String::from("Spain 0 - Brazil 0")]);
+		assert_eq!(sb.get_score("Mexico", "Canada"), Some(&(0, 5)));
+	}
+
+	#[test]
+	fn tennis_counts_the_finished_sets() {
+		let mut sb: SportBoard<Tennis> = SportBoard::new();
+		sb.start_game("Nadal", "Federer").expect("Couldn't create the game");
+		sb.update_score("Nadal", "Federer", vec![(6, 4), (5, 7), (3, 2)]).expect("Couldn't update the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Nadal 1 - Federer 1 (6-4, 5-7, 3-2)")]);
+	}
+
+	#[test]
+	fn impossible_tennis_set_is_rejected() {
+		let mut sb: SportBoard<Tennis> = SportBoard::new();
+		sb.start_game("Nadal", "Federer").expect("Couldn't create the game");
+		let result = sb.update_score("Nadal", "Federer", vec![(8, 6)]);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Score 8 is out of range"));
+		assert_eq!(sb.get_score("Nadal", "Federer"), Some(&Vec::new()));
+	}
+
+	#[test]
+	fn basketball_adds_up_the_quarters() {
+		let mut sb: SportBoard<Basketball> = SportBoard::new();
+		sb.start_game("Lakers", "Celtics").expect("Couldn't create the game");
+		sb.update_score("Lakers", "Celtics", vec![(25, 20), (30, 28), (22, 26)]).expect("Couldn't update the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Lakers 77 - Celtics 74 (25-20, 30-28, 22-26)")]);
+	}
+
+	#[test]
+	fn volleyball_has_a_shorter_fifth_set() {
+		let mut sb: SportBoard<Volleyball> = SportBoard::new();
+		sb.start_game("Italy", "Poland").expect("Couldn't create the game");
+		sb.update_score("Italy", "Poland", vec![(25, 20), (23, 25), (26, 24), (20, 25), (15, 13)]).expect("Couldn't update the game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Italy 3 - Poland 2 (25-20, 23-25, 26-24, 20-25, 15-13)")]);
+	}
+
+	#[test]
+	fn games_are_ordered_by_the_headline_scores() {
+		let mut sb: SportBoard<Volleyball> = SportBoard::new();
+		sb.start_game("Italy", "Poland").expect("Couldn't create the first game");
+		sb.start_game("Brazil", "Japan").expect("Couldn't create the second game");
+		sb.start_game("France", "Serbia").expect("Couldn't create the third game");
+		sb.update_score("Italy", "Poland", vec![(25, 20)]).expect("Couldn't update the first game");
+
+		assert_eq!(sb.get_summary(), vec![
+			String::from("Italy 1 - Poland 0 (25-20)"),
+			String::from("France 0 - Serbia 0"),
+			String::from("Brazil 0 - Japan 0"),
+		]);
+	}
+
+	#[test]
+	fn board_rules_apply_to_every_sport() {
+		let mut sb: SportBoard<Basketball> = SportBoard::new();
+		sb.start_game("Lakers", "Celtics").expect("Couldn't create the game");
+
+		assert!(sb.start_game("Bulls", "Bulls").err().is_some_and(|result| result.to_string() == "Bulls cannot play with itself"));
+		assert!(sb.start_game("Celtics", "Bulls").err().is_some_and(|result| result.to_string() == "Celtics is currently playing a game"));
+		assert!(sb.update_score("Celtics", "Lakers", Vec::new()).err().is_some_and(|result| result.to_string() == "Couldn't find a game for update"));
+		assert!(sb.update_score("Laker", "Celtics", Vec::new()).err().is_some_and(|result| result.to_string() == "Couldn't find a game for update, did you mean Lakers?"));
+		sb.finish_game("Lakers", "Celtics").expect("Couldn't finish the game");
+		assert!(sb.finish_game("Lakers", "Celtics").err().is_some_and(|result| result.to_string() == "Couldn't find a game for removal"));
+		assert!(sb.get_summary().is_empty());
+	}
+}
@@ -0,0 +1,382 @@
+//! # Game states
+//!
+//! The life cycle of a game as a state machine, so a game can't jump between the states in a way that makes no sense, ex. from finished back to live
+
+use std::fmt;
+use std::string::{String, ToString};
+use std::time::Instant;
+
+use log::{trace, warn};
+
+use crate::builder::GameDetails;
+use crate::called_off::CalledOffGame;
+use crate::error::{Operation, ScoreBoardError};
+use crate::events::ScoreBoardEvent;
+use crate::ScoreBoard;
+
+/// The state of a game
+///
+/// The allowed transitions are:
+///
+/// | From | To |
+/// | ------ | ------ |
+/// | `Scheduled` | `Live`, `Abandoned`, `Postponed` |
+/// | `Live` | `Paused`, `Finished`, `Abandoned` |
+/// | `Paused` | `Live`, `Abandoned` |
+///
+/// `Finished`, `Abandoned` and `Postponed` are final
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameState {
+	/// Planned with `ScoreBoard::schedule_game()`, not started yet
+	Scheduled,
+	/// Being played
+	Live,
+	/// Interrupted, ex. for the half-time break or bad weather. The score can still be corrected
+	Paused,
+	/// Ended with a result
+	Finished,
+	/// Ended without a result
+	Abandoned,
+	/// Put off before the start to a later date. The fixture can be scheduled again with `ScoreBoard::schedule_game()`
+	Postponed,
+}
+
+impl GameState {
+	/// Tells if a game in this state can move to the given one
+	pub fn can_transition_to(self, next: GameState) -> bool {
+		matches!(
+			(self, next),
+			(GameState::Scheduled, GameState::Live)
+				| (GameState::Scheduled, GameState::Abandoned)
+				| (GameState::Scheduled, GameState::Postponed)
+				| (GameState::Live, GameState::Paused)
+				| (GameState::Live, GameState::Finished)
+				| (GameState::Live, GameState::Abandoned)
+				| (GameState::Paused, GameState::Live)
+				| (GameState::Paused, GameState::Abandoned)
+		)
+	}
+
+	/// Moves to the given state
+	///
+	/// # Errors
+	///
+	/// * When the transition isn't allowed, see the table at `GameState`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::GameState;
+	///
+	/// assert_eq!(GameState::Live.transition(GameState::Paused), Ok(GameState::Paused));
+	/// assert!(GameState::Finished.transition(GameState::Live).is_err());
+	/// ```
+	pub fn transition(self, next: GameState) -> Result<GameState, ScoreBoardError> {
+		if self.can_transition_to(next) {
+			Ok(next)
+		} else {
+			warn!("A game cannot go from {} to {}", self, next);
+			Err(ScoreBoardError::IllegalTransition { from: self, to: next })
+		}
+	}
+
+	/// Name of the state, as used in the JSON presentation of the events
+	pub(crate) fn get_name(&self) -> &'static str {
+		match self {
+			GameState::Scheduled => "scheduled",
+			GameState::Live => "live",
+			GameState::Paused => "paused",
+			GameState::Finished => "finished",
+			GameState::Abandoned => "abandoned",
+			GameState::Postponed => "postponed",
+		}
+	}
+}
+
+impl fmt::Display for GameState {
+	/// Implementation of `Display` trait, allowing it to be converted to a String
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.get_name())
+	}
+}
+
+impl ScoreBoard {
+	/// Moves a game to another state. A scheduled game becomes live with `start_game()`, keeping the venue and the time zone of its fixture, a live game is finished with `finish_game()`, and the other changes are recorded as `ScoreBoardEvent::StateChanged`. An abandoned or postponed game is removed from the score board or the schedule, without being archived, and is kept in `get_called_off_games()` instead. See also `abandon_game()` and `postpone_fixture()`, which give the reason
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	/// * `state` - The new state of the game
+	///
+	/// # Errors
+	///
+	/// * When there is neither a running nor a scheduled game between the given teams
+	/// * When the transition isn't allowed, see the table at `GameState`
+	/// * When starting or finishing the game fails
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::GameState;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.set_game_state("Japan", "Indonesia", GameState::Paused).unwrap();
+	/// assert_eq!(sb.get_game("Japan", "Indonesia").unwrap().state, GameState::Paused);
+	/// assert!(sb.set_game_state("Japan", "Indonesia", GameState::Finished).is_err());
+	/// ```
+	pub fn set_game_state<T: ToString, U: ToString>(&mut self, home: T, away: U, state: GameState) -> Result<(), ScoreBoardError> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		operation_span!(tracing::Level::INFO, "set_game_state", home = %home_name, away = %away_name, state = %state);
+
+		trace!("Moving the game between '{}' and '{}' to {}", home_name, away_name, state);
+
+		self.change_state(home_name, away_name, state, None)
+	}
+
+	/// Moves a game to another state
+	///
+	/// # Arguments
+	///
+	/// * `home_name` - name of the home team, after following the aliases
+	/// * `away_name` - name of the away team, after following the aliases
+	/// * `state` - the new state of the game
+	/// * `reason` - the reason of calling the game off, if it is abandoned or postponed
+	///
+	pub(crate) fn change_state(&mut self, home_name: String, away_name: String, state: GameState, reason: Option<String>) -> Result<(), ScoreBoardError> {
+		if let Some(game_index) = self.find_game_index(&home_name, &away_name) {
+			self.data[game_index].state.transition(state)?;
+
+			if state == GameState::Finished {
+				return self.finish_game(home_name, away_name);
+			}
+
+			let game = &self.data[game_index];
+			self.write_ahead(&ScoreBoardEvent::StateChanged { home: home_name.clone(), home_score: game.home_team.score, away: away_name.clone(), away_score: game.away_team.score, state })?;
+
+			let now = Instant::now();
+			let half_minutes = self.get_half_minutes();
+			let game = &mut self.data[game_index];
+			match state {
+				GameState::Paused => {
+					game.clock.pause(now);
+					game.pause_score.get_or_insert((game.home_team.score, game.away_team.score));
+				},
+				_ => {
+					game.clock.resume(now);
+					game.pause_score = None;
+				},
+			}
+			game.state = state;
+			if game.is_half_time_break(now, half_minutes) {
+				game.freeze_half_time_score();
+			}
+			game.revision += 1;
+			let (home_score, away_score, revision, kickoff_time) = (game.home_team.score, game.away_team.score, game.revision, game.kickoff_time);
+
+			if state == GameState::Abandoned {
+				self.remove_game(game_index);
+				self.call_off(CalledOffGame::new(&home_name, home_score, &away_name, away_score, kickoff_time, state, reason));
+			}
+
+			self.record_event(ScoreBoardEvent::StateChanged { home: home_name, home_score, away: away_name, away_score, state }, revision);
+
+			return Ok(());
+		}
+
+		if self.schedule.iter().any(|fixture| fixture.home_name == home_name && fixture.away_name == away_name) {
+			GameState::Scheduled.transition(state)?;
+
+			if state == GameState::Live {
+				// The game is started from the details of its fixture, see `start_new_game()`
+				return self.start_new_game(home_name, away_name, GameDetails::default());
+			}
+
+			let event = ScoreBoardEvent::StateChanged { home: home_name.clone(), home_score: 0, away: away_name.clone(), away_score: 0, state };
+			self.write_ahead(&event)?;
+
+			let kickoff_time = self.schedule.iter()
+				.filter(|fixture| fixture.home_name == home_name && fixture.away_name == away_name)
+				.map(|fixture| fixture.kickoff)
+				.min();
+			self.remove_from_schedule(&home_name, &away_name);
+			if let Some(kickoff_time) = kickoff_time {
+				self.call_off(CalledOffGame::new(&home_name, 0, &away_name, 0, kickoff_time, state, reason));
+			}
+			self.record_event(event, 0);
+
+			return Ok(());
+		}
+
+		warn!("Couldn't find a game for state change");
+		Err(self.game_not_found(home_name, away_name, Operation::ChangeState))
+	}
+
+	/// Provides the state of the game the event is about, after the event
+	pub(crate) fn get_event_state(&self, event: &ScoreBoardEvent) -> GameState {
+		match event {
+			ScoreBoardEvent::GameStarted { .. } => GameState::Live,
+			ScoreBoardEvent::ScoreUpdated { home, away, .. } | ScoreBoardEvent::SidesSwapped { home, away, .. } | ScoreBoardEvent::PeriodStarted { home, away, .. } => self.data.iter()
+				.find(|game| &game.home_team.name == home && &game.away_team.name == away)
+				.map_or(GameState::Live, |game| game.state),
+			ScoreBoardEvent::GameFinished { .. } => GameState::Finished,
+			ScoreBoardEvent::StateChanged { state, .. } => *state,
+			ScoreBoardEvent::GameReopened { .. } => GameState::Live,
+			ScoreBoardEvent::ResultFinalized { .. } => GameState::Finished,
+			ScoreBoardEvent::TeamRenamed { new, .. } => self.data.iter()
+				.find(|game| &game.home_team.name == new || &game.away_team.name == new)
+				.map_or(GameState::Scheduled, |game| game.state),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::SystemTime;
+	use std::vec::Vec;
+
+	use crate::archive::Decision;
+	use crate::timezone::UtcOffset;
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	const ALL_STATES: [GameState; 6] = [GameState::Scheduled, GameState::Live, GameState::Paused, GameState::Finished, GameState::Abandoned, GameState::Postponed];
+
+	fn set_state(sb: &mut ScoreBoard, state: GameState) -> Result<(), ScoreBoardError> {
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, state)
+	}
+
+	#[test]
+	fn final_states_have_no_transitions() {
+		for next in ALL_STATES {
+			assert!(!GameState::Finished.can_transition_to(next));
+			assert!(!GameState::Abandoned.can_transition_to(next));
+			assert!(!GameState::Postponed.can_transition_to(next));
+			assert!(!next.can_transition_to(GameState::Scheduled));
+		}
+	}
+
+	#[test]
+	fn illegal_transition_is_rejected() {
+		let result = GameState::Paused.transition(GameState::Finished);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "A game cannot go from paused to finished"));
+	}
+
+	#[test]
+	fn paused_game_can_be_resumed() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		set_state(&mut sb, GameState::Paused).expect("Couldn't pause the game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 0).expect("Couldn't correct the score");
+		set_state(&mut sb, GameState::Live).expect("Couldn't resume the game");
+		set_state(&mut sb, GameState::Finished).expect("Couldn't finish the game");
+
+		let states: Vec<GameState> = sb.get_events().iter().map(|record| record.state).collect();
+		assert_eq!(states, vec![GameState::Live, GameState::Paused, GameState::Paused, GameState::Live, GameState::Finished, GameState::Finished]);
+		assert_eq!(sb.get_archive_summary(), vec![String::from("Ghana 1 - 0 Uruguay")]);
+	}
+
+	#[test]
+	fn paused_game_cannot_be_finished() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the other game");
+		set_state(&mut sb, GameState::Paused).expect("Couldn't pause the game");
+		let illegal_transition = ScoreBoardError::IllegalTransition { from: GameState::Paused, to: GameState::Finished };
+
+		assert_eq!(sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME), Err(illegal_transition.clone()));
+		assert_eq!(sb.finish_game_after(HOME_TEAM_NAME, AWAY_TEAM_NAME, Decision::AfterExtraTime), Err(illegal_transition.clone()));
+		assert_eq!(sb.finish_all(), Err(illegal_transition));
+		assert_eq!(sb.get_summary().len(), 2);
+		assert!(sb.get_archive().is_empty());
+
+		set_state(&mut sb, GameState::Live).expect("Couldn't resume the game");
+		assert_eq!(sb.finish_all(), Ok(2));
+	}
+
+	#[test]
+	fn state_change_is_recorded() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 1).expect("Couldn't update the game");
+		set_state(&mut sb, GameState::Paused).expect("Couldn't pause the game");
+
+		let record = sb.get_events().last().expect("Couldn't find the event");
+		assert_eq!(record.event, ScoreBoardEvent::StateChanged {
+			home: String::from(HOME_TEAM_NAME),
+			home_score: 1,
+			away: String::from(AWAY_TEAM_NAME),
+			away_score: 1,
+			state: GameState::Paused,
+		});
+		assert_eq!(record.revision, 3);
+	}
+
+	#[test]
+	fn abandoned_game_is_removed_without_a_result() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		set_state(&mut sb, GameState::Paused).expect("Couldn't pause the game");
+		set_state(&mut sb, GameState::Abandoned).expect("Couldn't abandon the game");
+
+		assert!(sb.get_summary().is_empty());
+		assert!(sb.get_archive().is_empty());
+		assert_eq!(sb.get_events().last().map(|record| record.state), Some(GameState::Abandoned));
+	}
+
+	#[test]
+	fn scheduled_game_can_start_or_be_abandoned() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, SystemTime::now()).expect("Couldn't schedule the game");
+		sb.schedule_game("Spain", "Brazil", SystemTime::now()).expect("Couldn't schedule the other game");
+		set_state(&mut sb, GameState::Live).expect("Couldn't start the game");
+		sb.set_game_state("Spain", "Brazil", GameState::Abandoned).expect("Couldn't abandon the other game");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Ghana 0 - Uruguay 0")]);
+		assert!(sb.schedule.is_empty());
+		assert_eq!(sb.get_events().last().map(|record| (record.state, record.revision)), Some((GameState::Abandoned, 0)));
+	}
+
+	#[test]
+	fn started_fixture_keeps_its_details() {
+		let doha = UtcOffset::from_hours(3).expect("Couldn't create the offset");
+		let mut sb = ScoreBoard::new();
+		sb.new_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).venue("Al Janoub").utc_offset(doha).schedule(SystemTime::now()).expect("Couldn't schedule the game");
+		set_state(&mut sb, GameState::Live).expect("Couldn't start the game");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the started game");
+		assert_eq!(game.venue.as_deref(), Some("Al Janoub"));
+		assert_eq!(game.utc_offset, doha);
+	}
+
+	#[test]
+	fn scheduled_game_cannot_be_paused() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, SystemTime::now()).expect("Couldn't schedule the game");
+
+		let result = set_state(&mut sb, GameState::Paused);
+		assert!(result.err().is_some_and(|result| result.to_string() == "A game cannot go from scheduled to paused"));
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn unknown_game_has_no_state() {
+		let mut sb = ScoreBoard::new();
+
+		let result = set_state(&mut sb, GameState::Live);
+		assert!(matches!(result, Err(ScoreBoardError::GameNotFound { operation: Operation::ChangeState, .. })));
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for state change"));
+	}
+}
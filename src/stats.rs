@@ -0,0 +1,311 @@
+//! # Tournament statistics
+//!
+//! Aggregates over the finished games, like the standings of the teams and the histograms of the scores. With the `parallel` feature, the archive is split into parts aggregated in parallel with `rayon`, and the partial results are merged
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::string::String;
+use std::vec::Vec;
+
+use log::trace;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::archive::ArchivedGame;
+use crate::ScoreBoard;
+
+/// The fewest finished games aggregated by a task of its own, since smaller parts are aggregated quicker than they are handed over
+#[cfg(feature = "parallel")]
+const MIN_GAMES_PER_TASK: usize = 1024;
+
+/// Points for a win
+const WIN_POINTS: u32 = 3;
+
+/// Points for a draw
+const DRAW_POINTS: u32 = 1;
+
+/// Results of a team in the finished games, see `ScoreBoard::get_standings()`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TeamStanding {
+	/// Name of the team
+	pub team: String,
+	/// Number of the finished games
+	pub played: u32,
+	/// Number of the won games
+	pub won: u32,
+	/// Number of the drawn games
+	pub drawn: u32,
+	/// Number of the lost games
+	pub lost: u32,
+	/// Goals scored by the team
+	pub goals_for: u32,
+	/// Goals scored against the team
+	pub goals_against: u32,
+	/// Points of the team, 3 for a win and 1 for a draw
+	pub points: u32,
+}
+
+impl TeamStanding {
+	/// Provides the difference between the goals scored by the team and against it
+	pub fn goal_difference(&self) -> i64 {
+		i64::from(self.goals_for) - i64::from(self.goals_against)
+	}
+
+	/// Adds the result of a game
+	///
+	/// # Arguments
+	///
+	/// * `scored` - goals scored by the team
+	/// * `conceded` - goals scored against the team
+	///
+	fn add_result(&mut self, scored: u8, conceded: u8) {
+		self.played += 1;
+		self.goals_for += u32::from(scored);
+		self.goals_against += u32::from(conceded);
+
+		match scored.cmp(&conceded) {
+			Ordering::Greater => {
+				self.won += 1;
+				self.points += WIN_POINTS;
+			},
+			Ordering::Equal => {
+				self.drawn += 1;
+				self.points += DRAW_POINTS;
+			},
+			Ordering::Less => self.lost += 1,
+		}
+	}
+
+	/// Adds the results of the same team from another part of the archive
+	///
+	/// # Arguments
+	///
+	/// * `other` - the results to add
+	///
+	fn merge(&mut self, other: TeamStanding) {
+		self.played += other.played;
+		self.won += other.won;
+		self.drawn += other.drawn;
+		self.lost += other.lost;
+		self.goals_for += other.goals_for;
+		self.goals_against += other.goals_against;
+		self.points += other.points;
+	}
+}
+
+impl ScoreBoard {
+	/// Provides the standings of all the teams that finished a game, by the final scores of the archived games. A win gives 3 points and a draw 1 point, also when the game was then decided in a penalty shootout. The teams are ordered by the points, the goal difference, the goals scored and the name
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "Saudi Arabia").unwrap();
+	/// sb.update_score("Argentina", 1, "Saudi Arabia", 2).unwrap();
+	/// sb.finish_game("Argentina", "Saudi Arabia").unwrap();
+	/// sb.start_game("Argentina", "Mexico").unwrap();
+	/// sb.update_score("Argentina", 2, "Mexico", 0).unwrap();
+	/// sb.finish_game("Argentina", "Mexico").unwrap();
+	///
+	/// let standings = sb.get_standings();
+	/// assert_eq!((standings[0].team.as_str(), standings[0].points, standings[0].goal_difference()), ("Argentina", 3, 1));
+	/// assert_eq!((standings[1].team.as_str(), standings[1].points), ("Saudi Arabia", 3));
+	/// ```
+	pub fn get_standings(&self) -> Vec<TeamStanding> {
+		trace!("Getting the standings of {} finished games", self.archive.len());
+
+		let standings: HashMap<&str, TeamStanding> = aggregate(
+			&self.archive,
+			|mut standings: HashMap<&str, TeamStanding>, game| {
+				standings.entry(&game.home_team).or_default().add_result(game.home_score, game.away_score);
+				standings.entry(&game.away_team).or_default().add_result(game.away_score, game.home_score);
+				standings
+			},
+			|mut standings, other| {
+				for (team, standing) in other {
+					standings.entry(team).or_default().merge(standing);
+				}
+				standings
+			},
+		);
+
+		let mut standings: Vec<TeamStanding> = standings.into_iter()
+			.map(|(team, standing)| TeamStanding { team: String::from(team), ..standing })
+			.collect();
+		standings.sort_by(|a, b| b.points.cmp(&a.points)
+			.then_with(|| b.goal_difference().cmp(&a.goal_difference()))
+			.then_with(|| b.goals_for.cmp(&a.goals_for))
+			.then_with(|| a.team.cmp(&b.team)));
+
+		standings
+	}
+
+	/// Provides the histogram of the goals of the finished games: the number of the games with no goals, with 1 goal, with 2 goals and so on, up to the most goals of a game
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Spain", "Costa Rica").unwrap();
+	/// sb.update_score("Spain", 7, "Costa Rica", 0).unwrap();
+	/// sb.finish_game("Spain", "Costa Rica").unwrap();
+	/// sb.start_game("Morocco", "Croatia").unwrap();
+	/// sb.finish_game("Morocco", "Croatia").unwrap();
+	///
+	/// assert_eq!(sb.get_goal_histogram(), vec![1, 0, 0, 0, 0, 0, 0, 1]);
+	/// ```
+	pub fn get_goal_histogram(&self) -> Vec<usize> {
+		trace!("Getting the goal histogram of {} finished games", self.archive.len());
+
+		aggregate(
+			&self.archive,
+			|mut histogram: Vec<usize>, game| {
+				let goals = usize::from(game.home_score) + usize::from(game.away_score);
+				if histogram.len() <= goals {
+					histogram.resize(goals + 1, 0);
+				}
+				histogram[goals] += 1;
+				histogram
+			},
+			|mut histogram, other| {
+				if histogram.len() < other.len() {
+					histogram.resize(other.len(), 0);
+				}
+				for (count, other_count) in histogram.iter_mut().zip(other) {
+					*count += other_count;
+				}
+				histogram
+			},
+		)
+	}
+
+	/// Provides the histogram of the final scores of the finished games, as (home score, away score) and the number of the games that ended with it. The most common scores come first, and the scores of the same count are ordered by the home and then the away score
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// for (home, away) in [("Spain", "Germany"), ("Croatia", "Belgium"), ("Portugal", "Ghana")] {
+	///     sb.start_game(home, away).unwrap();
+	///     sb.finish_game(home, away).unwrap();
+	/// }
+	/// sb.start_game("Japan", "Germany").unwrap();
+	/// sb.update_score("Japan", 2, "Germany", 1).unwrap();
+	/// sb.finish_game("Japan", "Germany").unwrap();
+	///
+	/// assert_eq!(sb.get_score_histogram(), vec![((0, 0), 3), ((2, 1), 1)]);
+	/// ```
+	pub fn get_score_histogram(&self) -> Vec<((u8, u8), usize)> {
+		trace!("Getting the score histogram of {} finished games", self.archive.len());
+
+		let histogram: HashMap<(u8, u8), usize> = aggregate(
+			&self.archive,
+			|mut histogram: HashMap<(u8, u8), usize>, game| {
+				*histogram.entry((game.home_score, game.away_score)).or_insert(0) += 1;
+				histogram
+			},
+			|mut histogram, other| {
+				for (score, count) in other {
+					*histogram.entry(score).or_insert(0) += count;
+				}
+				histogram
+			},
+		);
+
+		let mut histogram: Vec<((u8, u8), usize)> = histogram.into_iter().collect();
+		histogram.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+		histogram
+	}
+}
+
+/// Aggregates the finished games one after another
+///
+/// # Arguments
+///
+/// * `archive` - the finished games
+/// * `add` - adds a game to the partial result
+/// * `_merge` - merges two partial results, not needed for a single one
+///
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn aggregate<'a, T, A, M>(archive: &'a [ArchivedGame], add: A, _merge: M) -> T
+where
+	T: Default + Send,
+	A: Fn(T, &'a ArchivedGame) -> T + Send + Sync,
+	M: Fn(T, T) -> T + Send + Sync,
+{
+	archive.iter().fold(T::default(), add)
+}
+
+/// Aggregates parts of the finished games in parallel, and merges the partial results. The archives of up to `MIN_GAMES_PER_TASK` games are aggregated by a single task
+///
+/// # Arguments
+///
+/// * `archive` - the finished games
+/// * `add` - adds a game to the partial result
+/// * `merge` - merges two partial results
+///
+#[cfg(feature = "parallel")]
+pub(crate) fn aggregate<'a, T, A, M>(archive: &'a [ArchivedGame], add: A, merge: M) -> T
+where
+	T: Default + Send,
+	A: Fn(T, &'a ArchivedGame) -> T + Send + Sync,
+	M: Fn(T, T) -> T + Send + Sync,
+{
+	archive.par_iter().with_min_len(MIN_GAMES_PER_TASK).fold(T::default, add).reduce(T::default, merge)
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Plays and finishes a game with the given score
+	fn play(sb: &mut ScoreBoard, home: &str, home_score: u8, away: &str, away_score: u8) {
+		sb.start_game(home, away).expect("Couldn't create the game");
+		sb.update_score(home, home_score, away, away_score).expect("Couldn't update the game");
+		sb.finish_game(home, away).expect("Couldn't finish the game");
+	}
+
+	#[test]
+	fn empty_archive_has_no_statistics() {
+		let sb = ScoreBoard::new();
+
+		assert!(sb.get_standings().is_empty());
+		assert!(sb.get_goal_histogram().is_empty());
+		assert!(sb.get_score_histogram().is_empty());
+	}
+
+	#[test]
+	fn standings_count_every_result() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Netherlands", 2, "Senegal", 0);
+		play(&mut sb, "Netherlands", 1, "Ecuador", 1);
+		play(&mut sb, "Ecuador", 2, "Senegal", 1);
+		play(&mut sb, "Senegal", 2, "Qatar", 1);
+
+		let standings = sb.get_standings();
+		let teams: Vec<&str> = standings.iter().map(|standing| standing.team.as_str()).collect();
+		assert_eq!(teams, vec!["Netherlands", "Ecuador", "Senegal", "Qatar"]);
+		assert_eq!(standings[0], TeamStanding { team: String::from("Netherlands"), played: 2, won: 1, drawn: 1, lost: 0, goals_for: 3, goals_against: 1, points: 4 });
+		assert_eq!((standings[2].won, standings[2].lost, standings[2].goal_difference()), (1, 2, -2));
+	}
+
+	#[test]
+	fn large_archive_gives_the_same_statistics() {
+		let mut sb = ScoreBoard::new();
+		for id in 0..3000 {
+			play(&mut sb, &format!("Home {}", id % 7), (id % 5) as u8, &format!("Away {}", id % 11), (id % 3) as u8);
+		}
+
+		let standings = sb.get_standings();
+		assert_eq!(standings.iter().map(|standing| standing.played).sum::<u32>(), 6000);
+		assert_eq!(standings.iter().map(|standing| standing.goals_for).sum::<u32>(), standings.iter().map(|standing| standing.goals_against).sum::<u32>());
+		assert_eq!(sb.get_goal_histogram().iter().sum::<usize>(), 3000);
+		assert_eq!(sb.get_score_histogram().len(), 15);
+		assert_eq!(sb.get_score_histogram()[0].1, 200);
+	}
+}
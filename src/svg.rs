@@ -0,0 +1,172 @@
+//! # SVG rendering
+//!
+//! Draws the ranked games as an SVG image, so the board can be embedded in broadcast graphics and web pages as a crisp vector graphic
+
+use std::string::{String, ToString};
+
+use log::trace;
+
+use crate::text::escape_xml;
+use crate::ScoreBoard;
+
+/// Size, colors and fonts of the SVG image of the board, see `ScoreBoard::render_svg()`. All the sizes are in pixels, and the colors and the fonts are given the way the SVG and CSS expect them, ex. "#1d3557" or "Helvetica, sans-serif"
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::SvgStyle;
+///
+/// let style = SvgStyle { width: 800, accent_color: String::from("crimson"), ..SvgStyle::default() };
+/// assert_eq!(style.row_height, 40);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SvgStyle {
+	/// Width of the image
+	pub width: u32,
+	/// Height of a single game
+	pub row_height: u32,
+	/// Space around the games
+	pub padding: u32,
+	/// Width of the box with the score, in the middle of a row
+	pub score_width: u32,
+	/// Family of the font of the texts
+	pub font_family: String,
+	/// Size of the font of the texts
+	pub font_size: u32,
+	/// Color of the background of the image
+	pub background_color: String,
+	/// Colors of the odd and the even rows, counting from 1
+	pub row_colors: (String, String),
+	/// Color of the team names
+	pub text_color: String,
+	/// Color of the box with the score
+	pub accent_color: String,
+	/// Color of the score
+	pub score_color: String,
+}
+
+impl Default for SvgStyle {
+	/// Implementation of `Default` trait, with a dark board of 600 pixels width
+	fn default() -> SvgStyle {
+		SvgStyle {
+			width: 600,
+			row_height: 40,
+			padding: 8,
+			score_width: 96,
+			font_family: String::from("Helvetica, Arial, sans-serif"),
+			font_size: 18,
+			background_color: String::from("#0b1e3f"),
+			row_colors: (String::from("#14305f"), String::from("#102851")),
+			text_color: String::from("#ffffff"),
+			accent_color: String::from("#f1c40f"),
+			score_color: String::from("#0b1e3f"),
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Renders the running games as an SVG image, one row per game in the order of `get_summary()`. The home team is aligned to the score box in the middle of the row and the away team follows it. The height of the image follows the number of the games, and the scores follow the locale of the board (see `set_locale()`)
+	///
+	/// # Arguments
+	///
+	/// * `style` - the size, the colors and the fonts of the image
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::SvgStyle;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 2, "Indonesia", 0).unwrap();
+	///
+	/// let svg = sb.render_svg(&SvgStyle::default());
+	/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"600\" height=\"56\""));
+	/// assert!(svg.contains(">Japan</text>"));
+	/// assert!(svg.contains(">2 - 0</text>"));
+	/// ```
+	pub fn render_svg(&self, style: &SvgStyle) -> String {
+		trace!("Rendering the score board as SVG");
+
+		let height = style.padding * 2 + style.row_height * self.data.len() as u32;
+		let middle = style.width / 2;
+		let gap = style.font_size / 2;
+		let font_family = escape_xml(&style.font_family);
+
+		let mut svg = String::new();
+		svg.push_str(&format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n", style.width, height, style.width, height));
+		svg.push_str(&format!("  <rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n", style.width, height, escape_xml(&style.background_color)));
+
+		for (id, game) in self.data.iter().enumerate() {
+			let top = style.padding + style.row_height * id as u32;
+			let baseline = top + style.row_height / 2;
+			let row_color = if id % 2 == 0 { &style.row_colors.0 } else { &style.row_colors.1 };
+			let score = format!("{}{}{}",
+				self.locale.numerals.localize(&game.home_team.score.to_string()),
+				self.locale.score_separator,
+				self.locale.numerals.localize(&game.away_team.score.to_string()));
+
+			svg.push_str(&format!("  <g font-family=\"{}\" font-size=\"{}\" dominant-baseline=\"central\">\n", font_family, style.font_size));
+			svg.push_str(&format!("    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+				style.padding, top, style.width.saturating_sub(style.padding * 2), style.row_height, escape_xml(row_color)));
+			svg.push_str(&format!("    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+				middle.saturating_sub(style.score_width / 2), top, style.score_width, style.row_height, escape_xml(&style.accent_color)));
+			svg.push_str(&format!("    <text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"{}\">{}</text>\n",
+				middle.saturating_sub(style.score_width / 2 + gap), baseline, escape_xml(&style.text_color), escape_xml(&game.home_team.name)));
+			svg.push_str(&format!("    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-weight=\"bold\" fill=\"{}\">{}</text>\n",
+				middle, baseline, escape_xml(&style.score_color), escape_xml(&score)));
+			svg.push_str(&format!("    <text x=\"{}\" y=\"{}\" text-anchor=\"start\" fill=\"{}\">{}</text>\n",
+				middle + style.score_width / 2 + gap, baseline, escape_xml(&style.text_color), escape_xml(&game.away_team.name)));
+			svg.push_str("  </g>\n");
+		}
+
+		svg.push_str("</svg>\n");
+
+		svg
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_board_has_only_the_background() {
+		let sb = ScoreBoard::new();
+		let style = SvgStyle { width: 300, padding: 10, background_color: String::from("black"), ..SvgStyle::default() };
+
+		assert_eq!(sb.render_svg(&style), "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"300\" height=\"20\" viewBox=\"0 0 300 20\">\n  <rect width=\"300\" height=\"20\" fill=\"black\"/>\n</svg>\n");
+	}
+
+	#[test]
+	fn games_are_drawn_in_the_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		let svg = sb.render_svg(&SvgStyle::default());
+
+		assert!(svg.contains("height=\"96\""));
+		assert!(svg.contains("<rect x=\"8\" y=\"8\" width=\"584\" height=\"40\" fill=\"#14305f\"/>"));
+		assert!(svg.contains("<rect x=\"8\" y=\"48\" width=\"584\" height=\"40\" fill=\"#102851\"/>"));
+		assert!(svg.find(">Mexico<").expect("Couldn't find the first game") < svg.find(">Spain<").expect("Couldn't find the second game"));
+		assert!(svg.contains("<text x=\"243\" y=\"28\" text-anchor=\"end\" fill=\"#ffffff\">Mexico</text>"));
+		assert!(svg.contains("<text x=\"357\" y=\"68\" text-anchor=\"start\" fill=\"#ffffff\">Brazil</text>"));
+	}
+
+	#[test]
+	fn texts_are_escaped() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Bosnia & Herzegovina", "<Wales>").expect("Couldn't create the game");
+		let style = SvgStyle { font_family: String::from("\"Open Sans\""), ..SvgStyle::default() };
+		let svg = sb.render_svg(&style);
+
+		assert!(svg.contains(">Bosnia &amp; Herzegovina<"));
+		assert!(svg.contains(">&lt;Wales&gt;<"));
+		assert!(svg.contains("font-family=\"&quot;Open Sans&quot;\""));
+	}
+}
@@ -0,0 +1,139 @@
+//! # Swapped sides
+//!
+//! Flips the home and the away team of a running game, for the feeds that sometimes give the teams in the reversed order
+
+use std::mem;
+use std::string::ToString;
+
+use log::{trace, warn};
+
+use crate::error::{Operation, ScoreBoardError};
+use crate::events::ScoreBoardEvent;
+use crate::goal::Side;
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Swaps the home and the away team of a running game, together with their scores, goals, period scores, odds and expected goals. The game keeps its start time and place on the board, and its earlier recorded events stay as they were. A `ScoreBoardEvent::SidesSwapped` event is recorded, so the history and the journal follow the new orientation
+	///
+	/// # Arguments
+	///
+	/// * `team_a` - Name of one of the teams, in either order. Must be either a `String` or a type that is convertable to `String`
+	/// * `team_b` - Name of the other team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When there is no active match between the given teams
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("France", "Argentina").unwrap();
+	/// sb.update_score("France", 1, "Argentina", 2).unwrap();
+	/// sb.swap_sides("France", "Argentina").unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("Argentina 2 - France 1")]);
+	/// ```
+	pub fn swap_sides<T: ToString, U: ToString>(&mut self, team_a: T, team_b: U) -> Result<(), ScoreBoardError> {
+		let name_a = self.resolve_team(team_a);
+		let name_b = self.resolve_team(team_b);
+
+		operation_span!(tracing::Level::INFO, "swap_sides", team_a = %name_a, team_b = %name_b);
+
+		trace!("Swapping the sides of the game between '{}' and '{}'", name_a, name_b);
+
+		let game_index = match self.find_game_index(&name_a, &name_b).or_else(|| self.find_game_index(&name_b, &name_a)) {
+			Some(game_index) => game_index,
+			None => {
+				warn!("Couldn't find a game for update");
+				return Err(self.game_not_found(name_a, name_b, Operation::UpdateScore));
+			},
+		};
+
+		let game = &self.data[game_index];
+		let event = ScoreBoardEvent::SidesSwapped { home: game.away_team.name.clone(), home_score: game.away_team.score, away: game.home_team.name.clone(), away_score: game.home_team.score };
+		self.write_ahead(&event)?;
+
+		let game = &mut self.data[game_index];
+		mem::swap(&mut game.home_team, &mut game.away_team);
+		for goal in &mut game.goals {
+			goal.side = match goal.side {
+				Side::Home => Side::Away,
+				Side::Away => Side::Home,
+			};
+		}
+		for (_, (home_score, away_score)) in &mut game.periods {
+			mem::swap(home_score, away_score);
+		}
+		for (home_score, away_score) in game.half_time_score.iter_mut().chain(game.pause_score.iter_mut()) {
+			mem::swap(home_score, away_score);
+		}
+		if let Some(odds) = &mut game.odds {
+			mem::swap(&mut odds.home, &mut odds.away);
+		}
+		mem::swap(&mut game.xg.home, &mut game.xg.away);
+		game.revision += 1;
+
+		let revision = game.revision;
+		self.record_event(event, revision);
+		self.invalidate_summary();
+
+		trace!("Sides swapped successfully");
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::goal::GoalKind;
+	use crate::odds::Odds;
+	use crate::period::Period;
+
+	const HOME_TEAM_NAME: &str = "Brazil";
+	const AWAY_TEAM_NAME: &str = "Serbia";
+
+	#[test]
+	fn game_keeps_its_start_and_records_the_swap() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(AWAY_TEAM_NAME, HOME_TEAM_NAME).expect("Couldn't create the game");
+		sb.update_score(AWAY_TEAM_NAME, 0, HOME_TEAM_NAME, 2).expect("Couldn't update the game");
+		let before = sb.get_game(AWAY_TEAM_NAME, HOME_TEAM_NAME).expect("Couldn't find the game");
+		sb.swap_sides(AWAY_TEAM_NAME, HOME_TEAM_NAME).expect("Couldn't swap the sides");
+
+		let after = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the swapped game");
+		assert_eq!((after.home_score, after.away_score), (2, 0));
+		assert_eq!((after.kickoff_time, after.revision), (before.kickoff_time, before.revision + 1));
+		assert!(sb.get_game(AWAY_TEAM_NAME, HOME_TEAM_NAME).is_none());
+		assert_eq!(sb.get_events().len(), 3);
+		assert!(matches!(&sb.get_events()[2].event, ScoreBoardEvent::SidesSwapped { home, home_score: 2, .. } if home == HOME_TEAM_NAME));
+	}
+
+	#[test]
+	fn goals_and_periods_change_sides() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(AWAY_TEAM_NAME, HOME_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_goal(AWAY_TEAM_NAME, HOME_TEAM_NAME, Side::Away, GoalKind::Penalty).expect("Couldn't record the goal");
+		sb.start_period(AWAY_TEAM_NAME, HOME_TEAM_NAME, Period::SecondHalf).expect("Couldn't start the second half");
+		sb.set_odds(AWAY_TEAM_NAME, HOME_TEAM_NAME, Odds::new(4.0, 3.0, 1.5)).expect("Couldn't set the odds");
+		sb.swap_sides(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't swap the sides");
+
+		let game = sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the swapped game");
+		assert_eq!(game.half_time_score, Some((1, 0)));
+		assert_eq!(game.odds.map(|odds| (odds.home, odds.away)), Some((1.5, 4.0)));
+		assert_eq!(sb.get_goal_counts(HOME_TEAM_NAME, AWAY_TEAM_NAME).map(|(home, _)| home.penalty), Some(1));
+	}
+
+	#[test]
+	fn missing_game_cannot_be_swapped() {
+		let mut sb = ScoreBoard::new();
+
+		assert!(matches!(sb.swap_sides(HOME_TEAM_NAME, AWAY_TEAM_NAME), Err(ScoreBoardError::GameNotFound { .. })));
+		assert!(sb.get_summary().is_empty());
+	}
+}
@@ -0,0 +1,139 @@
+//! # Aligned tables
+//!
+//! Renders the summary as a table with aligned columns, for monospace venue displays and logs
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::ScoreBoard;
+
+/// The look of a table rendered with `ScoreBoard::render_table()`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TableStyle {
+	/// Aligned columns without borders, ex. "Spain  10 - Brazil 2"
+	#[default]
+	Plain,
+	/// Aligned columns framed with the box-drawing characters, ex. "│ Spain  │ 10 │ Brazil │ 2 │"
+	Boxed,
+}
+
+impl ScoreBoard {
+	/// Provides the summary as a table, one game per line in the order of `get_summary()`. The names of the teams are padded to the longest name of their column and the scores are aligned to the right, so the columns line up in a monospace font. The widths are counted in characters, and the dash between the teams and the digits of the scores follow the locale of the board (see `set_locale()`)
+	///
+	/// # Arguments
+	///
+	/// * `style` - the look of the table
+	///
+	/// # Returns
+	///
+	/// * The lines of the table, each ending with a new line, or an empty string when no games are played
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::TableStyle;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Mexico", "Canada").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 10, "Brazil", 2).unwrap();
+	///
+	/// assert_eq!(sb.render_table(TableStyle::Plain), "Spain  10 - Brazil 2\nMexico  0 - Canada 0\n");
+	/// assert_eq!(sb.render_table(TableStyle::Boxed), concat!(
+	///     "┌────────┬────┬────────┬───┐\n",
+	///     "│ Spain  │ 10 │ Brazil │ 2 │\n",
+	///     "│ Mexico │  0 │ Canada │ 0 │\n",
+	///     "└────────┴────┴────────┴───┘\n",
+	/// ));
+	/// ```
+	pub fn render_table(&self, style: TableStyle) -> String {
+		trace!("Rendering the summary as a {:?} table", style);
+
+		let numerals = self.locale.numerals;
+		let rows: Vec<[String; 4]> = self.data.iter()
+			.map(|game| [
+				game.home_team.name.clone(),
+				numerals.localize(&game.home_team.score.to_string()),
+				game.away_team.name.clone(),
+				numerals.localize(&game.away_team.score.to_string()),
+			])
+			.collect();
+
+		if rows.is_empty() {
+			return String::new();
+		}
+
+		let mut widths = [0; 4];
+		for row in &rows {
+			for (width, cell) in widths.iter_mut().zip(row) {
+				*width = (*width).max(cell.chars().count());
+			}
+		}
+		let [home_width, home_score_width, away_width, away_score_width] = widths;
+
+		let mut table = String::new();
+
+		match style {
+			TableStyle::Plain => {
+				for [home, home_score, away, away_score] in &rows {
+					table.push_str(&format!("{:<home_width$} {:>home_score_width$}{}{:<away_width$} {:>away_score_width$}\n", home, home_score, self.locale.dash, away, away_score));
+				}
+			},
+			TableStyle::Boxed => {
+				let border = |left: char, middle: char, right: char| {
+					let lines: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+					format!("{}{}{}\n", left, lines.join(&middle.to_string()), right)
+				};
+
+				table.push_str(&border('┌', '┬', '┐'));
+				for [home, home_score, away, away_score] in &rows {
+					table.push_str(&format!("│ {:<home_width$} │ {:>home_score_width$} │ {:<away_width$} │ {:>away_score_width$} │\n", home, home_score, away, away_score));
+				}
+				table.push_str(&border('└', '┴', '┘'));
+			},
+		}
+
+		table
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::locale::{Locale, Numerals};
+
+	#[test]
+	fn empty_board_gives_no_table() {
+		let sb = ScoreBoard::new();
+
+		assert!(sb.render_table(TableStyle::Plain).is_empty());
+		assert!(sb.render_table(TableStyle::Boxed).is_empty());
+	}
+
+	#[test]
+	fn widths_are_counted_in_characters() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Côte d'Ivoire", "Japan").expect("Couldn't create the first game");
+		sb.start_game("Wales", "Türkiye").expect("Couldn't create the second game");
+
+		assert_eq!(sb.render_table(TableStyle::Plain), "Wales         0 - Türkiye 0\nCôte d'Ivoire 0 - Japan   0\n");
+	}
+
+	#[test]
+	fn table_follows_the_locale() {
+		let mut sb = ScoreBoard::new();
+		sb.set_locale(Locale { dash: String::from(" : "), numerals: Numerals::EasternArabic, ..Locale::default() });
+		sb.start_game("Qatar", "Ecuador").expect("Couldn't create the game");
+		sb.update_score("Qatar", 0, "Ecuador", 12).expect("Couldn't update the game");
+
+		assert_eq!(sb.render_table(TableStyle::Plain), "Qatar ٠ : Ecuador ١٢\n");
+		assert!(sb.render_table(TableStyle::Boxed).contains("│ Qatar │ ٠ │ Ecuador │ ١٢ │\n"));
+	}
+}
@@ -0,0 +1,285 @@
+//! # Summary templates
+//!
+//! Renders the lines of the summary from a format string set on the board, ex. "{home} {home_score}:{away_score} {away} ({minute}')", so a new format of the output doesn't need a change in every consumer of the summary
+
+use std::mem;
+use std::string::{String, ToString};
+use std::time::Instant;
+use std::vec::Vec;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::locale::{Locale, Numerals};
+use crate::{Game, ScoreBoard};
+
+/// A piece of a summary template
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+	/// Text copied to the line as it is
+	Text(String),
+	/// Name of the home team, `{home}`
+	Home,
+	/// Name of the away team, `{away}`
+	Away,
+	/// Score of the home team, `{home_score}`
+	HomeScore,
+	/// Score of the away team, `{away_score}`
+	AwayScore,
+	/// Minute of the match as a number, `{minute}`
+	Minute,
+	/// Minute of the match as shown on the tickers, ex. "78'", "45+3'" or "HT", `{clock}`
+	Clock,
+}
+
+/// A parsed summary template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SummaryTemplate {
+	/// The text of the template, as it was given
+	text: String,
+	/// The pieces of the template, in the order of the line
+	parts: Vec<Part>,
+}
+
+impl SummaryTemplate {
+	/// Parses a template. `{{` and `}}` stand for the braces themselves
+	///
+	/// # Arguments
+	///
+	/// * `text` - the text of the template
+	///
+	/// # Errors
+	///
+	/// * When the template has an unknown placeholder or an unmatched brace
+	fn parse(text: &str) -> Result<SummaryTemplate, ScoreBoardError> {
+		let invalid = || ScoreBoardError::InvalidTemplate(text.to_string());
+
+		let mut parts = Vec::new();
+		let mut literal = String::new();
+		let mut chars = text.chars().peekable();
+
+		while let Some(c) = chars.next() {
+			match c {
+				'{' if chars.peek() == Some(&'{') => {
+					chars.next();
+					literal.push('{');
+				},
+				'}' if chars.peek() == Some(&'}') => {
+					chars.next();
+					literal.push('}');
+				},
+				'{' => {
+					let mut name = String::new();
+					loop {
+						match chars.next() {
+							Some('}') => break,
+							Some(c) => name.push(c),
+							None => return Err(invalid()),
+						}
+					}
+
+					let part = match name.as_str() {
+						"home" => Part::Home,
+						"away" => Part::Away,
+						"home_score" => Part::HomeScore,
+						"away_score" => Part::AwayScore,
+						"minute" => Part::Minute,
+						"clock" => Part::Clock,
+						_ => return Err(invalid()),
+					};
+
+					if !literal.is_empty() {
+						parts.push(Part::Text(mem::take(&mut literal)));
+					}
+					parts.push(part);
+				},
+				'}' => return Err(invalid()),
+				c => literal.push(c),
+			}
+		}
+
+		if !literal.is_empty() {
+			parts.push(Part::Text(literal));
+		}
+
+		Ok(SummaryTemplate { text: text.to_string(), parts })
+	}
+
+	/// Checks if the lines of the template change with the time, so they can't be cached
+	pub(crate) fn is_timed(&self) -> bool {
+		self.parts.iter().any(|part| matches!(part, Part::Minute | Part::Clock))
+	}
+
+	/// Renders the line of a game
+	///
+	/// # Arguments
+	///
+	/// * `game` - the rendered game
+	/// * `now` - the current time, for the minute of the match
+	/// * `half_minutes` - length of a half, in minutes
+	/// * `numerals` - the digits of the scores and the minutes
+	///
+	pub(crate) fn render(&self, game: &Game, now: Instant, half_minutes: u64, numerals: Numerals) -> String {
+		let mut line = String::new();
+
+		for part in &self.parts {
+			match part {
+				Part::Text(text) => line.push_str(text),
+				Part::Home => line.push_str(&game.home_team.name),
+				Part::Away => line.push_str(&game.away_team.name),
+				Part::HomeScore => line.push_str(&numerals.localize(&game.home_team.score.to_string())),
+				Part::AwayScore => line.push_str(&numerals.localize(&game.away_team.score.to_string())),
+				Part::Minute => line.push_str(&numerals.localize(&game.get_minute_number(now, half_minutes).to_string())),
+				Part::Clock => line.push_str(&numerals.localize(&game.get_minute(now, half_minutes))),
+			}
+		}
+
+		line
+	}
+}
+
+impl ScoreBoard {
+	/// Sets the format of the summary lines, used by `get_summary()`, `write_summary()`, `get_summary_sorted()` and `get_summary_grouped()`. The placeholders are replaced with the values of every game:
+	///
+	/// * `{home}` and `{away}` - names of the teams
+	/// * `{home_score}` and `{away_score}` - scores of the teams
+	/// * `{minute}` - minute of the match as a number, ex. "78", counting on through the added time, ex. "48" for "45+3'"
+	/// * `{clock}` - minute of the match as shown on the tickers, ex. "78'" or "45+3'", or "HT" and "FT" in the breaks between the periods, the same as in `get_summary_with_minutes()`
+	///
+	/// `{{` and `}}` stand for the braces themselves. The default format is "{home} {home_score} - {away} {away_score}"
+	///
+	/// # Arguments
+	///
+	/// * `template` - the format of the lines. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Errors
+	///
+	/// * When the template has an unknown placeholder or an unmatched brace. The format of the summary doesn't change then
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Argentina", "Croatia").unwrap();
+	/// sb.update_score("Argentina", 3, "Croatia", 0).unwrap();
+	/// sb.set_summary_template("{home} {home_score}:{away_score} {away} ({minute}')").unwrap();
+	///
+	/// assert_eq!(sb.get_summary(), vec![String::from("Argentina 3:0 Croatia (1')")]);
+	/// assert!(sb.set_summary_template("{home} {score} {away}").is_err());
+	/// ```
+	pub fn set_summary_template<T: ToString>(&mut self, template: T) -> Result<(), ScoreBoardError> {
+		let template = template.to_string();
+
+		trace!("Rendering the summary with the template '{}'", template);
+
+		let template = match SummaryTemplate::parse(&template) {
+			Ok(template) => template,
+			Err(error) => {
+				warn!("Couldn't parse the summary template");
+				return Err(error);
+			},
+		};
+
+		self.summary_template = Some(template);
+		self.invalidate_summary();
+
+		Ok(())
+	}
+
+	/// Brings back the default format of the summary lines, ex. "Japan 2 - Indonesia 0"
+	pub fn clear_summary_template(&mut self) {
+		trace!("Rendering the summary with the default format");
+
+		self.summary_template = None;
+		self.invalidate_summary();
+	}
+
+	/// Provides the format of the summary lines set with `set_summary_template()`, or `None` for the default format
+	pub fn get_summary_template(&self) -> Option<&str> {
+		self.summary_template.as_ref().map(|template| template.text.as_str())
+	}
+
+	/// Renders the summary line of a game, with the template of the board or in the default format, following the locale of the board
+	///
+	/// # Arguments
+	///
+	/// * `game` - the rendered game
+	/// * `now` - the current time, for the minute of the match
+	///
+	pub(crate) fn render_line(&self, game: &Game, now: Instant) -> String {
+		match &self.summary_template {
+			Some(template) => template.render(game, now, self.get_half_minutes(), self.locale.numerals),
+			None if self.locale == Locale::default() => game.to_string(),
+			None => self.locale.render_game(game),
+		}
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::order::SortOrder;
+	use crate::period::Period;
+	use crate::state::GameState;
+
+	fn get_board() -> ScoreBoard {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "Croatia").expect("Couldn't create the first game");
+		sb.start_game("France", "Morocco").expect("Couldn't create the second game");
+		sb.update_score("France", 2, "Morocco", 0).expect("Couldn't update the second game");
+		sb
+	}
+
+	#[test]
+	fn braces_can_be_escaped() {
+		let mut sb = get_board();
+		sb.set_summary_template("{{{home}}} {home_score}").expect("Couldn't set the template");
+
+		assert_eq!(sb.get_summary(), vec![String::from("{France} 2"), String::from("{Argentina} 0")]);
+		assert_eq!(sb.get_summary_template(), Some("{{{home}}} {home_score}"));
+	}
+
+	#[test]
+	fn invalid_template_keeps_the_format() {
+		let mut sb = get_board();
+
+		for template in ["{home", "home}", "{Home}", "{}"] {
+			assert_eq!(sb.set_summary_template(template), Err(ScoreBoardError::InvalidTemplate(String::from(template))));
+		}
+		assert_eq!(sb.get_summary()[0], "France 2 - Morocco 0");
+		assert_eq!(sb.get_summary_template(), None);
+	}
+
+	#[test]
+	fn template_is_used_by_all_the_summaries() {
+		let mut sb = get_board();
+		sb.new_game("Japan", "Spain").group('E').start().expect("Couldn't create the third game");
+		sb.set_summary_template("{away} ({away_score}) @ {home} ({home_score})").expect("Couldn't set the template");
+
+		assert_eq!(sb.get_summary()[0], "Morocco (0) @ France (2)");
+		assert_eq!(sb.get_summary_sorted(SortOrder::Kickoff)[0], "Croatia (0) @ Argentina (0)");
+		assert_eq!(sb.get_summary_grouped()[&Some('E')], vec![String::from("Spain (0) @ Japan (0)")]);
+		let mut output = Vec::new();
+		sb.write_summary(&mut output).expect("Couldn't write the summary");
+		assert!(String::from_utf8(output).expect("Couldn't read the summary").starts_with("Morocco (0) @ France (2)\n"));
+
+		sb.clear_summary_template();
+		assert_eq!(sb.get_summary()[0], "France 2 - Morocco 0");
+	}
+
+	#[test]
+	fn clock_shows_the_breaks() {
+		let mut sb = get_board();
+		sb.set_summary_template("{home} {clock}").expect("Couldn't set the template");
+		sb.set_game_state("France", "Morocco", GameState::Paused).expect("Couldn't pause the game");
+		sb.set_game_state("Argentina", "Croatia", GameState::Paused).expect("Couldn't pause the other game");
+		sb.start_period("France", "Morocco", Period::SecondHalf).expect("Couldn't start the second half");
+
+		assert_eq!(sb.get_summary(), vec![String::from("France HT"), String::from("Argentina 1'")]);
+	}
+}
@@ -0,0 +1,172 @@
+//! # Text helpers
+//!
+//! Escaping of team names and other free text for the markup-based and JSON export formats
+
+use std::string::String;
+use std::vec::Vec;
+
+/// Escapes the characters that have a special meaning in XML, so the text can be used both as an element content and as an attribute value
+///
+/// # Arguments
+///
+/// * `text` - the text to escape
+///
+pub(crate) fn escape_xml(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+
+	for c in text.chars() {
+		match c {
+			'&' => result.push_str("&amp;"),
+			'<' => result.push_str("&lt;"),
+			'>' => result.push_str("&gt;"),
+			'"' => result.push_str("&quot;"),
+			'\'' => result.push_str("&apos;"),
+			_ => result.push(c),
+		}
+	}
+
+	result
+}
+
+/// Escapes the text so it can be used inside a JSON string
+///
+/// # Arguments
+///
+/// * `text` - the text to escape
+///
+pub(crate) fn escape_json(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+
+	for c in text.chars() {
+		match c {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+			_ => result.push(c),
+		}
+	}
+
+	result
+}
+
+/// Calculates the edit distance between two texts: the number of single character insertions, removals and replacements, and swaps of two neighbouring characters, turning one into the other. The case of the letters is ignored
+///
+/// # Arguments
+///
+/// * `a` - the first text
+/// * `b` - the second text
+///
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+	let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+	let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+
+	// distances[i][j] is the distance between the first i characters of a and the first j characters of b
+	let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+	for (i, row) in distances.iter_mut().enumerate() {
+		row[0] = i;
+	}
+	distances[0] = (0..=b.len()).collect();
+
+	for i in 1..=a.len() {
+		for j in 1..=b.len() {
+			let replacement = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+			let mut distance = replacement.min(distances[i - 1][j] + 1).min(distances[i][j - 1] + 1);
+
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				distance = distance.min(distances[i - 2][j - 2] + 1);
+			}
+
+			distances[i][j] = distance;
+		}
+	}
+
+	distances[a.len()][b.len()]
+}
+
+/// Finds the candidate most similar to the text, see `edit_distance()`
+///
+/// # Arguments
+///
+/// * `text` - the text to match, ex. a misspelled name
+/// * `candidates` - the texts to choose from
+///
+/// # Returns
+///
+/// * The most similar candidate, or `None` if none differs by at most a quarter of its letters (or a single letter for the short ones)
+///
+pub(crate) fn find_closest<'a, I: IntoIterator<Item = &'a str>>(text: &str, candidates: I) -> Option<&'a str> {
+	candidates.into_iter()
+		.map(|candidate| (edit_distance(text, candidate), candidate))
+		.filter(|(distance, candidate)| *distance <= (candidate.chars().count() / 4).max(1))
+		.min_by_key(|(distance, _)| *distance)
+		.map(|(_, candidate)| candidate)
+}
+
+/// Builds a stable identifier of a game from the lower case team names, with every character other than a letter or a digit replaced by "-", ex. "south-korea-japan". It is safe to use in MQTT topics and as a message key, as it never contains wildcards or separators
+///
+/// # Arguments
+///
+/// * `home` - name of the home team
+/// * `away` - name of the away team
+///
+#[cfg_attr(not(any(feature = "mqtt", feature = "kafka")), allow(dead_code))]
+pub(crate) fn get_game_id(home: &str, away: &str) -> String {
+	format!("{}-{}", home, away)
+		.chars()
+		.map(|c| if c.is_alphanumeric() { c.to_lowercase().next().unwrap_or(c) } else { '-' })
+		.collect()
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn plain_text_is_not_changed() {
+		assert_eq!(escape_xml("Bosnia and Herzegovina"), "Bosnia and Herzegovina");
+	}
+
+	#[test]
+	fn special_characters_are_escaped() {
+		assert_eq!(escape_xml("Trinidad & Tobago <\"'>"), "Trinidad &amp; Tobago &lt;&quot;&apos;&gt;");
+	}
+
+	#[test]
+	fn json_special_characters_are_escaped() {
+		assert_eq!(escape_json("C\u{f4}te d'Ivoire \"A\\B\"\n\u{1}"), "C\u{f4}te d'Ivoire \\\"A\\\\B\\\"\\n\\u0001");
+	}
+
+	#[test]
+	fn edit_distance_counts_the_changes() {
+		assert_eq!(edit_distance("Brazil", "Brasil"), 1);
+		assert_eq!(edit_distance("spain", "SPAIN"), 0);
+		assert_eq!(edit_distance("Gremany", "Germany"), 1);
+		assert_eq!(edit_distance("Algeria", "Nigeria"), 2);
+		assert_eq!(edit_distance("", "Chad"), 4);
+		assert_eq!(edit_distance("Chile", "China"), 2);
+	}
+
+	#[test]
+	fn closest_candidate_is_found() {
+		let teams = ["Spain", "Brazil", "Germany"];
+
+		assert_eq!(find_closest("Spian", teams), Some("Spain"));
+		assert_eq!(find_closest("brasil", teams), Some("Brazil"));
+		assert_eq!(find_closest("Japan", teams), None);
+		assert_eq!(find_closest("Spain", []), None);
+	}
+
+	#[test]
+	fn game_id_has_no_separators() {
+		assert_eq!(get_game_id("South Korea", "Japan"), "south-korea-japan");
+		assert_eq!(get_game_id("Bosnia/Herzegovina", "Côte d'Ivoire"), "bosnia-herzegovina-côte-d-ivoire");
+		assert_eq!(get_game_id("#1", "+2"), "-1--2");
+	}
+}
@@ -0,0 +1,254 @@
+//! # Two-legged ties
+//!
+//! Links the two games of a knockout round played home and away, and resolves the winner on aggregate, away goals and penalties
+
+use std::string::{String, ToString};
+
+use crate::archive::Decision;
+use crate::ScoreBoard;
+
+/// The way the winner of a tie was decided
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieResolution {
+	/// More goals scored over both legs
+	Aggregate,
+	/// Equal aggregate score, more goals scored away from home
+	AwayGoals,
+	/// Penalty shootout at the end of the second leg
+	Penalties,
+}
+
+/// Score of a single leg of a tie
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Leg {
+	home_score: u8,
+	away_score: u8,
+	/// The way the leg was decided, `None` while it's still played
+	decision: Option<Decision>,
+}
+
+/// A knockout round of two games between the same teams, each of them hosting one. Returned by `ScoreBoard::get_tie()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tie {
+	first_team: String,
+	second_team: String,
+	away_goals: bool,
+	/// The game hosted by the first team, if it has started
+	first_leg: Option<Leg>,
+	/// The game hosted by the second team, if it has started
+	second_leg: Option<Leg>,
+}
+
+impl Tie {
+	/// Name of the team hosting the first leg
+	pub fn first_team(&self) -> &str {
+		&self.first_team
+	}
+
+	/// Name of the team hosting the second leg
+	pub fn second_team(&self) -> &str {
+		&self.second_team
+	}
+
+	/// Tells if both legs are finished
+	pub fn is_complete(&self) -> bool {
+		self.first_leg.is_some_and(|leg| leg.decision.is_some()) && self.second_leg.is_some_and(|leg| leg.decision.is_some())
+	}
+
+	/// Provides the goals scored by both teams over the legs played so far, including the one in progress
+	///
+	/// # Returns
+	///
+	/// * The goals of the first and the second team
+	pub fn aggregate_score(&self) -> (u16, u16) {
+		let (first_home, second_away) = self.first_leg.map_or((0, 0), |leg| (leg.home_score as u16, leg.away_score as u16));
+		let (second_home, first_away) = self.second_leg.map_or((0, 0), |leg| (leg.home_score as u16, leg.away_score as u16));
+
+		(first_home + first_away, second_home + second_away)
+	}
+
+	/// Provides the name of the team going through, once both legs are finished
+	///
+	/// # Returns
+	///
+	/// * Name of the winner, or `None` if the tie isn't complete or it's level after all the tiebreakers
+	pub fn winner(&self) -> Option<&str> {
+		self.resolve().map(|(first_wins, _)| if first_wins { self.first_team.as_str() } else { self.second_team.as_str() })
+	}
+
+	/// Provides the way the winner was decided, once both legs are finished
+	///
+	/// # Returns
+	///
+	/// * The deciding tiebreaker, or `None` if there is no winner yet
+	pub fn decided_by(&self) -> Option<TieResolution> {
+		self.resolve().map(|(_, resolution)| resolution)
+	}
+
+	/// Applies the tiebreakers in order: aggregate score, away goals (if enabled) and the penalty shootout of the second leg
+	///
+	/// # Returns
+	///
+	/// * Whether the first team wins, with the deciding tiebreaker
+	fn resolve(&self) -> Option<(bool, TieResolution)> {
+		if !self.is_complete() {
+			return None;
+		}
+
+		let first_leg = self.first_leg?;
+		let second_leg = self.second_leg?;
+
+		let (first, second) = self.aggregate_score();
+		if first != second {
+			return Some((first > second, TieResolution::Aggregate));
+		}
+
+		if self.away_goals && first_leg.away_score != second_leg.away_score {
+			return Some((second_leg.away_score > first_leg.away_score, TieResolution::AwayGoals));
+		}
+
+		match second_leg.decision {
+			Some(Decision::AfterPenalties { home, away }) if home != away => Some((away > home, TieResolution::Penalties)),
+			_ => None,
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Links the two legs of a knockout tie: the game hosted by the first team and the one hosted by the second team. The legs are looked up among the live games and, for the finished ones, in the archive (the most recent result counts)
+	///
+	/// # Arguments
+	///
+	/// * `first_team` - Name of the team hosting the first leg
+	/// * `second_team` - Name of the team hosting the second leg
+	/// * `away_goals` - Whether the away goals rule breaks a level aggregate score, before the penalties
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Decision, TieResolution};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Ajax", "Porto").unwrap();
+	/// sb.update_score("Ajax", 2, "Porto", 1).unwrap();
+	/// sb.finish_game("Ajax", "Porto").unwrap();
+	/// sb.start_game("Porto", "Ajax").unwrap();
+	/// sb.update_score("Porto", 1, "Ajax", 0).unwrap();
+	/// sb.finish_game_after("Porto", "Ajax", Decision::AfterPenalties { home: 5, away: 4 }).unwrap();
+	///
+	/// let tie = sb.get_tie("Ajax", "Porto", false);
+	/// assert_eq!(tie.aggregate_score(), (2, 2));
+	/// assert_eq!(tie.winner(), Some("Porto"));
+	/// assert_eq!(tie.decided_by(), Some(TieResolution::Penalties));
+	/// ```
+	pub fn get_tie<T: ToString, U: ToString>(&self, first_team: T, second_team: U, away_goals: bool) -> Tie {
+		let first_team = self.resolve_team(first_team);
+		let second_team = self.resolve_team(second_team);
+
+		Tie {
+			first_leg: self.find_leg(&first_team, &second_team),
+			second_leg: self.find_leg(&second_team, &first_team),
+			first_team,
+			second_team,
+			away_goals,
+		}
+	}
+
+	/// Finds the score of a game between the teams, live or archived
+	fn find_leg(&self, home: &str, away: &str) -> Option<Leg> {
+		let live = self.data.iter()
+			.find(|game| game.home_team.name == home && game.away_team.name == away)
+			.map(|game| Leg { home_score: game.home_team.score, away_score: game.away_team.score, decision: None });
+
+		live.or_else(|| self.archive.iter()
+			.rev()
+			.find(|game| game.home_team == home && game.away_team == away)
+			.map(|game| Leg { home_score: game.home_score, away_score: game.away_score, decision: Some(game.decision) }))
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn play(sb: &mut ScoreBoard, home: &str, home_score: u8, away: &str, away_score: u8, decision: Decision) {
+		sb.start_game(home, away).expect("Couldn't create the game");
+		sb.update_score(home, home_score, away, away_score).expect("Couldn't update the game");
+		sb.finish_game_after(home, away, decision).expect("Couldn't finish the game");
+	}
+
+	#[test]
+	fn aggregate_decides() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Ajax", 3, "Porto", 0, Decision::RegularTime);
+		play(&mut sb, "Porto", 2, "Ajax", 1, Decision::RegularTime);
+
+		let tie = sb.get_tie("Ajax", "Porto", true);
+		assert_eq!(tie.aggregate_score(), (4, 2));
+		assert_eq!(tie.winner(), Some("Ajax"));
+		assert_eq!(tie.decided_by(), Some(TieResolution::Aggregate));
+	}
+
+	#[test]
+	fn away_goals_decide_when_enabled() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Ajax", 2, "Porto", 1, Decision::RegularTime);
+		play(&mut sb, "Porto", 1, "Ajax", 0, Decision::RegularTime);
+
+		let tie = sb.get_tie("Ajax", "Porto", true);
+		assert_eq!(tie.aggregate_score(), (2, 2));
+		assert_eq!(tie.winner(), Some("Porto"));
+		assert_eq!(tie.decided_by(), Some(TieResolution::AwayGoals));
+
+		assert_eq!(sb.get_tie("Ajax", "Porto", false).winner(), None);
+	}
+
+	#[test]
+	fn penalties_decide_a_level_tie() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Ajax", 1, "Porto", 1, Decision::RegularTime);
+		play(&mut sb, "Porto", 1, "Ajax", 1, Decision::AfterPenalties { home: 2, away: 4 });
+
+		let tie = sb.get_tie("Ajax", "Porto", true);
+		assert_eq!(tie.winner(), Some("Ajax"));
+		assert_eq!(tie.decided_by(), Some(TieResolution::Penalties));
+	}
+
+	#[test]
+	fn incomplete_tie_has_no_winner() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Ajax", 3, "Porto", 0, Decision::RegularTime);
+		sb.start_game("Porto", "Ajax").expect("Couldn't create the second leg");
+		sb.update_score("Porto", 1, "Ajax", 0).expect("Couldn't update the second leg");
+
+		let tie = sb.get_tie("Ajax", "Porto", false);
+		assert!(!tie.is_complete());
+		assert_eq!(tie.aggregate_score(), (3, 1));
+		assert_eq!(tie.winner(), None);
+		assert_eq!(tie.decided_by(), None);
+	}
+
+	#[test]
+	fn missing_legs_count_as_nothing() {
+		let sb = ScoreBoard::new();
+		let tie = sb.get_tie("Ajax", "Porto", false);
+
+		assert_eq!(tie.first_team(), "Ajax");
+		assert_eq!(tie.second_team(), "Porto");
+		assert_eq!(tie.aggregate_score(), (0, 0));
+		assert!(!tie.is_complete());
+	}
+
+	#[test]
+	fn most_recent_result_counts() {
+		let mut sb = ScoreBoard::new();
+		play(&mut sb, "Ajax", 0, "Porto", 4, Decision::RegularTime);
+		play(&mut sb, "Ajax", 1, "Porto", 0, Decision::RegularTime);
+
+		assert_eq!(sb.get_tie("Ajax", "Porto", false).aggregate_score(), (1, 0));
+	}
+}
@@ -0,0 +1,232 @@
+//! # Game timeline
+//!
+//! Presents the recorded events and the notes of a single game as a chronological feed, ex. for the page of the match
+
+use std::string::{String, ToString};
+use std::time::SystemTime;
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::events::ScoreBoardEvent;
+use crate::goal::Goal;
+use crate::notes::GameNote;
+use crate::period::Period;
+use crate::state::GameState;
+use crate::ScoreBoard;
+
+/// What happened in a game
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TimelineKind {
+	/// The game has started
+	Kickoff,
+	/// The score has changed, with the goal that changed it, if it was recorded with `ScoreBoard::record_goal()`
+	ScoreChange(Option<Goal>),
+	/// The game was paused
+	Paused,
+	/// The paused game was resumed
+	Resumed,
+	/// The game has finished
+	Finished,
+	/// The game was abandoned
+	Abandoned,
+	/// The fixture was postponed
+	Postponed,
+	/// The finished game was reopened with `ScoreBoard::reopen_game()`
+	Reopened,
+	/// The home and the away team were swapped with `ScoreBoard::swap_sides()`
+	SidesSwapped,
+	/// The game moved to the period with `ScoreBoard::start_period()`
+	PeriodStarted(Period),
+	/// A note of a commentator, added with `ScoreBoard::add_note()`
+	Note(String),
+}
+
+/// A single entry of the timeline of a game
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimelineEntry {
+	/// Wall clock time of the change
+	pub time: SystemTime,
+	/// What happened
+	pub kind: TimelineKind,
+	/// Score of the home team after the change, or at the time of the note
+	pub home_score: u8,
+	/// Score of the away team after the change, or at the time of the note
+	pub away_score: u8,
+}
+
+impl TimelineEntry {
+	/// Builds the entry of a recorded event
+	///
+	/// # Arguments
+	///
+	/// * `time` - time of the event
+	/// * `event` - the recorded change
+	///
+	/// # Returns
+	///
+	/// * The entry, or `None` for a renamed team, which isn't a change of the game
+	///
+	fn from_event(time: SystemTime, event: &ScoreBoardEvent) -> Option<TimelineEntry> {
+		let (kind, home_score, away_score) = match event {
+			ScoreBoardEvent::GameStarted { .. } => (TimelineKind::Kickoff, 0, 0),
+			ScoreBoardEvent::ScoreUpdated { home_score, away_score, goal, .. } => (TimelineKind::ScoreChange(goal.clone()), *home_score, *away_score),
+			ScoreBoardEvent::GameFinished { home_score, away_score, .. }
+			| ScoreBoardEvent::ResultFinalized { home_score, away_score, .. } => (TimelineKind::Finished, *home_score, *away_score),
+			ScoreBoardEvent::StateChanged { home_score, away_score, state, .. } => {
+				let kind = match state {
+					GameState::Paused => TimelineKind::Paused,
+					GameState::Finished => TimelineKind::Finished,
+					GameState::Abandoned => TimelineKind::Abandoned,
+					GameState::Postponed => TimelineKind::Postponed,
+					GameState::Live | GameState::Scheduled => TimelineKind::Resumed,
+				};
+				(kind, *home_score, *away_score)
+			},
+			ScoreBoardEvent::GameReopened { home_score, away_score, .. } => (TimelineKind::Reopened, *home_score, *away_score),
+			ScoreBoardEvent::SidesSwapped { home_score, away_score, .. } => (TimelineKind::SidesSwapped, *home_score, *away_score),
+			ScoreBoardEvent::PeriodStarted { home_score, away_score, period, .. } => (TimelineKind::PeriodStarted(*period), *home_score, *away_score),
+			ScoreBoardEvent::TeamRenamed { .. } => return None,
+		};
+
+		Some(TimelineEntry { time, kind, home_score, away_score })
+	}
+
+	/// Builds the entry of a note
+	///
+	/// # Arguments
+	///
+	/// * `note` - the note
+	/// * `previous` - the entry before the note, with the score at the time of the note
+	///
+	fn from_note(note: &GameNote, previous: Option<&TimelineEntry>) -> TimelineEntry {
+		let (home_score, away_score) = previous.map_or((0, 0), |entry| (entry.home_score, entry.away_score));
+
+		TimelineEntry { time: note.time, kind: TimelineKind::Note(note.text.clone()), home_score, away_score }
+	}
+}
+
+impl ScoreBoard {
+	/// Provides the timeline of the latest game between two teams: the kickoff, every score change, the pauses and resumptions, the notes of the commentators and the end, with their times, the oldest coming first. The game may be running or already over. Earlier games between the same teams are left out, see `get_audit_log()` for all of them
+	///
+	/// Score updates merged with `DebounceMode::Coalesce` show up as a single change
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team. Must be either a `String` or a type that is convertable to `String`
+	/// * `away` - Name of the away team. Must be either a `String` or a type that is convertable to `String`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{GameState, TimelineKind};
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// sb.set_game_state("Japan", "Indonesia", GameState::Paused).unwrap();
+	/// sb.set_game_state("Japan", "Indonesia", GameState::Live).unwrap();
+	/// sb.finish_game("Japan", "Indonesia").unwrap();
+	///
+	/// let kinds: Vec<TimelineKind> = sb.get_timeline("Japan", "Indonesia").into_iter().map(|entry| entry.kind).collect();
+	/// assert_eq!(kinds, vec![TimelineKind::Kickoff, TimelineKind::ScoreChange(None), TimelineKind::Paused, TimelineKind::Resumed, TimelineKind::Finished]);
+	/// ```
+	pub fn get_timeline<T: ToString, U: ToString>(&self, home: T, away: U) -> Vec<TimelineEntry> {
+		let home_name = self.resolve_team(home);
+		let away_name = self.resolve_team(away);
+
+		trace!("Getting the timeline of the game between '{}' and '{}'", home_name, away_name);
+
+		let records: Vec<_> = self.events.iter()
+			.filter(|record| record.event.get_teams() == (home_name.as_str(), away_name.as_str()))
+			.collect();
+		let kickoff = records.iter()
+			.rposition(|record| matches!(record.event, ScoreBoardEvent::GameStarted { .. }))
+			.unwrap_or(0);
+		let records = &records[kickoff..];
+
+		let first_sequence = records.first().map_or(u64::MAX, |record| record.sequence);
+		let mut notes = self.notes.iter()
+			.filter(|note| note.home == home_name && note.away == away_name && note.after_sequence >= first_sequence)
+			.peekable();
+
+		let mut timeline: Vec<TimelineEntry> = Vec::new();
+		// The final result repeats the finish of the game
+		for record in records.iter().filter(|record| !matches!(record.event, ScoreBoardEvent::ResultFinalized { .. })) {
+			while let Some(note) = notes.next_if(|note| note.after_sequence < record.sequence) {
+				timeline.push(TimelineEntry::from_note(note, timeline.last()));
+			}
+			timeline.extend(TimelineEntry::from_event(record.time, &record.event));
+		}
+		for note in notes {
+			timeline.push(TimelineEntry::from_note(note, timeline.last()));
+		}
+
+		timeline
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::goal::{GoalKind, Side};
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	#[test]
+	fn unknown_game_has_empty_timeline() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert!(sb.get_timeline(AWAY_TEAM_NAME, HOME_TEAM_NAME).is_empty());
+		assert!(sb.get_timeline("Mexico", "Canada").is_empty());
+	}
+
+	#[test]
+	fn timeline_follows_the_score() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the other game");
+		sb.record_goal(HOME_TEAM_NAME, AWAY_TEAM_NAME, Side::Away, GoalKind::Penalty).expect("Couldn't record the goal");
+		sb.update_score("Mexico", 1, "Canada", 0).expect("Couldn't update the other game");
+		sb.update_score(HOME_TEAM_NAME, 1, AWAY_TEAM_NAME, 1).expect("Couldn't update the game");
+		let timeline = sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME);
+
+		let scores: Vec<(TimelineKind, u8, u8)> = timeline.iter().map(|entry| (entry.kind.clone(), entry.home_score, entry.away_score)).collect();
+		assert_eq!(scores, vec![
+			(TimelineKind::Kickoff, 0, 0),
+			(TimelineKind::ScoreChange(Some(Goal { side: Side::Away, kind: GoalKind::Penalty, scorer: None })), 0, 1),
+			(TimelineKind::ScoreChange(None), 1, 1),
+		]);
+		assert!(timeline.windows(2).all(|pair| pair[0].time <= pair[1].time));
+	}
+
+	#[test]
+	fn only_the_latest_game_is_shown() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the first game");
+		sb.update_score(HOME_TEAM_NAME, 3, AWAY_TEAM_NAME, 0).expect("Couldn't update the first game");
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Abandoned).expect("Couldn't abandon the first game");
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the second game");
+		sb.finish_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't finish the second game");
+
+		let kinds: Vec<TimelineKind> = sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).into_iter().map(|entry| entry.kind).collect();
+		assert_eq!(kinds, vec![TimelineKind::Kickoff, TimelineKind::Finished]);
+	}
+
+	#[test]
+	fn abandoned_fixture_has_a_single_entry() {
+		let mut sb = ScoreBoard::new();
+		sb.schedule_game(HOME_TEAM_NAME, AWAY_TEAM_NAME, SystemTime::now()).expect("Couldn't schedule the game");
+		sb.set_game_state(HOME_TEAM_NAME, AWAY_TEAM_NAME, GameState::Abandoned).expect("Couldn't abandon the game");
+
+		let kinds: Vec<TimelineKind> = sb.get_timeline(HOME_TEAM_NAME, AWAY_TEAM_NAME).into_iter().map(|entry| entry.kind).collect();
+		assert_eq!(kinds, vec![TimelineKind::Abandoned]);
+	}
+}
@@ -0,0 +1,330 @@
+//! # Time zones of the kickoffs
+//!
+//! Keeps the UTC offset of the stadium with every scheduled kickoff, and lists the schedule in the local times of the stadiums or in any requested zone, since the match days span venues in several time zones
+//!
+//! The zones are kept as fixed offsets. With the `time-zones` feature, the named IANA zones, ex. "Europe/Madrid", are looked up in the `chrono-tz` database, with the offset in force at every kickoff, so the clock changes of the daylight saving time are followed
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::string::String;
+use std::time::{Duration, SystemTime};
+use std::vec::Vec;
+
+#[cfg(feature = "time-zones")]
+use chrono::{DateTime, Offset, TimeZone};
+#[cfg(feature = "time-zones")]
+use chrono_tz::Tz;
+use log::{trace, warn};
+
+use crate::datetime::UtcDateTime;
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// The largest offset from UTC used anywhere, in minutes
+const MAX_OFFSET_MINUTES: u32 = 18 * 60;
+
+/// A fixed offset of a time zone from UTC, ex. +03:00 for Doha. It isn't a named IANA zone and doesn't follow the daylight saving time, so the offset of a stadium has to be the one in force on the match day, ex. +02:00 for Madrid in the summer and +01:00 in the winter. With the `time-zones` feature, `UtcOffset::in_zone()` finds it from the name of the zone
+///
+/// # Examples
+///
+/// ```
+/// use scoreboard_world_cup::UtcOffset;
+///
+/// let doha = UtcOffset::from_hours(3).unwrap();
+/// assert_eq!(doha.to_string(), "+03:00");
+/// assert_eq!(UtcOffset::from_minutes(-150).unwrap().to_string(), "-02:30");
+/// assert_eq!(UtcOffset::UTC.to_string(), "Z");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UtcOffset {
+	/// Minutes ahead of UTC, negative for the zones behind it
+	minutes: i32,
+}
+
+impl UtcOffset {
+	/// The UTC time itself
+	pub const UTC: UtcOffset = UtcOffset { minutes: 0 };
+
+	/// Creates an offset of whole hours
+	///
+	/// # Arguments
+	///
+	/// * `hours` - hours ahead of UTC, negative for the zones behind it
+	///
+	/// # Errors
+	///
+	/// * When the offset is over 18 hours either way
+	pub fn from_hours(hours: i32) -> Result<UtcOffset, ScoreBoardError> {
+		UtcOffset::from_minutes(hours.saturating_mul(60))
+	}
+
+	/// Creates an offset of minutes, for the zones like +05:30
+	///
+	/// # Arguments
+	///
+	/// * `minutes` - minutes ahead of UTC, negative for the zones behind it
+	///
+	/// # Errors
+	///
+	/// * When the offset is over 18 hours either way
+	pub fn from_minutes(minutes: i32) -> Result<UtcOffset, ScoreBoardError> {
+		if minutes.unsigned_abs() > MAX_OFFSET_MINUTES {
+			warn!("UTC offset of {} minutes is out of range", minutes);
+			return Err(ScoreBoardError::InvalidUtcOffset(minutes));
+		}
+
+		Ok(UtcOffset { minutes })
+	}
+
+	/// Provides the offset of a named IANA zone in force at a moment, following the daylight saving time of the zone
+	///
+	/// # Arguments
+	///
+	/// * `zone` - name of the zone, ex. "Europe/Madrid"
+	/// * `time` - the moment, ex. the kickoff
+	///
+	/// # Errors
+	///
+	/// * When the zone isn't in the `chrono-tz` database
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, UNIX_EPOCH};
+	///
+	/// use scoreboard_world_cup::UtcOffset;
+	///
+	/// let final_kickoff = UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+	/// let summer_kickoff = final_kickoff - Duration::from_secs(180 * 24 * 3600);
+	/// assert_eq!(UtcOffset::in_zone("Europe/Madrid", final_kickoff).unwrap().to_string(), "+01:00");
+	/// assert_eq!(UtcOffset::in_zone("Europe/Madrid", summer_kickoff).unwrap().to_string(), "+02:00");
+	/// assert!(UtcOffset::in_zone("Europe/Atlantis", final_kickoff).is_err());
+	/// ```
+	#[cfg(feature = "time-zones")]
+	pub fn in_zone(zone: &str, time: SystemTime) -> Result<UtcOffset, ScoreBoardError> {
+		Ok(UtcOffset::at(parse_zone(zone)?, time))
+	}
+
+	/// Provides the offset of a zone in force at a moment, or UTC for the moments out of the range of the calendar
+	///
+	/// # Arguments
+	///
+	/// * `zone` - the zone
+	/// * `time` - the moment
+	///
+	#[cfg(feature = "time-zones")]
+	fn at(zone: Tz, time: SystemTime) -> UtcOffset {
+		let seconds = match time.duration_since(SystemTime::UNIX_EPOCH) {
+			Ok(since) => i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+			Err(before) => i64::try_from(before.duration().as_secs()).map_or(i64::MIN, |seconds| -seconds),
+		};
+
+		DateTime::from_timestamp(seconds, 0).map_or(UtcOffset::UTC, |utc| {
+			UtcOffset { minutes: zone.offset_from_utc_datetime(&utc.naive_utc()).fix().local_minus_utc() / 60 }
+		})
+	}
+
+	/// Provides the minutes ahead of UTC, negative for the zones behind it
+	pub fn get_minutes(&self) -> i32 {
+		self.minutes
+	}
+
+	/// Provides the local time of the zone at a wall clock timestamp, as a calendar date
+	///
+	/// # Arguments
+	///
+	/// * `time` - the timestamp
+	///
+	fn to_local(self, time: SystemTime) -> UtcDateTime {
+		let shift = Duration::from_secs(u64::from(self.minutes.unsigned_abs()) * 60);
+		let local = match self.minutes.cmp(&0) {
+			Ordering::Greater => time.checked_add(shift),
+			Ordering::Less => time.checked_sub(shift),
+			Ordering::Equal => Some(time),
+		};
+
+		UtcDateTime::from_system_time(local.unwrap_or(time))
+	}
+
+	/// Formats a timestamp as the local date and time of the zone, with the offset, ex. "2022-12-18 18:00 +03:00"
+	///
+	/// # Arguments
+	///
+	/// * `time` - the timestamp
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, UNIX_EPOCH};
+	///
+	/// use scoreboard_world_cup::UtcOffset;
+	///
+	/// let final_kickoff = UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+	/// assert_eq!(UtcOffset::from_hours(-5).unwrap().format(final_kickoff), "2022-12-18 10:00 -05:00");
+	/// ```
+	pub fn format(&self, time: SystemTime) -> String {
+		let local = self.to_local(time);
+
+		format!("{:04}-{:02}-{:02} {:02}:{:02} {}", local.year, local.month, local.day, local.hour, local.minute, self)
+	}
+}
+
+impl fmt::Display for UtcOffset {
+	/// Implementation of `Display` trait, allowing it to be converted to a String, the way RFC 3339 shows it: "Z" for UTC, and ex. "+05:30" for the others
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.minutes == 0 {
+			return write!(f, "Z");
+		}
+
+		let sign = if self.minutes < 0 { '-' } else { '+' };
+		let minutes = self.minutes.unsigned_abs();
+		write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+	}
+}
+
+impl ScoreBoard {
+	/// Lists the scheduled games in the order of their kickoffs, every kickoff in the local time of its stadium, ex. "2022-12-18 18:00 +03:00 Argentina - France". The time zone of a fixture is set with `GameBuilder::utc_offset()`, and is UTC otherwise
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::{Duration, UNIX_EPOCH};
+	///
+	/// use scoreboard_world_cup::UtcOffset;
+	///
+	/// let kickoff = UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.new_game("Argentina", "France").utc_offset(UtcOffset::from_hours(3).unwrap()).schedule(kickoff).unwrap();
+	/// sb.schedule_game("Croatia", "Morocco", kickoff - Duration::from_secs(24 * 3600)).unwrap();
+	///
+	/// assert_eq!(sb.get_schedule(), vec![String::from("2022-12-17 15:00 Z Croatia - Morocco"), String::from("2022-12-18 18:00 +03:00 Argentina - France")]);
+	/// assert_eq!(sb.get_schedule_in(UtcOffset::from_hours(1).unwrap())[1], "2022-12-18 16:00 +01:00 Argentina - France");
+	/// ```
+	pub fn get_schedule(&self) -> Vec<String> {
+		trace!("Listing the schedule in the local times");
+
+		self.list_schedule(|fixture_offset, _| fixture_offset)
+	}
+
+	/// Lists the scheduled games in the order of their kickoffs, every kickoff in the given time zone, see `get_schedule()`
+	///
+	/// # Arguments
+	///
+	/// * `offset` - the time zone of the kickoffs, ex. the one of the viewers
+	pub fn get_schedule_in(&self, offset: UtcOffset) -> Vec<String> {
+		trace!("Listing the schedule at {}", offset);
+
+		self.list_schedule(|_, _| offset)
+	}
+
+	/// Lists the scheduled games in the order of their kickoffs, every kickoff in a named IANA zone with the offset in force at the kickoff, see `get_schedule()`
+	///
+	/// # Arguments
+	///
+	/// * `zone` - name of the zone, ex. "America/New_York" for the viewers on the east coast
+	///
+	/// # Errors
+	///
+	/// * When the zone isn't in the `chrono-tz` database
+	#[cfg(feature = "time-zones")]
+	pub fn get_schedule_in_zone(&self, zone: &str) -> Result<Vec<String>, ScoreBoardError> {
+		trace!("Listing the schedule in {}", zone);
+
+		let zone = parse_zone(zone)?;
+		Ok(self.list_schedule(|_, kickoff| UtcOffset::at(zone, kickoff)))
+	}
+
+	/// Lists the scheduled games in the order of their kickoffs
+	///
+	/// # Arguments
+	///
+	/// * `get_offset` - provides the time zone of a kickoff from the time zone of its stadium and the kickoff
+	///
+	fn list_schedule<F: Fn(UtcOffset, SystemTime) -> UtcOffset>(&self, get_offset: F) -> Vec<String> {
+		let mut fixtures: Vec<_> = self.schedule.iter().collect();
+		fixtures.sort_by_key(|fixture| fixture.kickoff);
+
+		fixtures.into_iter()
+			.map(|fixture| format!("{} {} - {}", get_offset(fixture.utc_offset, fixture.kickoff).format(fixture.kickoff), fixture.home_name, fixture.away_name))
+			.collect()
+	}
+}
+
+/// Finds a named IANA zone in the `chrono-tz` database
+///
+/// # Arguments
+///
+/// * `zone` - name of the zone, ex. "Europe/Madrid"
+///
+/// # Errors
+///
+/// * When the database doesn't have the zone
+#[cfg(feature = "time-zones")]
+fn parse_zone(zone: &str) -> Result<Tz, ScoreBoardError> {
+	zone.parse().map_err(|_| {
+		warn!("Couldn't find the time zone {}", zone);
+		ScoreBoardError::UnknownTimeZone(String::from(zone))
+	})
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::UNIX_EPOCH;
+
+	/// Kickoff of the 2022 final, 15:00 UTC
+	fn final_kickoff() -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(1_671_375_600)
+	}
+
+	#[test]
+	fn offsets_are_limited_to_18_hours() {
+		assert!(UtcOffset::from_hours(14).is_ok());
+		assert_eq!(UtcOffset::from_minutes(-18 * 60).map(|offset| offset.get_minutes()), Ok(-1080));
+		assert_eq!(UtcOffset::from_hours(19), Err(ScoreBoardError::InvalidUtcOffset(1140)));
+		assert_eq!(UtcOffset::from_minutes(i32::MIN).map_err(|error| error.to_string()), Err(format!("UTC offset of {} minutes is out of range", i32::MIN)));
+	}
+
+	#[test]
+	fn local_time_can_fall_on_another_day() {
+		let auckland = UtcOffset::from_minutes(13 * 60 + 45).expect("Couldn't create the offset");
+		let honolulu = UtcOffset::from_hours(-10).expect("Couldn't create the offset");
+
+		assert_eq!(auckland.format(final_kickoff()), "2022-12-19 04:45 +13:45");
+		assert_eq!(honolulu.format(final_kickoff() - Duration::from_secs(10 * 3600)), "2022-12-17 19:00 -10:00");
+		assert_eq!(UtcOffset::UTC.format(final_kickoff()), "2022-12-18 15:00 Z");
+	}
+
+	#[test]
+	fn schedule_follows_the_kickoffs_across_the_zones() {
+		let mut sb = ScoreBoard::new();
+		sb.new_game("USA", "Mexico").utc_offset(UtcOffset::from_hours(-6).expect("Couldn't create the offset"))
+			.schedule(final_kickoff() + Duration::from_secs(4 * 3600)).expect("Couldn't schedule the first game");
+		sb.new_game("Japan", "Spain").utc_offset(UtcOffset::from_hours(3).expect("Couldn't create the offset"))
+			.schedule(final_kickoff()).expect("Couldn't schedule the second game");
+
+		assert_eq!(sb.get_schedule(), vec![String::from("2022-12-18 18:00 +03:00 Japan - Spain"), String::from("2022-12-18 13:00 -06:00 USA - Mexico")]);
+		assert_eq!(sb.get_schedule_in(UtcOffset::UTC), vec![String::from("2022-12-18 15:00 Z Japan - Spain"), String::from("2022-12-18 19:00 Z USA - Mexico")]);
+	}
+
+	#[cfg(feature = "time-zones")]
+	#[test]
+	fn named_zone_follows_the_clock_change() {
+		let mut sb = ScoreBoard::new();
+		let madrid_summer = final_kickoff() - Duration::from_secs(180 * 24 * 3600);
+		sb.new_game("Spain", "Portugal").utc_offset(UtcOffset::in_zone("Europe/Madrid", madrid_summer).expect("Couldn't find the zone"))
+			.schedule(madrid_summer).expect("Couldn't schedule the first game");
+		sb.schedule_game("USA", "Canada", final_kickoff()).expect("Couldn't schedule the second game");
+
+		assert_eq!(sb.get_schedule()[0], "2022-06-21 17:00 +02:00 Spain - Portugal");
+		assert_eq!(sb.get_schedule_in_zone("America/New_York"), Ok(vec![String::from("2022-06-21 11:00 -04:00 Spain - Portugal"), String::from("2022-12-18 10:00 -05:00 USA - Canada")]));
+		assert_eq!(sb.get_schedule_in_zone("Mars/Olympus_Mons"), Err(ScoreBoardError::UnknownTimeZone(String::from("Mars/Olympus_Mons"))));
+	}
+}
@@ -0,0 +1,372 @@
+//! # Transactions
+//!
+//! Stages score board changes on the board itself, keeping a savepoint of the running games, so they can be previewed and then either committed or rolled back
+
+use std::collections::HashMap;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::archive::ArchivedGame;
+use crate::error::ScoreBoardError;
+use crate::events::EventRecord;
+use crate::order::RankKey;
+use crate::{Fixture, Game, ScoreBoard};
+
+/// A set of changes staged on top of a score board. The changes are invisible on the board until `commit()` is called. Dropping the transaction without committing rolls the changes back
+pub struct Transaction<'a> {
+	/// The board the changes are staged on
+	board: &'a mut ScoreBoard,
+}
+
+/// The state of a score board from before its staged changes. Only the running games and the fixtures are copied, the histories only have their length noted, since the staged changes are appended to them
+#[derive(Clone)]
+pub(crate) struct Savepoint {
+	/// The running games
+	data: Vec<Game>,
+	/// The index of the running games
+	team_index: HashMap<String, RankKey>,
+	/// The scheduled fixtures
+	schedule: Vec<Fixture>,
+	/// Sequence number of the last event
+	sequence: u64,
+	/// Number of the recorded events
+	event_count: usize,
+	/// The last recorded event, as a staged score update may be coalesced into it
+	last_event: Option<EventRecord>,
+	/// Staged changes of the archive, the earliest coming first
+	archive_changes: Vec<ArchiveChange>,
+	/// Number of the called off games
+	called_off_count: usize,
+	/// Lines of the staged changes for the journal, appended when the changes are applied
+	journal_lines: String,
+}
+
+/// A staged change of the archive
+#[derive(Clone)]
+pub(crate) enum ArchiveChange {
+	/// A result was added at the end
+	Added,
+	/// A result was taken from the given position
+	Removed(usize, Box<ArchivedGame>),
+}
+
+impl ScoreBoard {
+	/// Begins a transaction on the score board
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let mut transaction = sb.begin();
+	/// transaction.start_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!(transaction.get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// transaction.commit().unwrap();
+	/// assert_eq!(sb.get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// ```
+	pub fn begin(&mut self) -> Transaction<'_> {
+		trace!("Beginning a transaction");
+
+		self.stage();
+
+		Transaction { board: self }
+	}
+
+	/// Makes the given changes atomically: either all of them succeed, or the score board is left unchanged. Nobody is notified about the events until all the changes are made
+	///
+	/// # Arguments
+	///
+	/// * `changes` - the changes to make
+	///
+	/// # Errors
+	///
+	/// * The same as the changes, leaving the score board as it was
+	/// * When the journal can't be written, leaving the score board as it was
+	pub(crate) fn staged<T, F>(&mut self, changes: F) -> Result<T, ScoreBoardError>
+	where
+		F: FnOnce(&mut ScoreBoard) -> Result<T, ScoreBoardError>,
+	{
+		self.stage();
+
+		match changes(self) {
+			Ok(result) => {
+				self.apply_staged()?;
+				Ok(result)
+			},
+			Err(error) => {
+				self.discard_staged();
+				Err(error)
+			},
+		}
+	}
+
+	/// Starts staging the changes: from now on, the changes are neither journaled nor passed on, until they are applied
+	pub(crate) fn stage(&mut self) {
+		self.savepoint = Some(Savepoint {
+			data: self.data.clone(),
+			team_index: self.team_index.clone(),
+			schedule: self.schedule.clone(),
+			sequence: self.get_last_sequence(),
+			event_count: self.events.len(),
+			last_event: self.events.last().cloned(),
+			archive_changes: Vec::new(),
+			called_off_count: self.called_off.len(),
+			journal_lines: String::new(),
+		});
+	}
+
+	/// Keeps the staged changes. The staged changes are appended to the journal first, then their events are passed on to the notified parties
+	///
+	/// # Errors
+	///
+	/// * When the journal can't be written, leaving the score board as it was
+	pub(crate) fn apply_staged(&mut self) -> Result<(), ScoreBoardError> {
+		let savepoint = match self.savepoint.take() {
+			Some(savepoint) => savepoint,
+			None => return Ok(()),
+		};
+
+		if let Some(journal) = &self.journal {
+			if let Err(error) = journal.append(&savepoint.journal_lines) {
+				self.restore(savepoint);
+				return Err(error);
+			}
+		}
+
+		self.publish_events_after(savepoint.sequence);
+
+		Ok(())
+	}
+
+	/// Undoes the staged changes
+	pub(crate) fn discard_staged(&mut self) {
+		if let Some(savepoint) = self.savepoint.take() {
+			self.restore(savepoint);
+		}
+	}
+
+	/// Notes a change of the archive, so it can be undone when the staged changes are discarded
+	///
+	/// # Arguments
+	///
+	/// * `change` - the change of the archive
+	///
+	pub(crate) fn stage_archive_change(&mut self, change: ArchiveChange) {
+		if let Some(savepoint) = &mut self.savepoint {
+			savepoint.archive_changes.push(change);
+		}
+	}
+
+	/// Keeps a line of a staged change for the journal, until the changes are applied
+	///
+	/// # Arguments
+	///
+	/// * `line` - the line, with the new line at the end
+	///
+	pub(crate) fn stage_journal_line(&mut self, line: &str) {
+		if let Some(savepoint) = &mut self.savepoint {
+			savepoint.journal_lines.push_str(line);
+		}
+	}
+
+	/// Checks if the changes are being staged
+	pub(crate) fn is_staging(&self) -> bool {
+		self.savepoint.is_some()
+	}
+
+	/// Brings the score board back to a savepoint
+	///
+	/// # Arguments
+	///
+	/// * `savepoint` - the state from before the staged changes
+	///
+	fn restore(&mut self, savepoint: Savepoint) {
+		trace!("Discarding the staged changes");
+
+		self.data = savepoint.data;
+		self.team_index = savepoint.team_index;
+		self.schedule = savepoint.schedule;
+
+		self.events.truncate(savepoint.event_count);
+		if let (Some(last), Some(original)) = (self.events.last_mut(), savepoint.last_event) {
+			*last = original;
+		}
+
+		for change in savepoint.archive_changes.into_iter().rev() {
+			match change {
+				ArchiveChange::Added => { self.archive.pop(); },
+				ArchiveChange::Removed(index, game) => self.archive.insert(index, *game),
+			}
+		}
+
+		self.called_off.truncate(savepoint.called_off_count);
+		self.invalidate_summary();
+	}
+}
+
+impl Transaction<'_> {
+	/// Stages the start of a game. See `ScoreBoard::start_game()` for the details
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::start_game()`, taking the previously staged changes into account
+	pub fn start_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		self.board.start_game(home, away)
+	}
+
+	/// Stages a score update. See `ScoreBoard::update_score()` for the details
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::update_score()`, taking the previously staged changes into account
+	pub fn update_score<T: ToString, U: ToString>(&mut self, home: T, new_home_score: u8, away: U, new_away_score: u8) -> Result<(), ScoreBoardError> {
+		self.board.update_score(home, new_home_score, away, new_away_score)
+	}
+
+	/// Stages the end of a game. See `ScoreBoard::finish_game()` for the details
+	///
+	/// # Errors
+	///
+	/// * The same as `ScoreBoard::finish_game()`, taking the previously staged changes into account
+	pub fn finish_game<T: ToString, U: ToString>(&mut self, home: T, away: U) -> Result<(), ScoreBoardError> {
+		self.board.finish_game(home, away)
+	}
+
+	/// Previews the summary of the score board as it would look after the commit
+	pub fn get_summary(&self) -> Vec<String> {
+		self.board.get_summary()
+	}
+
+	/// Makes all the staged changes visible on the score board
+	///
+	/// # Errors
+	///
+	/// * When the journal of the board can't be written (see `ScoreBoard::set_journal()`), leaving the score board as it was
+	pub fn commit(self) -> Result<(), ScoreBoardError> {
+		trace!("Committing a transaction");
+
+		self.board.apply_staged()
+	}
+
+	/// Discards all the staged changes, leaving the score board as it was before the transaction
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// let mut transaction = sb.begin();
+	/// transaction.start_game("Japan", "Indonesia").unwrap();
+	/// transaction.rollback();
+	/// assert!(sb.get_summary().is_empty());
+	/// ```
+	pub fn rollback(self) {
+		trace!("Rolling back a transaction");
+	}
+}
+
+impl Drop for Transaction<'_> {
+	fn drop(&mut self) {
+		self.board.discard_staged();
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const NOTHING_TO_SHOW: Vec<String> = Vec::new();
+
+	#[test]
+	fn committed_changes_are_visible_on_the_board() {
+		let expected_summary = vec![String::from("Spain 2 - Brazil 1")];
+
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		transaction.update_score("Spain", 2, "Brazil", 1).expect("Couldn't stage the update");
+		transaction.finish_game("Mexico", "Canada").expect("Couldn't stage the finish");
+		transaction.commit().expect("Couldn't commit the transaction");
+
+		assert_eq!(sb.get_summary(), expected_summary);
+		assert_eq!(sb.get_events().len(), 5);
+	}
+
+	#[test]
+	fn preview_shows_the_staged_changes() {
+		let expected_summary = vec![String::from("Spain 1 - Brazil 0"), String::from("Mexico 0 - Canada 0")];
+
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		transaction.update_score("Spain", 1, "Brazil", 0).expect("Couldn't stage the update");
+
+		assert_eq!(transaction.get_summary(), expected_summary);
+	}
+
+	#[test]
+	fn rolled_back_changes_are_discarded() {
+		let mut sb = ScoreBoard::new();
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		transaction.rollback();
+
+		assert_eq!(sb.get_summary(), NOTHING_TO_SHOW);
+		assert!(sb.get_events().is_empty());
+	}
+
+	#[test]
+	fn dropped_transaction_is_rolled_back() {
+		let mut sb = ScoreBoard::new();
+		{
+			let mut transaction = sb.begin();
+			transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		}
+
+		assert_eq!(sb.get_summary(), NOTHING_TO_SHOW);
+	}
+
+	#[test]
+	fn staged_operations_are_validated_against_staged_state() {
+		let mut sb = ScoreBoard::new();
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		let result_1 = transaction.start_game("Brazil", "Germany");
+		let result_2 = transaction.finish_game("Mexico", "Canada");
+
+		assert!(result_1.err().is_some_and(|result| result.to_string() == "Brazil is currently playing a game"));
+		assert!(result_2.err().is_some_and(|result| result.to_string() == "Couldn't find a game for removal"));
+	}
+
+	#[test]
+	fn failed_staged_operation_keeps_other_staged_changes() {
+		let mut sb = ScoreBoard::new();
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		let _ = transaction.update_score("Spain", 1, "Italy", 0);
+		transaction.commit().expect("Couldn't commit the transaction");
+
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+	}
+
+	#[test]
+	fn rolled_back_finish_stays_out_of_the_archive() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the game");
+		sb.update_score("Mexico", 1, "Canada", 0).expect("Couldn't update the game");
+		let mut transaction = sb.begin();
+		transaction.update_score("Mexico", 2, "Canada", 0).expect("Couldn't stage the update");
+		transaction.finish_game("Mexico", "Canada").expect("Couldn't stage the finish");
+		transaction.rollback();
+
+		assert_eq!(sb.get_summary(), vec![String::from("Mexico 1 - Canada 0")]);
+		assert!(sb.get_archive_summary().is_empty());
+		assert_eq!(sb.get_events().len(), 2);
+	}
+}
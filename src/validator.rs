@@ -0,0 +1,228 @@
+//! # Validation hooks
+//!
+//! Lets the users of the score board enforce their own domain rules (ex. a maximum number of goals per update, or a list of allowed pairings) before a game is started, updated or finished
+
+use std::sync::Arc;
+
+use log::{trace, warn};
+
+use crate::error::{Rejection, ScoreBoardError};
+use crate::{Command, ScoreBoard};
+
+/// A user supplied rule, checked before every start, update and finish of a game
+///
+/// Any closure taking a `&Command` and a `&ScoreBoard` and returning `Result<(), Rejection>` is a validator too
+pub trait Validator {
+	/// Checks if the operation is allowed
+	///
+	/// # Arguments
+	///
+	/// * `command` - the operation about to be applied
+	/// * `board` - the score board, in the state from before the operation
+	///
+	/// # Errors
+	///
+	/// * When the operation breaks the rule. The operation is then not applied
+	fn validate(&self, command: &Command, board: &ScoreBoard) -> Result<(), Rejection>;
+}
+
+impl<F> Validator for F
+where
+	F: Fn(&Command, &ScoreBoard) -> Result<(), Rejection>,
+{
+	fn validate(&self, command: &Command, board: &ScoreBoard) -> Result<(), Rejection> {
+		self(command, board)
+	}
+}
+
+impl ScoreBoard {
+	/// Adds a validator to the score board. Validators are called in the order they were added, after the built-in checks (ex. that the game exists) succeed and before the board is changed. The first rejection stops the operation
+	///
+	/// # Arguments
+	///
+	/// * `validator` - the rule to enforce
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use scoreboard_world_cup::{Command, Rejection, ScoreBoard};
+	///
+	/// let mut sb = ScoreBoard::new();
+	/// sb.add_validator(|command: &Command, _: &ScoreBoard| match command {
+	///     Command::UpdateScore { home_score, away_score, .. } if *home_score > 20 || *away_score > 20 => Err(Rejection::new("sane scores", "more than 20 goals")),
+	///     _ => Ok(()),
+	/// });
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let result = sb.update_score("Japan", 21, "Indonesia", 0);
+	/// assert_eq!(result.unwrap_err().to_string(), "Rejected by sane scores: more than 20 goals");
+	/// assert_eq!(sb.get_summary(), vec![String::from("Japan 0 - Indonesia 0")]);
+	/// ```
+	pub fn add_validator<V: Validator + Send + Sync + 'static>(&mut self, validator: V) {
+		trace!("Adding a validator");
+
+		self.validators.push(Arc::new(validator));
+	}
+
+	/// Removes all the validators from the score board
+	pub fn clear_validators(&mut self) {
+		trace!("Removing all validators");
+
+		self.validators.clear();
+	}
+
+	/// Runs all the validators against an operation. The command is only built when there are any validators to run
+	///
+	/// # Arguments
+	///
+	/// * `get_command` - builds the operation about to be applied
+	///
+	/// # Errors
+	///
+	/// * The first rejection returned by a validator
+	///
+	pub(crate) fn validate<F: FnOnce() -> Command>(&self, get_command: F) -> Result<(), ScoreBoardError> {
+		if self.validators.is_empty() {
+			return Ok(());
+		}
+
+		let command = get_command();
+
+		for validator in &self.validators {
+			if let Err(rejection) = validator.validate(&command, self) {
+				warn!("{}", rejection);
+				return Err(ScoreBoardError::Rejected(rejection));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+
+	const MAX_GOALS_PER_UPDATE: u8 = 10;
+
+	/// Rejects updates that change the total score of a game too much at once
+	struct MaxGoalsPerUpdate;
+
+	impl Validator for MaxGoalsPerUpdate {
+		fn validate(&self, command: &Command, board: &ScoreBoard) -> Result<(), Rejection> {
+			if let Command::UpdateScore { home, home_score, away, away_score } = command {
+				let (old_home_score, old_away_score) = board.get_score(home, away).unwrap_or((0, 0));
+				let step = home_score.abs_diff(old_home_score) + away_score.abs_diff(old_away_score);
+				if step > MAX_GOALS_PER_UPDATE {
+					return Err(Rejection::new("max goals per update", format!("{} goals at once", step)));
+				}
+			}
+			Ok(())
+		}
+	}
+
+	fn only_pairing(allowed_home: &'static str, allowed_away: &'static str) -> impl Fn(&Command, &ScoreBoard) -> Result<(), Rejection> {
+		move |command, _| match command {
+			Command::StartGame { home, away } if home != allowed_home || away != allowed_away => {
+				Err(Rejection::new("registered pairings", format!("{} - {} is not a registered pairing", home, away)))
+			},
+			_ => Ok(()),
+		}
+	}
+
+	#[test]
+	fn operations_pass_without_validators() {
+		let mut sb = ScoreBoard::new();
+		sb.clear_max_score();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		let result = sb.update_score("Spain", 99, "Brazil", 0);
+
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn rejected_update_is_not_applied() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(MaxGoalsPerUpdate);
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 5, "Brazil", 0).expect("Couldn't update the game");
+		let result = sb.update_score("Spain", 5, "Brazil", 11);
+
+		assert_eq!(result, Err(ScoreBoardError::Rejected(Rejection::new("max goals per update", "11 goals at once"))));
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 5 - Brazil 0")]);
+		assert_eq!(sb.get_events().len(), 2);
+	}
+
+	#[test]
+	fn rejected_start_is_not_applied() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(only_pairing("Spain", "Brazil"));
+		let result_1 = sb.start_game("Spain", "Brazil");
+		let result_2 = sb.start_game("Mexico", "Canada");
+
+		assert!(result_1.is_ok());
+		assert!(result_2.err().is_some_and(|result| result.to_string() == "Rejected by registered pairings: Mexico - Canada is not a registered pairing"));
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+	}
+
+	#[test]
+	fn rejected_finish_keeps_the_game() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.add_validator(|command: &Command, _: &ScoreBoard| match command {
+			Command::FinishGame { .. } => Err(Rejection::new("no finishing", "games never end")),
+			_ => Ok(()),
+		});
+		let result = sb.finish_game("Spain", "Brazil");
+
+		assert!(result.is_err());
+		assert_eq!(sb.get_summary(), vec![String::from("Spain 0 - Brazil 0")]);
+	}
+
+	#[test]
+	fn built_in_checks_come_before_validators() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(|_: &Command, _: &ScoreBoard| Err(Rejection::new("reject all", "nothing is allowed")));
+		let result = sb.update_score("Spain", 1, "Brazil", 0);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Couldn't find a game for update"));
+	}
+
+	#[test]
+	fn first_rejection_stops_the_operation() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(|_: &Command, _: &ScoreBoard| Err(Rejection::new("first", "rejected")));
+		sb.add_validator(|_: &Command, _: &ScoreBoard| Err(Rejection::new("second", "rejected")));
+		let result = sb.start_game("Spain", "Brazil");
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Rejected by first: rejected"));
+	}
+
+	#[test]
+	fn cleared_validators_are_not_called() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(|_: &Command, _: &ScoreBoard| Err(Rejection::new("reject all", "nothing is allowed")));
+		sb.clear_validators();
+		let result = sb.start_game("Spain", "Brazil");
+
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn rejection_inside_a_batch_fails_the_batch() {
+		let mut sb = ScoreBoard::new();
+		sb.add_validator(MaxGoalsPerUpdate);
+		let result = sb.apply_batch(vec![
+			Command::StartGame { home: String::from("Spain"), away: String::from("Brazil") },
+			Command::UpdateScore { home: String::from("Spain"), home_score: 11, away: String::from("Brazil"), away_score: 0 },
+		]);
+
+		assert!(result.err().is_some_and(|result| result.to_string() == "Command 2 of 2 failed: Rejected by max goals per update: 11 goals at once"));
+		assert!(sb.get_summary().is_empty());
+	}
+}
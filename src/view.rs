@@ -0,0 +1,400 @@
+//! # Structured view of the games
+//!
+//! Presents the running games as structured values, for the clients that need more than the summary lines
+
+use std::string::String;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use std::vec::Vec;
+
+use log::trace;
+
+use crate::odds::Odds;
+use crate::order::{self, SortOrder};
+use crate::period::{self, Period, PeriodScore};
+use crate::state::GameState;
+use crate::timezone::UtcOffset;
+use crate::xg::ExpectedGoals;
+use crate::{Game, ScoreBoard};
+
+/// A read-only view of a running game. Views can be compared and used as keys of sets and maps; two views are equal when they show the same revision of the same game with the same odds and expected goals
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GameView {
+	/// Name of the home team
+	pub home_team: String,
+	/// Score of the home team
+	pub home_score: u8,
+	/// Name of the away team
+	pub away_team: String,
+	/// Score of the away team
+	pub away_score: u8,
+	/// Wall clock time of the start of the game
+	pub kickoff_time: SystemTime,
+	/// Revision of the game, see `EventRecord::revision`
+	pub revision: u64,
+	/// The current period, see `ScoreBoard::start_period()`
+	pub period: Period,
+	/// The goals scored in every entered period, the earliest coming first
+	pub periods: Vec<PeriodScore>,
+	/// Score at the end of the first half, from the half-time break on, see `ScoreBoard::half_time_score()`
+	pub half_time_score: Option<(u8, u8)>,
+	/// State of the game, either `GameState::Live` or `GameState::Paused`
+	pub state: GameState,
+	/// Time of the last score update of the game, or `None` if the score wasn't updated since the start
+	pub last_updated: Option<Instant>,
+	/// Name of the stadium, if it was given with `GameBuilder::venue()`
+	pub venue: Option<String>,
+	/// Time zone of the stadium, if it was given with `GameBuilder::utc_offset()`, and UTC otherwise
+	pub utc_offset: UtcOffset,
+	/// Group of the tournament, if it was given with `GameBuilder::group()`
+	pub group: Option<char>,
+	/// The current betting odds, if they were set with `ScoreBoard::set_odds()`
+	pub odds: Option<Odds>,
+	/// Expected goals of both teams, see `ScoreBoard::record_xg()`
+	pub xg: ExpectedGoals,
+}
+
+/// A borrowed view of a running game, with only the teams and the scores. It's built without any allocation, see `ScoreBoard::games_ref()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GameRef<'a> {
+	/// Name of the home team
+	pub home_team: &'a str,
+	/// Score of the home team
+	pub home_score: u8,
+	/// Name of the away team
+	pub away_team: &'a str,
+	/// Score of the away team
+	pub away_score: u8,
+}
+
+impl<'a> From<&'a Game> for GameRef<'a> {
+	fn from(game: &'a Game) -> GameRef<'a> {
+		GameRef {
+			home_team: &game.home_team.name,
+			home_score: game.home_team.score,
+			away_team: &game.away_team.name,
+			away_score: game.away_team.score,
+		}
+	}
+}
+
+impl GameView {
+	/// Tells if the score was updated within the given time, ex. to flash the row of the game on a display
+	///
+	/// # Arguments
+	///
+	/// * `within` - how long an update is considered recent
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::time::Duration;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// assert!(!sb.get_game("Japan", "Indonesia").unwrap().recently_updated(Duration::from_secs(5)));
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// assert!(sb.get_game("Japan", "Indonesia").unwrap().recently_updated(Duration::from_secs(5)));
+	/// ```
+	pub fn recently_updated(&self, within: Duration) -> bool {
+		self.last_updated.is_some_and(|last_updated| last_updated.elapsed() <= within)
+	}
+
+	/// Provides a caption with the half-time score, ex. "HT: 1-0", from the half-time break on
+	pub fn get_half_time_caption(&self) -> Option<String> {
+		self.half_time_score.map(|(home, away)| format!("HT: {}-{}", home, away))
+	}
+}
+
+impl From<&Game> for GameView {
+	fn from(game: &Game) -> GameView {
+		let score = (game.home_team.score, game.away_team.score);
+
+		GameView {
+			home_team: game.home_team.name.clone(),
+			home_score: game.home_team.score,
+			away_team: game.away_team.name.clone(),
+			away_score: game.away_team.score,
+			kickoff_time: game.kickoff_time,
+			revision: game.revision,
+			period: game.get_period(),
+			periods: period::split_periods(&game.periods, score),
+			half_time_score: game.half_time_score,
+			state: game.state,
+			last_updated: game.last_update,
+			venue: game.venue.clone(),
+			utc_offset: game.utc_offset,
+			group: game.group,
+			odds: game.odds,
+			xg: game.xg,
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Provides a view of a running game
+	///
+	/// # Arguments
+	///
+	/// * `home` - Name of the home team
+	/// * `away` - Name of the away team
+	///
+	/// # Returns
+	///
+	/// * The view of the game, or `None` if there is no active match between the given teams
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	/// let game = sb.get_game("Japan", "Indonesia").unwrap();
+	/// assert_eq!((game.home_score, game.away_score), (1, 0));
+	/// ```
+	pub fn get_game(&self, home: &str, away: &str) -> Option<GameView> {
+		self.find_game(home, away).map(GameView::from)
+	}
+
+	/// Provides the views of all the running games, in the order of the summary
+	pub fn get_games(&self) -> Vec<GameView> {
+		self.data.iter().map(GameView::from).collect()
+	}
+
+	/// Provides the borrowed views of all the running games, in the order of the summary. Neither the views nor the iterator allocate, so it suits the hot paths that only check the scores
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 1, "Brazil", 0).unwrap();
+	///
+	/// let goals: u32 = sb.games_ref().map(|game| u32::from(game.home_score) + u32::from(game.away_score)).sum();
+	/// assert_eq!(goals, 1);
+	/// assert_eq!(sb.games_ref().next().map(|game| game.home_team), Some("Spain"));
+	/// ```
+	pub fn games_ref(&self) -> impl ExactSizeIterator<Item = GameRef<'_>> + DoubleEndedIterator + '_ {
+		self.data.iter().map(GameRef::from)
+	}
+
+	/// Provides a view of the leading game, the one with the highest total score, ex. for a "match of the moment" widget. The ties are broken as in the summary, and by the most recent start when the summary is ordered by the kickoff
+	///
+	/// # Returns
+	///
+	/// * The view of the leading game, or `None` if no games are running
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Japan", 2, "Indonesia", 1).unwrap();
+	/// assert_eq!(sb.leader().unwrap().home_team, "Japan");
+	/// ```
+	pub fn leader(&self) -> Option<GameView> {
+		let sort_order = match self.sort_order {
+			SortOrder::Kickoff => SortOrder::Ranking,
+			sort_order => sort_order,
+		};
+
+		self.data.iter().min_by(|a, b| order::compare(sort_order, a, b)).map(GameView::from)
+	}
+
+	/// Provides a view of the game that has been running for the longest time, as the game most likely to have missed its finish, ex. when its feed dropped
+	///
+	/// # Returns
+	///
+	/// * The view of the game started first, or `None` if no games are running. Of the games started at the same time, the one higher in the summary is chosen
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.start_game("Spain", "Brazil").unwrap();
+	/// sb.update_score("Spain", 1, "Brazil", 0).unwrap();
+	/// assert_eq!(sb.longest_running_game().unwrap().home_team, "Japan");
+	/// ```
+	pub fn longest_running_game(&self) -> Option<GameView> {
+		self.data.iter().min_by_key(|game| game.start_time).map(GameView::from)
+	}
+
+	/// Provides the views of all the running games, the longest running coming first. The `kickoff_time` of every view tells how long the game has been running
+	pub fn games_by_age(&self) -> Vec<GameView> {
+		let mut games: Vec<&Game> = self.data.iter().collect();
+		games.sort_by_key(|game| game.start_time);
+
+		games.into_iter().map(GameView::from).collect()
+	}
+
+	/// Provides the views of all the running games, in the order of the summary, as a shared slice. The slice is a consistent picture of the board at the time of the call and cloning it doesn't copy the games, so it can be handed to many readers (ex. other threads) without keeping the board locked
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::thread;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// let snapshot = sb.snapshot_arc();
+	/// sb.update_score("Japan", 1, "Indonesia", 0).unwrap();
+	///
+	/// let reader = {
+	///     let snapshot = snapshot.clone();
+	///     thread::spawn(move || snapshot[0].home_score)
+	/// };
+	/// assert_eq!(reader.join().unwrap(), 0);
+	/// ```
+	pub fn snapshot_arc(&self) -> Arc<[GameView]> {
+		trace!("Taking a shared snapshot of the score board");
+
+		self.data.iter().map(GameView::from).collect()
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::collections::HashSet;
+
+	#[test]
+	fn games_come_in_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+
+		let games = sb.get_games();
+		let names: Vec<&str> = games.iter().map(|game| game.home_team.as_str()).collect();
+		assert_eq!(names, vec!["Mexico", "Spain"]);
+		assert_eq!(games[0].revision, 2);
+	}
+
+	#[test]
+	fn borrowed_games_come_in_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 2).expect("Couldn't update the first game");
+
+		let games: Vec<GameRef> = sb.games_ref().collect();
+		assert_eq!(games, vec![
+			GameRef { home_team: "Mexico", home_score: 0, away_team: "Canada", away_score: 2 },
+			GameRef { home_team: "Spain", home_score: 0, away_team: "Brazil", away_score: 0 },
+		]);
+		assert_eq!(sb.games_ref().len(), 2);
+		assert_eq!(sb.games_ref().next_back().map(|game| game.away_team), Some("Brazil"));
+	}
+
+	#[test]
+	fn games_are_ordered_by_their_start() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the third game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the third game");
+		let index = sb.find_game_index("Spain", "Brazil").expect("Couldn't find the second game");
+		sb.data[index].start_time = Instant::now().checked_sub(Duration::from_secs(3 * 3600)).expect("Couldn't go back in time");
+
+		let names: Vec<String> = sb.games_by_age().into_iter().map(|game| game.home_team).collect();
+		assert_eq!(names, vec!["Spain", "Mexico", "Japan"]);
+		assert_eq!(sb.longest_running_game().map(|game| game.home_team), Some(String::from("Spain")));
+	}
+
+	#[test]
+	fn leader_has_the_highest_total_score() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the third game");
+		sb.update_score("Mexico", 1, "Canada", 1).expect("Couldn't update the first game");
+		sb.update_score("Spain", 2, "Brazil", 0).expect("Couldn't update the second game");
+
+		assert_eq!(sb.leader().map(|game| game.home_team), Some(String::from("Spain")));
+
+		sb.set_sort_order(SortOrder::GoalDifference);
+		assert_eq!(sb.leader().map(|game| game.home_team), Some(String::from("Spain")));
+
+		sb.set_sort_order(SortOrder::Kickoff);
+		assert_eq!(sb.leader().map(|game| game.home_team), Some(String::from("Spain")));
+		assert_eq!(sb.get_games()[0].home_team, "Mexico");
+	}
+
+	#[test]
+	fn empty_board_has_no_leader_nor_longest_running_game() {
+		let sb = ScoreBoard::new();
+
+		assert_eq!(sb.longest_running_game(), None);
+		assert_eq!(sb.leader(), None);
+		assert!(sb.games_by_age().is_empty());
+	}
+
+	#[test]
+	fn half_time_caption_shows_the_score_at_the_break() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Italy").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Italy", 0).expect("Couldn't update the game");
+		sb.start_period("Spain", "Italy", Period::SecondHalf).expect("Couldn't start the second half");
+		sb.update_score("Spain", 3, "Italy", 0).expect("Couldn't update the game");
+
+		let game = sb.get_game("Spain", "Italy").expect("Couldn't find the game");
+		assert_eq!(game.get_half_time_caption(), Some(String::from("HT: 1-0")));
+	}
+
+	#[test]
+	fn old_update_is_not_recent() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Italy").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Italy", 0).expect("Couldn't update the game");
+		sb.data[0].last_update = Instant::now().checked_sub(Duration::from_secs(10));
+
+		let game = sb.get_game("Spain", "Italy").expect("Couldn't find the game");
+		assert!(game.last_updated.is_some());
+		assert!(game.recently_updated(Duration::from_secs(30)));
+		assert!(!game.recently_updated(Duration::from_secs(3)));
+	}
+
+	#[test]
+	fn views_can_be_kept_in_a_set() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Spain", "Italy").expect("Couldn't create the game");
+		let mut seen = HashSet::new();
+		assert!(seen.insert(sb.get_game("Spain", "Italy").expect("Couldn't find the game")));
+		assert!(!seen.insert(sb.get_game("Spain", "Italy").expect("Couldn't find the game")));
+
+		sb.update_score("Spain", 1, "Italy", 0).expect("Couldn't update the game");
+		assert!(seen.insert(sb.get_game("Spain", "Italy").expect("Couldn't find the game")));
+		assert_eq!(seen.len(), 2);
+	}
+
+	#[test]
+	fn shared_snapshot_doesnt_follow_the_board() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		let snapshot = sb.snapshot_arc();
+		let copy = Arc::clone(&snapshot);
+		sb.finish_game("Mexico", "Canada").expect("Couldn't finish the first game");
+
+		let names: Vec<&str> = snapshot.iter().map(|game| game.home_team.as_str()).collect();
+		assert_eq!(names, vec!["Spain", "Mexico"]);
+		assert!(Arc::ptr_eq(&snapshot, &copy));
+		assert_eq!(sb.snapshot_arc().len(), 1);
+	}
+
+	#[test]
+	fn missing_game_has_no_view() {
+		let sb = ScoreBoard::new();
+
+		assert_eq!(sb.get_game("Spain", "Italy"), None);
+		assert!(sb.get_games().is_empty());
+	}
+}
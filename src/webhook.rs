@@ -0,0 +1,256 @@
+//! # Webhook notifications
+//!
+//! Pushes every change of the score board to HTTP endpoints as a JSON payload, so the downstream systems don't need to poll the board
+
+use std::string::String;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+use std::vec::Vec;
+
+use log::{debug, trace, warn};
+
+use crate::events::EventRecord;
+use crate::ScoreBoard;
+
+/// Settings of the webhook notifications
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookConfig {
+	/// URLs the events are posted to. Every event is posted to all of them, in the given order
+	pub urls: Vec<String>,
+	/// How many times a failed delivery is retried before the event is dropped for the URL
+	pub max_retries: u32,
+	/// Delay before the first retry. It doubles with every following retry
+	pub initial_backoff: Duration,
+	/// Time limit of a single delivery attempt
+	pub timeout: Duration,
+}
+
+impl WebhookConfig {
+	/// Returns settings posting to the given URLs, with 3 retries starting after 500 ms and a 5 second time limit of an attempt
+	///
+	/// # Arguments
+	///
+	/// * `urls` - URLs the events are posted to
+	pub fn new(urls: Vec<String>) -> WebhookConfig {
+		WebhookConfig { urls, max_retries: 3, initial_backoff: Duration::from_millis(500), timeout: Duration::from_secs(5) }
+	}
+}
+
+/// Hands the events over to the background thread delivering them
+#[derive(Clone)]
+pub(crate) struct WebhookNotifier {
+	/// Sending end of the queue of JSON payloads
+	sender: Sender<String>,
+}
+
+impl WebhookNotifier {
+	/// Queues an event for delivery. Never blocks the score board operation
+	///
+	/// # Arguments
+	///
+	/// * `record` - the event to deliver
+	///
+	pub(crate) fn notify(&self, record: &EventRecord) {
+		if self.sender.send(record.to_json()).is_err() {
+			warn!("The webhook delivery thread has stopped, event {} is dropped", record.sequence);
+		}
+	}
+}
+
+impl ScoreBoard {
+	/// Turns on the webhook notifications. From now on, every recorded event (the start of a game, a score update and the end of a game) is posted as JSON (see `EventRecord::to_json()`) to all the configured URLs. The deliveries happen on a background thread, in the order of the events, and failed deliveries are retried with an exponential backoff. Changes of batches and transactions are posted once they are applied
+	///
+	/// Calling it again replaces the previous settings. The events queued with the previous settings are still delivered
+	///
+	/// # Arguments
+	///
+	/// * `config` - settings of the notifications
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use scoreboard_world_cup::WebhookConfig;
+	///
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.set_webhooks(WebhookConfig::new(vec![String::from("https://example.com/hooks/goals")]));
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// ```
+	pub fn set_webhooks(&mut self, config: WebhookConfig) {
+		trace!("Setting webhooks for {} URLs", config.urls.len());
+
+		let (sender, receiver) = mpsc::channel::<String>();
+
+		thread::spawn(move || {
+			let agent: ureq::Agent = ureq::Agent::config_builder()
+				.timeout_global(Some(config.timeout))
+				.build()
+				.into();
+
+			for payload in receiver {
+				for url in &config.urls {
+					deliver(&agent, &config, url, &payload);
+				}
+			}
+
+			trace!("Webhook delivery thread stopped");
+		});
+
+		self.webhooks = Some(WebhookNotifier { sender });
+	}
+
+	/// Turns off the webhook notifications. The already queued events are still delivered
+	pub fn clear_webhooks(&mut self) {
+		trace!("Clearing webhooks");
+
+		self.webhooks = None;
+	}
+}
+
+/// Posts a payload to a single URL, retrying with an exponential backoff
+///
+/// # Arguments
+///
+/// * `agent` - the HTTP client
+/// * `config` - settings of the retries
+/// * `url` - the URL to post to
+/// * `payload` - the JSON payload
+///
+fn deliver(agent: &ureq::Agent, config: &WebhookConfig, url: &str, payload: &str) {
+	let mut backoff = config.initial_backoff;
+
+	for attempt in 0..=config.max_retries {
+		if attempt > 0 {
+			thread::sleep(backoff);
+			backoff = backoff.saturating_mul(2);
+		}
+
+		match agent.post(url).header("Content-Type", "application/json").send(payload) {
+			Ok(_) => {
+				debug!("Event delivered to {}", url);
+				return;
+			},
+			Err(error) => debug!("Delivery attempt {} to {} failed: {}", attempt + 1, url, error),
+		}
+	}
+
+	warn!("Couldn't deliver an event to {} after {} attempts", url, config.max_retries + 1);
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::io::{Read, Write};
+	use std::net::{TcpListener, TcpStream};
+	use std::sync::mpsc::Receiver;
+
+	const WAIT_TIME: Duration = Duration::from_secs(5);
+
+	/// Starts a local HTTP server answering with the given statuses, one per request, and passing the request bodies on
+	fn start_server(statuses: Vec<u16>) -> (String, Receiver<String>) {
+		let listener = TcpListener::bind("127.0.0.1:0").expect("Couldn't start the server");
+		let url = format!("http://{}/hook", listener.local_addr().expect("Couldn't get the server address"));
+		let (sender, receiver) = mpsc::channel();
+
+		thread::spawn(move || {
+			for status in statuses {
+				let (mut stream, _) = listener.accept().expect("Couldn't accept a connection");
+				let body = read_body(&mut stream);
+				let _ = stream.write_all(format!("HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status).as_bytes());
+				let _ = sender.send(body);
+			}
+		});
+
+		(url, receiver)
+	}
+
+	fn read_body(stream: &mut TcpStream) -> String {
+		let mut request = Vec::new();
+		let mut buffer = [0; 1024];
+
+		loop {
+			let read = stream.read(&mut buffer).expect("Couldn't read the request");
+			request.extend_from_slice(&buffer[..read]);
+			let text = String::from_utf8_lossy(&request).into_owned();
+			if let Some(header_end) = text.find("\r\n\r\n") {
+				let content_length = text[..header_end]
+					.lines()
+					.find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|value| value.trim().parse::<usize>().unwrap_or(0)))
+					.unwrap_or(0);
+				if request.len() >= header_end + 4 + content_length || read == 0 {
+					return text[header_end + 4..].to_string();
+				}
+			}
+			if read == 0 {
+				return String::new();
+			}
+		}
+	}
+
+	fn get_config(url: String) -> WebhookConfig {
+		WebhookConfig { initial_backoff: Duration::from_millis(1), ..WebhookConfig::new(vec![url]) }
+	}
+
+	#[test]
+	fn every_event_is_posted() {
+		let (url, bodies) = start_server(vec![200, 200, 200]);
+
+		let mut sb = ScoreBoard::new();
+		sb.set_webhooks(get_config(url));
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+		sb.update_score("Spain", 1, "Brazil", 0).expect("Couldn't update the game");
+		sb.finish_game("Spain", "Brazil").expect("Couldn't finish the game");
+
+		let body_1 = bodies.recv_timeout(WAIT_TIME).expect("The start wasn't posted");
+		let body_2 = bodies.recv_timeout(WAIT_TIME).expect("The update wasn't posted");
+		let body_3 = bodies.recv_timeout(WAIT_TIME).expect("The finish wasn't posted");
+		assert_eq!(body_1, sb.get_events()[0].to_json());
+		assert!(body_2.contains("\"type\":\"score_updated\""));
+		assert!(body_3.contains("\"type\":\"game_finished\""));
+	}
+
+	#[test]
+	fn failed_delivery_is_retried() {
+		let (url, bodies) = start_server(vec![500, 503, 200]);
+
+		let mut sb = ScoreBoard::new();
+		sb.set_webhooks(get_config(url));
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the game");
+
+		for _ in 0..3 {
+			let body = bodies.recv_timeout(WAIT_TIME).expect("The event wasn't posted");
+			assert_eq!(body, sb.get_events()[0].to_json());
+		}
+	}
+
+	#[test]
+	fn rolled_back_changes_are_not_posted() {
+		let (url, bodies) = start_server(vec![200]);
+
+		let mut sb = ScoreBoard::new();
+		sb.set_webhooks(get_config(url));
+		let mut transaction = sb.begin();
+		transaction.start_game("Spain", "Brazil").expect("Couldn't stage the start");
+		transaction.rollback();
+		let mut transaction = sb.begin();
+		transaction.start_game("Mexico", "Canada").expect("Couldn't stage the start");
+		transaction.commit().expect("Couldn't commit the transaction");
+
+		let body = bodies.recv_timeout(WAIT_TIME).expect("The committed start wasn't posted");
+		assert!(body.contains("\"home\":\"Mexico\""));
+	}
+
+	#[test]
+	fn config_has_sensible_defaults() {
+		let config = WebhookConfig::new(vec![String::from("http://localhost/hook")]);
+
+		assert_eq!(config.max_retries, 3);
+		assert_eq!(config.initial_backoff, Duration::from_millis(500));
+		assert_eq!(config.timeout, Duration::from_secs(5));
+	}
+}
@@ -0,0 +1,154 @@
+//! # Expected goals
+//!
+//! Accumulates the expected goals (xG) of both teams of the running games, so the performance graphics can show them next to the real score
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::string::ToString;
+
+use log::{trace, warn};
+
+use crate::error::ScoreBoardError;
+use crate::ScoreBoard;
+
+/// Expected goals of both teams of a game
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExpectedGoals {
+	/// Expected goals of the home team
+	pub home: f64,
+	/// Expected goals of the away team
+	pub away: f64,
+}
+
+impl PartialEq for ExpectedGoals {
+	/// Compares the exact values, so the expected goals can be a part of `GameView`, which is `Eq` and `Hash`
+	fn eq(&self, other: &ExpectedGoals) -> bool {
+		(self.home.to_bits(), self.away.to_bits()) == (other.home.to_bits(), other.away.to_bits())
+	}
+}
+
+impl Eq for ExpectedGoals {}
+
+impl Hash for ExpectedGoals {
+	/// Hashes the exact values, consistently with `PartialEq`
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(self.home.to_bits(), self.away.to_bits()).hash(state);
+	}
+}
+
+impl fmt::Display for ExpectedGoals {
+	/// Implementation of `Display` trait, allowing it to be converted to a String, ex. "xG 1.35 - 0.42"
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "xG {:.2} - {:.2}", self.home, self.away)
+	}
+}
+
+impl ScoreBoard {
+	/// Adds expected goals of a chance to the team's running game. The total is shown in `GameView::xg`. A negative change corrects a wrongly recorded chance, and the total never goes below 0. The expected goals don't change the score, so they are neither recorded as events nor change the revision of the game
+	///
+	/// # Arguments
+	///
+	/// * `team` - Name of the team that had the chance. Must be either a `String` or a type that is convertable to `String`
+	/// * `delta` - expected goals of the chance, ex. 0.76 for a penalty
+	///
+	/// # Errors
+	///
+	/// * When the change isn't a finite number
+	/// * When the team isn't playing a game
+	/// * When the journal can't be written, see `set_journal()`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.record_xg("Japan", 0.76).unwrap();
+	/// sb.record_xg("Indonesia", 0.08).unwrap();
+	/// sb.record_xg("Japan", 0.31).unwrap();
+	/// assert_eq!(sb.get_game("Japan", "Indonesia").unwrap().xg.to_string(), "xG 1.07 - 0.08");
+	/// ```
+	pub fn record_xg<T: ToString>(&mut self, team: T, delta: f64) -> Result<(), ScoreBoardError> {
+		let team_name = self.resolve_team(team);
+
+		trace!("Recording {} expected goals for '{}'", delta, team_name);
+
+		if !delta.is_finite() {
+			warn!("Expected goals {} are invalid", delta);
+			return Err(ScoreBoardError::InvalidExpectedGoals(delta.to_string()));
+		}
+
+		let game_index = match self.find_game_index_of_team(&team_name) {
+			Some(game_index) => game_index,
+			None => {
+				warn!("{} isn't playing a game", team_name);
+				return Err(ScoreBoardError::TeamNotPlaying(team_name));
+			},
+		};
+
+		self.write_ahead_change(&["xg", &team_name, &delta.to_string()])?;
+
+		let game = &mut self.data[game_index];
+		let total = if game.home_team.name == team_name { &mut game.xg.home } else { &mut game.xg.away };
+		*total = (*total + delta).max(0.0);
+
+		Ok(())
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::string::String;
+
+	const HOME_TEAM_NAME: &str = "Ghana";
+	const AWAY_TEAM_NAME: &str = "Uruguay";
+
+	fn get_xg(sb: &ScoreBoard) -> ExpectedGoals {
+		sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't find the game").xg
+	}
+
+	#[test]
+	fn new_game_has_no_expected_goals() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert_eq!(get_xg(&sb), ExpectedGoals::default());
+	}
+
+	#[test]
+	fn expected_goals_are_accumulated_per_team() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_xg(AWAY_TEAM_NAME, 0.5).expect("Couldn't record the first chance");
+		sb.record_xg(HOME_TEAM_NAME, 0.25).expect("Couldn't record the second chance");
+		sb.record_xg(AWAY_TEAM_NAME, 0.125).expect("Couldn't record the third chance");
+
+		assert_eq!(get_xg(&sb), ExpectedGoals { home: 0.25, away: 0.625 });
+		assert_eq!(sb.get_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).map(|game| game.revision), Some(1));
+	}
+
+	#[test]
+	fn corrections_dont_go_below_zero() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+		sb.record_xg(HOME_TEAM_NAME, 0.5).expect("Couldn't record the chance");
+		sb.record_xg(HOME_TEAM_NAME, -0.75).expect("Couldn't correct the chance");
+
+		assert_eq!(get_xg(&sb).home, 0.0);
+	}
+
+	#[test]
+	fn invalid_expected_goals_are_rejected() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game(HOME_TEAM_NAME, AWAY_TEAM_NAME).expect("Couldn't create the game");
+
+		assert_eq!(sb.record_xg(HOME_TEAM_NAME, f64::NAN), Err(ScoreBoardError::InvalidExpectedGoals(String::from("NaN"))));
+		assert_eq!(sb.record_xg("Mexico", 0.1), Err(ScoreBoardError::TeamNotPlaying(String::from("Mexico"))));
+		assert_eq!(get_xg(&sb), ExpectedGoals::default());
+	}
+}
@@ -0,0 +1,177 @@
+//! # XML export
+//!
+//! Renders the currently played games as an XML document, for systems that can't consume the other formats
+
+use std::string::String;
+use std::time::SystemTime;
+
+use log::trace;
+
+use crate::datetime::UtcDateTime;
+use crate::text::escape_xml;
+use crate::ScoreBoard;
+
+impl ScoreBoard {
+	/// Exports all the currently played games, and the games that were called off, as an XML document
+	///
+	/// The document has the following structure:
+	///
+	/// ```xml
+	/// <?xml version="1.0" encoding="utf-8"?>
+	/// <scoreboard generated="2022-12-18T16:45:00Z" games="1">
+	///   <game rank="1" kickoff="2022-12-18T15:00:00Z" total="6">
+	///     <home name="Argentina" score="3"/>
+	///     <away name="France" score="3"/>
+	///   </game>
+	///   <called-off state="abandoned" kickoff="2022-12-18T13:00:00Z" reason="Thunderstorm">
+	///     <home name="Japan" score="1"/>
+	///     <away name="Indonesia" score="0"/>
+	///   </called-off>
+	/// </scoreboard>
+	/// ```
+	///
+	/// * `scoreboard` - the root element. `generated` is the time of the export and `games` is the number of `game` elements
+	/// * `game` - a single currently played game. Games come in the order of the summary, with `rank` starting from 1. `kickoff` is the start time of the game and `total` is the sum of both scores
+	/// * `called-off` - an abandoned game or a postponed fixture, the earliest called off coming first. `state` is either `abandoned` or `postponed`, `kickoff` is the actual or the planned start of the game, and the optional `reason` is the one given when the game was called off. These are not counted in `games`
+	/// * `home` and `away` - the teams of the game, with their names and scores
+	///
+	/// All the times are in UTC, in the RFC 3339 format
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut sb = scoreboard_world_cup::ScoreBoard::new();
+	/// sb.start_game("Japan", "Indonesia").unwrap();
+	/// sb.update_score("Japan", 2, "Indonesia", 0).unwrap();
+	/// let xml = sb.export_xml();
+	/// assert!(xml.contains("<home name=\"Japan\" score=\"2\"/>"));
+	/// assert!(xml.contains("<away name=\"Indonesia\" score=\"0\"/>"));
+	/// ```
+	pub fn export_xml(&self) -> String {
+		self.render_xml(SystemTime::now())
+	}
+
+	/// Renders the XML document
+	///
+	/// # Arguments
+	///
+	/// * `generated` - time of the export
+	///
+	fn render_xml(&self, generated: SystemTime) -> String {
+		trace!("Exporting the score board as XML");
+
+		let mut result = String::new();
+		result.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+		result.push_str(&format!(
+			"<scoreboard generated=\"{}\" games=\"{}\">\n",
+			UtcDateTime::from_system_time(generated).to_rfc3339(),
+			self.data.len(),
+		));
+
+		for (id, game) in self.data.iter().enumerate() {
+			result.push_str(&format!(
+				"  <game rank=\"{}\" kickoff=\"{}\" total=\"{}\">\n",
+				id + 1,
+				UtcDateTime::from_system_time(game.kickoff_time).to_rfc3339(),
+				game.get_total_score(),
+			));
+			result.push_str(&format!("    <home name=\"{}\" score=\"{}\"/>\n", escape_xml(&game.home_team.name), game.home_team.score));
+			result.push_str(&format!("    <away name=\"{}\" score=\"{}\"/>\n", escape_xml(&game.away_team.name), game.away_team.score));
+			result.push_str("  </game>\n");
+		}
+
+		for game in &self.called_off {
+			result.push_str(&format!(
+				"  <called-off state=\"{}\" kickoff=\"{}\"",
+				game.state.get_name(),
+				UtcDateTime::from_system_time(game.kickoff_time).to_rfc3339(),
+			));
+			if let Some(reason) = &game.reason {
+				result.push_str(&format!(" reason=\"{}\"", escape_xml(reason)));
+			}
+			result.push_str(">\n");
+			result.push_str(&format!("    <home name=\"{}\" score=\"{}\"/>\n", escape_xml(&game.home_team), game.home_score));
+			result.push_str(&format!("    <away name=\"{}\" score=\"{}\"/>\n", escape_xml(&game.away_team), game.away_score));
+			result.push_str("  </called-off>\n");
+		}
+
+		result.push_str("</scoreboard>\n");
+
+		trace!("Score board exported");
+
+		result
+	}
+}
+
+// ***********
+// Unit tests
+// ***********
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::time::{Duration, UNIX_EPOCH};
+
+	fn get_export_time() -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(1_671_381_900)
+	}
+
+	#[test]
+	fn empty_board_exports_an_empty_document() {
+		let expected = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<scoreboard generated=\"2022-12-18T16:45:00Z\" games=\"0\">\n</scoreboard>\n";
+
+		let sb = ScoreBoard::new();
+
+		assert_eq!(sb.render_xml(get_export_time()), expected);
+	}
+
+	#[test]
+	fn game_is_exported_with_scores_and_kickoff() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Argentina", "France").expect("Couldn't create the game");
+		sb.update_score("Argentina", 3, "France", 3).expect("Couldn't update the game");
+		sb.data[0].kickoff_time = UNIX_EPOCH + Duration::from_secs(1_671_375_600);
+		let result = sb.render_xml(get_export_time());
+
+		assert!(result.contains("<scoreboard generated=\"2022-12-18T16:45:00Z\" games=\"1\">\n"));
+		assert!(result.contains("  <game rank=\"1\" kickoff=\"2022-12-18T15:00:00Z\" total=\"6\">\n    <home name=\"Argentina\" score=\"3\"/>\n    <away name=\"France\" score=\"3\"/>\n  </game>\n"));
+	}
+
+	#[test]
+	fn games_are_exported_in_summary_order() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Mexico", "Canada").expect("Couldn't create the first game");
+		sb.start_game("Spain", "Brazil").expect("Couldn't create the second game");
+		sb.update_score("Mexico", 0, "Canada", 5).expect("Couldn't update the first game");
+		let result = sb.export_xml();
+
+		let mexico = result.find("<home name=\"Mexico\" score=\"0\"/>").expect("Missing the first game");
+		let spain = result.find("<home name=\"Spain\" score=\"0\"/>").expect("Missing the second game");
+		assert!(mexico < spain);
+		assert!(result.contains("<game rank=\"2\""));
+	}
+
+	#[test]
+	fn team_names_are_escaped() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Trinidad & Tobago", "\"Antigua\"").expect("Couldn't create the game");
+		let result = sb.export_xml();
+
+		assert!(result.contains("<home name=\"Trinidad &amp; Tobago\" score=\"0\"/>"));
+		assert!(result.contains("<away name=\"&quot;Antigua&quot;\" score=\"0\"/>"));
+	}
+
+	#[test]
+	fn called_off_games_are_exported_apart() {
+		let mut sb = ScoreBoard::new();
+		sb.start_game("Japan", "Indonesia").expect("Couldn't create the game");
+		sb.update_score("Japan", 1, "Indonesia", 0).expect("Couldn't update the game");
+		sb.abandon_game("Japan", "Indonesia", "Storm & hail").expect("Couldn't abandon the game");
+		sb.called_off[0].kickoff_time = UNIX_EPOCH + Duration::from_secs(1_671_368_400);
+		let result = sb.render_xml(get_export_time());
+
+		assert!(result.contains("<scoreboard generated=\"2022-12-18T16:45:00Z\" games=\"0\">\n"));
+		assert!(result.contains("  <called-off state=\"abandoned\" kickoff=\"2022-12-18T13:00:00Z\" reason=\"Storm &amp; hail\">\n    <home name=\"Japan\" score=\"1\"/>\n    <away name=\"Indonesia\" score=\"0\"/>\n  </called-off>\n"));
+	}
+}